@@ -398,3 +398,23 @@ specify it.",
         .multiple(false),
     )
 }
+
+pub fn keys_subcommand() -> Command {
+  Command::new("keys")
+    .version(env!("CARGO_PKG_VERSION"))
+    .author(env!("CARGO_PKG_AUTHORS"))
+    .about("Prints the keybindings, including the ones changed in config.yml")
+    .long_about(
+      "Prints the same table as the help menu (`?`), grouped by view, with the bindings \
+from your config.yml applied. Use `--format json` for something scripts can read.",
+    )
+    .arg(
+      Arg::new("format")
+        .short('f')
+        .long("format")
+        .value_name("FORMAT")
+        .value_parser(["markdown", "json"])
+        .default_value("markdown")
+        .help("Specifies the output format"),
+    )
+}
@@ -4,7 +4,9 @@ mod handle;
 mod update;
 mod util;
 
-pub use self::clap::{list_subcommand, play_subcommand, playback_subcommand, search_subcommand};
+pub use self::clap::{
+  keys_subcommand, list_subcommand, play_subcommand, playback_subcommand, search_subcommand,
+};
 use cli_app::CliApp;
 pub use handle::handle_matches;
 pub use update::{check_for_update, check_for_update_silent, UpdateInfo};
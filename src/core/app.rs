@@ -1,8 +1,19 @@
 use crate::cli::UpdateInfo;
-use crate::core::sort::{SortContext, SortState};
-use crate::core::user_config::UserConfig;
+use crate::core::config::ClientConfig;
+use crate::core::keymap::{self, Action};
+use crate::core::play_counts::PlayCounts;
+use crate::core::session::{self, Session, SessionPlayback, SessionStore};
+use crate::core::sort::{reorder, PlaylistSorts, SortContext, SortState, Sorter, TrackRow};
+use crate::core::theme_files::{self, ThemeDirStamp};
+use crate::core::undo::{UndoHistory, UndoOp};
+use crate::core::user_config::{ColorSupport, UserConfig};
+use crate::infra::lyrics::{self, FetchedLyrics, LrclibRecord, LyricsCache, LyricsLine};
 use crate::infra::network::IoEvent;
-use anyhow::anyhow;
+use crate::infra::notifications::{Notification, NotificationKind};
+use crate::tui::event::Key;
+use crate::tui::ui::help;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
 use ratatui::layout::Size;
 use rspotify::{
   model::enums::Country,
@@ -11,7 +22,7 @@ use rspotify::{
     artist::FullArtist,
     context::CurrentPlaybackContext,
     device::DevicePayload,
-    idtypes::{ArtistId, PlaylistId, ShowId, TrackId},
+    idtypes::{AlbumId, ArtistId, PlayableId, PlaylistId, ShowId, TrackId},
     page::{CursorBasedPage, Page},
     playing::PlayHistory,
     playlist::{PlaylistItem, SimplifiedPlaylist},
@@ -28,7 +39,8 @@ use std::sync::mpsc::Sender;
 use std::sync::Arc;
 use std::{
   cmp::{max, min},
-  collections::HashSet,
+  collections::{HashMap, HashSet},
+  fs,
   time::{Duration, Instant, SystemTime},
 };
 
@@ -123,6 +135,9 @@ pub enum DialogContext {
   PlaylistSearch,
   AddTrackToPlaylistPicker,
   RemoveTrackFromPlaylistConfirm,
+  AudioOutputPicker,
+  LyricsSearch,
+  UndoHistory,
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
@@ -148,6 +163,7 @@ pub enum ActiveBlock {
   Discover,
   Artists,
   BasicView,
+  NowPlaying,
   Dialog(DialogContext),
   UpdatePrompt,
   AnnouncementPrompt,
@@ -162,6 +178,7 @@ pub enum RouteId {
   AlbumList,
   Artist,
   BasicView,
+  NowPlaying,
   Error,
   Home,
   RecentlyPlayed,
@@ -180,6 +197,22 @@ pub enum RouteId {
   HelpMenu,
 }
 
+impl RouteId {
+  /// Routes shown on top of whatever view was open before them
+  pub fn is_overlay(&self) -> bool {
+    matches!(
+      self,
+      RouteId::Error
+        | RouteId::SelectedDevice
+        | RouteId::Dialog
+        | RouteId::UpdatePrompt
+        | RouteId::AnnouncementPrompt
+        | RouteId::Settings
+        | RouteId::HelpMenu
+    )
+  }
+}
+
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub enum AnnouncementLevel {
   Info,
@@ -197,7 +230,7 @@ pub struct Announcement {
   pub received_at: Instant,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Route {
   pub id: RouteId,
   pub active_block: ActiveBlock,
@@ -285,6 +318,8 @@ pub struct SearchResult {
   pub selected_shows_index: Option<usize>,
   pub hovered_block: SearchResultBlock,
   pub selected_block: SearchResultBlock,
+  /// Place of each song in the results by track id, to go back to it after sorting
+  pub track_ranks: HashMap<String, usize>,
 }
 
 #[derive(Default)]
@@ -292,6 +327,30 @@ pub struct TrackTable {
   pub tracks: Vec<FullTrack>,
   pub selected_index: usize,
   pub context: Option<TrackTableContext>,
+  /// When and by whom each row was added, in row order so a track that is in a playlist twice
+  /// keeps both dates. Only playlists and liked songs have it
+  pub added: Vec<TrackAdded>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TrackAdded {
+  pub at: Option<DateTime<Utc>>,
+  /// Display name of the user, or their id when they have none
+  pub by: Option<String>,
+}
+
+impl TrackAdded {
+  pub fn from_playlist_item(item: &PlaylistItem) -> Self {
+    TrackAdded {
+      at: item.added_at,
+      by: item.added_by.as_ref().map(|user| {
+        user
+          .display_name
+          .clone()
+          .unwrap_or_else(|| user.id.id().to_string())
+      }),
+    }
+  }
 }
 
 #[derive(Clone)]
@@ -309,6 +368,106 @@ pub struct PendingPlaylistTrackRemoval {
   pub position: usize,
 }
 
+/// A local output the native player can switch to
+#[derive(Clone, Debug, PartialEq)]
+pub struct AudioOutputChoice {
+  pub backend: String,
+  /// `None` for the backend's default device
+  pub device: Option<String>,
+  pub label: String,
+}
+
+/// Which part of the lyrics search popup takes key presses
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LyricsSearchFocus {
+  #[default]
+  Title,
+  Artist,
+  Results,
+}
+
+/// Manual LRCLIB search for the playing track, for when the automatic match is wrong or missing
+#[derive(Clone, Debug, Default)]
+pub struct LyricsSearchState {
+  pub track_id: String,
+  pub duration_secs: f64,
+  pub title: String,
+  pub artist: String,
+  pub focus: LyricsSearchFocus,
+  pub results: Vec<LrclibRecord>,
+  pub selected_index: usize,
+  pub searching: bool,
+  pub error: Option<String>,
+}
+
+/// Lyrics sync editor: the lines of the playing track, stamped one by one while listening
+#[derive(Clone, Debug, Default)]
+pub struct LyricsSyncEditor {
+  pub track_id: String,
+  pub track: String,
+  pub artist: String,
+  pub duration_ms: u128,
+  pub lines: Vec<String>,
+  pub stamps: Vec<Option<u128>>,
+  pub selected_index: usize,
+  /// Play the stamped lines back like regular synced lyrics
+  pub previewing: bool,
+}
+
+impl LyricsSyncEditor {
+  /// Stamp the selected line and move on to the next one
+  pub fn stamp(&mut self, time_ms: u128) {
+    if let Some(stamp) = self.stamps.get_mut(self.selected_index) {
+      *stamp = Some(time_ms);
+      self.selected_index = (self.selected_index + 1).min(self.lines.len().saturating_sub(1));
+    }
+  }
+
+  pub fn nudge(&mut self, delta_ms: i64) {
+    if let Some(Some(stamp)) = self.stamps.get_mut(self.selected_index) {
+      *stamp = (*stamp as i128 + i128::from(delta_ms)).max(0) as u128;
+    }
+  }
+
+  pub fn clear(&mut self) {
+    if let Some(stamp) = self.stamps.get_mut(self.selected_index) {
+      *stamp = None;
+    }
+  }
+
+  pub fn unstamped_count(&self) -> usize {
+    self.stamps.iter().filter(|stamp| stamp.is_none()).count()
+  }
+
+  /// Stamped lines as they would play, for the preview
+  pub fn stamped_lines(&self) -> Vec<LyricsLine> {
+    let mut lines: Vec<LyricsLine> = self
+      .lines
+      .iter()
+      .zip(&self.stamps)
+      .filter_map(|(text, stamp)| {
+        Some(LyricsLine {
+          time_ms: (*stamp)?,
+          text: text.clone(),
+          words: Vec::new(),
+        })
+      })
+      .collect();
+    lines.sort_by_key(|line| line.time_ms);
+    lines
+  }
+
+  pub fn to_lrc(&self) -> String {
+    let lines: Vec<(u128, &str)> = self
+      .lines
+      .iter()
+      .zip(&self.stamps)
+      .filter_map(|(text, stamp)| Some(((*stamp)?, text.as_str())))
+      .collect();
+    lyrics::write_lrc(&self.track, &self.artist, self.duration_ms, &lines)
+  }
+}
+
 #[derive(Clone)]
 pub struct SelectedShow {
   pub show: SimplifiedShow,
@@ -370,8 +529,7 @@ pub enum LyricsStatus {
 pub struct NativeTrackInfo {
   pub name: String,
   pub artists_display: String,
-  #[allow(dead_code)]
-  pub album: String, // Reserved for future use (e.g., displaying album in playbar)
+  pub album: String,
   pub duration_ms: u32,
 }
 
@@ -423,6 +581,10 @@ pub enum SettingsCategory {
   Behavior,
   Keybindings,
   Theme,
+  Streaming,
+  Equalizer,
+  Integrations,
+  Account,
 }
 
 impl SettingsCategory {
@@ -431,6 +593,10 @@ impl SettingsCategory {
       SettingsCategory::Behavior,
       SettingsCategory::Keybindings,
       SettingsCategory::Theme,
+      SettingsCategory::Streaming,
+      SettingsCategory::Equalizer,
+      SettingsCategory::Integrations,
+      SettingsCategory::Account,
     ]
   }
 
@@ -439,6 +605,10 @@ impl SettingsCategory {
       SettingsCategory::Behavior => "Behavior",
       SettingsCategory::Keybindings => "Keybindings",
       SettingsCategory::Theme => "Theme",
+      SettingsCategory::Streaming => "Streaming",
+      SettingsCategory::Equalizer => "Equalizer",
+      SettingsCategory::Integrations => "Integrations",
+      SettingsCategory::Account => "Account",
     }
   }
 
@@ -447,6 +617,10 @@ impl SettingsCategory {
       SettingsCategory::Behavior => 0,
       SettingsCategory::Keybindings => 1,
       SettingsCategory::Theme => 2,
+      SettingsCategory::Streaming => 3,
+      SettingsCategory::Equalizer => 4,
+      SettingsCategory::Integrations => 5,
+      SettingsCategory::Account => 6,
     }
   }
 
@@ -455,6 +629,10 @@ impl SettingsCategory {
       0 => SettingsCategory::Behavior,
       1 => SettingsCategory::Keybindings,
       2 => SettingsCategory::Theme,
+      3 => SettingsCategory::Streaming,
+      4 => SettingsCategory::Equalizer,
+      5 => SettingsCategory::Integrations,
+      6 => SettingsCategory::Account,
       _ => SettingsCategory::Behavior,
     }
   }
@@ -495,13 +673,103 @@ pub struct SettingItem {
   pub value: SettingValue,
 }
 
+/// Settings read once at startup, marked in the settings list
+const RESTART_REQUIRED: &[&str] = &[
+  "behavior.tick_rate_milliseconds",
+  "behavior.enable_discord_rpc",
+  "behavior.discord_rpc_client_id",
+  "behavior.enable_global_song_count",
+  "streaming.enabled",
+  "streaming.device_name",
+  "streaming.bitrate",
+  "streaming.audio_cache",
+  "streaming.normalisation",
+  "streaming.normalisation_mode",
+  "streaming.normalisation_pregain",
+  "streaming.normalisation_threshold",
+  "streaming.normalisation_attack",
+  "streaming.normalisation_release",
+  "account.client_id",
+  "account.fallback_client_id",
+  "account.port",
+];
+
+impl SettingItem {
+  pub fn requires_restart(&self) -> bool {
+    RESTART_REQUIRED.contains(&self.id.as_str())
+  }
+
+  /// Check an edited value before it replaces the current one
+  pub fn validate(&self, value: &SettingValue) -> Result<()> {
+    match (self.id.as_str(), value) {
+      ("account.client_id", SettingValue::String(v)) => {
+        ClientConfig::validate_client_key(v.trim()).map_err(|e| anyhow!("Client ID: {}", e))
+      }
+      ("account.fallback_client_id", SettingValue::String(v)) if !v.trim().is_empty() => {
+        ClientConfig::validate_client_key(v.trim())
+          .map_err(|e| anyhow!("Fallback client ID: {}", e))
+      }
+      ("account.port", SettingValue::Number(v)) if !(1..=65535).contains(v) => {
+        Err(anyhow!("Port must be between 1 and 65535"))
+      }
+      ("streaming.device_name", SettingValue::String(v)) if v.trim().is_empty() => {
+        Err(anyhow!("Device name can't be empty"))
+      }
+      ("behavior.discord_rpc_client_id", SettingValue::String(v))
+        if !v.trim().chars().all(|c| c.is_ascii_digit()) =>
+      {
+        Err(anyhow!("Discord application IDs are numbers"))
+      }
+      _ => Ok(()),
+    }
+  }
+}
+
+/// Why the keys recorded for a binding can't be used as they are
+#[derive(Clone, Debug, PartialEq)]
+pub enum KeyCaptureIssue {
+  /// Message from `check_reserved_keys`
+  Reserved(String),
+  /// Keys already used by another setting of the Keybindings tab
+  Conflict { index: usize, name: String },
+}
+
+/// A key binding being recorded in the Keybindings tab of Settings
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KeyCapture {
+  pub keys: Vec<Key>,
+  pub issue: Option<KeyCaptureIssue>,
+  /// Enter was pressed on a conflict, waiting for swap or unbind
+  pub resolving: bool,
+}
+
+/// Which of `m` and `'` is waiting for its letter
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MarkPrompt {
+  Set,
+  Jump,
+}
+
+/// A row remembered with `m<letter>`
+#[derive(Clone, Debug)]
+pub struct Mark {
+  pub route: Route,
+  pub row: usize,
+  /// Playlist and page offset the track table showed, so jumping can load them again
+  pub playlist: Option<(PlaylistId<'static>, u32)>,
+}
+
 pub struct App {
   pub instant_since_last_current_playback_poll: Instant,
   navigation_stack: Vec<Route>,
+  /// Routes left with the back action, most recent last
+  forward_navigation_stack: Vec<Route>,
   pub spectrum_data: Option<SpectrumData>,
   pub audio_capture_active: bool,
   pub home_scroll: u16,
   pub user_config: UserConfig,
+  /// Copy of client.yml for the playback settings; credentials are never edited from the UI
+  pub client_config: ClientConfig,
   pub artists: Vec<FullArtist>,
   pub artist: Option<Artist>,
   pub album_table_context: AlbumTableContext,
@@ -571,6 +839,9 @@ pub struct App {
   pub help_menu_page: u32,
   pub help_menu_max_lines: u32,
   pub help_menu_offset: u32,
+  /// Search typed into the help menu, matched against every column
+  pub help_filter: String,
+  pub help_filter_editing: bool,
   pub is_loading: bool,
   io_tx: Option<Sender<IoEvent>>,
   pub is_fetching_current_playback: bool,
@@ -581,8 +852,20 @@ pub struct App {
   pub update_prompt_acknowledged: bool,
   pub active_announcement: Option<Announcement>,
   pub pending_announcements: Vec<Announcement>,
-  pub lyrics: Option<Vec<(u128, String)>>,
+  pub lyrics: Option<Vec<LyricsLine>>,
+  /// False when the lyrics are plain text without timestamps
+  pub lyrics_synced: bool,
+  /// Provider the current lyrics came from
+  pub lyrics_source: Option<String>,
+  /// Per-track timing correction, positive to show lines later
+  pub lyrics_offset_ms: i64,
   pub lyrics_status: LyricsStatus,
+  /// Open while the user is timing the lyrics of the playing track
+  pub lyrics_sync: Option<LyricsSyncEditor>,
+  /// Upcoming items of the playback queue, shown in the now playing view
+  pub up_next: Vec<PlayableItem>,
+  /// Only the playbar is drawn, for small terminal splits
+  pub mini_mode: bool,
   pub global_song_count: Option<u64>,
   pub global_song_count_failed: bool,
   // Settings screen state
@@ -591,6 +874,9 @@ pub struct App {
   pub settings_selected_index: usize,
   pub settings_edit_mode: bool,
   pub settings_edit_buffer: String,
+  /// Why the value being edited was rejected, shown until the next edit
+  pub settings_edit_error: Option<String>,
+  pub key_capture: KeyCapture,
   /// Immediate track info from native player for instant UI updates
   pub native_track_info: Option<NativeTrackInfo>,
   /// Whether native streaming is active (disables API-based progress calculation)
@@ -624,16 +910,44 @@ pub struct App {
   pub sort_menu_selected: usize,
   /// Current sort context (what we're sorting)
   pub sort_context: Option<SortContext>,
-  /// Current sort state per context
-  pub playlist_sort: SortState,
+  /// Current sort state per context, playlists keep theirs per playlist
+  pub playlist_sorts: PlaylistSorts,
+  pub liked_songs_sort: SortState,
   pub album_sort: SortState,
   pub artist_sort: SortState,
+  pub recently_played_sort: SortState,
+  pub album_tracks_sort: SortState,
+  pub search_sort: SortState,
   /// Animation frame counter for the "Liked" heart flash effect (0-10)
   pub liked_song_animation_frame: Option<u8>,
   /// Ephemeral status message shown in the playbar
   pub status_message: Option<String>,
   /// When to clear the status message
   pub status_message_expires_at: Option<Instant>,
+  /// Keys typed so far of a chord that is not complete yet
+  pub pending_keys: Vec<Key>,
+  /// Count typed before an action, e.g. the 5 of `5j`
+  pub count_prefix: Option<usize>,
+  pub mark_prompt: Option<MarkPrompt>,
+  pub marks: HashMap<char, Mark>,
+  /// Track table row a visual range was started on
+  pub visual_anchor: Option<usize>,
+  pub session_store: SessionStore,
+  /// Session of the last run whose view is reopened once the data it needs has loaded
+  pub session_to_restore: Option<Session>,
+  /// Until when a restored playlist view waits for the playlist list
+  pub session_restore_deadline: Option<Instant>,
+  pub play_counts: PlayCounts,
+  /// Set by the back action once there is nowhere left to go back to
+  pub should_quit: bool,
+  /// Palette the frame is mapped onto before it reaches the terminal
+  pub color_support: ColorSupport,
+  /// config.yml and the theme files as last seen on disk, to reload them when they change
+  pub config_file_modified: Option<SystemTime>,
+  pub theme_files_stamp: ThemeDirStamp,
+  pub config_checked_at: Instant,
+  /// Notifications raised by handlers or the network thread, delivered on the next UI tick
+  pub pending_notifications: Vec<Notification>,
   /// Pending track table selection to apply when new page loads
   pub pending_track_table_selection: Option<PendingTrackSelection>,
   /// Maps visible track table rows to source playlist item positions.
@@ -641,10 +955,19 @@ pub struct App {
   pub playlist_track_positions: Option<Vec<usize>>,
   /// Selected playlist index in the add-to-playlist picker dialog
   pub playlist_picker_selected_index: usize,
+  /// Outputs listed in the audio output picker dialog
+  pub audio_output_choices: Vec<AudioOutputChoice>,
+  pub audio_output_selected_index: usize,
+  /// The outputs are still being listed in the background
+  pub audio_outputs_loading: bool,
+  pub lyrics_search: LyricsSearchState,
   /// Pending track to add in add-to-playlist dialog flow
   pub pending_playlist_track_add: Option<PendingPlaylistTrackAdd>,
   /// Pending track removal info in remove-from-playlist confirmation flow
   pub pending_playlist_track_removal: Option<PendingPlaylistTrackRemoval>,
+  pub undo_history: UndoHistory,
+  /// Selected entry in the undo history dialog, 0 being the newest
+  pub undo_history_selected_index: usize,
   /// Full flat list of all user playlists (all pages combined)
   pub all_playlists: Vec<SimplifiedPlaylist>,
   /// Folder tree from rootlist (None if not fetched or streaming disabled)
@@ -667,6 +990,8 @@ pub struct App {
 pub enum PendingTrackSelection {
   First,
   Last,
+  /// A row of the page, e.g. the one a mark points at
+  Index(usize),
 }
 
 impl Default for App {
@@ -687,6 +1012,7 @@ impl Default for App {
       artists: vec![],
       artist: None,
       user_config: UserConfig::new(),
+      client_config: ClientConfig::new(),
       saved_album_tracks_index: 0,
       recently_played: Default::default(),
       size: Size::default(),
@@ -706,6 +1032,7 @@ impl Default for App {
       saved_album_ids_set: HashSet::new(),
       saved_show_ids_set: HashSet::new(),
       navigation_stack: vec![DEFAULT_ROUTE],
+      forward_navigation_stack: Vec::new(),
       large_search_limit: 20,
       small_search_limit: 4,
       api_error: String::new(),
@@ -735,6 +1062,7 @@ impl Default for App {
         selected_tracks_index: None,
         selected_shows_index: None,
         tracks: None,
+        track_ranks: HashMap::new(),
       },
       song_progress_ms: 0,
       seek_ms: None,
@@ -758,6 +1086,8 @@ impl Default for App {
       help_menu_page: 0,
       help_menu_max_lines: 0,
       help_menu_offset: 0,
+      help_filter: String::new(),
+      help_filter_editing: false,
       is_loading: false,
       io_tx: None,
       is_fetching_current_playback: false,
@@ -769,7 +1099,13 @@ impl Default for App {
       active_announcement: None,
       pending_announcements: Vec::new(),
       lyrics: None,
+      lyrics_synced: false,
+      lyrics_source: None,
+      lyrics_offset_ms: 0,
       lyrics_status: LyricsStatus::default(),
+      lyrics_sync: None,
+      up_next: Vec::new(),
+      mini_mode: false,
       global_song_count: None,
       global_song_count_failed: false,
       // Settings defaults
@@ -778,6 +1114,8 @@ impl Default for App {
       settings_selected_index: 0,
       settings_edit_mode: false,
       settings_edit_buffer: String::new(),
+      settings_edit_error: None,
+      key_capture: KeyCapture::default(),
       native_track_info: None,
       is_streaming_active: false,
       native_device_id: None,
@@ -788,17 +1126,42 @@ impl Default for App {
       sort_menu_visible: false,
       sort_menu_selected: 0,
       sort_context: None,
-      playlist_sort: SortState::new(),
+      playlist_sorts: PlaylistSorts::default(),
+      liked_songs_sort: SortState::new(),
       album_sort: SortState::new(),
       artist_sort: SortState::new(),
+      recently_played_sort: SortState::new(),
+      album_tracks_sort: SortState::new(),
+      search_sort: SortState::new(),
       liked_song_animation_frame: None,
       status_message: None,
       status_message_expires_at: None,
+      pending_keys: Vec::new(),
+      count_prefix: None,
+      mark_prompt: None,
+      marks: HashMap::new(),
+      visual_anchor: None,
+      session_store: SessionStore::default(),
+      session_to_restore: None,
+      session_restore_deadline: None,
+      play_counts: PlayCounts::default(),
+      should_quit: false,
+      color_support: ColorSupport::TrueColor,
+      config_file_modified: None,
+      theme_files_stamp: Vec::new(),
+      config_checked_at: Instant::now(),
+      pending_notifications: Vec::new(),
       pending_track_table_selection: None,
       playlist_track_positions: None,
       playlist_picker_selected_index: 0,
+      audio_output_choices: Vec::new(),
+      audio_output_selected_index: 0,
+      audio_outputs_loading: false,
+      lyrics_search: LyricsSearchState::default(),
       pending_playlist_track_add: None,
       pending_playlist_track_removal: None,
+      undo_history: UndoHistory::default(),
+      undo_history_selected_index: 0,
       all_playlists: Vec::new(),
       _playlist_folder_nodes: None,
       playlist_folder_items: Vec::new(),
@@ -820,10 +1183,24 @@ impl App {
     user_config: UserConfig,
     spotify_token_expiry: SystemTime,
   ) -> App {
+    let config_file_modified = user_config
+      .path_to_config
+      .as_ref()
+      .and_then(|paths| fs::metadata(&paths.config_file_path).ok()?.modified().ok());
+    let theme_files_stamp = user_config
+      .themes_dir()
+      .map(|dir| theme_files::stamp(&dir))
+      .unwrap_or_default();
     App {
       io_tx: Some(io_tx),
+      color_support: user_config.behavior.color_support.resolve(),
       user_config,
       spotify_token_expiry,
+      config_file_modified,
+      theme_files_stamp,
+      session_store: SessionStore::new(),
+      play_counts: PlayCounts::load(),
+      playlist_sorts: PlaylistSorts::load(),
       ..App::default()
     }
   }
@@ -1045,6 +1422,84 @@ impl App {
     }
   }
 
+  /// Pick up edits to config.yml and the theme files, checked about once a second
+  fn reload_config_if_changed(&mut self) {
+    if self.config_checked_at.elapsed() < Duration::from_secs(1) {
+      return;
+    }
+    self.config_checked_at = Instant::now();
+
+    self.reload_config_file_if_changed();
+    self.reload_theme_files_if_changed();
+  }
+
+  fn reload_config_file_if_changed(&mut self) {
+    let Some(paths) = &self.user_config.path_to_config else {
+      return;
+    };
+    let modified = fs::metadata(&paths.config_file_path)
+      .and_then(|metadata| metadata.modified())
+      .ok();
+    if modified == self.config_file_modified {
+      return;
+    }
+    self.config_file_modified = modified;
+
+    match self.user_config.reload() {
+      // Our own saves from the settings screen land here too, they change nothing
+      Ok(config) if config.same_settings(&self.user_config) => {}
+      Ok(config) => {
+        self.apply_reloaded_config(config);
+        self.set_status_message("Reloaded config.yml", 3);
+      }
+      Err(e) => {
+        self.set_status_message(format!("config.yml not reloaded: {}", e), 8);
+      }
+    }
+  }
+
+  /// Swap in a config read from disk and bring the state derived from it up to date
+  fn apply_reloaded_config(&mut self, config: UserConfig) {
+    self.user_config = config;
+    self.color_support = self.user_config.behavior.color_support.resolve();
+    self.set_night_mode(self.user_config.behavior.night_mode);
+    #[cfg(feature = "streaming")]
+    if let Some(ref player) = self.streaming_player {
+      player.set_equalizer(self.user_config.equalizer.active_gains());
+    }
+    #[cfg(feature = "cover-art")]
+    self.apply_adaptive_theme();
+    // Unsaved edits in an open settings screen would otherwise write the old values back
+    if self.get_current_route().active_block == ActiveBlock::Settings {
+      self.load_settings_for_category();
+    }
+    self.update_help_docs_size();
+  }
+
+  /// Keep the help menu's row count in step with the keymap it lists
+  pub fn update_help_docs_size(&mut self) {
+    self.help_docs_size = help::visible_help_docs(self).len() as u32;
+  }
+
+  /// Pick up theme files that were added, edited or removed since the last check
+  fn reload_theme_files_if_changed(&mut self) {
+    let Some(dir) = self.user_config.themes_dir() else {
+      return;
+    };
+    let stamp = theme_files::stamp(&dir);
+    if stamp == self.theme_files_stamp {
+      return;
+    }
+    self.theme_files_stamp = stamp;
+
+    let errors = self.user_config.load_theme_files();
+    if let Some(error) = errors.first() {
+      self.set_status_message(format!("Theme file skipped: {}", error), 6);
+    } else if let Some(name) = &self.user_config.behavior.theme_file {
+      self.set_status_message(format!("Reloaded theme: {}", name), 3);
+    }
+  }
+
   pub fn update_on_tick(&mut self) {
     if let Some(expires_at) = self.status_message_expires_at {
       if Instant::now() >= expires_at {
@@ -1062,6 +1517,16 @@ impl App {
     }
 
     self.poll_current_playback();
+    self.reload_config_if_changed();
+
+    if self.user_config.behavior.restore_session {
+      if self.session_to_restore.is_some() {
+        session::restore_pending(self);
+      } else {
+        let current = session::capture(self);
+        self.session_store.update(current);
+      }
+    }
 
     if let Some(CurrentPlaybackContext {
       item: Some(item),
@@ -1402,6 +1867,20 @@ impl App {
     info!("error occurred: {}", e);
     self.push_navigation_stack(RouteId::Error, ActiveBlock::Error);
     self.api_error = e.to_string();
+    self.queue_notification(Notification::new(
+      NotificationKind::Error,
+      "spotatui error",
+      self.api_error.clone(),
+    ));
+  }
+
+  /// Queue a notification; the UI loop filters it against the user's notification settings
+  pub fn queue_notification(&mut self, notification: Notification) {
+    // Don't let a burst of errors pile up while notifications are switched off
+    const MAX_PENDING_NOTIFICATIONS: usize = 8;
+    if self.pending_notifications.len() < MAX_PENDING_NOTIFICATIONS {
+      self.pending_notifications.push(notification);
+    }
   }
 
   /// Check if native streaming is the active playback device
@@ -1588,6 +2067,7 @@ impl App {
         active_block: next_active_block,
         hovered_block: next_active_block,
       });
+      self.forward_navigation_stack.clear();
     }
   }
 
@@ -1596,7 +2076,208 @@ impl App {
     if self.navigation_stack.len() == 1 {
       None
     } else {
-      self.navigation_stack.pop()
+      let route = self.navigation_stack.pop();
+      // The sync editor belongs to the lyrics view it was opened in
+      if route
+        .as_ref()
+        .is_some_and(|route| matches!(route.id, RouteId::BasicView | RouteId::NowPlaying))
+      {
+        self.lyrics_sync = None;
+      }
+      route
+    }
+  }
+
+  /// Pop the current route and remember it for `navigate_forward`
+  pub fn navigate_back(&mut self) -> Option<Route> {
+    let route = self.pop_navigation_stack()?;
+    self.forward_navigation_stack.push(route.clone());
+    Some(route)
+  }
+
+  /// Return to the route last left with `navigate_back`
+  pub fn navigate_forward(&mut self) -> bool {
+    match self.forward_navigation_stack.pop() {
+      Some(route) => {
+        info!("navigating forward to {:?}", route.id);
+        self.navigation_stack.push(route);
+        true
+      }
+      None => false,
+    }
+  }
+
+  /// Rows of the track table covered by the visual range, the selected row included
+  pub fn visual_range(&self) -> Option<std::ops::RangeInclusive<usize>> {
+    let anchor = self.visual_anchor?;
+    let last = self.track_table.tracks.len().checked_sub(1)?;
+    let selected = self.track_table.selected_index.min(last);
+    let anchor = anchor.min(last);
+    Some(min(anchor, selected)..=max(anchor, selected))
+  }
+
+  /// Top route that is not drawn over another one, like the help menu or a dialog
+  pub fn content_route(&self) -> &Route {
+    self
+      .navigation_stack
+      .iter()
+      .rev()
+      .find(|route| !route.id.is_overlay())
+      .unwrap_or(&DEFAULT_ROUTE)
+  }
+
+  /// Id of the playlist open in the track table
+  pub fn active_playlist_id(&self) -> Option<String> {
+    let index = self.active_playlist_index?;
+    self
+      .all_playlists
+      .get(index)
+      .map(|playlist| playlist.id.id().to_string())
+  }
+
+  pub fn sort_state(&self, context: SortContext) -> SortState {
+    match context {
+      SortContext::PlaylistTracks => self
+        .active_playlist_id()
+        .map(|id| self.playlist_sorts.get(&id))
+        .unwrap_or_default(),
+      SortContext::LikedSongs => self.liked_songs_sort,
+      SortContext::SavedAlbums => self.album_sort,
+      SortContext::SavedArtists => self.artist_sort,
+      SortContext::RecentlyPlayed => self.recently_played_sort,
+      SortContext::AlbumTracks => self.album_tracks_sort,
+      SortContext::SearchResults => self.search_sort,
+    }
+  }
+
+  /// Change the sort of `context` and sort what it shows accordingly
+  pub fn set_sort_state(&mut self, context: SortContext, state: SortState) {
+    match context {
+      SortContext::PlaylistTracks => {
+        if let Some(id) = self.active_playlist_id() {
+          self.playlist_sorts.set(&id, state);
+        }
+      }
+      SortContext::LikedSongs => self.liked_songs_sort = state,
+      SortContext::SavedAlbums => self.album_sort = state,
+      SortContext::SavedArtists => self.artist_sort = state,
+      SortContext::RecentlyPlayed => self.recently_played_sort = state,
+      SortContext::AlbumTracks => self.album_tracks_sort = state,
+      SortContext::SearchResults => self.search_sort = state,
+    }
+    self.resort(context);
+  }
+
+  /// Sort what `context` shows by its sort state, also used when a new page of it has loaded
+  pub fn resort(&mut self, context: SortContext) {
+    let sorter = Sorter::new(self.sort_state(context));
+    match context {
+      SortContext::PlaylistTracks => {
+        // Playlists are sorted as a whole, which takes every page of them
+        if let Some(playlist) = self
+          .active_playlist_index
+          .and_then(|index| self.all_playlists.get(index))
+        {
+          let playlist_id = playlist.id.clone().into_static();
+          self.dispatch(IoEvent::FetchAllPlaylistTracksAndSort(playlist_id));
+        }
+      }
+      SortContext::LikedSongs => {
+        if self.track_table.context != Some(TrackTableContext::SavedTracks) {
+          return;
+        }
+        let rows: Vec<TrackRow> = self
+          .track_table
+          .tracks
+          .iter()
+          .enumerate()
+          .map(|(row, track)| {
+            let added_at = self.track_table.added.get(row).and_then(|added| added.at);
+            TrackRow {
+              track,
+              added_at,
+              // Liked songs come newest first
+              natural_key: added_at.map_or(0, |at| -at.timestamp_millis()),
+            }
+          })
+          .collect();
+        let order = sorter.order(&rows);
+        self.track_table.tracks = reorder(std::mem::take(&mut self.track_table.tracks), &order);
+        self.track_table.added = reorder(std::mem::take(&mut self.track_table.added), &order);
+        self.visual_anchor = None;
+      }
+      SortContext::SavedAlbums => {
+        for page in &mut self.library.saved_albums.pages {
+          sorter.sort(&mut page.items);
+        }
+      }
+      SortContext::SavedArtists => {
+        for page in &mut self.library.saved_artists.pages {
+          sorter.sort(&mut page.items);
+        }
+        sorter.sort(&mut self.artists);
+      }
+      SortContext::RecentlyPlayed => {
+        if let Some(page) = &mut self.recently_played.result {
+          sorter.sort(&mut page.items);
+        }
+      }
+      SortContext::AlbumTracks => match self.album_table_context {
+        AlbumTableContext::Simplified => {
+          if let Some(album) = &mut self.selected_album_simplified {
+            sorter.sort(&mut album.tracks.items);
+          }
+        }
+        AlbumTableContext::Full => {
+          if let Some(album) = &mut self.selected_album_full {
+            sorter.sort(&mut album.album.tracks.items);
+          }
+        }
+      },
+      SortContext::SearchResults => {
+        let Some(page) = &mut self.search_results.tracks else {
+          return;
+        };
+        let ranks = &self.search_results.track_ranks;
+        let rows: Vec<TrackRow> = page
+          .items
+          .iter()
+          .map(|track| TrackRow {
+            track,
+            added_at: None,
+            natural_key: track
+              .id
+              .as_ref()
+              .and_then(|id| ranks.get(id.id()))
+              .map_or(i64::MAX, |rank| *rank as i64),
+          })
+          .collect();
+        let order = sorter.order(&rows);
+        page.items = reorder(std::mem::take(&mut page.items), &order);
+      }
+    }
+  }
+
+  /// Load the session of the last run to reopen its view, returning what was playing when
+  /// playback should resume too
+  pub fn start_session(&mut self) -> Option<SessionPlayback> {
+    if !self.user_config.behavior.restore_session {
+      return None;
+    }
+    let session = self.session_store.load()?;
+    let playback = session
+      .playback
+      .clone()
+      .filter(|_| self.user_config.behavior.resume_playback_on_start);
+    self.session_to_restore = Some(session);
+    self.session_restore_deadline = Some(Instant::now() + session::PLAYLIST_WAIT);
+    playback
+  }
+
+  pub fn save_session(&mut self) {
+    if self.user_config.behavior.restore_session {
+      let current = session::capture(self);
+      self.session_store.save(current);
     }
   }
 
@@ -1782,88 +2463,460 @@ impl App {
     }
   }
 
-  pub fn shuffle(&mut self) {
-    if let Some(context) = &self.current_playback_context.clone() {
-      let new_shuffle_state = !context.shuffle_state;
-      info!("toggling shuffle: {}", new_shuffle_state);
-
-      // Use native streaming player for instant control (bypasses event channel latency)
-      #[cfg(feature = "streaming")]
-      if self.is_native_streaming_active_for_playback() {
-        if let Some(ref player) = self.streaming_player {
-          // Try to set shuffle on the native player
-          let _ = player.set_shuffle(new_shuffle_state);
+  /// Open the picker and list the local outputs in the background. Only meaningful with the
+  /// native player running.
+  pub fn open_audio_output_picker(&mut self) {
+    #[cfg(feature = "streaming")]
+    if self.streaming_player.is_some() {
+      self.audio_output_choices.clear();
+      self.audio_output_selected_index = 0;
+      self.audio_outputs_loading = true;
+      self.push_navigation_stack(
+        RouteId::Dialog,
+        ActiveBlock::Dialog(DialogContext::AudioOutputPicker),
+      );
+      self.dispatch(IoEvent::GetAudioOutputs);
+      return;
+    }
 
-          // Update UI state immediately
-          if let Some(ctx) = &mut self.current_playback_context {
-            ctx.shuffle_state = new_shuffle_state;
-          }
-          self.user_config.behavior.shuffle_enabled = new_shuffle_state;
-          let _ = self.user_config.save_config();
+    self.set_status_message("Audio output selection needs native streaming", 4);
+  }
 
-          // Notify MPRIS clients of the change
-          #[cfg(all(feature = "mpris", target_os = "linux"))]
-          if let Some(ref mpris) = self.mpris_manager {
-            mpris.set_shuffle(new_shuffle_state);
-          }
-          return;
-        }
-      }
+  /// Fill the picker once the outputs are listed, with the one in use selected
+  #[cfg(feature = "streaming")]
+  pub fn set_audio_output_choices(&mut self, choices: Vec<AudioOutputChoice>) {
+    self.audio_outputs_loading = false;
+    self.audio_output_selected_index = self
+      .streaming_player
+      .as_ref()
+      .and_then(|player| {
+        let current = player.audio_output();
+        choices.iter().position(|choice| {
+          current.backend.as_deref() == Some(choice.backend.as_str())
+            && current.device == choice.device
+        })
+      })
+      .unwrap_or(0);
+    self.audio_output_choices = choices;
+  }
 
-      // Fallback to API-based shuffle for external devices
-      self.dispatch(IoEvent::Shuffle(new_shuffle_state));
+  /// Switch the native player to the chosen output and remember it for next time
+  pub fn select_audio_output(&mut self, index: usize) {
+    let Some(choice) = self.audio_output_choices.get(index).cloned() else {
+      return;
     };
-  }
 
-  pub fn get_current_user_saved_albums_next(&mut self) {
-    match self
-      .library
-      .saved_albums
-      .get_results(Some(self.library.saved_albums.index + 1))
-      .cloned()
-    {
-      Some(_) => self.library.saved_albums.index += 1,
-      None => {
-        if let Some(saved_albums) = &self.library.saved_albums.get_results(None) {
-          let offset = Some(saved_albums.offset + saved_albums.limit);
-          self.dispatch(IoEvent::GetCurrentUserSavedAlbums(offset));
-        }
-      }
+    #[cfg(feature = "streaming")]
+    if let Some(ref player) = self.streaming_player {
+      player.set_audio_output(Some(choice.backend.clone()), choice.device.clone());
+    }
+
+    self.client_config.streaming_audio_backend = Some(choice.backend);
+    self.client_config.streaming_audio_device = choice.device;
+    if let Err(e) = self.client_config.save_ui_settings() {
+      self.handle_error(anyhow!("Failed to save audio output: {}", e));
+      return;
     }
+    self.set_status_message(format!("Audio output: {}", choice.label), 4);
   }
 
-  pub fn get_current_user_saved_albums_previous(&mut self) {
-    if self.library.saved_albums.index > 0 {
-      self.library.saved_albums.index -= 1;
+  /// Recolor the theme from the current cover when the adaptive preset is selected
+  #[cfg(feature = "cover-art")]
+  pub fn apply_adaptive_theme(&mut self) {
+    if !self.user_config.behavior.adaptive_theme {
+      return;
+    }
+    if let Some(palette) = self.cover_art.palette() {
+      self.user_config.theme = palette.to_theme();
     }
   }
 
-  pub fn current_user_saved_album_delete(&mut self, block: ActiveBlock) {
-    info!("removing album from saved albums");
-    match block {
-      ActiveBlock::SearchResultBlock => {
-        if let Some(albums) = &self.search_results.albums {
-          if let Some(selected_index) = self.search_results.selected_album_index {
-            let selected_album = &albums.items[selected_index];
-            if let Some(album_id) = selected_album.id.clone() {
-              self.dispatch(IoEvent::CurrentUserSavedAlbumDelete(album_id.into_static()));
-            }
-          }
+  /// Start fetching the cover thumbnails of the albums selected in the visible lists
+  #[cfg(feature = "cover-art")]
+  pub fn request_cover_thumbnails(&mut self) {
+    use crate::tui::cover_art::thumbnail_url;
+
+    if !self.user_config.behavior.draw_cover_thumbnails {
+      return;
+    }
+
+    let mut images = Vec::new();
+    match self.get_current_route().id {
+      RouteId::AlbumList => {
+        if let Some(saved) = self
+          .library
+          .saved_albums
+          .get_results(None)
+          .and_then(|page| page.items.get(self.album_list_index))
+        {
+          images.push(saved.album.images.as_slice());
         }
       }
-      ActiveBlock::AlbumList => {
-        if let Some(albums) = self.library.saved_albums.get_results(None) {
-          if let Some(selected_album) = albums.items.get(self.album_list_index) {
-            let album_id = selected_album.album.id.clone();
-            self.dispatch(IoEvent::CurrentUserSavedAlbumDelete(album_id.into_static()));
-          }
+      RouteId::Search => {
+        let results = &self.search_results;
+        if let Some(album) = results
+          .albums
+          .as_ref()
+          .zip(results.selected_album_index)
+          .and_then(|(albums, index)| albums.items.get(index))
+        {
+          images.push(album.images.as_slice());
+        }
+        if let Some(track) = results
+          .tracks
+          .as_ref()
+          .zip(results.selected_tracks_index)
+          .and_then(|(tracks, index)| tracks.items.get(index))
+        {
+          images.push(track.album.images.as_slice());
         }
       }
-      ActiveBlock::ArtistBlock => {
-        if let Some(artist) = &self.artist {
-          if let Some(selected_album) = artist.albums.items.get(artist.selected_album_index) {
+      _ => {}
+    }
+
+    let urls: Vec<String> = images
+      .into_iter()
+      .filter_map(thumbnail_url)
+      .filter(|url| self.cover_art.request_thumbnail(url))
+      .map(str::to_string)
+      .collect();
+    for url in urls {
+      self.dispatch(IoEvent::GetCoverThumbnail(url));
+    }
+  }
+
+  /// Open the lyrics search popup for the playing track, prefilled and already searching
+  pub fn open_lyrics_search(&mut self) {
+    let Some(CurrentPlaybackContext {
+      item: Some(PlayableItem::Track(track)),
+      ..
+    }) = &self.current_playback_context
+    else {
+      self.set_status_message("Lyrics search needs a playing track", 4);
+      return;
+    };
+    let Some(track_id) = track.id.as_ref().map(|id| id.id().to_string()) else {
+      self.set_status_message("Lyrics search needs a playing track", 4);
+      return;
+    };
+
+    self.lyrics_search = LyricsSearchState {
+      track_id,
+      duration_secs: track.duration.num_seconds() as f64,
+      title: track.name.clone(),
+      artist: track
+        .artists
+        .first()
+        .map(|artist| artist.name.clone())
+        .unwrap_or_default(),
+      ..Default::default()
+    };
+    self.push_navigation_stack(
+      RouteId::Dialog,
+      ActiveBlock::Dialog(DialogContext::LyricsSearch),
+    );
+    self.search_lyrics();
+  }
+
+  pub fn search_lyrics(&mut self) {
+    let search = &mut self.lyrics_search;
+    if search.title.trim().is_empty() {
+      return;
+    }
+    search.searching = true;
+    search.error = None;
+    search.focus = LyricsSearchFocus::Results;
+    let (title, artist) = (search.title.clone(), search.artist.clone());
+    self.dispatch(IoEvent::SearchLyrics(title, artist));
+  }
+
+  /// Name of the view whose key bindings apply right now
+  pub fn key_view(&self) -> &'static str {
+    let block = self.get_current_route().active_block;
+    match block {
+      ActiveBlock::BasicView | ActiveBlock::NowPlaying if self.lyrics_sync.is_some() => {
+        "lyrics_sync"
+      }
+      _ => keymap::view_name(block),
+    }
+  }
+
+  /// Bare id of the playing track. Native playback events store it as a `spotify:track:` URI,
+  /// while the API and the lyrics cache use the bare id.
+  pub fn playing_track_id(&self) -> Option<&str> {
+    let id = self.last_track_id.as_deref()?;
+    Some(id.strip_prefix("spotify:track:").unwrap_or(id))
+  }
+
+  /// Text fields of the lyrics search take every key, including the global ones
+  pub fn is_editing_lyrics_search(&self) -> bool {
+    self.get_current_route().active_block == ActiveBlock::Dialog(DialogContext::LyricsSearch)
+      && self.lyrics_search.focus != LyricsSearchFocus::Results
+  }
+
+  /// Pin the selected search result to the track and show it straight away
+  pub fn pin_lyrics_search_result(&mut self) {
+    let search = &self.lyrics_search;
+    let Some(record) = search.results.get(search.selected_index).cloned() else {
+      return;
+    };
+    let track_id = search.track_id.clone();
+    let Some(fetched) = record.into_lyrics() else {
+      self.set_status_message("That result has no lyrics", 4);
+      return;
+    };
+
+    if let Err(e) = LyricsCache::new().pin(&track_id, &fetched) {
+      self.handle_error(anyhow!("Failed to save lyrics: {}", e));
+      return;
+    }
+
+    if self.playing_track_id() == Some(track_id.as_str()) {
+      let (lines, synced) = lyrics::parse_lyrics(&fetched.text);
+      self.lyrics = Some(lines);
+      self.lyrics_synced = synced;
+      self.lyrics_source = Some(fetched.source);
+      self.lyrics_status = LyricsStatus::Found;
+    }
+    self.set_status_message("Lyrics pinned to this track", 4);
+  }
+
+  /// Nudge the lyrics of the playing track later (positive) or earlier and remember it
+  pub fn adjust_lyrics_offset(&mut self, delta_ms: i64) {
+    let Some(track_id) = self.playing_track_id().map(str::to_string) else {
+      return;
+    };
+    self.lyrics_offset_ms += delta_ms;
+    if let Err(e) = LyricsCache::new().set_offset_ms(&track_id, self.lyrics_offset_ms) {
+      self.handle_error(anyhow!("Failed to save lyrics offset: {}", e));
+      return;
+    }
+    self.set_status_message(
+      format!(
+        "Lyrics offset: {:+.1}s",
+        self.lyrics_offset_ms as f64 / 1000.0
+      ),
+      2,
+    );
+  }
+
+  /// Start timing the current lyrics line by line. Lines that already have a time keep it.
+  pub fn open_lyrics_sync_editor(&mut self) {
+    let (Some(lines), LyricsStatus::Found) = (&self.lyrics, &self.lyrics_status) else {
+      self.set_status_message("No lyrics to sync for this track", 4);
+      return;
+    };
+    let Some(CurrentPlaybackContext {
+      item: Some(PlayableItem::Track(track)),
+      ..
+    }) = &self.current_playback_context
+    else {
+      return;
+    };
+    let Some(track_id) = track.id.as_ref().map(|id| id.id().to_string()) else {
+      return;
+    };
+    if self.user_config.lyrics.local_dir.is_none() {
+      self.set_status_message("Set lyrics.local_dir to save synced lyrics", 4);
+      return;
+    }
+
+    let synced = self.lyrics_synced;
+    self.lyrics_sync = Some(LyricsSyncEditor {
+      track_id,
+      track: track.name.clone(),
+      artist: track
+        .artists
+        .first()
+        .map(|artist| artist.name.clone())
+        .unwrap_or_default(),
+      duration_ms: track.duration.num_milliseconds().max(0) as u128,
+      lines: lines.iter().map(|line| line.text.clone()).collect(),
+      stamps: lines
+        .iter()
+        .map(|line| synced.then_some(line.time_ms))
+        .collect(),
+      ..Default::default()
+    });
+  }
+
+  /// Stamp the selected line with the playback position, as long as it is still the same track
+  pub fn stamp_lyrics_line(&mut self) {
+    let progress_ms = self.song_progress_ms;
+    let same_track = self.playing_track_id().map(str::to_string);
+    let Some(editor) = self.lyrics_sync.as_mut() else {
+      return;
+    };
+    if same_track.as_deref() != Some(editor.track_id.as_str()) {
+      let message = format!("Play \"{}\" to keep syncing", editor.track);
+      self.set_status_message(message, 4);
+      return;
+    }
+    editor.stamp(progress_ms);
+  }
+
+  /// Write the stamped lines as `Artist - Title.lrc` into the local lyrics directory
+  pub fn save_lyrics_sync(&mut self) {
+    let Some(editor) = self.lyrics_sync.as_ref() else {
+      return;
+    };
+    let unstamped = editor.unstamped_count();
+    if unstamped > 0 {
+      self.set_status_message(format!("{} lines still need a time", unstamped), 4);
+      return;
+    }
+    let Some(dir) = self.user_config.lyrics.local_dir.clone() else {
+      return;
+    };
+
+    let file_name: String = format!("{} - {}.lrc", editor.artist, editor.track)
+      .chars()
+      .map(|c| match c {
+        '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+        c => c,
+      })
+      .collect();
+    let path = dir.join(file_name);
+    let text = editor.to_lrc();
+    let track_id = editor.track_id.clone();
+    if let Err(e) = std::fs::create_dir_all(&dir).and_then(|_| std::fs::write(&path, &text)) {
+      self.handle_error(anyhow!("Failed to save {}: {}", path.display(), e));
+      return;
+    }
+
+    // Pin it as well, so it also beats lyrics pinned earlier or cached from LRCLIB
+    let fetched = FetchedLyrics {
+      text,
+      synced: true,
+      source: "local".to_string(),
+    };
+    if let Err(e) = LyricsCache::new().pin(&track_id, &fetched) {
+      self.handle_error(anyhow!("Failed to pin saved lyrics: {}", e));
+    }
+
+    if self.playing_track_id() == Some(track_id.as_str()) {
+      let (lines, synced) = lyrics::parse_lyrics(&fetched.text);
+      self.lyrics = Some(lines);
+      self.lyrics_synced = synced;
+      self.lyrics_source = Some(fetched.source);
+      self.lyrics_status = LyricsStatus::Found;
+    }
+    self.lyrics_sync = None;
+    self.set_status_message(format!("Saved {}", path.display()), 4);
+  }
+
+  /// Playback position in lyrics time, with the track's offset applied
+  pub fn lyrics_position_ms(&self) -> u128 {
+    (self.song_progress_ms as i128 - i128::from(self.lyrics_offset_ms)).max(0) as u128
+  }
+
+  pub fn toggle_night_mode(&mut self) {
+    let enabled = !self.user_config.behavior.night_mode;
+    self.set_night_mode(enabled);
+    let _ = self.user_config.save_config();
+
+    let message = match (enabled, self.is_streaming_active) {
+      (true, true) => "Night mode on",
+      (true, false) => "Night mode on (only affects native playback)",
+      (false, _) => "Night mode off",
+    };
+    self.set_status_message(message, 4);
+  }
+
+  fn set_night_mode(&mut self, enabled: bool) {
+    self.user_config.behavior.night_mode = enabled;
+
+    #[cfg(feature = "streaming")]
+    if let Some(ref player) = self.streaming_player {
+      player.set_night_mode(enabled);
+    }
+  }
+
+  pub fn shuffle(&mut self) {
+    if let Some(context) = &self.current_playback_context.clone() {
+      let new_shuffle_state = !context.shuffle_state;
+      info!("toggling shuffle: {}", new_shuffle_state);
+
+      // Use native streaming player for instant control (bypasses event channel latency)
+      #[cfg(feature = "streaming")]
+      if self.is_native_streaming_active_for_playback() {
+        if let Some(ref player) = self.streaming_player {
+          // Try to set shuffle on the native player
+          let _ = player.set_shuffle(new_shuffle_state);
+
+          // Update UI state immediately
+          if let Some(ctx) = &mut self.current_playback_context {
+            ctx.shuffle_state = new_shuffle_state;
+          }
+          self.user_config.behavior.shuffle_enabled = new_shuffle_state;
+          let _ = self.user_config.save_config();
+
+          // Notify MPRIS clients of the change
+          #[cfg(all(feature = "mpris", target_os = "linux"))]
+          if let Some(ref mpris) = self.mpris_manager {
+            mpris.set_shuffle(new_shuffle_state);
+          }
+          return;
+        }
+      }
+
+      // Fallback to API-based shuffle for external devices
+      self.dispatch(IoEvent::Shuffle(new_shuffle_state));
+    };
+  }
+
+  pub fn get_current_user_saved_albums_next(&mut self) {
+    match self
+      .library
+      .saved_albums
+      .get_results(Some(self.library.saved_albums.index + 1))
+      .cloned()
+    {
+      Some(_) => self.library.saved_albums.index += 1,
+      None => {
+        if let Some(saved_albums) = &self.library.saved_albums.get_results(None) {
+          let offset = Some(saved_albums.offset + saved_albums.limit);
+          self.dispatch(IoEvent::GetCurrentUserSavedAlbums(offset));
+        }
+      }
+    }
+  }
+
+  pub fn get_current_user_saved_albums_previous(&mut self) {
+    if self.library.saved_albums.index > 0 {
+      self.library.saved_albums.index -= 1;
+    }
+  }
+
+  pub fn current_user_saved_album_delete(&mut self, block: ActiveBlock) {
+    info!("removing album from saved albums");
+    match block {
+      ActiveBlock::SearchResultBlock => {
+        if let Some(albums) = &self.search_results.albums {
+          if let Some(selected_index) = self.search_results.selected_album_index {
+            let selected_album = &albums.items[selected_index];
+            if let Some(album_id) = selected_album.id.clone() {
+              let name = selected_album.name.clone();
+              self.remove_saved_album(album_id.into_static(), name);
+            }
+          }
+        }
+      }
+      ActiveBlock::AlbumList => {
+        if let Some(albums) = self.library.saved_albums.get_results(None) {
+          if let Some(selected_album) = albums.items.get(self.album_list_index) {
+            let album_id = selected_album.album.id.clone();
+            let name = selected_album.album.name.clone();
+            self.remove_saved_album(album_id.into_static(), name);
+          }
+        }
+      }
+      ActiveBlock::ArtistBlock => {
+        if let Some(artist) = &self.artist {
+          if let Some(selected_album) = artist.albums.items.get(artist.selected_album_index) {
             if let Some(album_id) = selected_album.id.clone() {
-              self.dispatch(IoEvent::CurrentUserSavedAlbumDelete(album_id.into_static()));
+              let name = selected_album.name.clone();
+              self.remove_saved_album(album_id.into_static(), name);
             }
           }
         }
@@ -1872,6 +2925,11 @@ impl App {
     }
   }
 
+  fn remove_saved_album(&mut self, id: AlbumId<'static>, name: String) {
+    self.dispatch(IoEvent::CurrentUserSavedAlbumDelete(id.clone()));
+    self.record_undo(UndoOp::RemovedAlbum { id, name });
+  }
+
   pub fn current_user_saved_album_add(&mut self, block: ActiveBlock) {
     info!("adding album to saved albums");
     match block {
@@ -1948,39 +3006,32 @@ impl App {
 
   pub fn user_unfollow_artists(&mut self, block: ActiveBlock) {
     info!("unfollowing artist");
-    match block {
-      ActiveBlock::SearchResultBlock => {
-        if let Some(artists) = &self.search_results.artists {
-          if let Some(selected_index) = self.search_results.selected_artists_index {
-            let selected_artist: &FullArtist = &artists.items[selected_index];
-            self.dispatch(IoEvent::UserUnfollowArtists(vec![selected_artist
-              .id
-              .clone()
-              .into_static()]));
-          }
-        }
-      }
-      ActiveBlock::AlbumList => {
-        if let Some(artists) = self.library.saved_artists.get_results(None) {
-          if let Some(selected_artist) = artists.items.get(self.artists_list_index) {
-            self.dispatch(IoEvent::UserUnfollowArtists(vec![selected_artist
-              .id
-              .clone()
-              .into_static()]));
-          }
-        }
-      }
-      ActiveBlock::ArtistBlock => {
-        if let Some(artist) = &self.artist {
-          let selected_artis = &artist.related_artists[artist.selected_related_artist_index];
-          self.dispatch(IoEvent::UserUnfollowArtists(vec![selected_artis
-            .id
-            .clone()
-            .into_static()]));
-        }
-      }
-      _ => (),
+    let selected_artist = match block {
+      ActiveBlock::SearchResultBlock => match (
+        &self.search_results.artists,
+        self.search_results.selected_artists_index,
+      ) {
+        (Some(artists), Some(selected_index)) => artists.items.get(selected_index),
+        _ => None,
+      },
+      ActiveBlock::AlbumList => self
+        .library
+        .saved_artists
+        .get_results(None)
+        .and_then(|artists| artists.items.get(self.artists_list_index)),
+      ActiveBlock::ArtistBlock => self.artist.as_ref().and_then(|artist| {
+        artist
+          .related_artists
+          .get(artist.selected_related_artist_index)
+      }),
+      _ => None,
     };
+    if let Some(selected_artist) = selected_artist {
+      let id = selected_artist.id.clone().into_static();
+      let name = selected_artist.name.clone();
+      self.dispatch(IoEvent::UserUnfollowArtists(vec![id.clone()]));
+      self.record_undo(UndoOp::UnfollowedArtist { id, name });
+    }
   }
 
   pub fn user_follow_artists(&mut self, block: ActiveBlock) {
@@ -2037,12 +3088,14 @@ impl App {
         self.get_playlist_display_item_at(selected_index)
       {
         if let Some(playlist) = self.all_playlists.get(*index) {
+          let op = UndoOp::unfollowed_playlist(playlist);
           let selected_id = playlist.id.clone();
           let user_id = user.id.clone();
           self.dispatch(IoEvent::UserUnfollowPlaylist(
             user_id.into_static(),
             selected_id.into_static(),
           ));
+          self.record_undo(op);
         }
       }
     }
@@ -2056,12 +3109,14 @@ impl App {
       &self.user,
     ) {
       let selected_playlist = &playlists.items[selected_index];
+      let op = UndoOp::unfollowed_playlist(selected_playlist);
       let selected_id = selected_playlist.id.clone();
       let user_id = user.id.clone();
       self.dispatch(IoEvent::UserUnfollowPlaylist(
         user_id.into_static(),
         selected_id.into_static(),
       ));
+      self.record_undo(op);
     }
   }
 
@@ -2097,39 +3152,123 @@ impl App {
 
   pub fn user_unfollow_show(&mut self, block: ActiveBlock) {
     info!("unfollowing show");
-    match block {
-      ActiveBlock::Podcasts => {
-        if let Some(shows) = self.library.saved_shows.get_results(None) {
-          if let Some(selected_show) = shows.items.get(self.shows_list_index) {
-            let show_id = selected_show.show.id.clone();
-            self.dispatch(IoEvent::CurrentUserSavedShowDelete(show_id.into_static()));
-          }
+    let selected_show = match block {
+      ActiveBlock::Podcasts => self
+        .library
+        .saved_shows
+        .get_results(None)
+        .and_then(|shows| shows.items.get(self.shows_list_index))
+        .map(|selected_show| {
+          (
+            selected_show.show.id.clone(),
+            selected_show.show.name.clone(),
+          )
+        }),
+      ActiveBlock::SearchResultBlock => match (
+        &self.search_results.shows,
+        self.search_results.selected_shows_index,
+      ) {
+        (Some(shows), Some(selected_index)) => shows
+          .items
+          .get(selected_index)
+          .map(|show| (show.id.clone(), show.name.clone())),
+        _ => None,
+      },
+      ActiveBlock::EpisodeTable => match self.episode_table_context {
+        EpisodeTableContext::Full => self
+          .selected_show_full
+          .as_ref()
+          .map(|selected| (selected.show.id.clone(), selected.show.name.clone())),
+        EpisodeTableContext::Simplified => self
+          .selected_show_simplified
+          .as_ref()
+          .map(|selected| (selected.show.id.clone(), selected.show.name.clone())),
+      },
+      _ => None,
+    };
+    if let Some((show_id, name)) = selected_show {
+      let id = show_id.into_static();
+      self.dispatch(IoEvent::CurrentUserSavedShowDelete(id.clone()));
+      self.record_undo(UndoOp::UnfollowedShow { id, name });
+    }
+  }
+
+  /// Remember a change for undo and say how to take it back
+  pub fn record_undo(&mut self, op: UndoOp) {
+    let key = self.user_config.keys.display(Action::Undo);
+    let message = if key.is_empty() {
+      op.description()
+    } else {
+      format!("{} — press {} to undo", op.description(), key)
+    };
+    self.set_status_message(message, 6);
+    self.undo_history.push(op);
+  }
+
+  /// Reverse the entry `index` places from the newest one in the undo history
+  pub fn undo(&mut self, index: usize) {
+    let Some(op) = self.undo_history.take(index) else {
+      self.set_status_message("Nothing to undo", 3);
+      return;
+    };
+    info!("undoing: {}", op.description());
+    match &op {
+      UndoOp::RemovedFromPlaylist {
+        playlist_id,
+        track_id,
+        position,
+        ..
+      } => self.dispatch(IoEvent::RestoreTrackToPlaylist(
+        playlist_id.clone(),
+        track_id.clone(),
+        *position,
+      )),
+      UndoOp::Unliked { id, .. } => {
+        let id_str = match id {
+          PlayableId::Track(id) => id.id(),
+          PlayableId::Episode(id) => id.id(),
+        };
+        // Liked again by hand since, toggling now would unlike it
+        if !self.liked_song_ids_set.contains(id_str) {
+          self.dispatch(IoEvent::ToggleSaveTrack(id.clone()));
         }
       }
-      ActiveBlock::SearchResultBlock => {
-        if let Some(shows) = &self.search_results.shows {
-          if let Some(selected_index) = self.search_results.selected_shows_index {
-            let show_id = shows.items[selected_index].id.clone();
-            self.dispatch(IoEvent::CurrentUserSavedShowDelete(show_id.into_static()));
-          }
-        }
+      UndoOp::UnfollowedArtist { id, .. } => {
+        self.dispatch(IoEvent::UserFollowArtists(vec![id.clone()]));
+      }
+      UndoOp::UnfollowedPlaylist {
+        owner_id,
+        id,
+        public,
+        ..
+      } => {
+        self.dispatch(IoEvent::UserFollowPlaylist(
+          owner_id.clone(),
+          id.clone(),
+          *public,
+        ));
+        self.dispatch(IoEvent::GetPlaylists);
+      }
+      UndoOp::RemovedAlbum { id, .. } => {
+        self.dispatch(IoEvent::CurrentUserSavedAlbumAdd(id.clone()));
+      }
+      UndoOp::UnfollowedShow { id, .. } => {
+        self.dispatch(IoEvent::CurrentUserSavedShowAdd(id.clone()));
       }
-      ActiveBlock::EpisodeTable => match self.episode_table_context {
-        EpisodeTableContext::Full => {
-          if let Some(selected_episode) = self.selected_show_full.clone() {
-            let show_id = selected_episode.show.id;
-            self.dispatch(IoEvent::CurrentUserSavedShowDelete(show_id.into_static()));
-          }
-        }
-        EpisodeTableContext::Simplified => {
-          if let Some(selected_episode) = self.selected_show_simplified.clone() {
-            let show_id = selected_episode.show.id;
-            self.dispatch(IoEvent::CurrentUserSavedShowDelete(show_id.into_static()));
-          }
-        }
-      },
-      _ => (),
     }
+    self.set_status_message(format!("Undone: {}", op.description()), 4);
+  }
+
+  pub fn open_undo_history(&mut self) {
+    if self.undo_history.is_empty() {
+      self.set_status_message("Nothing to undo", 3);
+      return;
+    }
+    self.undo_history_selected_index = 0;
+    self.push_navigation_stack(
+      RouteId::Dialog,
+      ActiveBlock::Dialog(DialogContext::UndoHistory),
+    );
   }
 
   /// Toggle the audio analysis visualization view
@@ -2216,28 +3355,6 @@ impl App {
 
   /// Load settings for the current category into settings_items
   pub fn load_settings_for_category(&mut self) {
-    use crate::event::Key;
-
-    // Helper to convert Key to displayable string
-    fn key_to_string(key: &Key) -> String {
-      match key {
-        Key::Char(c) => c.to_string(),
-        Key::Ctrl(c) => format!("ctrl-{}", c),
-        Key::Alt(c) => format!("alt-{}", c),
-        Key::Enter => "enter".to_string(),
-        Key::Esc => "esc".to_string(),
-        Key::Backspace => "backspace".to_string(),
-        Key::Delete => "del".to_string(),
-        Key::Left => "left".to_string(),
-        Key::Right => "right".to_string(),
-        Key::Up => "up".to_string(),
-        Key::Down => "down".to_string(),
-        Key::PageUp => "pageup".to_string(),
-        Key::PageDown => "pagedown".to_string(),
-        _ => "unknown".to_string(),
-      }
-    }
-
     self.settings_items = match self.settings_category {
       SettingsCategory::Behavior => vec![
         SettingItem {
@@ -2272,233 +3389,127 @@ impl App {
         },
         SettingItem {
           id: "behavior.enforce_wide_search_bar".to_string(),
-          name: "Wide Search Bar".to_string(),
-          description: "Force search bar to take full width".to_string(),
-          value: SettingValue::Bool(self.user_config.behavior.enforce_wide_search_bar),
-        },
-        SettingItem {
-          id: "behavior.set_window_title".to_string(),
-          name: "Set Window Title".to_string(),
-          description: "Update terminal window title with track info".to_string(),
-          value: SettingValue::Bool(self.user_config.behavior.set_window_title),
-        },
-        SettingItem {
-          id: "behavior.enable_discord_rpc".to_string(),
-          name: "Discord Rich Presence".to_string(),
-          description: "Show your current track in Discord".to_string(),
-          value: SettingValue::Bool(self.user_config.behavior.enable_discord_rpc),
-        },
-        SettingItem {
-          id: "behavior.enable_announcements".to_string(),
-          name: "Remote Announcements".to_string(),
-          description: "Show one-time announcements from remote JSON feed".to_string(),
-          value: SettingValue::Bool(self.user_config.behavior.enable_announcements),
-        },
-        SettingItem {
-          id: "behavior.announcement_feed_url".to_string(),
-          name: "Announcements Feed URL".to_string(),
-          description: "Remote JSON feed URL (HTTPS)".to_string(),
-          value: SettingValue::String(
-            self
-              .user_config
-              .behavior
-              .announcement_feed_url
-              .clone()
-              .unwrap_or_default(),
-          ),
-        },
-        SettingItem {
-          id: "behavior.liked_icon".to_string(),
-          name: "Liked Icon".to_string(),
-          description: "Icon for liked songs".to_string(),
-          value: SettingValue::String(self.user_config.behavior.liked_icon.clone()),
-        },
-        SettingItem {
-          id: "behavior.shuffle_icon".to_string(),
-          name: "Shuffle Icon".to_string(),
-          description: "Icon for shuffle mode".to_string(),
-          value: SettingValue::String(self.user_config.behavior.shuffle_icon.clone()),
-        },
-        SettingItem {
-          id: "behavior.playing_icon".to_string(),
-          name: "Playing Icon".to_string(),
-          description: "Icon for playing state".to_string(),
-          value: SettingValue::String(self.user_config.behavior.playing_icon.clone()),
-        },
-        SettingItem {
-          id: "behavior.paused_icon".to_string(),
-          name: "Paused Icon".to_string(),
-          description: "Icon for paused state".to_string(),
-          value: SettingValue::String(self.user_config.behavior.paused_icon.clone()),
-        },
-        #[cfg(feature = "cover-art")]
-        SettingItem {
-          id: "behavior.draw_cover_art".to_string(),
-          name: "Draw Cover Art".to_string(),
-          description: "Enable rendering song/episode cover art".to_string(),
-          value: SettingValue::Bool(self.user_config.behavior.draw_cover_art),
-        },
-        #[cfg(feature = "cover-art")]
-        SettingItem {
-          id: "behavior.draw_cover_art_forced".to_string(),
-          name: "Force Draw Cover Art".to_string(),
-          description: "Force rendering of cover art despite terminal support".to_string(),
-          value: SettingValue::Bool(self.user_config.behavior.draw_cover_art_forced),
-        },
-      ],
-      SettingsCategory::Keybindings => vec![
-        SettingItem {
-          id: "keys.back".to_string(),
-          name: "Back".to_string(),
-          description: "Go back / quit".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.back)),
-        },
-        SettingItem {
-          id: "keys.next_page".to_string(),
-          name: "Next Page".to_string(),
-          description: "Navigate to next page".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.next_page)),
-        },
-        SettingItem {
-          id: "keys.previous_page".to_string(),
-          name: "Previous Page".to_string(),
-          description: "Navigate to previous page".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.previous_page)),
-        },
-        SettingItem {
-          id: "keys.toggle_playback".to_string(),
-          name: "Toggle Playback".to_string(),
-          description: "Play/pause".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.toggle_playback)),
-        },
-        SettingItem {
-          id: "keys.seek_backwards".to_string(),
-          name: "Seek Backwards".to_string(),
-          description: "Seek backwards in track".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.seek_backwards)),
-        },
-        SettingItem {
-          id: "keys.seek_forwards".to_string(),
-          name: "Seek Forwards".to_string(),
-          description: "Seek forwards in track".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.seek_forwards)),
-        },
-        SettingItem {
-          id: "keys.next_track".to_string(),
-          name: "Next Track".to_string(),
-          description: "Skip to next track".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.next_track)),
-        },
-        SettingItem {
-          id: "keys.previous_track".to_string(),
-          name: "Previous Track".to_string(),
-          description: "Go to previous track".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.previous_track)),
-        },
-        SettingItem {
-          id: "keys.shuffle".to_string(),
-          name: "Shuffle".to_string(),
-          description: "Toggle shuffle mode".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.shuffle)),
-        },
-        SettingItem {
-          id: "keys.repeat".to_string(),
-          name: "Repeat".to_string(),
-          description: "Cycle repeat mode".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.repeat)),
-        },
-        SettingItem {
-          id: "keys.search".to_string(),
-          name: "Search".to_string(),
-          description: "Open search".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.search)),
-        },
-        SettingItem {
-          id: "keys.help".to_string(),
-          name: "Help".to_string(),
-          description: "Show help menu".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.help)),
+          name: "Wide Search Bar".to_string(),
+          description: "Force search bar to take full width".to_string(),
+          value: SettingValue::Bool(self.user_config.behavior.enforce_wide_search_bar),
         },
         SettingItem {
-          id: "keys.open_settings".to_string(),
-          name: "Open Settings".to_string(),
-          description: "Open settings menu".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.open_settings)),
+          id: "behavior.set_window_title".to_string(),
+          name: "Set Window Title".to_string(),
+          description: "Update terminal window title with track info".to_string(),
+          value: SettingValue::Bool(self.user_config.behavior.set_window_title),
         },
         SettingItem {
-          id: "keys.save_settings".to_string(),
-          name: "Save Settings".to_string(),
-          description: "Save settings to file".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.save_settings)),
+          id: "behavior.restore_session".to_string(),
+          name: "Restore Session".to_string(),
+          description: "Reopen the last view and selection on start".to_string(),
+          value: SettingValue::Bool(self.user_config.behavior.restore_session),
         },
         SettingItem {
-          id: "keys.jump_to_album".to_string(),
-          name: "Jump to Album".to_string(),
-          description: "Jump to currently playing album".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.jump_to_album)),
+          id: "behavior.resume_playback_on_start".to_string(),
+          name: "Resume Playback on Start".to_string(),
+          description: "Load the last track, paused, when native streaming starts".to_string(),
+          value: SettingValue::Bool(self.user_config.behavior.resume_playback_on_start),
         },
         SettingItem {
-          id: "keys.jump_to_artist_album".to_string(),
-          name: "Jump to Artist".to_string(),
-          description: "Jump to artist's albums".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.jump_to_artist_album)),
+          id: "behavior.liked_icon".to_string(),
+          name: "Liked Icon".to_string(),
+          description: "Icon for liked songs".to_string(),
+          value: SettingValue::String(self.user_config.behavior.liked_icon.clone()),
         },
         SettingItem {
-          id: "keys.jump_to_context".to_string(),
-          name: "Jump to Context".to_string(),
-          description: "Jump to current playback context".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.jump_to_context)),
+          id: "behavior.shuffle_icon".to_string(),
+          name: "Shuffle Icon".to_string(),
+          description: "Icon for shuffle mode".to_string(),
+          value: SettingValue::String(self.user_config.behavior.shuffle_icon.clone()),
         },
         SettingItem {
-          id: "keys.manage_devices".to_string(),
-          name: "Manage Devices".to_string(),
-          description: "Open device selection".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.manage_devices)),
+          id: "behavior.playing_icon".to_string(),
+          name: "Playing Icon".to_string(),
+          description: "Icon for playing state".to_string(),
+          value: SettingValue::String(self.user_config.behavior.playing_icon.clone()),
         },
         SettingItem {
-          id: "keys.decrease_volume".to_string(),
-          name: "Decrease Volume".to_string(),
-          description: "Decrease playback volume".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.decrease_volume)),
+          id: "behavior.paused_icon".to_string(),
+          name: "Paused Icon".to_string(),
+          description: "Icon for paused state".to_string(),
+          value: SettingValue::String(self.user_config.behavior.paused_icon.clone()),
         },
+        #[cfg(feature = "cover-art")]
         SettingItem {
-          id: "keys.increase_volume".to_string(),
-          name: "Increase Volume".to_string(),
-          description: "Increase playback volume".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.increase_volume)),
+          id: "behavior.draw_cover_art".to_string(),
+          name: "Draw Cover Art".to_string(),
+          description: "Enable rendering song/episode cover art".to_string(),
+          value: SettingValue::Bool(self.user_config.behavior.draw_cover_art),
         },
+        #[cfg(feature = "cover-art")]
         SettingItem {
-          id: "keys.add_item_to_queue".to_string(),
-          name: "Add to Queue".to_string(),
-          description: "Add selected item to queue".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.add_item_to_queue)),
+          id: "behavior.draw_cover_art_forced".to_string(),
+          name: "Force Draw Cover Art".to_string(),
+          description: "Force rendering of cover art despite terminal support".to_string(),
+          value: SettingValue::Bool(self.user_config.behavior.draw_cover_art_forced),
         },
+        #[cfg(feature = "cover-art")]
         SettingItem {
-          id: "keys.copy_song_url".to_string(),
-          name: "Copy Song URL".to_string(),
-          description: "Copy current song URL to clipboard".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.copy_song_url)),
+          id: "behavior.draw_cover_art_blocks".to_string(),
+          name: "Draw Cover Art With Blocks".to_string(),
+          description: "Draw cover art with Unicode blocks when there is no image protocol"
+            .to_string(),
+          value: SettingValue::Bool(self.user_config.behavior.draw_cover_art_blocks),
         },
+        #[cfg(feature = "cover-art")]
         SettingItem {
-          id: "keys.copy_album_url".to_string(),
-          name: "Copy Album URL".to_string(),
-          description: "Copy current album URL to clipboard".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.copy_album_url)),
+          id: "behavior.cover_art_fallback".to_string(),
+          name: "Cover Art Fallback".to_string(),
+          description: "Half blocks or braille in terminals without an image protocol".to_string(),
+          value: SettingValue::Preset(
+            self
+              .user_config
+              .behavior
+              .cover_art_fallback
+              .name()
+              .to_string(),
+          ),
         },
+        #[cfg(feature = "cover-art")]
         SettingItem {
-          id: "keys.audio_analysis".to_string(),
-          name: "Audio Analysis".to_string(),
-          description: "Open audio analysis view".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.audio_analysis)),
+          id: "behavior.cover_art_cache_mb".to_string(),
+          name: "Cover Cache Size (MB)".to_string(),
+          description: "Disk space for downloaded covers, 0 to disable".to_string(),
+          value: SettingValue::Number(self.user_config.behavior.cover_art_cache_mb as i64),
         },
+        #[cfg(feature = "cover-art")]
         SettingItem {
-          id: "keys.basic_view".to_string(),
-          name: "Basic View".to_string(),
-          description: "Open lyrics/basic view".to_string(),
-          value: SettingValue::Key(key_to_string(&self.user_config.keys.basic_view)),
+          id: "behavior.draw_cover_thumbnails".to_string(),
+          name: "Cover Thumbnails".to_string(),
+          description: "Show the selected album's cover in album lists and search".to_string(),
+          value: SettingValue::Bool(self.user_config.behavior.draw_cover_thumbnails),
         },
       ],
+      SettingsCategory::Keybindings => Action::ALL
+        .iter()
+        .filter(|action| **action != Action::Submit && keymap::is_default_global(**action))
+        .map(|action| SettingItem {
+          id: format!("keys.{}", action.name()),
+          name: action
+            .name()
+            .split('_')
+            .map(|word| {
+              let mut chars = word.chars();
+              chars
+                .next()
+                .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+          description: action.description().to_string(),
+          value: SettingValue::Key(self.user_config.keys.first_binding(*action)),
+        })
+        .collect(),
       SettingsCategory::Theme => {
+        use crate::core::user_config::ThemePreset;
+
         fn color_to_string(color: ratatui::style::Color) -> String {
           match color {
             ratatui::style::Color::Rgb(r, g, b) => format!("{},{},{}", r, g, b),
@@ -2523,12 +3534,31 @@ impl App {
           }
         }
 
+        let current_preset = self
+          .user_config
+          .behavior
+          .theme_file
+          .as_deref()
+          .unwrap_or(ThemePreset::Default.name());
+        #[cfg(feature = "cover-art")]
+        let current_preset = if self.user_config.behavior.adaptive_theme {
+          ThemePreset::Adaptive.name()
+        } else {
+          current_preset
+        };
+
         vec![
           SettingItem {
             id: "theme.preset".to_string(),
             name: "Theme Preset".to_string(),
             description: "Choose a preset theme or customize below".to_string(),
-            value: SettingValue::Preset("Default (Cyan)".to_string()), // Default preset
+            value: SettingValue::Preset(current_preset.to_string()),
+          },
+          SettingItem {
+            id: "behavior.color_support".to_string(),
+            name: "Color Support".to_string(),
+            description: "Colors the terminal can show, Auto detects them".to_string(),
+            value: SettingValue::Preset(self.user_config.behavior.color_support.name().to_string()),
           },
           SettingItem {
             id: "theme.active".to_string(),
@@ -2598,13 +3628,311 @@ impl App {
           },
         ]
       }
+      SettingsCategory::Streaming => vec![
+        SettingItem {
+          id: "streaming.enabled".to_string(),
+          name: "Native Streaming".to_string(),
+          description: "Play audio in spotatui as a Spotify Connect device".to_string(),
+          value: SettingValue::Bool(self.client_config.enable_streaming),
+        },
+        SettingItem {
+          id: "streaming.device_name".to_string(),
+          name: "Device Name".to_string(),
+          description: "Name shown in Spotify Connect device lists".to_string(),
+          value: SettingValue::String(self.client_config.streaming_device_name.clone()),
+        },
+        SettingItem {
+          id: "streaming.bitrate".to_string(),
+          name: "Bitrate (kbps)".to_string(),
+          description: "Audio quality: 96, 160 or 320".to_string(),
+          value: SettingValue::Preset(self.client_config.streaming_bitrate.to_string()),
+        },
+        SettingItem {
+          id: "streaming.audio_cache".to_string(),
+          name: "Audio Cache".to_string(),
+          description: "Keep played audio on disk to save bandwidth".to_string(),
+          value: SettingValue::Bool(self.client_config.streaming_audio_cache),
+        },
+        SettingItem {
+          id: "streaming.normalisation".to_string(),
+          name: "Volume Normalisation".to_string(),
+          description: "Even out loudness between tracks".to_string(),
+          value: SettingValue::Bool(self.client_config.streaming_normalisation),
+        },
+        SettingItem {
+          id: "streaming.normalisation_mode".to_string(),
+          name: "Normalisation Mode".to_string(),
+          description: "Track or album gain; Auto picks album gain for albums".to_string(),
+          value: SettingValue::Preset(
+            self
+              .client_config
+              .streaming_normalisation_mode
+              .name()
+              .to_string(),
+          ),
+        },
+        SettingItem {
+          id: "streaming.normalisation_pregain".to_string(),
+          name: "Pregain (dB)".to_string(),
+          description: "Gain added on top of the normalised level (-15 to +15)".to_string(),
+          value: SettingValue::Number(
+            self
+              .client_config
+              .streaming_normalisation_pregain_db
+              .round() as i64,
+          ),
+        },
+        SettingItem {
+          id: "streaming.normalisation_threshold".to_string(),
+          name: "Limiter Threshold (dBFS)".to_string(),
+          description: "Level where the limiter starts to act (-30 to 0)".to_string(),
+          value: SettingValue::Number(
+            self
+              .client_config
+              .streaming_normalisation_threshold_dbfs
+              .round() as i64,
+          ),
+        },
+        SettingItem {
+          id: "streaming.normalisation_attack".to_string(),
+          name: "Limiter Attack (ms)".to_string(),
+          description: "How quickly the limiter reacts to peaks (1-500)".to_string(),
+          value: SettingValue::Number(self.client_config.streaming_normalisation_attack_ms as i64),
+        },
+        SettingItem {
+          id: "streaming.normalisation_release".to_string(),
+          name: "Limiter Release (ms)".to_string(),
+          description: "How quickly the limiter lets go after a peak (1-1000)".to_string(),
+          value: SettingValue::Number(self.client_config.streaming_normalisation_release_ms as i64),
+        },
+        SettingItem {
+          id: "behavior.night_mode".to_string(),
+          name: "Night Mode".to_string(),
+          description: "Compress loud and quiet passages for low-volume listening".to_string(),
+          value: SettingValue::Bool(self.user_config.behavior.night_mode),
+        },
+      ],
+      SettingsCategory::Integrations => vec![
+        SettingItem {
+          id: "behavior.enable_discord_rpc".to_string(),
+          name: "Discord Rich Presence".to_string(),
+          description: "Show your current track in Discord".to_string(),
+          value: SettingValue::Bool(self.user_config.behavior.enable_discord_rpc),
+        },
+        SettingItem {
+          id: "behavior.discord_rpc_client_id".to_string(),
+          name: "Discord Application ID".to_string(),
+          description: "Your own Discord application, empty for spotatui's".to_string(),
+          value: SettingValue::String(
+            self
+              .user_config
+              .behavior
+              .discord_rpc_client_id
+              .clone()
+              .unwrap_or_default(),
+          ),
+        },
+        SettingItem {
+          id: "behavior.enable_global_song_count".to_string(),
+          name: "Global Song Count".to_string(),
+          description: "Count your plays towards the spotatui-wide song counter".to_string(),
+          value: SettingValue::Bool(self.user_config.behavior.enable_global_song_count),
+        },
+        SettingItem {
+          id: "behavior.enable_announcements".to_string(),
+          name: "Remote Announcements".to_string(),
+          description: "Show one-time announcements from remote JSON feed".to_string(),
+          value: SettingValue::Bool(self.user_config.behavior.enable_announcements),
+        },
+        SettingItem {
+          id: "behavior.announcement_feed_url".to_string(),
+          name: "Announcements Feed URL".to_string(),
+          description: "Remote JSON feed URL (HTTPS)".to_string(),
+          value: SettingValue::String(
+            self
+              .user_config
+              .behavior
+              .announcement_feed_url
+              .clone()
+              .unwrap_or_default(),
+          ),
+        },
+        SettingItem {
+          id: "behavior.enable_notifications".to_string(),
+          name: "Notifications".to_string(),
+          description: "Announce events while spotatui is in the background".to_string(),
+          value: SettingValue::Bool(self.user_config.behavior.enable_notifications),
+        },
+        SettingItem {
+          id: "behavior.notification_method".to_string(),
+          name: "Notification Method".to_string(),
+          description: "OSC 9, OSC 777, desktop (D-Bus) or terminal bell".to_string(),
+          value: SettingValue::Preset(
+            self
+              .user_config
+              .behavior
+              .notification_method
+              .name()
+              .to_string(),
+          ),
+        },
+        SettingItem {
+          id: "behavior.notify_on_track_change".to_string(),
+          name: "Notify on Track Change".to_string(),
+          description: "Send a notification when a new track starts".to_string(),
+          value: SettingValue::Bool(self.user_config.behavior.notify_on_track_change),
+        },
+        SettingItem {
+          id: "behavior.notify_on_liked".to_string(),
+          name: "Notify on Like".to_string(),
+          description: "Send a notification when a track is saved to Liked Songs".to_string(),
+          value: SettingValue::Bool(self.user_config.behavior.notify_on_liked),
+        },
+        SettingItem {
+          id: "behavior.notify_on_error".to_string(),
+          name: "Notify on Error".to_string(),
+          description: "Send a notification when an error occurs".to_string(),
+          value: SettingValue::Bool(self.user_config.behavior.notify_on_error),
+        },
+      ],
+      SettingsCategory::Account => vec![
+        SettingItem {
+          id: "account.client_id".to_string(),
+          name: "Client ID".to_string(),
+          description: "Spotify app client ID, 32 hex digits".to_string(),
+          value: SettingValue::String(self.client_config.client_id.clone()),
+        },
+        SettingItem {
+          id: "account.fallback_client_id".to_string(),
+          name: "Fallback Client ID".to_string(),
+          description: "Client ID tried when the first one is rejected, empty for none".to_string(),
+          value: SettingValue::String(
+            self
+              .client_config
+              .fallback_client_id
+              .clone()
+              .unwrap_or_default(),
+          ),
+        },
+        SettingItem {
+          id: "account.port".to_string(),
+          name: "Redirect Port".to_string(),
+          description: "Port of the local auth callback, must match the app's redirect URI"
+            .to_string(),
+          value: SettingValue::Number(self.client_config.get_port() as i64),
+        },
+      ],
+      SettingsCategory::Equalizer => {
+        use crate::core::user_config::EQ_BAND_FREQUENCIES;
+
+        let equalizer = &self.user_config.equalizer;
+        let mut items = vec![
+          SettingItem {
+            id: "eq.enabled".to_string(),
+            name: "Equalizer".to_string(),
+            description: "Apply the equalizer to native playback".to_string(),
+            value: SettingValue::Bool(equalizer.enabled),
+          },
+          SettingItem {
+            id: "eq.preset".to_string(),
+            name: "Preset".to_string(),
+            description: "Built-in curve, or Custom after editing a band".to_string(),
+            value: SettingValue::Preset(equalizer.preset.name().to_string()),
+          },
+        ];
+        items.extend(EQ_BAND_FREQUENCIES.iter().enumerate().map(|(band, hz)| {
+          let label = if *hz >= 1000.0 {
+            format!("{} kHz (dB)", hz / 1000.0)
+          } else {
+            format!("{} Hz (dB)", hz)
+          };
+          SettingItem {
+            id: format!("eq.band.{}", band),
+            name: label,
+            description: "Band gain in dB (-12 to +12)".to_string(),
+            value: SettingValue::Number(equalizer.gains_db[band].round() as i64),
+          }
+        }));
+        items
+      }
     };
     self.settings_selected_index = 0;
   }
 
+  /// Apply Equalizer settings edits immediately so they can be heard before saving.
+  ///
+  /// Picking a preset rewrites the band items; editing a band switches the preset to Custom.
+  pub fn preview_equalizer_settings(&mut self) {
+    use crate::core::user_config::{EqualizerPreset, EQ_MAX_GAIN_DB};
+
+    if self.settings_category != SettingsCategory::Equalizer {
+      return;
+    }
+
+    let mut enabled = self.user_config.equalizer.enabled;
+    let mut preset = self.user_config.equalizer.preset;
+    let mut gains = self.user_config.equalizer.gains_db;
+    let max_gain = EQ_MAX_GAIN_DB as i64;
+
+    for setting in &mut self.settings_items {
+      match (setting.id.as_str(), &mut setting.value) {
+        ("eq.enabled", SettingValue::Bool(v)) => enabled = *v,
+        ("eq.preset", SettingValue::Preset(v)) => preset = EqualizerPreset::from_name(v),
+        (id, SettingValue::Number(v)) => {
+          if let Some(band) = id
+            .strip_prefix("eq.band.")
+            .and_then(|band| band.parse::<usize>().ok())
+            .filter(|band| *band < gains.len())
+          {
+            *v = (*v).clamp(-max_gain, max_gain);
+            gains[band] = *v as f32;
+          }
+        }
+        _ => {}
+      }
+    }
+
+    let equalizer = &mut self.user_config.equalizer;
+    if preset != equalizer.preset && preset != EqualizerPreset::Custom {
+      equalizer.set_preset(preset);
+    } else if gains != equalizer.gains_db {
+      equalizer.preset = EqualizerPreset::Custom;
+      equalizer.gains_db = gains;
+    } else {
+      equalizer.preset = preset;
+    }
+    equalizer.enabled = enabled;
+
+    // Reflect preset changes back into the list
+    let preset_name = equalizer.preset.name().to_string();
+    let gains = equalizer.gains_db;
+    for setting in &mut self.settings_items {
+      if setting.id == "eq.preset" {
+        setting.value = SettingValue::Preset(preset_name.clone());
+      } else if let Some(band) = setting
+        .id
+        .strip_prefix("eq.band.")
+        .and_then(|band| band.parse::<usize>().ok())
+        .filter(|band| *band < gains.len())
+      {
+        setting.value = SettingValue::Number(gains[band].round() as i64);
+      }
+    }
+
+    #[cfg(feature = "streaming")]
+    if let Some(ref player) = self.streaming_player {
+      player.set_equalizer(self.user_config.equalizer.active_gains());
+    }
+  }
+
   /// Apply changes from settings_items back to user_config
   pub fn apply_settings_changes(&mut self) {
-    for setting in &self.settings_items {
+    self.preview_equalizer_settings();
+
+    // Taken out for the loop so settings can call back into `self`
+    let settings_items = std::mem::take(&mut self.settings_items);
+    let mut rebinds = Vec::new();
+    for setting in &settings_items {
       match setting.id.as_str() {
         // Behavior settings
         "behavior.seek_milliseconds" => {
@@ -2642,6 +3970,16 @@ impl App {
             self.user_config.behavior.set_window_title = *v;
           }
         }
+        "behavior.restore_session" => {
+          if let SettingValue::Bool(v) = &setting.value {
+            self.user_config.behavior.restore_session = *v;
+          }
+        }
+        "behavior.resume_playback_on_start" => {
+          if let SettingValue::Bool(v) = &setting.value {
+            self.user_config.behavior.resume_playback_on_start = *v;
+          }
+        }
         "behavior.enable_discord_rpc" => {
           if let SettingValue::Bool(v) = &setting.value {
             self.user_config.behavior.enable_discord_rpc = *v;
@@ -2682,192 +4020,169 @@ impl App {
             self.user_config.behavior.paused_icon = v.clone();
           }
         }
-        #[cfg(feature = "cover-art")]
-        "behavior.draw_cover_art" => {
-          if let SettingValue::Bool(v) = setting.value {
-            self.user_config.behavior.draw_cover_art = v;
+        "behavior.enable_notifications" => {
+          if let SettingValue::Bool(v) = &setting.value {
+            self.user_config.behavior.enable_notifications = *v;
           }
         }
-        #[cfg(feature = "cover-art")]
-        "behavior.draw_cover_art_forced" => {
-          if let SettingValue::Bool(v) = setting.value {
-            self.user_config.behavior.draw_cover_art_forced = v;
+        "behavior.color_support" => {
+          if let SettingValue::Preset(v) = &setting.value {
+            self.user_config.behavior.color_support = ColorSupport::from_name(v);
+            self.color_support = self.user_config.behavior.color_support.resolve();
           }
         }
-        // Keybindings
-        "keys.back" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.back = key;
-            }
+        "behavior.notification_method" => {
+          if let SettingValue::Preset(v) = &setting.value {
+            self.user_config.behavior.notification_method =
+              crate::core::user_config::NotificationMethod::from_name(v);
           }
         }
-        "keys.next_page" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.next_page = key;
-            }
+        "behavior.notify_on_track_change" => {
+          if let SettingValue::Bool(v) = &setting.value {
+            self.user_config.behavior.notify_on_track_change = *v;
           }
         }
-        "keys.previous_page" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.previous_page = key;
-            }
+        "behavior.notify_on_liked" => {
+          if let SettingValue::Bool(v) = &setting.value {
+            self.user_config.behavior.notify_on_liked = *v;
           }
         }
-        "keys.toggle_playback" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.toggle_playback = key;
-            }
+        "behavior.notify_on_error" => {
+          if let SettingValue::Bool(v) = &setting.value {
+            self.user_config.behavior.notify_on_error = *v;
           }
         }
-        "keys.seek_backwards" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.seek_backwards = key;
-            }
+        #[cfg(feature = "cover-art")]
+        "behavior.draw_cover_art" => {
+          if let SettingValue::Bool(v) = setting.value {
+            self.user_config.behavior.draw_cover_art = v;
           }
         }
-        "keys.seek_forwards" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.seek_forwards = key;
-            }
+        #[cfg(feature = "cover-art")]
+        "behavior.draw_cover_art_forced" => {
+          if let SettingValue::Bool(v) = setting.value {
+            self.user_config.behavior.draw_cover_art_forced = v;
           }
         }
-        "keys.next_track" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.next_track = key;
-            }
+        #[cfg(feature = "cover-art")]
+        "behavior.draw_cover_art_blocks" => {
+          if let SettingValue::Bool(v) = setting.value {
+            self.user_config.behavior.draw_cover_art_blocks = v;
           }
         }
-        "keys.previous_track" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.previous_track = key;
-            }
+        #[cfg(feature = "cover-art")]
+        "behavior.cover_art_fallback" => {
+          if let SettingValue::Preset(v) = &setting.value {
+            self.user_config.behavior.cover_art_fallback =
+              crate::core::user_config::CoverArtFallback::from_name(v);
           }
         }
-        "keys.shuffle" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.shuffle = key;
-            }
+        #[cfg(feature = "cover-art")]
+        "behavior.cover_art_cache_mb" => {
+          if let SettingValue::Number(v) = &setting.value {
+            self.user_config.behavior.cover_art_cache_mb = (*v).max(0) as u64;
           }
         }
-        "keys.repeat" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.repeat = key;
-            }
+        #[cfg(feature = "cover-art")]
+        "behavior.draw_cover_thumbnails" => {
+          if let SettingValue::Bool(v) = setting.value {
+            self.user_config.behavior.draw_cover_thumbnails = v;
           }
         }
-        "keys.search" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.search = key;
+        // Keybindings
+        id if id.starts_with("keys.") => {
+          let action = Action::from_name(&id["keys.".len()..]);
+          if let (Some(action), SettingValue::Key(v)) = (action, &setting.value) {
+            // Only rebind what changed, rebinding replaces all keys of the action
+            if *v != self.user_config.keys.first_binding(action) {
+              rebinds.push((action, v.clone()));
             }
           }
         }
-        "keys.help" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.help = key;
-            }
+        "streaming.enabled" => {
+          if let SettingValue::Bool(v) = &setting.value {
+            self.client_config.enable_streaming = *v;
           }
         }
-        "keys.open_settings" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.open_settings = key;
-            }
+        "streaming.device_name" => {
+          if let SettingValue::String(v) = &setting.value {
+            self.client_config.streaming_device_name = v.trim().to_string();
           }
         }
-        "keys.save_settings" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.save_settings = key;
+        "streaming.bitrate" => {
+          if let SettingValue::Preset(v) = &setting.value {
+            if let Ok(bitrate) = v.parse() {
+              self.client_config.streaming_bitrate = bitrate;
             }
           }
         }
-        "keys.jump_to_album" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.jump_to_album = key;
-            }
+        "streaming.audio_cache" => {
+          if let SettingValue::Bool(v) = &setting.value {
+            self.client_config.streaming_audio_cache = *v;
           }
         }
-        "keys.jump_to_artist_album" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.jump_to_artist_album = key;
-            }
+        "account.client_id" => {
+          if let SettingValue::String(v) = &setting.value {
+            self.client_config.client_id = v.trim().to_string();
           }
         }
-        "keys.jump_to_context" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.jump_to_context = key;
-            }
+        "account.fallback_client_id" => {
+          if let SettingValue::String(v) = &setting.value {
+            let v = v.trim();
+            self.client_config.fallback_client_id = (!v.is_empty()).then(|| v.to_string());
           }
         }
-        "keys.manage_devices" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.manage_devices = key;
-            }
+        "account.port" => {
+          if let SettingValue::Number(v) = &setting.value {
+            self.client_config.port = Some((*v).clamp(1, 65535) as u16);
           }
         }
-        "keys.decrease_volume" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.decrease_volume = key;
-            }
+        "behavior.discord_rpc_client_id" => {
+          if let SettingValue::String(v) = &setting.value {
+            let v = v.trim();
+            self.user_config.behavior.discord_rpc_client_id =
+              (!v.is_empty()).then(|| v.to_string());
           }
         }
-        "keys.increase_volume" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.increase_volume = key;
-            }
+        "behavior.enable_global_song_count" => {
+          if let SettingValue::Bool(v) = &setting.value {
+            self.user_config.behavior.enable_global_song_count = *v;
           }
         }
-        "keys.add_item_to_queue" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.add_item_to_queue = key;
-            }
+        "streaming.normalisation" => {
+          if let SettingValue::Bool(v) = &setting.value {
+            self.client_config.streaming_normalisation = *v;
           }
         }
-        "keys.copy_song_url" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.copy_song_url = key;
-            }
+        "streaming.normalisation_mode" => {
+          if let SettingValue::Preset(v) = &setting.value {
+            self.client_config.streaming_normalisation_mode =
+              crate::core::config::NormalisationMode::from_name(v);
           }
         }
-        "keys.copy_album_url" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.copy_album_url = key;
-            }
+        "streaming.normalisation_pregain" => {
+          if let SettingValue::Number(v) = &setting.value {
+            self.client_config.streaming_normalisation_pregain_db = (*v).clamp(-15, 15) as f64;
           }
         }
-        "keys.audio_analysis" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.audio_analysis = key;
-            }
+        "streaming.normalisation_threshold" => {
+          if let SettingValue::Number(v) = &setting.value {
+            self.client_config.streaming_normalisation_threshold_dbfs = (*v).clamp(-30, 0) as f64;
           }
         }
-        "keys.basic_view" => {
-          if let SettingValue::Key(v) = &setting.value {
-            if let Ok(key) = crate::core::user_config::parse_key_public(v.clone()) {
-              self.user_config.keys.basic_view = key;
-            }
+        "streaming.normalisation_attack" => {
+          if let SettingValue::Number(v) = &setting.value {
+            self.client_config.streaming_normalisation_attack_ms = (*v).clamp(1, 500) as u64;
+          }
+        }
+        "streaming.normalisation_release" => {
+          if let SettingValue::Number(v) = &setting.value {
+            self.client_config.streaming_normalisation_release_ms = (*v).clamp(1, 1000) as u64;
+          }
+        }
+        "behavior.night_mode" => {
+          if let SettingValue::Bool(v) = &setting.value {
+            self.set_night_mode(*v);
           }
         }
         // Theme preset - applies all colors at once
@@ -2875,10 +4190,24 @@ impl App {
           if let SettingValue::Preset(preset_name) = &setting.value {
             use crate::core::user_config::ThemePreset;
             let preset = ThemePreset::from_name(preset_name);
-            if preset != ThemePreset::Custom {
+            // Theme files take precedence so they can replace a built-in preset
+            let theme_file = self
+              .user_config
+              .theme_files
+              .iter()
+              .find(|file| &file.name == preset_name);
+            if let Some(file) = theme_file {
+              self.user_config.theme = file.theme;
+            } else if preset != ThemePreset::Custom {
               // Apply the preset's theme colors
               self.user_config.theme = preset.to_theme();
             }
+            self.user_config.behavior.theme_file = theme_file.map(|file| file.name.clone());
+            #[cfg(feature = "cover-art")]
+            {
+              self.user_config.behavior.adaptive_theme = preset == ThemePreset::Adaptive;
+              self.apply_adaptive_theme();
+            }
           }
         }
         // Note: Individual color changes and keybindings require more complex parsing
@@ -2886,5 +4215,25 @@ impl App {
         _ => {}
       }
     }
+    self.settings_items = settings_items;
+
+    // Free the keys of every changed action first, so swapped bindings don't collide halfway
+    for (action, _) in &rebinds {
+      let _ = self.user_config.keys.bind(None, *action, Vec::new());
+    }
+    for (action, value) in rebinds {
+      let rebound = if value.is_empty() {
+        Ok(())
+      } else if value == keymap::default_binding(action) {
+        self.user_config.keys.reset(None, action)
+      } else {
+        keymap::parse_sequence(&value)
+          .and_then(|keys| self.user_config.keys.bind(None, action, vec![keys]))
+      };
+      if let Err(e) = rebound {
+        self.set_status_message(e.to_string(), 5);
+      }
+    }
+    self.update_help_docs_size();
   }
 }
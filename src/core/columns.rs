@@ -0,0 +1,420 @@
+// Which columns the song, album track and episode tables show, in what order, how wide and how
+// aligned. Set under `columns:` in config.yml, the defaults are the layouts the tables always had.
+
+use anyhow::{anyhow, Result};
+use ratatui::layout::Alignment;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnField {
+  Liked,
+  Title,
+  Artist,
+  Album,
+  Length,
+  /// When the track was added to the playlist or liked songs
+  AddedAt,
+  /// Who added the track, mostly interesting for collaborative playlists
+  AddedBy,
+  Popularity,
+  ReleaseDate,
+  ReleaseYear,
+  TrackNumber,
+  Disc,
+  Explicit,
+  Isrc,
+  /// How often the track was started in spotatui
+  PlayCount,
+  /// Whether an episode was played to the end
+  Played,
+}
+
+impl ColumnField {
+  /// Name in config.yml
+  pub fn name(self) -> &'static str {
+    match self {
+      ColumnField::Liked => "liked",
+      ColumnField::Title => "title",
+      ColumnField::Artist => "artist",
+      ColumnField::Album => "album",
+      ColumnField::Length => "length",
+      ColumnField::AddedAt => "added_at",
+      ColumnField::AddedBy => "added_by",
+      ColumnField::Popularity => "popularity",
+      ColumnField::ReleaseDate => "release_date",
+      ColumnField::ReleaseYear => "release_year",
+      ColumnField::TrackNumber => "track_number",
+      ColumnField::Disc => "disc",
+      ColumnField::Explicit => "explicit",
+      ColumnField::Isrc => "isrc",
+      ColumnField::PlayCount => "play_count",
+      ColumnField::Played => "played",
+    }
+  }
+
+  pub fn header(self, table: ColumnTable) -> &'static str {
+    match (self, table) {
+      (ColumnField::Title, ColumnTable::Episodes) => "Name",
+      (ColumnField::Length, ColumnTable::Episodes) => "Duration",
+      (ColumnField::ReleaseDate, ColumnTable::Episodes) => "Date",
+      (ColumnField::Liked | ColumnField::Played, _) => "",
+      (ColumnField::Title, _) => "Title",
+      (ColumnField::Artist, _) => "Artist",
+      (ColumnField::Album, _) => "Album",
+      (ColumnField::Length, _) => "Length",
+      (ColumnField::AddedAt, _) => "Added",
+      (ColumnField::AddedBy, _) => "Added By",
+      (ColumnField::Popularity, _) => "Pop.",
+      (ColumnField::ReleaseDate, _) => "Released",
+      (ColumnField::ReleaseYear, _) => "Year",
+      (ColumnField::TrackNumber, _) => "#",
+      (ColumnField::Disc, _) => "Disc",
+      (ColumnField::Explicit, _) => "E",
+      (ColumnField::Isrc, _) => "ISRC",
+      (ColumnField::PlayCount, _) => "Plays",
+    }
+  }
+
+  fn default_width(self) -> ColumnWidth {
+    match self {
+      ColumnField::Liked | ColumnField::Played | ColumnField::Explicit => ColumnWidth::Cells(2),
+      ColumnField::TrackNumber => ColumnWidth::Cells(3),
+      ColumnField::ReleaseYear | ColumnField::Disc => ColumnWidth::Cells(4),
+      ColumnField::Popularity | ColumnField::PlayCount => ColumnWidth::Cells(5),
+      ColumnField::AddedAt | ColumnField::ReleaseDate => ColumnWidth::Cells(10),
+      ColumnField::Isrc => ColumnWidth::Cells(12),
+      ColumnField::Length => ColumnWidth::Percent(10),
+      ColumnField::AddedBy => ColumnWidth::Percent(15),
+      ColumnField::Title | ColumnField::Artist | ColumnField::Album => ColumnWidth::Percent(30),
+    }
+  }
+
+  fn default_align(self) -> ColumnAlign {
+    match self {
+      ColumnField::Popularity | ColumnField::PlayCount => ColumnAlign::Right,
+      _ => ColumnAlign::Left,
+    }
+  }
+}
+
+/// The tables whose columns can be configured
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColumnTable {
+  /// Playlists, liked songs and the other track lists
+  Songs,
+  AlbumTracks,
+  Episodes,
+}
+
+impl ColumnTable {
+  fn name(self) -> &'static str {
+    match self {
+      ColumnTable::Songs => "songs",
+      ColumnTable::AlbumTracks => "album_tracks",
+      ColumnTable::Episodes => "episodes",
+    }
+  }
+
+  /// Whether the rows of this table have what `field` shows
+  pub fn supports(self, field: ColumnField) -> bool {
+    match self {
+      ColumnTable::Songs => field != ColumnField::Played,
+      ColumnTable::AlbumTracks => matches!(
+        field,
+        ColumnField::Liked
+          | ColumnField::Title
+          | ColumnField::Artist
+          | ColumnField::Length
+          | ColumnField::ReleaseDate
+          | ColumnField::ReleaseYear
+          | ColumnField::TrackNumber
+          | ColumnField::Disc
+          | ColumnField::Explicit
+          | ColumnField::PlayCount
+      ),
+      ColumnTable::Episodes => matches!(
+        field,
+        ColumnField::Played
+          | ColumnField::Title
+          | ColumnField::Length
+          | ColumnField::ReleaseDate
+          | ColumnField::ReleaseYear
+          | ColumnField::Explicit
+      ),
+    }
+  }
+}
+
+/// Written as a number of cells (`12`) or a percentage (`"30%"`) of the width the fixed columns
+/// leave over
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "WidthValue", into = "WidthValue")]
+pub enum ColumnWidth {
+  Cells(u16),
+  Percent(u16),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum WidthValue {
+  Cells(u16),
+  Text(String),
+}
+
+impl TryFrom<WidthValue> for ColumnWidth {
+  type Error = String;
+
+  fn try_from(value: WidthValue) -> Result<Self, Self::Error> {
+    let text = match value {
+      WidthValue::Cells(cells) => return Ok(ColumnWidth::Cells(cells)),
+      WidthValue::Text(text) => text,
+    };
+    let text = text.trim();
+    let width = match text.strip_suffix('%') {
+      Some(percent) => percent
+        .trim()
+        .parse()
+        .ok()
+        .filter(|percent| (1..=100).contains(percent))
+        .map(ColumnWidth::Percent),
+      None => text.parse().ok().map(ColumnWidth::Cells),
+    };
+    width.ok_or_else(|| {
+      format!(
+        "column width `{}` is neither a number of cells nor a percentage like 30%",
+        text
+      )
+    })
+  }
+}
+
+impl From<ColumnWidth> for WidthValue {
+  fn from(width: ColumnWidth) -> Self {
+    match width {
+      ColumnWidth::Cells(cells) => WidthValue::Cells(cells),
+      ColumnWidth::Percent(percent) => WidthValue::Text(format!("{}%", percent)),
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnAlign {
+  #[default]
+  Left,
+  Center,
+  Right,
+}
+
+impl From<ColumnAlign> for Alignment {
+  fn from(align: ColumnAlign) -> Self {
+    match align {
+      ColumnAlign::Left => Alignment::Left,
+      ColumnAlign::Center => Alignment::Center,
+      ColumnAlign::Right => Alignment::Right,
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColumnConfigString {
+  pub field: ColumnField,
+  pub width: Option<ColumnWidth>,
+  pub align: Option<ColumnAlign>,
+}
+
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColumnsConfigString {
+  pub songs: Option<Vec<ColumnConfigString>>,
+  pub album_tracks: Option<Vec<ColumnConfigString>>,
+  pub episodes: Option<Vec<ColumnConfigString>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Column {
+  pub field: ColumnField,
+  pub width: ColumnWidth,
+  pub align: ColumnAlign,
+}
+
+impl Column {
+  fn new(field: ColumnField, width: ColumnWidth) -> Self {
+    Column {
+      field,
+      width,
+      align: field.default_align(),
+    }
+  }
+
+  fn to_config(self) -> ColumnConfigString {
+    ColumnConfigString {
+      field: self.field,
+      width: Some(self.width),
+      align: Some(self.align),
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnsConfig {
+  pub songs: Vec<Column>,
+  pub album_tracks: Vec<Column>,
+  pub episodes: Vec<Column>,
+}
+
+impl Default for ColumnsConfig {
+  fn default() -> Self {
+    use ColumnField::*;
+    use ColumnWidth::*;
+    Self {
+      songs: vec![
+        Column::new(Liked, Cells(2)),
+        Column::new(Title, Percent(30)),
+        Column::new(Artist, Percent(30)),
+        Column::new(Album, Percent(30)),
+        Column::new(Length, Percent(10)),
+      ],
+      album_tracks: vec![
+        Column::new(Liked, Cells(2)),
+        Column::new(TrackNumber, Cells(3)),
+        Column::new(Title, Percent(40)),
+        Column::new(Artist, Percent(40)),
+        Column::new(Length, Percent(20)),
+      ],
+      episodes: vec![
+        Column::new(Played, Cells(2)),
+        Column::new(ReleaseDate, Percent(10)),
+        Column::new(Title, Percent(70)),
+        Column::new(Length, Percent(20)),
+      ],
+    }
+  }
+}
+
+impl ColumnsConfig {
+  pub fn get(&self, table: ColumnTable) -> &[Column] {
+    match table {
+      ColumnTable::Songs => &self.songs,
+      ColumnTable::AlbumTracks => &self.album_tracks,
+      ColumnTable::Episodes => &self.episodes,
+    }
+  }
+
+  pub fn load(&mut self, columns: ColumnsConfigString) -> Result<()> {
+    if let Some(songs) = columns.songs {
+      self.songs = resolve(ColumnTable::Songs, songs)?;
+    }
+    if let Some(album_tracks) = columns.album_tracks {
+      self.album_tracks = resolve(ColumnTable::AlbumTracks, album_tracks)?;
+    }
+    if let Some(episodes) = columns.episodes {
+      self.episodes = resolve(ColumnTable::Episodes, episodes)?;
+    }
+    Ok(())
+  }
+
+  pub fn to_config(&self) -> ColumnsConfigString {
+    let build =
+      |columns: &[Column]| Some(columns.iter().map(|column| column.to_config()).collect());
+    ColumnsConfigString {
+      songs: build(&self.songs),
+      album_tracks: build(&self.album_tracks),
+      episodes: build(&self.episodes),
+    }
+  }
+}
+
+fn resolve(table: ColumnTable, columns: Vec<ColumnConfigString>) -> Result<Vec<Column>> {
+  if columns.is_empty() {
+    return Err(anyhow!(
+      "columns.{} needs at least one column",
+      table.name()
+    ));
+  }
+  columns
+    .into_iter()
+    .map(|column| {
+      if !table.supports(column.field) {
+        return Err(anyhow!(
+          "column `{}` is not available in columns.{}",
+          column.field.name(),
+          table.name()
+        ));
+      }
+      Ok(Column {
+        field: column.field,
+        width: column.width.unwrap_or_else(|| column.field.default_width()),
+        align: column.align.unwrap_or_else(|| column.field.default_align()),
+      })
+    })
+    .collect()
+}
+
+/// Widths in cells of `columns` in a table `width` cells wide
+pub fn resolve_widths(columns: &[Column], width: u16) -> Vec<u16> {
+  // Room taken by the borders and the highlight symbol
+  let available = width.saturating_sub(3);
+  let fixed = columns
+    .iter()
+    .map(|column| match column.width {
+      ColumnWidth::Cells(cells) => cells,
+      ColumnWidth::Percent(_) => 0,
+    })
+    .fold(0u16, u16::saturating_add);
+  let rest = u32::from(available.saturating_sub(fixed));
+  columns
+    .iter()
+    .map(|column| match column.width {
+      ColumnWidth::Cells(cells) => cells,
+      ColumnWidth::Percent(percent) => (rest * u32::from(percent) / 100) as u16,
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn widths_are_cells_or_percentages() {
+    let columns: Vec<ColumnConfigString> = serde_yaml::from_str(
+      "- field: added_at\n  width: 12\n- field: title\n  width: 60%\n  align: right\n- field: isrc",
+    )
+    .unwrap();
+    let songs = resolve(ColumnTable::Songs, columns).unwrap();
+    assert_eq!(songs[0].width, ColumnWidth::Cells(12));
+    assert_eq!(songs[1].width, ColumnWidth::Percent(60));
+    assert_eq!(songs[1].align, ColumnAlign::Right);
+    assert_eq!(songs[2].width, ColumnWidth::Cells(12));
+
+    // 40 cells less 3 for the borders, 24 of which the fixed columns take
+    assert_eq!(resolve_widths(&songs, 40), vec![12, 7, 12]);
+
+    assert!(serde_yaml::from_str::<ColumnWidth>("\"wide\"").is_err());
+    assert!(serde_yaml::from_str::<ColumnWidth>("\"0%\"").is_err());
+  }
+
+  #[test]
+  fn fields_must_fit_the_table() {
+    let mut config = ColumnsConfig::default();
+    let episodes = ColumnsConfigString {
+      episodes: Some(vec![ColumnConfigString {
+        field: ColumnField::AddedBy,
+        width: None,
+        align: None,
+      }]),
+      ..Default::default()
+    };
+    let error = config.load(episodes).unwrap_err();
+    assert_eq!(
+      error.to_string(),
+      "column `added_by` is not available in columns.episodes"
+    );
+
+    let defaults = ColumnsConfig::default();
+    let mut reloaded = ColumnsConfig::default();
+    reloaded.load(defaults.to_config()).unwrap();
+    assert_eq!(reloaded, defaults);
+  }
+}
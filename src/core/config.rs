@@ -37,6 +37,72 @@ pub struct ClientConfig {
   pub streaming_bitrate: u16,
   #[serde(default)]
   pub streaming_audio_cache: bool,
+  /// Local output picked in the audio output picker; `None` uses librespot's default
+  #[serde(default)]
+  pub streaming_audio_backend: Option<String>,
+  #[serde(default)]
+  pub streaming_audio_device: Option<String>,
+  // Volume normalisation (native streaming only)
+  #[serde(default)]
+  pub streaming_normalisation: bool,
+  #[serde(default)]
+  pub streaming_normalisation_mode: NormalisationMode,
+  #[serde(default)]
+  pub streaming_normalisation_pregain_db: f64,
+  #[serde(default = "default_normalisation_threshold_dbfs")]
+  pub streaming_normalisation_threshold_dbfs: f64,
+  #[serde(default = "default_normalisation_attack_ms")]
+  pub streaming_normalisation_attack_ms: u64,
+  #[serde(default = "default_normalisation_release_ms")]
+  pub streaming_normalisation_release_ms: u64,
+}
+
+/// Which ReplayGain value librespot normalises to
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NormalisationMode {
+  /// Album gain while playing an album in order, track gain otherwise
+  #[default]
+  Auto,
+  Track,
+  Album,
+}
+
+impl NormalisationMode {
+  pub fn all() -> &'static [NormalisationMode] {
+    &[
+      NormalisationMode::Auto,
+      NormalisationMode::Track,
+      NormalisationMode::Album,
+    ]
+  }
+
+  pub fn name(&self) -> &'static str {
+    match self {
+      NormalisationMode::Auto => "Auto",
+      NormalisationMode::Track => "Track",
+      NormalisationMode::Album => "Album",
+    }
+  }
+
+  pub fn from_name(name: &str) -> Self {
+    Self::all()
+      .iter()
+      .find(|mode| mode.name().eq_ignore_ascii_case(name))
+      .copied()
+      .unwrap_or_default()
+  }
+
+  pub fn next(&self) -> Self {
+    let modes = Self::all();
+    let current_idx = modes.iter().position(|m| m == self).unwrap_or(0);
+    modes[(current_idx + 1) % modes.len()]
+  }
+
+  pub fn prev(&self) -> Self {
+    let modes = Self::all();
+    let current_idx = modes.iter().position(|m| m == self).unwrap_or(0);
+    modes[(current_idx + modes.len() - 1) % modes.len()]
+  }
 }
 
 fn default_streaming_enabled() -> bool {
@@ -51,6 +117,19 @@ fn default_bitrate() -> u16 {
   320
 }
 
+// Normalisation defaults match librespot's
+fn default_normalisation_threshold_dbfs() -> f64 {
+  -2.0
+}
+
+fn default_normalisation_attack_ms() -> u64 {
+  5
+}
+
+fn default_normalisation_release_ms() -> u64 {
+  100
+}
+
 pub struct ConfigPaths {
   pub config_file_path: PathBuf,
   pub token_cache_path: PathBuf,
@@ -69,6 +148,14 @@ impl ClientConfig {
       streaming_device_name: default_device_name(),
       streaming_bitrate: default_bitrate(),
       streaming_audio_cache: false,
+      streaming_audio_backend: None,
+      streaming_audio_device: None,
+      streaming_normalisation: false,
+      streaming_normalisation_mode: NormalisationMode::default(),
+      streaming_normalisation_pregain_db: 0.0,
+      streaming_normalisation_threshold_dbfs: default_normalisation_threshold_dbfs(),
+      streaming_normalisation_attack_ms: default_normalisation_attack_ms(),
+      streaming_normalisation_release_ms: default_normalisation_release_ms(),
     }
   }
 
@@ -142,6 +229,36 @@ impl ClientConfig {
     Ok(())
   }
 
+  /// Write the settings edited in the UI, leaving the rest of the file
+  /// (client secret, setup version, device id) as it is on disk
+  pub fn save_ui_settings(&self) -> Result<()> {
+    let paths = self.get_or_build_paths()?;
+    let config_string = fs::read_to_string(&paths.config_file_path)?;
+    let mut config_yml: ClientConfig = serde_yaml::from_str(&config_string)?;
+
+    config_yml.client_id = self.client_id.clone();
+    config_yml.fallback_client_id = self.fallback_client_id.clone();
+    config_yml.port = self.port;
+    config_yml.enable_streaming = self.enable_streaming;
+    config_yml.streaming_device_name = self.streaming_device_name.clone();
+    config_yml.streaming_bitrate = self.streaming_bitrate;
+    config_yml.streaming_audio_cache = self.streaming_audio_cache;
+
+    config_yml.streaming_audio_backend = self.streaming_audio_backend.clone();
+    config_yml.streaming_audio_device = self.streaming_audio_device.clone();
+    config_yml.streaming_normalisation = self.streaming_normalisation;
+    config_yml.streaming_normalisation_mode = self.streaming_normalisation_mode;
+    config_yml.streaming_normalisation_pregain_db = self.streaming_normalisation_pregain_db;
+    config_yml.streaming_normalisation_threshold_dbfs = self.streaming_normalisation_threshold_dbfs;
+    config_yml.streaming_normalisation_attack_ms = self.streaming_normalisation_attack_ms;
+    config_yml.streaming_normalisation_release_ms = self.streaming_normalisation_release_ms;
+
+    let new_config = serde_yaml::to_string(&config_yml)?;
+    let mut config_file = fs::File::create(&paths.config_file_path)?;
+    write!(config_file, "{}", new_config)?;
+    Ok(())
+  }
+
   pub fn load_config(&mut self) -> Result<()> {
     let paths = self.get_or_build_paths()?;
     if paths.config_file_path.exists() {
@@ -158,6 +275,15 @@ impl ClientConfig {
       self.streaming_device_name = config_yml.streaming_device_name;
       self.streaming_bitrate = config_yml.streaming_bitrate;
       self.streaming_audio_cache = config_yml.streaming_audio_cache;
+      self.streaming_audio_backend = config_yml.streaming_audio_backend;
+      self.streaming_audio_device = config_yml.streaming_audio_device;
+      self.streaming_normalisation = config_yml.streaming_normalisation;
+      self.streaming_normalisation_mode = config_yml.streaming_normalisation_mode;
+      self.streaming_normalisation_pregain_db = config_yml.streaming_normalisation_pregain_db;
+      self.streaming_normalisation_threshold_dbfs =
+        config_yml.streaming_normalisation_threshold_dbfs;
+      self.streaming_normalisation_attack_ms = config_yml.streaming_normalisation_attack_ms;
+      self.streaming_normalisation_release_ms = config_yml.streaming_normalisation_release_ms;
 
       Ok(())
     } else {
@@ -302,7 +428,7 @@ impl ClientConfig {
     }
   }
 
-  fn validate_client_key(key: &str) -> Result<()> {
+  pub fn validate_client_key(key: &str) -> Result<()> {
     const EXPECTED_LEN: usize = 32;
     if key.len() != EXPECTED_LEN {
       Err(Error::from(std::io::Error::new(
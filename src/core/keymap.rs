@@ -0,0 +1,1125 @@
+//! Action registry for key bindings
+//!
+//! Every command spotatui understands is an [`Action`]. [`KeyBindings`] maps key sequences to
+//! actions, either globally or for a single view, and is what all key handlers dispatch through.
+
+use super::app::ActiveBlock;
+use super::user_config::{check_reserved_keys_public, parse_key_public};
+use crate::event::Key;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+macro_rules! actions {
+  ($($variant:ident => $name:literal, $description:literal;)*) => {
+    /// A command that can be bound to keys
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    pub enum Action {
+      $($variant,)*
+    }
+
+    impl Action {
+      pub const ALL: &'static [Action] = &[$(Action::$variant,)*];
+
+      /// Name of the action in config.yml
+      pub fn name(&self) -> &'static str {
+        match self {
+          $(Action::$variant => $name,)*
+        }
+      }
+
+      pub fn description(&self) -> &'static str {
+        match self {
+          $(Action::$variant => $description,)*
+        }
+      }
+    }
+  };
+}
+
+actions! {
+  Back => "back", "Go back or exit when nowhere left to back to";
+  Forward => "forward", "Go forward again to where back came from";
+  NextPage => "next_page", "Scroll down to next result page";
+  PreviousPage => "previous_page", "Scroll up to previous result page";
+  JumpToStart => "jump_to_start", "Jump to start of playlist";
+  JumpToEnd => "jump_to_end", "Jump to end of playlist";
+  JumpToAlbum => "jump_to_album", "Jump to currently playing album";
+  JumpToArtistAlbum => "jump_to_artist_album", "Jump to currently playing artist's album list";
+  JumpToContext => "jump_to_context", "Jump to current play context";
+  ManageDevices => "manage_devices", "Select device to play music on";
+  DecreaseVolume => "decrease_volume", "Decrease volume";
+  IncreaseVolume => "increase_volume", "Increase volume";
+  TogglePlayback => "toggle_playback", "Pause/Resume playback";
+  SeekBackwards => "seek_backwards", "Seek backwards";
+  SeekForwards => "seek_forwards", "Seek forwards";
+  NextTrack => "next_track", "Skip to next track";
+  PreviousTrack => "previous_track", "Skip to previous track";
+  Help => "help", "Show help menu";
+  Shuffle => "shuffle", "Toggle shuffle";
+  Repeat => "repeat", "Cycle repeat mode";
+  Search => "search", "Enter input for search";
+  Submit => "submit", "Start playback or enter album/artist/playlist";
+  CopySongUrl => "copy_song_url", "Copy url to currently playing song/episode";
+  CopyAlbumUrl => "copy_album_url", "Copy url to currently playing album/show";
+  AudioAnalysis => "audio_analysis", "Go to audio analysis screen";
+  BasicView => "basic_view", "Go to lyrics view";
+  ToggleNightMode => "toggle_night_mode", "Toggle night mode (compress loud and quiet passages)";
+  NowPlaying => "now_playing", "Go to fullscreen now playing view";
+  ToggleMiniMode => "toggle_mini_mode", "Toggle mini player (playbar only)";
+  AddItemToQueue => "add_item_to_queue", "Add track to queue";
+  OpenSettings => "open_settings", "Open settings";
+  SaveSettings => "save_settings", "Save settings";
+  AddPlayingToPlaylist => "add_playing_to_playlist", "Quick-add currently playing track to playlist";
+  MoveUp => "move_up", "Move selection up";
+  MoveDown => "move_down", "Move selection down";
+  MoveLeft => "move_left", "Move selection left";
+  MoveRight => "move_right", "Move selection right";
+  SelectTop => "select_top", "Jump to the top of the visible list";
+  SelectMiddle => "select_middle", "Jump to the middle of the visible list";
+  SelectBottom => "select_bottom", "Jump to the bottom of the visible list";
+  SetMark => "set_mark", "Mark the selected row under the letter typed next";
+  JumpToMark => "jump_to_mark", "Jump to the row marked with the letter typed next";
+  VisualSelect => "visual_select", "Start or stop selecting a range of tracks";
+  Save => "save", "Save (like) the selected track";
+  Follow => "follow", "Save album or follow artist/playlist/show";
+  AddToPlaylist => "add_to_playlist", "Add selected track to a playlist";
+  Remove => "remove", "Delete saved album, unfollow artist/playlist/show";
+  RemoveFromPlaylist => "remove_from_playlist", "Remove selected track from the open playlist";
+  Undo => "undo", "Undo the last removal, unlike or unfollow";
+  UndoHistory => "undo_history", "Show the removals, unlikes and unfollows that can be undone";
+  PlayRandom => "play_random", "Play random song in playlist";
+  Recommendations => "recommendations", "Play recommendations for song/artist";
+  PlayArtist => "play_artist", "Play all tracks of the selected artist";
+  OpenSortMenu => "open_sort_menu", "Open sort menu";
+  ReverseOrder => "reverse_order", "Reverse the order of podcast episodes";
+  CycleVisualizer => "cycle_visualizer", "Cycle visualizer style";
+  AudioOutput => "audio_output", "Choose local audio output for native playback";
+  LyricsSyncEditor => "lyrics_sync_editor", "Open or close the lyrics sync editor";
+  SearchLyrics => "search_lyrics", "Search LRCLIB and pin lyrics to the track";
+  LyricsEarlier => "lyrics_earlier", "Show lyrics 100ms earlier for this track";
+  LyricsLater => "lyrics_later", "Show lyrics 100ms later for this track";
+  ClearLyricsStamp => "clear_lyrics_stamp", "Clear the timestamp of the selected line";
+  PreviewLyricsSync => "preview_lyrics_sync", "Preview the timed lyrics";
+  SaveLyricsSync => "save_lyrics_sync", "Save the timed lyrics as .lrc";
+  PreviousTimeRange => "previous_time_range", "Show top tracks of the previous time range";
+  NextTimeRange => "next_time_range", "Show top tracks of the next time range";
+  NextField => "next_field", "Switch between the fields of a dialog";
+  FilterHelp => "filter_help", "Search the help";
+  ResetBinding => "reset_binding", "Reset the selected key binding to its default";
+  ResetAllBindings => "reset_all_bindings", "Reset every key binding to its default";
+  SortByDefault => "sort_by_default", "Restore the original order";
+  SortByName => "sort_by_name", "Sort by name";
+  SortByDateAdded => "sort_by_date_added", "Sort by date added";
+  SortByArtist => "sort_by_artist", "Sort by artist";
+  SortByDuration => "sort_by_duration", "Sort by duration";
+  SortByAlbum => "sort_by_album", "Sort by album";
+  SortByPopularity => "sort_by_popularity", "Sort by popularity";
+  SortByReleaseDate => "sort_by_release_date", "Sort by release date";
+  SortByTrackNumber => "sort_by_track_number", "Sort by disc and track number";
+  SortByExplicit => "sort_by_explicit", "Sort clean tracks before explicit ones";
+  SortByRandom => "sort_by_random", "Shuffle into a new random order";
+  SortByDefaultDescending => "sort_by_default_descending", "Reverse the original order";
+  SortByNameDescending => "sort_by_name_descending", "Sort by name, descending";
+  SortByDateAddedDescending => "sort_by_date_added_descending", "Sort by date added, descending";
+  SortByArtistDescending => "sort_by_artist_descending", "Sort by artist, descending";
+  SortByDurationDescending => "sort_by_duration_descending", "Sort by duration, descending";
+  SortByAlbumDescending => "sort_by_album_descending", "Sort by album, descending";
+  SortByPopularityDescending => "sort_by_popularity_descending", "Sort by popularity, descending";
+  SortByReleaseDateDescending => "sort_by_release_date_descending", "Sort by release date, newest first";
+  SortByTrackNumberDescending => "sort_by_track_number_descending", "Sort by track number, descending";
+  SortByExplicitDescending => "sort_by_explicit_descending", "Sort explicit tracks first";
+  ToggleSortTieBreaker => "toggle_sort_tie_breaker", "Toggle the highlighted field as a tie-breaker";
+}
+
+impl Action {
+  pub fn from_name(name: &str) -> Option<Action> {
+    Self::ALL
+      .iter()
+      .copied()
+      .find(|action| action.name() == name)
+  }
+}
+
+/// Views that can carry their own bindings, named after the block they belong to
+pub const VIEWS: &[&str] = &[
+  "album_list",
+  "album_tracks",
+  "analysis",
+  "announcement_prompt",
+  "artist",
+  "artists",
+  "basic_view",
+  "dialog",
+  "discover",
+  "empty",
+  "episode_table",
+  "error",
+  "help_menu",
+  "home",
+  "input",
+  "library",
+  "lyrics_sync",
+  "my_playlists",
+  "now_playing",
+  "playbar",
+  "podcasts",
+  "recently_played",
+  "search_results",
+  "select_device",
+  "settings",
+  "sort_menu",
+  "track_table",
+  "update_prompt",
+];
+
+/// The view whose bindings apply while `block` is active
+///
+/// `lyrics_sync` has no block of its own, see `App::key_view`.
+pub fn view_name(block: ActiveBlock) -> &'static str {
+  match block {
+    ActiveBlock::Analysis => "analysis",
+    ActiveBlock::PlayBar => "playbar",
+    ActiveBlock::AlbumTracks => "album_tracks",
+    ActiveBlock::AlbumList => "album_list",
+    ActiveBlock::ArtistBlock => "artist",
+    ActiveBlock::Empty => "empty",
+    ActiveBlock::Error => "error",
+    ActiveBlock::HelpMenu => "help_menu",
+    ActiveBlock::Home => "home",
+    ActiveBlock::Input => "input",
+    ActiveBlock::Library => "library",
+    ActiveBlock::MyPlaylists => "my_playlists",
+    ActiveBlock::Podcasts => "podcasts",
+    ActiveBlock::EpisodeTable => "episode_table",
+    ActiveBlock::RecentlyPlayed => "recently_played",
+    ActiveBlock::SearchResultBlock => "search_results",
+    ActiveBlock::SelectDevice => "select_device",
+    ActiveBlock::TrackTable => "track_table",
+    ActiveBlock::Discover => "discover",
+    ActiveBlock::Artists => "artists",
+    ActiveBlock::BasicView => "basic_view",
+    ActiveBlock::NowPlaying => "now_playing",
+    ActiveBlock::Dialog(_) => "dialog",
+    ActiveBlock::UpdatePrompt => "update_prompt",
+    ActiveBlock::AnnouncementPrompt => "announcement_prompt",
+    ActiveBlock::Settings => "settings",
+    ActiveBlock::SortMenu => "sort_menu",
+  }
+}
+
+fn find_view(name: &str) -> Option<&'static str> {
+  VIEWS.iter().copied().find(|view| *view == name)
+}
+
+/// Keys that keep their meaning whatever the config says, so every view stays usable
+///
+/// Keys reserved for `None` cannot be bound at all.
+pub const RESERVED_KEYS: &[(Key, Option<Action>)] = &[
+  (Key::Up, Some(Action::MoveUp)),
+  (Key::Down, Some(Action::MoveDown)),
+  (Key::Left, Some(Action::MoveLeft)),
+  (Key::Right, Some(Action::MoveRight)),
+  (Key::Enter, Some(Action::Submit)),
+  (Key::Backspace, None),
+  (Key::Esc, None),
+];
+
+fn is_reserved(key: Key) -> bool {
+  RESERVED_KEYS.iter().any(|(reserved, _)| *reserved == key)
+}
+
+// On macOS, use Ctrl+, for settings since Alt+, produces ≤ on most keyboard layouts
+// On other platforms, keep Alt+, for consistency with many apps
+#[cfg(target_os = "macos")]
+const OPEN_SETTINGS: Key = Key::Ctrl(',');
+#[cfg(not(target_os = "macos"))]
+const OPEN_SETTINGS: Key = Key::Alt(',');
+
+const SORT_MENU: &[(Action, &[Key])] = &[
+  (Action::OpenSortMenu, &[Key::Char(',')]),
+  (Action::SortByDefault, &[Key::Char('d')]),
+  (Action::SortByName, &[Key::Char('n')]),
+  (Action::SortByDateAdded, &[Key::Char('a')]),
+  (Action::SortByArtist, &[Key::Char('r')]),
+  (Action::SortByDuration, &[Key::Char('t')]),
+  (Action::SortByAlbum, &[Key::Char('l')]),
+  (Action::SortByDefaultDescending, &[Key::Char('D')]),
+  (Action::SortByNameDescending, &[Key::Char('N')]),
+  (Action::SortByDateAddedDescending, &[Key::Char('A')]),
+  (Action::SortByArtistDescending, &[Key::Char('R')]),
+  (Action::SortByDurationDescending, &[Key::Char('T')]),
+  (Action::SortByAlbumDescending, &[Key::Char('L')]),
+  (Action::SortByPopularity, &[Key::Char('p')]),
+  (Action::SortByReleaseDate, &[Key::Char('y')]),
+  (Action::SortByTrackNumber, &[Key::Char('o')]),
+  (Action::SortByExplicit, &[Key::Char('x')]),
+  (Action::SortByRandom, &[Key::Char('s')]),
+  (Action::SortByPopularityDescending, &[Key::Char('P')]),
+  (Action::SortByReleaseDateDescending, &[Key::Char('Y')]),
+  (Action::SortByTrackNumberDescending, &[Key::Char('O')]),
+  (Action::SortByExplicitDescending, &[Key::Char('X')]),
+  (Action::ToggleSortTieBreaker, &[Key::Char('+')]),
+];
+
+/// Actions with the keys that trigger them in one scope
+type ScopeDefaults = &'static [(Action, &'static [Key])];
+
+/// Default bindings, `None` being the global keymap. Every key listed is a binding of its own.
+const DEFAULTS: &[(Option<&str>, ScopeDefaults)] = &[
+  (
+    None,
+    &[
+      (Action::Back, &[Key::Char('q')]),
+      (Action::Forward, &[Key::Tab]),
+      (Action::NextPage, &[Key::Ctrl('d')]),
+      (Action::PreviousPage, &[Key::Ctrl('u')]),
+      (Action::JumpToStart, &[Key::Ctrl('a')]),
+      (Action::JumpToEnd, &[Key::Ctrl('e')]),
+      (Action::JumpToAlbum, &[Key::Char('a')]),
+      (Action::JumpToArtistAlbum, &[Key::Char('A')]),
+      (Action::JumpToContext, &[Key::Char('o')]),
+      (Action::ManageDevices, &[Key::Char('d')]),
+      (Action::DecreaseVolume, &[Key::Char('-')]),
+      (Action::IncreaseVolume, &[Key::Char('+')]),
+      (Action::TogglePlayback, &[Key::Char(' ')]),
+      (Action::SeekBackwards, &[Key::Char('<')]),
+      (Action::SeekForwards, &[Key::Char('>')]),
+      (Action::NextTrack, &[Key::Char('n')]),
+      (Action::PreviousTrack, &[Key::Char('p')]),
+      (Action::Help, &[Key::Char('?')]),
+      (Action::Shuffle, &[Key::Ctrl('s')]),
+      (Action::Repeat, &[Key::Ctrl('r')]),
+      (Action::Search, &[Key::Char('/')]),
+      (Action::Submit, &[Key::Enter]),
+      (Action::CopySongUrl, &[Key::Char('c')]),
+      (Action::CopyAlbumUrl, &[Key::Char('C')]),
+      (Action::AudioAnalysis, &[Key::Char('v')]),
+      (Action::BasicView, &[Key::Char('B')]),
+      (Action::ToggleNightMode, &[Key::Char('N')]),
+      (Action::NowPlaying, &[Key::Char('F')]),
+      (Action::ToggleMiniMode, &[Key::Alt('m')]),
+      (Action::AddItemToQueue, &[Key::Char('z')]),
+      (Action::OpenSettings, &[OPEN_SETTINGS]),
+      (Action::SaveSettings, &[Key::Alt('s')]),
+      (Action::AddPlayingToPlaylist, &[Key::Char('W')]),
+      (Action::MoveUp, &[Key::Char('k'), Key::Up, Key::Ctrl('p')]),
+      (
+        Action::MoveDown,
+        &[Key::Char('j'), Key::Down, Key::Ctrl('n')],
+      ),
+      (
+        Action::MoveLeft,
+        &[Key::Char('h'), Key::Left, Key::Ctrl('b')],
+      ),
+      (
+        Action::MoveRight,
+        &[Key::Char('l'), Key::Right, Key::Ctrl('f')],
+      ),
+      (Action::SelectTop, &[Key::Char('H')]),
+      (Action::SelectMiddle, &[Key::Char('M')]),
+      (Action::SelectBottom, &[Key::Char('L'), Key::Char('G')]),
+      (Action::SetMark, &[Key::Char('m')]),
+      (Action::JumpToMark, &[Key::Char('\'')]),
+      (Action::Undo, &[Key::Char('u')]),
+      (Action::UndoHistory, &[Key::Char('U')]),
+    ],
+  ),
+  (
+    Some("album_list"),
+    &[
+      (Action::Remove, &[Key::Char('D')]),
+      (Action::OpenSortMenu, &[Key::Char(',')]),
+    ],
+  ),
+  (
+    Some("album_tracks"),
+    &[
+      (Action::Save, &[Key::Char('s')]),
+      (Action::Follow, &[Key::Char('w')]),
+      (Action::Recommendations, &[Key::Char('r')]),
+      (Action::OpenSortMenu, &[Key::Char(',')]),
+    ],
+  ),
+  (
+    Some("analysis"),
+    &[(Action::CycleVisualizer, &[Key::Char('V')])],
+  ),
+  (
+    Some("announcement_prompt"),
+    &[(Action::Submit, &[Key::Enter, Key::Char(' ')])],
+  ),
+  (
+    Some("artist"),
+    &[
+      (Action::Follow, &[Key::Char('w')]),
+      (Action::Remove, &[Key::Char('D')]),
+      (Action::Recommendations, &[Key::Char('r')]),
+    ],
+  ),
+  (
+    Some("artists"),
+    &[
+      (Action::Remove, &[Key::Char('D')]),
+      (Action::PlayArtist, &[Key::Char('e')]),
+      (Action::Recommendations, &[Key::Char('r')]),
+      (Action::OpenSortMenu, &[Key::Char(',')]),
+    ],
+  ),
+  (
+    Some("basic_view"),
+    &[
+      (Action::Save, &[Key::Char('s')]),
+      (Action::LyricsSyncEditor, &[Key::Char('E')]),
+      (Action::SearchLyrics, &[Key::Char('f')]),
+      (Action::LyricsEarlier, &[Key::Char('[')]),
+      (Action::LyricsLater, &[Key::Char(']')]),
+    ],
+  ),
+  (Some("dialog"), &[(Action::NextField, &[Key::Tab])]),
+  (
+    Some("discover"),
+    &[
+      (Action::PreviousTimeRange, &[Key::Char('[')]),
+      (Action::NextTimeRange, &[Key::Char(']')]),
+    ],
+  ),
+  (Some("empty"), &[(Action::AddToPlaylist, &[Key::Char('w')])]),
+  (
+    Some("episode_table"),
+    &[
+      (Action::Follow, &[Key::Char('s')]),
+      (Action::Remove, &[Key::Char('D')]),
+      (Action::ReverseOrder, &[Key::Char('S')]),
+    ],
+  ),
+  (
+    Some("help_menu"),
+    &[(Action::FilterHelp, &[Key::Char('/')])],
+  ),
+  (Some("library"), &[(Action::Remove, &[Key::Char('D')])]),
+  (
+    Some("lyrics_sync"),
+    &[
+      (Action::LyricsSyncEditor, &[Key::Char('E')]),
+      (Action::LyricsEarlier, &[Key::Char('[')]),
+      (Action::LyricsLater, &[Key::Char(']')]),
+      (Action::ClearLyricsStamp, &[Key::Char('x'), Key::Backspace]),
+      (Action::PreviewLyricsSync, &[Key::Char('P')]),
+      (Action::SaveLyricsSync, &[Key::Char('S')]),
+    ],
+  ),
+  (Some("my_playlists"), &[(Action::Remove, &[Key::Char('D')])]),
+  (
+    Some("now_playing"),
+    &[
+      (Action::Save, &[Key::Char('s')]),
+      (Action::LyricsSyncEditor, &[Key::Char('E')]),
+      (Action::SearchLyrics, &[Key::Char('f')]),
+      (Action::LyricsEarlier, &[Key::Char('[')]),
+      (Action::LyricsLater, &[Key::Char(']')]),
+    ],
+  ),
+  (
+    Some("playbar"),
+    &[
+      (Action::Save, &[Key::Char('s')]),
+      (Action::AddToPlaylist, &[Key::Char('w')]),
+    ],
+  ),
+  (Some("podcasts"), &[(Action::Remove, &[Key::Char('D')])]),
+  (
+    Some("recently_played"),
+    &[
+      (Action::Save, &[Key::Char('s')]),
+      (Action::Recommendations, &[Key::Char('r')]),
+      (Action::OpenSortMenu, &[Key::Char(',')]),
+    ],
+  ),
+  (
+    Some("search_results"),
+    &[
+      (Action::Follow, &[Key::Char('w')]),
+      (Action::Remove, &[Key::Char('D')]),
+      (Action::Recommendations, &[Key::Char('r')]),
+      (Action::OpenSortMenu, &[Key::Char(',')]),
+    ],
+  ),
+  (
+    Some("select_device"),
+    &[(Action::AudioOutput, &[Key::Char('O')])],
+  ),
+  (
+    Some("settings"),
+    &[
+      (Action::ResetBinding, &[Key::Char('r')]),
+      (Action::ResetAllBindings, &[Key::Char('R')]),
+    ],
+  ),
+  (Some("sort_menu"), SORT_MENU),
+  (
+    Some("track_table"),
+    &[
+      (Action::Save, &[Key::Char('s')]),
+      (Action::AddToPlaylist, &[Key::Char('w')]),
+      (Action::RemoveFromPlaylist, &[Key::Char('x')]),
+      (Action::PlayRandom, &[Key::Char('S')]),
+      (Action::Recommendations, &[Key::Char('r')]),
+      (Action::OpenSortMenu, &[Key::Char(',')]),
+      (Action::VisualSelect, &[Key::Char('V')]),
+    ],
+  ),
+  (
+    Some("update_prompt"),
+    &[(Action::Submit, &[Key::Enter, Key::Char(' ')])],
+  ),
+];
+
+/// Default bindings longer than a single key
+const DEFAULT_CHORDS: &[(Option<&str>, Action, &[Key])] =
+  &[(None, Action::SelectTop, &[Key::Char('g'), Key::Char('g')])];
+
+/// How to write a key in config.yml, the inverse of `parse_key`
+pub fn key_to_config_string(key: Key) -> String {
+  match key {
+    Key::Char(' ') => "space".to_string(),
+    Key::Char(c) => c.to_string(),
+    Key::Ctrl(c) => format!("ctrl-{}", c),
+    Key::Alt(c) => format!("alt-{}", c),
+    Key::Enter => "enter".to_string(),
+    Key::Tab => "tab".to_string(),
+    Key::Esc => "esc".to_string(),
+    Key::Backspace => "backspace".to_string(),
+    Key::Delete => "del".to_string(),
+    Key::Left => "left".to_string(),
+    Key::Right => "right".to_string(),
+    Key::Up => "up".to_string(),
+    Key::Down => "down".to_string(),
+    Key::Home => "home".to_string(),
+    Key::End => "end".to_string(),
+    Key::Ins => "ins".to_string(),
+    Key::PageUp => "pageup".to_string(),
+    Key::PageDown => "pagedown".to_string(),
+    Key::F0 => "f0".to_string(),
+    Key::F1 => "f1".to_string(),
+    Key::F2 => "f2".to_string(),
+    Key::F3 => "f3".to_string(),
+    Key::F4 => "f4".to_string(),
+    Key::F5 => "f5".to_string(),
+    Key::F6 => "f6".to_string(),
+    Key::F7 => "f7".to_string(),
+    Key::F8 => "f8".to_string(),
+    Key::F9 => "f9".to_string(),
+    Key::F10 => "f10".to_string(),
+    Key::F11 => "f11".to_string(),
+    Key::F12 => "f12".to_string(),
+    Key::Unknown => "unknown".to_string(),
+  }
+}
+
+/// Parse a chord written as keys separated by spaces, e.g. `g g` or `space p`
+pub fn parse_sequence(sequence: &str) -> Result<Vec<Key>> {
+  // A lone space is the space key, not an empty chord
+  if sequence.chars().count() == 1 {
+    return Ok(vec![parse_key_public(sequence.to_string())?]);
+  }
+  let keys = sequence
+    .split_whitespace()
+    .map(|key| parse_key_public(key.to_string()))
+    .collect::<Result<Vec<Key>>>()?;
+  if keys.is_empty() {
+    return Err(anyhow!("Empty key binding"));
+  }
+  Ok(keys)
+}
+
+pub fn sequence_to_config_string(keys: &[Key]) -> String {
+  keys
+    .iter()
+    .map(|key| key_to_config_string(*key))
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+fn sequence_to_string(keys: &[Key]) -> String {
+  keys
+    .iter()
+    .map(|key| key.to_string())
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+fn join_sequences(sequences: &[&[Key]]) -> String {
+  sequences
+    .iter()
+    .map(|keys| sequence_to_string(keys))
+    .collect::<Vec<_>>()
+    .join(" | ")
+}
+
+/// Human readable name of a keymap, used in conflict errors
+pub fn scope_label(view: Option<&str>) -> String {
+  match view {
+    Some(view) => format!("the {} view", view),
+    None => "the global keymap".to_string(),
+  }
+}
+
+/// One or several key sequences bound to an action in config.yml
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KeyBindingString {
+  One(String),
+  Many(Vec<String>),
+}
+
+impl KeyBindingString {
+  fn sequences(&self) -> Vec<&str> {
+    match self {
+      KeyBindingString::One(sequence) => vec![sequence.as_str()],
+      KeyBindingString::Many(sequences) => sequences.iter().map(String::as_str).collect(),
+    }
+  }
+}
+
+/// The `keybindings` section of config.yml: global actions at the top level, per-view
+/// overrides under `views`
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct KeyBindingsString {
+  #[serde(flatten)]
+  actions: BTreeMap<String, KeyBindingString>,
+  #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+  views: BTreeMap<String, BTreeMap<String, KeyBindingString>>,
+}
+
+/// Result of looking up the keys typed so far
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Lookup {
+  Action(Action),
+  /// The keys start a longer chord, wait for the next key
+  Pending,
+  None,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Binding {
+  view: Option<&'static str>,
+  keys: Vec<Key>,
+  action: Action,
+}
+
+#[derive(Clone, Debug)]
+pub struct KeyBindings {
+  bindings: Vec<Binding>,
+  /// Actions whose keys in a scope come from the user rather than the defaults
+  custom: Vec<(Option<&'static str>, Action)>,
+}
+
+impl Default for KeyBindings {
+  fn default() -> Self {
+    let bindings = DEFAULTS
+      .iter()
+      .flat_map(|(view, actions)| {
+        actions.iter().flat_map(move |(action, keys)| {
+          keys.iter().map(move |key| Binding {
+            view: *view,
+            keys: vec![*key],
+            action: *action,
+          })
+        })
+      })
+      .chain(DEFAULT_CHORDS.iter().map(|(view, action, keys)| Binding {
+        view: *view,
+        keys: keys.to_vec(),
+        action: *action,
+      }))
+      .collect();
+    KeyBindings {
+      bindings,
+      custom: Vec::new(),
+    }
+  }
+}
+
+impl KeyBindings {
+  /// Resolve the keys typed so far, bindings of `view` taking precedence over global ones
+  pub fn lookup(&self, view: &str, keys: &[Key]) -> Lookup {
+    for scope in [Some(view), None] {
+      let mut pending = false;
+      for binding in self.bindings.iter().filter(|b| b.view == scope) {
+        if binding.keys == keys {
+          return Lookup::Action(binding.action);
+        }
+        pending |= binding.keys.starts_with(keys);
+      }
+      if pending {
+        return Lookup::Pending;
+      }
+    }
+    Lookup::None
+  }
+
+  /// The action a single key triggers in `view`, for handlers that read raw keys
+  pub fn action(&self, view: &str, key: Key) -> Option<Action> {
+    match self.lookup(view, &[key]) {
+      Lookup::Action(action) => Some(action),
+      _ => None,
+    }
+  }
+
+  /// Key sequences bound to `action` in exactly this scope
+  pub fn sequences(&self, view: Option<&str>, action: Action) -> Vec<&[Key]> {
+    self
+      .bindings
+      .iter()
+      .filter(|b| b.view == view && b.action == action)
+      .map(|b| b.keys.as_slice())
+      .collect()
+  }
+
+  /// Every action with keys in exactly this scope and how they read, in registry order
+  pub fn bound_in(&self, view: Option<&str>) -> Vec<(Action, String)> {
+    Action::ALL
+      .iter()
+      .filter_map(|action| {
+        let sequences = self.sequences(view, *action);
+        if sequences.is_empty() {
+          None
+        } else {
+          Some((*action, join_sequences(&sequences)))
+        }
+      })
+      .collect()
+  }
+
+  /// The keys of `action` for the help and the UI, from the global keymap or else the first
+  /// view binding it
+  pub fn display(&self, action: Action) -> String {
+    let mut sequences = self.sequences(None, action);
+    if sequences.is_empty() {
+      if let Some(view) = self
+        .bindings
+        .iter()
+        .find(|b| b.action == action)
+        .and_then(|b| b.view)
+      {
+        sequences = self.sequences(Some(view), action);
+      }
+    }
+    join_sequences(&sequences)
+  }
+
+  /// The keys of `action` while `view` is active, its own bindings or else the global ones
+  pub fn display_in(&self, view: &str, action: Action) -> String {
+    let mut sequences = self.sequences(Some(view), action);
+    if sequences.is_empty() {
+      sequences = self.sequences(None, action);
+    }
+    join_sequences(&sequences)
+  }
+
+  /// First global binding of `action` as written in config.yml, empty when unbound
+  pub fn first_binding(&self, action: Action) -> String {
+    self
+      .sequences(None, action)
+      .first()
+      .map(|keys| sequence_to_config_string(keys))
+      .unwrap_or_default()
+  }
+
+  /// Bind `action` to `sequences` in a scope, replacing its previous keys there
+  ///
+  /// Reserved keys stay bound to their action. Default bindings in the way are dropped, while
+  /// overlapping with another binding set by the user is an error naming the scope.
+  pub fn bind(
+    &mut self,
+    view: Option<&'static str>,
+    action: Action,
+    sequences: Vec<Vec<Key>>,
+  ) -> Result<()> {
+    for keys in &sequences {
+      check_reserved_keys_public(keys[0], action, view)?;
+      for binding in self.bindings.iter().filter(|b| b.view == view) {
+        if binding.action == action || !overlaps(&binding.keys, keys) {
+          continue;
+        }
+        if self.custom.contains(&(view, binding.action)) {
+          return Err(conflict_error(
+            keys,
+            action,
+            &binding.keys,
+            binding.action,
+            view,
+          ));
+        }
+      }
+    }
+
+    let kept: Vec<Vec<Key>> = self
+      .bindings
+      .iter()
+      .filter(|b| b.view == view && b.action == action && is_reserved(b.keys[0]))
+      .map(|b| b.keys.clone())
+      .collect();
+    self.bindings.retain(|b| {
+      b.view != view
+        || (b.action != action && !sequences.iter().any(|keys| overlaps(&b.keys, keys)))
+    });
+    for keys in sequences.into_iter().chain(kept) {
+      if !self.sequences(view, action).contains(&keys.as_slice()) {
+        self.bindings.push(Binding { view, keys, action });
+      }
+    }
+    if !self.custom.contains(&(view, action)) {
+      self.custom.push((view, action));
+    }
+    Ok(())
+  }
+
+  /// Give `action` its default keys in a scope again
+  pub fn reset(&mut self, view: Option<&'static str>, action: Action) -> Result<()> {
+    self.bind(view, action, default_sequences(view, action))?;
+    self.custom.retain(|custom| *custom != (view, action));
+    Ok(())
+  }
+
+  /// Apply the `keybindings` section of config.yml on top of the current bindings
+  pub fn load(&mut self, config: KeyBindingsString) -> Result<()> {
+    for (name, binding) in &config.actions {
+      let action = Action::from_name(name).ok_or_else(|| anyhow!("Unknown action \"{}\"", name))?;
+      self.bind(None, action, parse_binding(binding)?)?;
+    }
+    for (view_name, actions) in &config.views {
+      let view = find_view(view_name)
+        .ok_or_else(|| anyhow!("Unknown view \"{}\" in keybindings", view_name))?;
+      for (name, binding) in actions {
+        let action = Action::from_name(name)
+          .ok_or_else(|| anyhow!("Unknown action \"{}\" in the {} view", name, view))?;
+        self.bind(Some(view), action, parse_binding(binding)?)?;
+      }
+    }
+    Ok(())
+  }
+
+  /// The bindings as written to config.yml: every global action plus the views the user changed
+  pub fn to_config(&self) -> KeyBindingsString {
+    let entry = |view: Option<&str>, action: Action| {
+      let mut sequences: Vec<String> = self
+        .sequences(view, action)
+        .into_iter()
+        .filter(|keys| {
+          !RESERVED_KEYS
+            .iter()
+            .any(|(key, reserved)| *key == keys[0] && reserved.is_none())
+        })
+        .map(sequence_to_config_string)
+        .collect();
+      if sequences.len() == 1 {
+        KeyBindingString::One(sequences.remove(0))
+      } else {
+        KeyBindingString::Many(sequences)
+      }
+    };
+
+    let mut config = KeyBindingsString::default();
+    for action in Action::ALL {
+      if self.custom.contains(&(None, *action)) || is_default_global(*action) {
+        config
+          .actions
+          .insert(action.name().to_string(), entry(None, *action));
+      }
+    }
+    for (view, action) in &self.custom {
+      if let Some(view) = view {
+        config
+          .views
+          .entry(view.to_string())
+          .or_default()
+          .insert(action.name().to_string(), entry(Some(view), *action));
+      }
+    }
+    config
+  }
+}
+
+/// Key sequences `action` has in a scope out of the box
+fn default_sequences(view: Option<&str>, action: Action) -> Vec<Vec<Key>> {
+  DEFAULTS
+    .iter()
+    .filter(|(scope, _)| *scope == view)
+    .flat_map(|(_, actions)| actions.iter())
+    .filter(|(default, _)| *default == action)
+    .flat_map(|(_, keys)| keys.iter().map(|key| vec![*key]))
+    .chain(
+      DEFAULT_CHORDS
+        .iter()
+        .filter(|(scope, default, _)| *scope == view && *default == action)
+        .map(|(_, _, keys)| keys.to_vec()),
+    )
+    .collect()
+}
+
+/// First default global binding of `action` as written in config.yml, empty when it has none
+pub fn default_binding(action: Action) -> String {
+  default_sequences(None, action)
+    .first()
+    .map(|keys| sequence_to_config_string(keys))
+    .unwrap_or_default()
+}
+
+/// Whether `action` has keys in the global keymap out of the box
+pub fn is_default_global(action: Action) -> bool {
+  DEFAULTS
+    .iter()
+    .filter(|(view, _)| view.is_none())
+    .any(|(_, actions)| actions.iter().any(|(default, _)| *default == action))
+}
+
+fn parse_binding(binding: &KeyBindingString) -> Result<Vec<Vec<Key>>> {
+  binding
+    .sequences()
+    .into_iter()
+    .map(parse_sequence)
+    .collect()
+}
+
+/// Whether pressing one sequence would trigger or shadow the other
+pub fn overlaps(a: &[Key], b: &[Key]) -> bool {
+  a.starts_with(b) || b.starts_with(a)
+}
+
+fn conflict_error(
+  keys: &[Key],
+  action: Action,
+  other_keys: &[Key],
+  other: Action,
+  view: Option<&str>,
+) -> anyhow::Error {
+  if keys == other_keys {
+    anyhow!(
+      "{} is bound to both {} and {} in {}",
+      sequence_to_string(keys),
+      action.name(),
+      other.name(),
+      scope_label(view)
+    )
+  } else {
+    anyhow!(
+      "{} ({}) overlaps with {} ({}) in {}",
+      sequence_to_string(keys),
+      action.name(),
+      sequence_to_string(other_keys),
+      other.name(),
+      scope_label(view)
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn config(yaml: &str) -> KeyBindingsString {
+    serde_yaml::from_str(yaml).unwrap()
+  }
+
+  #[test]
+  fn chords_wait_for_the_next_key() {
+    let mut keys = KeyBindings::default();
+    keys
+      .load(config("jump_to_start: g g\ntoggle_playback: space p\n"))
+      .unwrap();
+
+    assert_eq!(keys.lookup("home", &[Key::Char('g')]), Lookup::Pending);
+    assert_eq!(
+      keys.lookup("home", &[Key::Char('g'), Key::Char('g')]),
+      Lookup::Action(Action::JumpToStart)
+    );
+    assert_eq!(keys.lookup("home", &[Key::Char(' ')]), Lookup::Pending);
+    assert_eq!(
+      keys.lookup("home", &[Key::Char(' '), Key::Char('p')]),
+      Lookup::Action(Action::TogglePlayback)
+    );
+    // The chord replaced the old binding instead of adding to it
+    assert_eq!(keys.lookup("home", &[Key::Ctrl('a')]), Lookup::None);
+  }
+
+  #[test]
+  fn vim_aliases_come_with_the_defaults() {
+    let mut keys = KeyBindings::default();
+    assert_eq!(
+      keys.lookup("home", &[Key::Char('g'), Key::Char('g')]),
+      Lookup::Action(Action::SelectTop)
+    );
+    assert_eq!(
+      keys.action("home", Key::Char('G')),
+      Some(Action::SelectBottom)
+    );
+    assert_eq!(keys.display(Action::SelectTop), "H | g g");
+
+    keys
+      .load(config(
+        "select_top: t
+",
+      ))
+      .unwrap();
+    assert_eq!(keys.lookup("home", &[Key::Char('g')]), Lookup::None);
+    keys.reset(None, Action::SelectTop).unwrap();
+    assert_eq!(keys.lookup("home", &[Key::Char('g')]), Lookup::Pending);
+  }
+
+  #[test]
+  fn view_bindings_override_global_ones() {
+    let mut keys = KeyBindings::default();
+    keys
+      .load(config("views:\n  track_table:\n    save: n\n"))
+      .unwrap();
+
+    assert_eq!(
+      keys.action("track_table", Key::Char('n')),
+      Some(Action::Save)
+    );
+    assert_eq!(keys.action("home", Key::Char('n')), Some(Action::NextTrack));
+    assert_eq!(keys.action("track_table", Key::Char('s')), None);
+    assert_eq!(keys.action("playbar", Key::Char('s')), Some(Action::Save));
+  }
+
+  #[test]
+  fn lyrics_views_keep_the_global_jumps() {
+    let keys = KeyBindings::default();
+    for view in ["basic_view", "now_playing"] {
+      assert_eq!(
+        keys.action(view, Key::Char('L')),
+        Some(Action::SelectBottom)
+      );
+      assert_eq!(
+        keys.action(view, Key::Char('f')),
+        Some(Action::SearchLyrics)
+      );
+    }
+  }
+
+  #[test]
+  fn remapping_drops_defaults_in_the_way() {
+    let mut keys = KeyBindings::default();
+    // Colemak users want n/e for movement
+    keys
+      .load(config("move_down: n\nmove_up: e\nnext_track: k\n"))
+      .unwrap();
+
+    assert_eq!(keys.action("home", Key::Char('n')), Some(Action::MoveDown));
+    assert_eq!(keys.action("home", Key::Char('k')), Some(Action::NextTrack));
+    assert_eq!(keys.action("home", Key::Char('j')), None);
+    // Arrows are reserved and stay put
+    assert_eq!(keys.action("home", Key::Down), Some(Action::MoveDown));
+  }
+
+  #[test]
+  fn conflicts_name_the_actions_and_scope() {
+    let mut keys = KeyBindings::default();
+    let err = keys
+      .load(config("next_track: x\nprevious_track: x\n"))
+      .unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "x is bound to both previous_track and next_track in the global keymap"
+    );
+
+    let mut keys = KeyBindings::default();
+    let err = keys
+      .load(config(
+        "views:\n  track_table:\n    save: g\n    play_random: g g\n",
+      ))
+      .unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "g (save) overlaps with g g (play_random) in the track_table view"
+    );
+  }
+
+  #[test]
+  fn reserved_keys_report_their_context() {
+    let mut keys = KeyBindings::default();
+    let err = keys
+      .load(config("views:\n  sort_menu:\n    sort_by_name: enter\n"))
+      .unwrap_err();
+    assert!(err.to_string().contains("the sort_menu view"), "{}", err);
+
+    assert!(KeyBindings::default()
+      .load(config("move_down: [down, n]\n"))
+      .is_ok());
+  }
+
+  #[test]
+  fn unknown_names_are_errors() {
+    assert!(KeyBindings::default()
+      .load(config("jump_everywhere: x\n"))
+      .is_err());
+    assert!(KeyBindings::default()
+      .load(config("views:\n  nowhere:\n    save: x\n"))
+      .is_err());
+  }
+
+  #[test]
+  fn reset_restores_every_default_key() {
+    let mut keys = KeyBindings::default();
+    keys.load(config("move_down: n\n")).unwrap();
+    assert_eq!(keys.action("home", Key::Ctrl('n')), None);
+
+    keys.reset(None, Action::MoveDown).unwrap();
+
+    for key in [Key::Char('j'), Key::Down, Key::Ctrl('n')] {
+      assert_eq!(keys.action("home", key), Some(Action::MoveDown));
+    }
+    assert_eq!(keys.action("home", Key::Char('n')), None);
+    assert_eq!(default_binding(Action::MoveDown), "j");
+    assert!(!keys.custom.contains(&(None, Action::MoveDown)));
+  }
+
+  #[test]
+  fn config_round_trips() {
+    let mut keys = KeyBindings::default();
+    keys
+      .load(config(
+        "jump_to_start: [g g, home]\nviews:\n  lyrics_sync:\n    clear_lyrics_stamp: d\n",
+      ))
+      .unwrap();
+
+    let saved = keys.to_config();
+    let mut reloaded = KeyBindings::default();
+    reloaded.load(saved.clone()).unwrap();
+    assert_eq!(reloaded.to_config(), saved);
+    assert_eq!(
+      saved.views["lyrics_sync"]["clear_lyrics_stamp"],
+      KeyBindingString::One("d".to_string())
+    );
+    // Backspace cannot be bound, so it is kept without being written out
+    assert_eq!(
+      reloaded.action("lyrics_sync", Key::Backspace),
+      Some(Action::ClearLyricsStamp)
+    );
+    assert_eq!(
+      KeyBindings::default().to_config().actions["move_down"],
+      KeyBindingString::Many(vec![
+        "j".to_string(),
+        "down".to_string(),
+        "ctrl-n".to_string()
+      ])
+    );
+  }
+
+  #[test]
+  fn defaults_have_no_conflicts() {
+    let keys = KeyBindings::default();
+    for (i, a) in keys.bindings.iter().enumerate() {
+      for b in &keys.bindings[i + 1..] {
+        assert!(
+          a.view != b.view || a.action == b.action || !overlaps(&a.keys, &b.keys),
+          "{:?} and {:?} overlap",
+          a,
+          b
+        );
+      }
+    }
+  }
+}
@@ -1,4 +1,10 @@
 pub mod app;
+pub mod columns;
 pub mod config;
+pub mod keymap;
+pub mod play_counts;
+pub mod session;
 pub mod sort;
+pub mod theme_files;
+pub mod undo;
 pub mod user_config;
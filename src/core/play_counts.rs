@@ -0,0 +1,129 @@
+// How often each track was started in spotatui, kept in the user cache directory for the
+// `play_count` table column
+
+use log::debug;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Coming back under this from a later position means the track started over, as with repeat one
+const RESTART_MS: u64 = 10_000;
+
+#[derive(Default)]
+pub struct PlayCounts {
+  path: Option<PathBuf>,
+  counts: HashMap<String, u32>,
+  /// Track in the last playback update and how far into it playback was
+  current: Option<(String, u64)>,
+  /// Whether a playback update came in yet, so the track playing at launch is not counted
+  primed: bool,
+}
+
+impl PlayCounts {
+  pub fn load() -> Self {
+    Self::load_from(dirs::cache_dir().map(|dir| dir.join("spotatui").join("play_counts.json")))
+  }
+
+  fn load_from(path: Option<PathBuf>) -> Self {
+    let counts = path
+      .as_ref()
+      .and_then(|path| fs::read_to_string(path).ok())
+      .and_then(|text| {
+        serde_json::from_str(&text)
+          .map_err(|e| debug!("ignoring unreadable play counts: {}", e))
+          .ok()
+      })
+      .unwrap_or_default();
+    Self {
+      path,
+      counts,
+      current: None,
+      primed: false,
+    }
+  }
+
+  /// `None` for tracks spotatui never played
+  pub fn get(&self, track_id: &str) -> Option<u32> {
+    self.counts.get(track_id).copied()
+  }
+
+  /// Follow a playback update with the playing track and its progress, counting the track when
+  /// it started since the last update: another track came on, or this one went back to its start
+  pub fn observe(&mut self, playing: Option<(&str, u64)>) {
+    let previous = self.current.take();
+    self.current = playing.map(|(track_id, progress)| (track_id.to_string(), progress));
+    if !std::mem::replace(&mut self.primed, true) {
+      return;
+    }
+    let Some((track_id, progress)) = playing else {
+      return;
+    };
+
+    let started = match previous {
+      Some((previous_id, previous_progress)) if previous_id == track_id => {
+        progress < RESTART_MS && previous_progress > progress + RESTART_MS
+      }
+      _ => true,
+    };
+    if started {
+      *self.counts.entry(track_id.to_string()).or_default() += 1;
+      self.save();
+    }
+  }
+
+  fn save(&self) {
+    let Some(path) = self.path.as_ref() else {
+      return;
+    };
+    let written = serde_json::to_string(&self.counts)
+      .map_err(std::io::Error::other)
+      .and_then(|json| {
+        path
+          .parent()
+          .map_or(Ok(()), fs::create_dir_all)
+          .and_then(|_| fs::write(path, json))
+      });
+    if let Err(e) = written {
+      debug!("failed to save play counts to {}: {}", path.display(), e);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn tracks_are_counted_once_per_start() {
+    let dir = std::env::temp_dir().join(format!("spotatui-play-counts-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let path = dir.join("play_counts.json");
+
+    let mut counts = PlayCounts::load_from(Some(path.clone()));
+    // Already playing at launch
+    counts.observe(Some(("a", 50_000)));
+    counts.observe(Some(("a", 55_000)));
+    counts.observe(Some(("b", 0)));
+    counts.observe(Some(("b", 180_000)));
+    // Repeat one
+    counts.observe(Some(("b", 2_000)));
+    counts.observe(Some(("a", 1_000)));
+    counts.observe(Some(("a", 4_000)));
+    assert_eq!(counts.get("a"), Some(1));
+    assert_eq!(counts.get("b"), Some(2));
+    assert_eq!(counts.get("c"), None);
+
+    let next_run = PlayCounts::load_from(Some(path));
+    assert_eq!(next_run.get("b"), Some(2));
+  }
+
+  #[test]
+  fn the_first_track_counts_when_nothing_played_at_launch() {
+    let mut counts = PlayCounts::default();
+    counts.observe(None);
+    counts.observe(Some(("a", 0)));
+    counts.observe(None);
+    counts.observe(Some(("a", 0)));
+    assert_eq!(counts.get("a"), Some(2));
+  }
+}
@@ -0,0 +1,398 @@
+// Where spotatui was when it last ran: the open view, the selected playlist and row, the last
+// search and what was playing. Written to the user cache directory while running and read back
+// on the next start.
+
+use super::app::{
+  ActiveBlock, App, PendingTrackSelection, PlaylistFolderItem, RouteId, TrackTableContext,
+};
+use crate::infra::network::IoEvent;
+use log::debug;
+use rspotify::model::{idtypes::PlaylistId, PlayableItem};
+use rspotify::prelude::Id;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use unicode_width::UnicodeWidthStr;
+
+/// Least time between two writes, so scrolling through a list does not write on every key
+const WRITE_INTERVAL: Duration = Duration::from_secs(2);
+/// Least time between writes that only move the playback position forward
+const POSITION_INTERVAL: Duration = Duration::from_secs(30);
+/// How long a saved playlist view waits for the playlist list before it is given up
+pub const PLAYLIST_WAIT: Duration = Duration::from_secs(15);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionView {
+  #[default]
+  Home,
+  Playlist,
+  LikedSongs,
+  RecentlyPlayed,
+  Albums,
+  Artists,
+  Podcasts,
+  Search,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SessionPlayback {
+  /// Album, playlist, artist or show the track was played from
+  #[serde(default)]
+  pub context_uri: Option<String>,
+  pub track_uri: String,
+  #[serde(default)]
+  pub position_ms: u32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+  #[serde(default)]
+  pub view: SessionView,
+  /// Id of the playlist open in the track table
+  #[serde(default)]
+  pub playlist_id: Option<String>,
+  /// Offset of the page of the playlist or liked songs that was shown
+  #[serde(default)]
+  pub offset: u32,
+  /// Selected row of that page
+  #[serde(default)]
+  pub row: usize,
+  #[serde(default)]
+  pub search_query: Option<String>,
+  #[serde(default)]
+  pub playback: Option<SessionPlayback>,
+}
+
+impl Session {
+  /// Everything but the playback position, which changes all the time while playing
+  fn same_place(&self, other: &Session) -> bool {
+    let without_position = |session: &Session| Session {
+      playback: session.playback.clone().map(|playback| SessionPlayback {
+        position_ms: 0,
+        ..playback
+      }),
+      ..session.clone()
+    };
+    without_position(self) == without_position(other)
+  }
+}
+
+/// Keeps the session file up to date, `None` path when sessions are not saved at all
+#[derive(Default)]
+pub struct SessionStore {
+  path: Option<PathBuf>,
+  saved: Option<Session>,
+  saved_at: Option<Instant>,
+}
+
+impl SessionStore {
+  pub fn new() -> Self {
+    Self {
+      path: dirs::cache_dir().map(|dir| dir.join("spotatui").join("session.json")),
+      ..Default::default()
+    }
+  }
+
+  #[cfg(test)]
+  fn with_path(path: PathBuf) -> Self {
+    Self {
+      path: Some(path),
+      ..Default::default()
+    }
+  }
+
+  /// The session written by the last run
+  pub fn load(&mut self) -> Option<Session> {
+    let text = fs::read_to_string(self.path.as_ref()?).ok()?;
+    let session: Session = serde_json::from_str(&text)
+      .map_err(|e| debug!("ignoring unreadable session file: {}", e))
+      .ok()?;
+    self.saved = Some(session.clone());
+    Some(session)
+  }
+
+  /// Write `session` once it differs from the last write and enough time has passed
+  pub fn update(&mut self, session: Session) {
+    let session = self.keep_playback(session);
+    let due = match (&self.saved, self.saved_at) {
+      (Some(saved), _) if *saved == session => false,
+      (Some(saved), Some(saved_at)) if saved.same_place(&session) => {
+        saved_at.elapsed() >= POSITION_INTERVAL
+      }
+      (_, Some(saved_at)) => saved_at.elapsed() >= WRITE_INTERVAL,
+      (_, None) => true,
+    };
+    if due {
+      self.save(session);
+    }
+  }
+
+  /// Write `session` right away, e.g. on exit
+  pub fn save(&mut self, session: Session) {
+    let Some(path) = self.path.as_ref() else {
+      return;
+    };
+    let session = self.keep_playback(session);
+    let written = serde_json::to_string_pretty(&session)
+      .map_err(std::io::Error::other)
+      .and_then(|json| {
+        path
+          .parent()
+          .map_or(Ok(()), fs::create_dir_all)
+          .and_then(|_| fs::write(path, json))
+      });
+    if let Err(e) = written {
+      debug!("failed to save session to {}: {}", path.display(), e);
+    }
+    self.saved = Some(session);
+    self.saved_at = Some(Instant::now());
+  }
+
+  /// Nothing known to be playing yet, e.g. right after start, keeps the last known playback
+  fn keep_playback(&self, mut session: Session) -> Session {
+    if session.playback.is_none() {
+      session.playback = self.saved.as_ref().and_then(|saved| saved.playback.clone());
+    }
+    session
+  }
+}
+
+/// The session as it stands
+pub fn capture(app: &App) -> Session {
+  let mut session = Session {
+    search_query: Some(app.input.iter().collect::<String>()).filter(|query| !query.is_empty()),
+    playback: capture_playback(app),
+    ..Default::default()
+  };
+
+  match app.content_route().id {
+    RouteId::TrackTable => match app.track_table.context {
+      Some(TrackTableContext::MyPlaylists) => {
+        if let Some(playlist) = app
+          .active_playlist_index
+          .and_then(|index| app.all_playlists.get(index))
+        {
+          session.view = SessionView::Playlist;
+          session.playlist_id = Some(playlist.id.id().to_string());
+          session.offset = app.playlist_offset;
+          session.row = app.track_table.selected_index;
+        }
+      }
+      Some(TrackTableContext::SavedTracks) => {
+        session.view = SessionView::LikedSongs;
+        session.offset = app
+          .library
+          .saved_tracks
+          .get_results(None)
+          .map(|page| page.offset)
+          .unwrap_or(0);
+        session.row = app.track_table.selected_index;
+      }
+      _ => {}
+    },
+    RouteId::RecentlyPlayed => session.view = SessionView::RecentlyPlayed,
+    RouteId::AlbumList => session.view = SessionView::Albums,
+    RouteId::Artists => session.view = SessionView::Artists,
+    RouteId::Podcasts => session.view = SessionView::Podcasts,
+    RouteId::Search if session.search_query.is_some() => session.view = SessionView::Search,
+    _ => {}
+  }
+  session
+}
+
+fn capture_playback(app: &App) -> Option<SessionPlayback> {
+  let context = app.current_playback_context.as_ref()?;
+  let track_uri = match context.item.as_ref()? {
+    PlayableItem::Track(track) => track.id.as_ref()?.uri(),
+    PlayableItem::Episode(episode) => episode.id.uri(),
+  };
+  Some(SessionPlayback {
+    context_uri: context.context.as_ref().map(|context| context.uri.clone()),
+    track_uri,
+    position_ms: u32::try_from(app.song_progress_ms).unwrap_or(u32::MAX),
+  })
+}
+
+/// Reopen the view of `app.session_to_restore` once what it needs has loaded
+///
+/// Playlists are looked up in the playlist list, so those wait until it arrives, or until
+/// `PLAYLIST_WAIT` has passed when it fails to load. A playlist that cannot be found leaves the
+/// home view up. Anything the user opens in the meantime wins over the saved view.
+pub fn restore_pending(app: &mut App) {
+  let Some(session) = app.session_to_restore.as_ref() else {
+    return;
+  };
+  // Update and announcement prompts open on start, the view is restored once they are closed
+  if app.get_current_route().id.is_overlay() {
+    return;
+  }
+  if app.content_route().id != RouteId::Home {
+    app.session_to_restore = None;
+    return;
+  }
+  if session.view == SessionView::Playlist
+    && app.playlists.is_none()
+    && app
+      .session_restore_deadline
+      .is_some_and(|deadline| Instant::now() < deadline)
+  {
+    return;
+  }
+  let Some(session) = app.session_to_restore.take() else {
+    return;
+  };
+
+  if let Some(query) = &session.search_query {
+    app.input = query.chars().collect();
+    app.input_idx = app.input.len();
+    app.input_cursor_position = UnicodeWidthStr::width(query.as_str())
+      .try_into()
+      .unwrap_or(u16::MAX);
+  }
+
+  match session.view {
+    SessionView::Home => {}
+    SessionView::Playlist => {
+      let Some(index) = session.playlist_id.as_deref().and_then(|id| {
+        app
+          .all_playlists
+          .iter()
+          .position(|playlist| playlist.id.id() == id)
+      }) else {
+        return;
+      };
+      app.active_playlist_index = Some(index);
+      app.selected_playlist_index = (0..app.get_playlist_display_count()).find(|display_index| {
+        matches!(
+          app.get_playlist_display_item_at(*display_index),
+          Some(PlaylistFolderItem::Playlist { index: item, .. }) if *item == index
+        )
+      });
+      app.track_table.context = Some(TrackTableContext::MyPlaylists);
+      app.playlist_offset = session.offset;
+      app.pending_track_table_selection = Some(PendingTrackSelection::Index(session.row));
+      let playlist_id: PlaylistId<'static> = app.all_playlists[index].id.clone().into_static();
+      app.dispatch(IoEvent::GetPlaylistItems(playlist_id, session.offset));
+    }
+    SessionView::LikedSongs => {
+      app.pending_track_table_selection = Some(PendingTrackSelection::Index(session.row));
+      app.dispatch(IoEvent::GetCurrentSavedTracks(Some(session.offset)));
+      app.push_navigation_stack(RouteId::TrackTable, ActiveBlock::TrackTable);
+    }
+    SessionView::RecentlyPlayed => {
+      app.dispatch(IoEvent::GetRecentlyPlayed);
+      app.push_navigation_stack(RouteId::RecentlyPlayed, ActiveBlock::RecentlyPlayed);
+    }
+    SessionView::Albums => {
+      app.dispatch(IoEvent::GetCurrentUserSavedAlbums(None));
+      app.push_navigation_stack(RouteId::AlbumList, ActiveBlock::AlbumList);
+    }
+    SessionView::Artists => {
+      app.dispatch(IoEvent::GetFollowedArtists(None));
+      app.push_navigation_stack(RouteId::Artists, ActiveBlock::Artists);
+    }
+    SessionView::Podcasts => {
+      app.dispatch(IoEvent::GetCurrentUserSavedShows(None));
+      app.push_navigation_stack(RouteId::Podcasts, ActiveBlock::Podcasts);
+    }
+    SessionView::Search => {
+      if let Some(query) = session.search_query {
+        let country = app.get_user_country();
+        app.dispatch(IoEvent::GetSearchResults(query, country));
+        app.push_navigation_stack(RouteId::Search, ActiveBlock::SearchResultBlock);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn temp_path(name: &str) -> PathBuf {
+    let dir =
+      std::env::temp_dir().join(format!("spotatui-session-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir.join("session.json")
+  }
+
+  fn playing(position_ms: u32) -> Session {
+    Session {
+      view: SessionView::LikedSongs,
+      row: 4,
+      playback: Some(SessionPlayback {
+        context_uri: None,
+        track_uri: "spotify:track:4uLU6hMCjMI75M1A2tKUQC".to_string(),
+        position_ms,
+      }),
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn sessions_survive_a_restart() {
+    let path = temp_path("restart");
+    let mut store = SessionStore::with_path(path.clone());
+    store.save(playing(61_000));
+
+    let mut next_run = SessionStore::with_path(path);
+    assert_eq!(next_run.load(), Some(playing(61_000)));
+  }
+
+  #[test]
+  fn position_alone_does_not_rewrite_the_file() {
+    let path = temp_path("position");
+    let mut store = SessionStore::with_path(path.clone());
+    store.update(playing(1_000));
+    store.update(playing(2_000));
+    assert_eq!(SessionStore::with_path(path).load(), Some(playing(1_000)));
+  }
+
+  #[test]
+  fn unknown_playback_keeps_the_last_one() {
+    let path = temp_path("playback");
+    let mut store = SessionStore::with_path(path.clone());
+    store.save(playing(5_000));
+    store.save(Session::default());
+
+    let saved = SessionStore::with_path(path).load().unwrap();
+    assert_eq!(saved.view, SessionView::Home);
+    assert_eq!(saved.playback, playing(5_000).playback);
+  }
+
+  #[test]
+  fn liked_songs_reopen_at_the_saved_row() {
+    let mut app = App::default();
+    app.session_to_restore = Some(playing(0));
+
+    restore_pending(&mut app);
+
+    assert!(app.session_to_restore.is_none());
+    assert_eq!(app.get_current_route().id, RouteId::TrackTable);
+    assert_eq!(
+      app.pending_track_table_selection,
+      Some(PendingTrackSelection::Index(4))
+    );
+  }
+
+  #[test]
+  fn playlists_that_never_load_are_given_up() {
+    let mut app = App::default();
+    app.session_to_restore = Some(Session {
+      view: SessionView::Playlist,
+      playlist_id: Some("37i9dQZF1DXcBWIGoYBM5M".to_string()),
+      ..Default::default()
+    });
+    app.session_restore_deadline = Some(Instant::now() + PLAYLIST_WAIT);
+
+    restore_pending(&mut app);
+    assert!(app.session_to_restore.is_some());
+
+    app.session_restore_deadline = Some(Instant::now());
+    restore_pending(&mut app);
+    assert!(app.session_to_restore.is_none());
+    assert_eq!(app.get_current_route().id, RouteId::Home);
+  }
+}
@@ -2,10 +2,26 @@
 //!
 //! Provides sorting functionality for playlists, albums, artists, etc.
 
-use rspotify::model::track::FullTrack;
+use crate::core::keymap::Action;
+use chrono::{DateTime, Utc};
+use log::debug;
+use rspotify::model::{
+  album::SavedAlbum, artist::FullArtist, playing::PlayHistory, track::FullTrack,
+  track::SimplifiedTrack,
+};
+use rspotify::prelude::Id;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Tie-breakers that can follow the main sort field
+pub const MAX_THEN_KEYS: usize = 2;
 
 /// Fields that can be used for sorting
-#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SortField {
   /// Original API order (no sorting applied)
   #[default]
@@ -20,6 +36,16 @@ pub enum SortField {
   Duration,
   /// By album name (for tracks)
   Album,
+  /// By Spotify popularity, 0 to 100
+  Popularity,
+  /// By album release date
+  ReleaseDate,
+  /// By disc, then track number on the album
+  TrackNumber,
+  /// Clean tracks before explicit ones
+  Explicit,
+  /// Shuffled, the same way until it is picked again
+  Random,
 }
 
 impl SortField {
@@ -32,24 +58,52 @@ impl SortField {
       SortField::Artist => "Artist",
       SortField::Duration => "Duration",
       SortField::Album => "Album",
+      SortField::Popularity => "Popularity",
+      SortField::ReleaseDate => "Release Date",
+      SortField::TrackNumber => "Track Number",
+      SortField::Explicit => "Explicit",
+      SortField::Random => "Random",
     }
   }
 
-  /// Get the keyboard shortcut for this field
-  pub fn shortcut(&self) -> Option<char> {
+  /// Sort menu action that sorts by this field
+  pub fn action(&self) -> Action {
     match self {
-      SortField::Default => Some('d'),
-      SortField::Name => Some('n'),
-      SortField::DateAdded => Some('a'),
-      SortField::Artist => Some('r'),
-      SortField::Duration => Some('t'),
-      SortField::Album => Some('l'),
+      SortField::Default => Action::SortByDefault,
+      SortField::Name => Action::SortByName,
+      SortField::DateAdded => Action::SortByDateAdded,
+      SortField::Artist => Action::SortByArtist,
+      SortField::Duration => Action::SortByDuration,
+      SortField::Album => Action::SortByAlbum,
+      SortField::Popularity => Action::SortByPopularity,
+      SortField::ReleaseDate => Action::SortByReleaseDate,
+      SortField::TrackNumber => Action::SortByTrackNumber,
+      SortField::Explicit => Action::SortByExplicit,
+      SortField::Random => Action::SortByRandom,
+    }
+  }
+
+  /// Sort menu action that sorts by this field in descending order, none for `Random`
+  pub fn descending_action(&self) -> Option<Action> {
+    match self {
+      SortField::Default => Some(Action::SortByDefaultDescending),
+      SortField::Name => Some(Action::SortByNameDescending),
+      SortField::DateAdded => Some(Action::SortByDateAddedDescending),
+      SortField::Artist => Some(Action::SortByArtistDescending),
+      SortField::Duration => Some(Action::SortByDurationDescending),
+      SortField::Album => Some(Action::SortByAlbumDescending),
+      SortField::Popularity => Some(Action::SortByPopularityDescending),
+      SortField::ReleaseDate => Some(Action::SortByReleaseDateDescending),
+      SortField::TrackNumber => Some(Action::SortByTrackNumberDescending),
+      SortField::Explicit => Some(Action::SortByExplicitDescending),
+      SortField::Random => None,
     }
   }
 }
 
 /// Sort order direction
-#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SortOrder {
   #[default]
   Ascending,
@@ -79,49 +133,99 @@ impl SortOrder {
 pub enum SortContext {
   /// Tracks in a playlist
   PlaylistTracks,
+  /// Liked songs page in the track table
+  LikedSongs,
   /// User's saved albums
   SavedAlbums,
   /// User's followed artists
   SavedArtists,
-  #[allow(dead_code)]
   /// Recently played tracks
   RecentlyPlayed,
+  /// Tracks of the open album
+  AlbumTracks,
+  /// Songs found by a search
+  SearchResults,
 }
 
 impl SortContext {
   /// Get the available sort fields for this context
   pub fn available_fields(&self) -> &'static [SortField] {
     match self {
-      SortContext::PlaylistTracks => &[
+      SortContext::PlaylistTracks | SortContext::LikedSongs => &[
         SortField::Default,
         SortField::Name,
         SortField::DateAdded,
         SortField::Artist,
         SortField::Album,
         SortField::Duration,
+        SortField::Popularity,
+        SortField::ReleaseDate,
+        SortField::TrackNumber,
+        SortField::Explicit,
+        SortField::Random,
       ],
       SortContext::SavedAlbums => &[
         SortField::Default,
         SortField::Name,
         SortField::DateAdded,
         SortField::Artist,
+        SortField::ReleaseDate,
+        SortField::Popularity,
+        SortField::Random,
       ],
-      SortContext::SavedArtists => &[SortField::Default, SortField::Name],
-      SortContext::RecentlyPlayed => &[
+      SortContext::SavedArtists => &[
+        SortField::Default,
+        SortField::Name,
+        SortField::Popularity,
+        SortField::Random,
+      ],
+      SortContext::RecentlyPlayed | SortContext::SearchResults => &[
         SortField::Default,
         SortField::Name,
         SortField::Artist,
         SortField::Album,
+        SortField::Duration,
+        SortField::Popularity,
+        SortField::ReleaseDate,
+        SortField::Explicit,
+        SortField::Random,
+      ],
+      SortContext::AlbumTracks => &[
+        SortField::Default,
+        SortField::Name,
+        SortField::Artist,
+        SortField::Duration,
+        SortField::Explicit,
+        SortField::Random,
       ],
     }
   }
+
+  /// Title of the sort menu
+  pub fn title(&self) -> &'static str {
+    match self {
+      SortContext::PlaylistTracks => "Sort Tracks",
+      // Only the loaded page of liked songs is sorted
+      SortContext::LikedSongs => "Sort This Page",
+      SortContext::SavedAlbums => "Sort Albums",
+      SortContext::SavedArtists => "Sort Artists",
+      SortContext::RecentlyPlayed => "Sort Recently Played",
+      SortContext::AlbumTracks => "Sort Album",
+      SortContext::SearchResults => "Sort Songs",
+    }
+  }
 }
 
 /// Current sort state
-#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SortState {
   pub field: SortField,
   pub order: SortOrder,
+  /// Fields that break ties left by `field`, in order and always ascending
+  pub then: [Option<SortField>; MAX_THEN_KEYS],
+  /// Seed of the `Random` order
+  pub seed: u64,
 }
 
 impl SortState {
@@ -130,20 +234,313 @@ impl SortState {
   }
 
   /// Apply a new sort field, toggling order if same field selected
+  ///
+  /// Picking `Random` again shuffles anew instead, and `Default` drops the tie-breakers.
   pub fn apply_field(&mut self, field: SortField) {
-    if self.field == field {
+    if field == SortField::Random {
+      self.field = field;
+      self.order = SortOrder::Ascending;
+      self.seed = rand::random();
+    } else if self.field == field {
       self.order = self.order.toggle();
     } else {
       self.field = field;
       self.order = SortOrder::Ascending;
     }
+
+    if field == SortField::Default {
+      self.then = [None; MAX_THEN_KEYS];
+    } else {
+      self.remove_then(field);
+    }
+  }
+
+  /// Add `field` as the last tie-breaker, or take it out when it already is one. With no room
+  /// left the first tie-breaker makes way.
+  pub fn toggle_then(&mut self, field: SortField) {
+    if matches!(field, SortField::Default | SortField::Random) || field == self.field {
+      return;
+    }
+    if self.then.contains(&Some(field)) {
+      self.remove_then(field);
+      return;
+    }
+    match self.then.iter().position(Option::is_none) {
+      Some(free) => self.then[free] = Some(field),
+      None => {
+        self.then.rotate_left(1);
+        self.then[MAX_THEN_KEYS - 1] = Some(field);
+      }
+    }
+  }
+
+  /// 1-based place of `field` among the tie-breakers
+  pub fn then_position(&self, field: SortField) -> Option<usize> {
+    self
+      .then
+      .iter()
+      .position(|then| *then == Some(field))
+      .map(|index| index + 1)
+  }
+
+  fn remove_then(&mut self, field: SortField) {
+    let rest: Vec<SortField> = self
+      .then
+      .iter()
+      .flatten()
+      .copied()
+      .filter(|then| *then != field)
+      .collect();
+    self.then = [None; MAX_THEN_KEYS];
+    for (slot, then) in self.then.iter_mut().zip(rest) {
+      *slot = Some(then);
+    }
+  }
+
+  /// Whether this is the order the items came in. The seed only matters for `Random`.
+  pub fn is_default(&self) -> bool {
+    Self { seed: 0, ..*self } == Self::default()
   }
 
   /// Reset to default sort state
   #[allow(dead_code)]
   pub fn reset(&mut self) {
-    self.field = SortField::Default;
-    self.order = SortOrder::Ascending;
+    *self = Self::default();
+  }
+}
+
+/// What the sort fields look at. Items without a value sort first.
+pub trait Sortable {
+  fn name(&self) -> &str;
+
+  /// Spotify id, for the `Random` order
+  fn item_id(&self) -> Option<&str> {
+    None
+  }
+
+  fn artist(&self) -> Option<&str> {
+    None
+  }
+
+  fn album(&self) -> Option<&str> {
+    None
+  }
+
+  fn duration_ms(&self) -> i64 {
+    0
+  }
+
+  fn added_at(&self) -> Option<DateTime<Utc>> {
+    None
+  }
+
+  fn popularity(&self) -> Option<u32> {
+    None
+  }
+
+  fn release_date(&self) -> Option<&str> {
+    None
+  }
+
+  /// Disc and track number
+  fn track_position(&self) -> Option<(i32, u32)> {
+    None
+  }
+
+  fn explicit(&self) -> bool {
+    false
+  }
+
+  /// Place in the order the API returned the items in, for `SortField::Default`
+  fn natural_key(&self) -> i64 {
+    0
+  }
+}
+
+/// The API fills in a popularity of 0 when it leaves it out
+fn known_popularity(popularity: u32) -> Option<u32> {
+  Some(popularity).filter(|popularity| *popularity > 0)
+}
+
+/// Newest first, the order Spotify lists liked, saved and played items in
+fn newest_first(at: DateTime<Utc>) -> i64 {
+  -at.timestamp_millis()
+}
+
+/// A track of a playlist, liked songs or search results, with what the list knows about it
+pub struct TrackRow<'a> {
+  pub track: &'a FullTrack,
+  pub added_at: Option<DateTime<Utc>>,
+  pub natural_key: i64,
+}
+
+impl Sortable for TrackRow<'_> {
+  fn name(&self) -> &str {
+    &self.track.name
+  }
+
+  fn item_id(&self) -> Option<&str> {
+    self.track.id.as_ref().map(|id| id.id())
+  }
+
+  fn artist(&self) -> Option<&str> {
+    self
+      .track
+      .artists
+      .first()
+      .map(|artist| artist.name.as_str())
+  }
+
+  fn album(&self) -> Option<&str> {
+    Some(&self.track.album.name)
+  }
+
+  fn duration_ms(&self) -> i64 {
+    self.track.duration.num_milliseconds()
+  }
+
+  fn added_at(&self) -> Option<DateTime<Utc>> {
+    self.added_at
+  }
+
+  fn popularity(&self) -> Option<u32> {
+    known_popularity(self.track.popularity)
+  }
+
+  fn release_date(&self) -> Option<&str> {
+    self.track.album.release_date.as_deref()
+  }
+
+  fn track_position(&self) -> Option<(i32, u32)> {
+    Some((self.track.disc_number, self.track.track_number))
+  }
+
+  fn explicit(&self) -> bool {
+    self.track.explicit
+  }
+
+  fn natural_key(&self) -> i64 {
+    self.natural_key
+  }
+}
+
+impl Sortable for PlayHistory {
+  fn name(&self) -> &str {
+    &self.track.name
+  }
+
+  fn item_id(&self) -> Option<&str> {
+    self.track.id.as_ref().map(|id| id.id())
+  }
+
+  fn artist(&self) -> Option<&str> {
+    self
+      .track
+      .artists
+      .first()
+      .map(|artist| artist.name.as_str())
+  }
+
+  fn album(&self) -> Option<&str> {
+    Some(&self.track.album.name)
+  }
+
+  fn duration_ms(&self) -> i64 {
+    self.track.duration.num_milliseconds()
+  }
+
+  fn popularity(&self) -> Option<u32> {
+    known_popularity(self.track.popularity)
+  }
+
+  fn release_date(&self) -> Option<&str> {
+    self.track.album.release_date.as_deref()
+  }
+
+  fn explicit(&self) -> bool {
+    self.track.explicit
+  }
+
+  fn natural_key(&self) -> i64 {
+    newest_first(self.played_at)
+  }
+}
+
+impl Sortable for SimplifiedTrack {
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn item_id(&self) -> Option<&str> {
+    self.id.as_ref().map(|id| id.id())
+  }
+
+  fn artist(&self) -> Option<&str> {
+    self.artists.first().map(|artist| artist.name.as_str())
+  }
+
+  fn duration_ms(&self) -> i64 {
+    self.duration.num_milliseconds()
+  }
+
+  fn track_position(&self) -> Option<(i32, u32)> {
+    Some((self.disc_number, self.track_number))
+  }
+
+  fn explicit(&self) -> bool {
+    self.explicit
+  }
+
+  fn natural_key(&self) -> i64 {
+    i64::from(self.disc_number) * 1000 + i64::from(self.track_number)
+  }
+}
+
+impl Sortable for SavedAlbum {
+  fn name(&self) -> &str {
+    &self.album.name
+  }
+
+  fn item_id(&self) -> Option<&str> {
+    Some(self.album.id.id())
+  }
+
+  fn artist(&self) -> Option<&str> {
+    self
+      .album
+      .artists
+      .first()
+      .map(|artist| artist.name.as_str())
+  }
+
+  fn added_at(&self) -> Option<DateTime<Utc>> {
+    Some(self.added_at)
+  }
+
+  fn popularity(&self) -> Option<u32> {
+    known_popularity(self.album.popularity)
+  }
+
+  fn release_date(&self) -> Option<&str> {
+    Some(&self.album.release_date)
+  }
+
+  fn natural_key(&self) -> i64 {
+    newest_first(self.added_at)
+  }
+}
+
+impl Sortable for FullArtist {
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn item_id(&self) -> Option<&str> {
+    Some(self.id.id())
+  }
+
+  fn popularity(&self) -> Option<u32> {
+    known_popularity(self.popularity)
   }
 }
 
@@ -156,41 +553,131 @@ impl Sorter {
     Self { state }
   }
 
-  pub fn sort_tracks(&self, tracks: &mut [FullTrack]) {
-    if self.state.field == SortField::Default {
-      return;
-    }
-
-    tracks.sort_by(|a, b| {
-      let order = match self.state.field {
-        SortField::Name => a.name.cmp(&b.name),
-        SortField::Duration => a.duration.cmp(&b.duration),
-        SortField::Artist => {
-          let empty_string = String::new();
-          let artist_a = a
-            .artists
-            .first()
-            .map(|ar| &ar.name)
-            .unwrap_or(&empty_string);
-          let artist_b = b
-            .artists
-            .first()
-            .map(|ar| &ar.name)
-            .unwrap_or(&empty_string);
-          artist_a.cmp(artist_b)
-        }
-        SortField::Album => a.album.name.cmp(&b.album.name),
-        // DateAdded requires PlaylistItem wrapper which we don't have here.
-        // Assuming Default order is DateAdded for playlists.
-        _ => std::cmp::Ordering::Equal,
+  /// Positions of `items` in sorted order. Whatever the sort keys leave tied stays in the
+  /// original API order.
+  pub fn order<T: Sortable>(&self, items: &[T]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    order.sort_by(|&a, &b| {
+      let (a, b) = (&items[a], &items[b]);
+      let primary = self.compare(self.state.field, a, b);
+      let primary = match self.state.order {
+        SortOrder::Ascending => primary,
+        SortOrder::Descending => primary.reverse(),
       };
+      self
+        .state
+        .then
+        .iter()
+        .flatten()
+        .fold(primary, |ordering, field| {
+          ordering.then_with(|| self.compare(*field, a, b))
+        })
+        .then_with(|| self.compare(SortField::Default, a, b))
+    });
+    order
+  }
 
-      if self.state.order == SortOrder::Descending {
-        order.reverse()
-      } else {
-        order
-      }
+  /// Sort `items` in place
+  pub fn sort<T: Sortable>(&self, items: &mut Vec<T>) {
+    let order = self.order(items);
+    *items = reorder(std::mem::take(items), &order);
+  }
+
+  fn compare<T: Sortable>(&self, field: SortField, a: &T, b: &T) -> Ordering {
+    let lowercase = |text: Option<&str>| text.map(str::to_lowercase);
+    match field {
+      SortField::Default => a.natural_key().cmp(&b.natural_key()),
+      SortField::Name => a.name().to_lowercase().cmp(&b.name().to_lowercase()),
+      SortField::DateAdded => a.added_at().cmp(&b.added_at()),
+      SortField::Artist => lowercase(a.artist()).cmp(&lowercase(b.artist())),
+      SortField::Album => lowercase(a.album()).cmp(&lowercase(b.album())),
+      SortField::Duration => a.duration_ms().cmp(&b.duration_ms()),
+      SortField::Popularity => a.popularity().cmp(&b.popularity()),
+      // Dates are `1981`, `1981-12` or `1981-12-15`, which compare fine as text
+      SortField::ReleaseDate => a.release_date().cmp(&b.release_date()),
+      SortField::TrackNumber => a.track_position().cmp(&b.track_position()),
+      SortField::Explicit => a.explicit().cmp(&b.explicit()),
+      SortField::Random => self.shuffle_key(a).cmp(&self.shuffle_key(b)),
+    }
+  }
+
+  /// Where `item` lands in the shuffled order, the same for the same seed on every run
+  fn shuffle_key<T: Sortable>(&self, item: &T) -> u64 {
+    // FNV-1a of the id, then splitmix64 with the seed
+    let text = item.item_id().unwrap_or(item.name());
+    let hash = text.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+      (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
     });
+    let mut key = hash ^ self.state.seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    key = (key ^ (key >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    key = (key ^ (key >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    key ^ (key >> 31)
+  }
+}
+
+/// `items` in the order of `order`, as returned by `Sorter::order`
+pub fn reorder<T>(items: Vec<T>, order: &[usize]) -> Vec<T> {
+  let mut slots: Vec<Option<T>> = items.into_iter().map(Some).collect();
+  order
+    .iter()
+    .filter_map(|index| slots.get_mut(*index).and_then(Option::take))
+    .collect()
+}
+
+/// Sorts picked for playlists, by playlist id, kept in the user cache directory so a playlist
+/// opens sorted the way it was left
+#[derive(Default)]
+pub struct PlaylistSorts {
+  path: Option<PathBuf>,
+  sorts: HashMap<String, SortState>,
+}
+
+impl PlaylistSorts {
+  pub fn load() -> Self {
+    Self::load_from(dirs::cache_dir().map(|dir| dir.join("spotatui").join("playlist_sorts.json")))
+  }
+
+  fn load_from(path: Option<PathBuf>) -> Self {
+    let sorts = path
+      .as_ref()
+      .and_then(|path| fs::read_to_string(path).ok())
+      .and_then(|text| {
+        serde_json::from_str(&text)
+          .map_err(|e| debug!("ignoring unreadable playlist sorts: {}", e))
+          .ok()
+      })
+      .unwrap_or_default();
+    Self { path, sorts }
+  }
+
+  pub fn get(&self, playlist_id: &str) -> SortState {
+    self.sorts.get(playlist_id).copied().unwrap_or_default()
+  }
+
+  pub fn set(&mut self, playlist_id: &str, state: SortState) {
+    if state.is_default() {
+      self.sorts.remove(playlist_id);
+    } else {
+      self.sorts.insert(playlist_id.to_string(), state);
+    }
+    self.save();
+  }
+
+  fn save(&self) {
+    let Some(path) = self.path.as_ref() else {
+      return;
+    };
+    let written = serde_json::to_string_pretty(&self.sorts)
+      .map_err(std::io::Error::other)
+      .and_then(|json| {
+        path
+          .parent()
+          .map_or(Ok(()), fs::create_dir_all)
+          .and_then(|_| fs::write(path, json))
+      });
+    if let Err(e) = written {
+      debug!("failed to save playlist sorts to {}: {}", path.display(), e);
+    }
   }
 }
 
@@ -236,4 +723,113 @@ mod tests {
     assert!(fields.contains(&SortField::Name));
     assert!(!fields.contains(&SortField::Artist));
   }
+
+  struct Song {
+    name: &'static str,
+    artist: &'static str,
+    album: &'static str,
+    track: u32,
+  }
+
+  impl Sortable for Song {
+    fn name(&self) -> &str {
+      self.name
+    }
+
+    fn artist(&self) -> Option<&str> {
+      Some(self.artist)
+    }
+
+    fn album(&self) -> Option<&str> {
+      Some(self.album)
+    }
+
+    fn track_position(&self) -> Option<(i32, u32)> {
+      Some((1, self.track))
+    }
+  }
+
+  fn songs() -> Vec<Song> {
+    vec![
+      Song {
+        name: "b2",
+        artist: "B",
+        album: "Two",
+        track: 1,
+      },
+      Song {
+        name: "a2",
+        artist: "a",
+        album: "Two",
+        track: 2,
+      },
+      Song {
+        name: "a1",
+        artist: "A",
+        album: "One",
+        track: 3,
+      },
+      Song {
+        name: "a3",
+        artist: "A",
+        album: "Two",
+        track: 1,
+      },
+    ]
+  }
+
+  #[test]
+  fn tie_breakers_follow_the_main_field() {
+    let mut state = SortState::new();
+    state.apply_field(SortField::Artist);
+    state.toggle_then(SortField::Album);
+    state.toggle_then(SortField::TrackNumber);
+    assert_eq!(state.then_position(SortField::TrackNumber), Some(2));
+
+    let mut items = songs();
+    Sorter::new(state).sort(&mut items);
+    let names: Vec<&str> = items.iter().map(|song| song.name).collect();
+    assert_eq!(names, vec!["a1", "a3", "a2", "b2"]);
+
+    // Picking a tie-breaker as the main field takes it out of the tie-breakers
+    state.apply_field(SortField::Album);
+    assert_eq!(state.then, [Some(SortField::TrackNumber), None]);
+    state.apply_field(SortField::Default);
+    assert_eq!(state.then, [None, None]);
+  }
+
+  #[test]
+  fn random_order_stays_put_for_a_seed() {
+    let mut state = SortState::new();
+    state.apply_field(SortField::Random);
+    let first = Sorter::new(state).order(&songs());
+    assert_eq!(Sorter::new(state).order(&songs()), first);
+
+    let mut sorted = first.clone();
+    sorted.sort();
+    assert_eq!(sorted, vec![0, 1, 2, 3]);
+  }
+
+  #[test]
+  fn playlist_sorts_survive_a_restart() {
+    let dir = std::env::temp_dir().join(format!("spotatui-sorts-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let path = dir.join("playlist_sorts.json");
+
+    let mut state = SortState::new();
+    state.apply_field(SortField::ReleaseDate);
+    state.apply_field(SortField::ReleaseDate);
+    let mut sorts = PlaylistSorts::load_from(Some(path.clone()));
+    sorts.set("37i9dQZF1DXcBWIGoYBM5M", state);
+    sorts.set("other", SortState::new());
+    let mut reversed = SortState::new();
+    reversed.apply_field(SortField::Default);
+    assert!(!reversed.is_default());
+    sorts.set("reversed", reversed);
+
+    let next_run = PlaylistSorts::load_from(Some(path));
+    assert_eq!(next_run.get("37i9dQZF1DXcBWIGoYBM5M"), state);
+    assert!(next_run.get("other").is_default());
+    assert_eq!(next_run.get("reversed").order, SortOrder::Descending);
+  }
 }
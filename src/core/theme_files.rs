@@ -0,0 +1,258 @@
+// Themes loaded from `~/.config/spotatui/themes/`: spotatui theme files using the same keys as
+// the `theme:` section of config.yml, and base16/base24 scheme files
+
+use crate::core::user_config::{apply_user_theme, Theme, UserTheme};
+use anyhow::{anyhow, Result};
+use log::warn;
+use ratatui::style::Color;
+use serde::Deserialize;
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+pub const THEMES_DIR: &str = "themes";
+
+#[derive(Clone, Debug)]
+pub struct ThemeFile {
+  pub name: String,
+  pub theme: Theme,
+}
+
+#[derive(Deserialize)]
+struct ThemeFileString {
+  name: Option<String>,
+  #[serde(flatten)]
+  colors: UserTheme,
+}
+
+/// Paths and modification times of the theme files, compared between polls to spot edits
+pub type ThemeDirStamp = Vec<(PathBuf, Option<SystemTime>)>;
+
+fn theme_paths(dir: &Path) -> Vec<PathBuf> {
+  let Ok(entries) = fs::read_dir(dir) else {
+    return Vec::new();
+  };
+  let mut paths: Vec<PathBuf> = entries
+    .filter_map(|entry| Some(entry.ok()?.path()))
+    .filter(|path| {
+      matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("yml" | "yaml")
+      )
+    })
+    .collect();
+  paths.sort();
+  paths
+}
+
+pub fn stamp(dir: &Path) -> ThemeDirStamp {
+  theme_paths(dir)
+    .into_iter()
+    .map(|path| {
+      let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+      (path, modified)
+    })
+    .collect()
+}
+
+/// Load every theme in `dir`, skipping files that fail to parse. The errors are returned so the
+/// caller can surface them
+pub fn load_dir(dir: &Path) -> (Vec<ThemeFile>, Vec<String>) {
+  let mut themes: Vec<ThemeFile> = Vec::new();
+  let mut errors = Vec::new();
+  for path in theme_paths(dir) {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let parsed = fs::read_to_string(&path)
+      .map_err(anyhow::Error::from)
+      .and_then(|contents| parse_theme_file(&stem, &contents));
+    match parsed {
+      // Later files win when two themes share a name
+      Ok(theme) => {
+        themes.retain(|t| t.name != theme.name);
+        themes.push(theme);
+      }
+      Err(e) => {
+        warn!("skipping theme {}: {}", path.display(), e);
+        errors.push(format!("{}: {}", file_name, e));
+      }
+    }
+  }
+  (themes, errors)
+}
+
+/// `default_name` is used when the file doesn't name its theme
+pub fn parse_theme_file(default_name: &str, contents: &str) -> Result<ThemeFile> {
+  let value: Value = serde_yaml::from_str(contents)?;
+  let Value::Mapping(map) = &value else {
+    return Err(anyhow!("expected a mapping of theme keys"));
+  };
+
+  let palette = match map.get("palette") {
+    Some(Value::Mapping(palette)) => Some(palette),
+    _ if map.contains_key("base00") => Some(map),
+    _ => None,
+  };
+  if let Some(palette) = palette {
+    let name = ["name", "scheme"]
+      .iter()
+      .find_map(|key| map.get(*key)?.as_str())
+      .unwrap_or(default_name);
+    return Ok(ThemeFile {
+      name: name.to_string(),
+      theme: base16_theme(palette)?,
+    });
+  }
+
+  let file: ThemeFileString = serde_yaml::from_value(value)?;
+  let mut theme = Theme::default();
+  apply_user_theme(&mut theme, file.colors)?;
+  Ok(ThemeFile {
+    name: file.name.unwrap_or_else(|| default_name.to_string()),
+    theme,
+  })
+}
+
+/// Map a base16 palette onto the theme following the base16 styling guidelines. The extra
+/// bright colors of base24 are used where a scheme provides them
+fn base16_theme(palette: &Mapping) -> Result<Theme> {
+  let color = |key: &str| -> Result<Option<Color>> {
+    let hex = match palette.get(key) {
+      Some(Value::String(hex)) => hex.clone(),
+      // Unquoted all-digit values like 282828 are read by YAML as numbers
+      Some(Value::Number(n)) if n.is_u64() => format!("{:06}", n),
+      _ => return Ok(None),
+    };
+    parse_hex_color(&hex).map(Some)
+  };
+  let required = |key: &str| -> Result<Color> {
+    color(key)?.ok_or_else(|| anyhow!("missing {} in base16 palette", key))
+  };
+
+  let background = required("base00")?;
+  let status_background = required("base01")?;
+  let muted = required("base03")?;
+  let foreground = required("base05")?;
+  let bright_foreground = required("base07")?;
+  let red = required("base08")?;
+  let yellow = required("base0A")?;
+  let cyan = required("base0C")?;
+  let blue = required("base0D")?;
+  let magenta = required("base0E")?;
+
+  Ok(Theme {
+    analysis_bar: cyan,
+    analysis_bar_text: background,
+    active: blue,
+    banner: blue,
+    error_border: red,
+    error_text: color("base12")?.unwrap_or(red),
+    hint: yellow,
+    hovered: magenta,
+    inactive: muted,
+    playbar_background: status_background,
+    playbar_progress: blue,
+    playbar_progress_text: bright_foreground,
+    playbar_text: foreground,
+    selected: cyan,
+    text: foreground,
+    background,
+    header: foreground,
+    highlighted_lyrics: color("base16")?.unwrap_or(cyan),
+  })
+}
+
+fn parse_hex_color(hex: &str) -> Result<Color> {
+  let digits = hex.trim().trim_start_matches('#');
+  if digits.len() != 6 || !digits.is_ascii() {
+    return Err(anyhow!("invalid hex color {:?}", hex));
+  }
+  let channel = |range: std::ops::Range<usize>| {
+    u8::from_str_radix(&digits[range], 16).map_err(|_| anyhow!("invalid hex color {:?}", hex))
+  };
+  Ok(Color::Rgb(channel(0..2)?, channel(2..4)?, channel(4..6)?))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const BASE16_PALETTE: &str = r##"
+  base00: "#1d1f21"
+  base01: "#282a2e"
+  base02: "#373b41"
+  base03: "#969896"
+  base04: "#b4b7b4"
+  base05: "#c5c8c6"
+  base06: "#e0e0e0"
+  base07: "#ffffff"
+  base08: "#cc6666"
+  base09: "#de935f"
+  base0A: "#f0c674"
+  base0B: "#b5bd68"
+  base0C: "#8abeb7"
+  base0D: "#81a2be"
+  base0E: "#b294bb"
+  base0F: "#a3685a"
+"##;
+
+  #[test]
+  fn parses_tinted_theming_scheme() {
+    let contents = format!(
+      "system: \"base16\"\nname: \"Tomorrow Night\"\npalette:\n{}",
+      BASE16_PALETTE
+    );
+    let file = parse_theme_file("tomorrow-night", &contents).unwrap();
+    assert_eq!(file.name, "Tomorrow Night");
+    assert_eq!(file.theme.background, Color::Rgb(0x1d, 0x1f, 0x21));
+    assert_eq!(file.theme.active, Color::Rgb(0x81, 0xa2, 0xbe));
+    assert_eq!(file.theme.error_text, Color::Rgb(0xcc, 0x66, 0x66));
+  }
+
+  #[test]
+  fn parses_legacy_base24_scheme() {
+    // Legacy schemes keep the palette at the top level, without leading '#'
+    let contents = format!(
+      "scheme: \"Legacy\"\n{}\nbase12: \"ff3333\"\nbase16: \"33ffff\"\n",
+      BASE16_PALETTE.replace('#', "").replace("\n  ", "\n")
+    );
+    let file = parse_theme_file("legacy", &contents).unwrap();
+    assert_eq!(file.name, "Legacy");
+    assert_eq!(file.theme.error_text, Color::Rgb(0xff, 0x33, 0x33));
+    assert_eq!(file.theme.highlighted_lyrics, Color::Rgb(0x33, 0xff, 0xff));
+  }
+
+  #[test]
+  fn parses_spotatui_theme_with_file_name_fallback() {
+    let file = parse_theme_file("team", "active: \"10, 20, 30\"\ntext: White\n").unwrap();
+    assert_eq!(file.name, "team");
+    assert_eq!(file.theme.active, Color::Rgb(10, 20, 30));
+    assert_eq!(file.theme.text, Color::White);
+    // Keys that aren't set keep the default theme's colors
+    assert_eq!(file.theme.hint, Theme::default().hint);
+  }
+
+  #[test]
+  fn load_dir_skips_broken_files() {
+    let dir = std::env::temp_dir().join(format!("spotatui-themes-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("good.yml"), "name: Team\ntext: White\n").unwrap();
+    fs::write(dir.join("broken.yaml"), "active: \"300, 0, 0\"\n").unwrap();
+    fs::write(dir.join("notes.txt"), "not a theme").unwrap();
+
+    let (themes, errors) = load_dir(&dir);
+    assert_eq!(themes.len(), 1);
+    assert_eq!(themes[0].name, "Team");
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].starts_with("broken.yaml"));
+
+    let _ = fs::remove_dir_all(dir);
+  }
+
+  #[test]
+  fn rejects_incomplete_base16_palette() {
+    assert!(parse_theme_file("broken", "base00: \"1d1f21\"\n").is_err());
+    assert!(parse_hex_color("#12345").is_err());
+  }
+}
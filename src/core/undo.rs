@@ -0,0 +1,177 @@
+// Destructive library and playlist changes, kept with what it takes to reverse them
+
+use rspotify::model::idtypes::{
+  AlbumId, ArtistId, PlayableId, PlaylistId, ShowId, TrackId, UserId,
+};
+use rspotify::model::playlist::SimplifiedPlaylist;
+use rspotify::prelude::Id;
+use std::collections::VecDeque;
+
+/// Oldest entries are dropped beyond this
+const UNDO_LIMIT: usize = 50;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum UndoOp {
+  RemovedFromPlaylist {
+    playlist_id: PlaylistId<'static>,
+    playlist_name: String,
+    track_id: TrackId<'static>,
+    track_name: String,
+    /// Position of the track in the playlist, so undoing puts it back in the same place
+    position: usize,
+  },
+  Unliked {
+    id: PlayableId<'static>,
+    name: String,
+  },
+  UnfollowedArtist {
+    id: ArtistId<'static>,
+    name: String,
+  },
+  UnfollowedPlaylist {
+    owner_id: UserId<'static>,
+    id: PlaylistId<'static>,
+    name: String,
+    public: Option<bool>,
+  },
+  RemovedAlbum {
+    id: AlbumId<'static>,
+    name: String,
+  },
+  UnfollowedShow {
+    id: ShowId<'static>,
+    name: String,
+  },
+}
+
+impl UndoOp {
+  pub fn unfollowed_playlist(playlist: &SimplifiedPlaylist) -> Self {
+    UndoOp::UnfollowedPlaylist {
+      owner_id: playlist.owner.id.clone().into_static(),
+      id: playlist.id.clone().into_static(),
+      name: playlist.name.clone(),
+      public: playlist.public,
+    }
+  }
+
+  /// What was done, e.g. "Removed Song from Playlist"
+  pub fn description(&self) -> String {
+    match self {
+      UndoOp::RemovedFromPlaylist {
+        track_name,
+        playlist_name,
+        ..
+      } => format!("Removed {} from {}", track_name, playlist_name),
+      UndoOp::Unliked { name, .. } => format!("Unliked {}", name),
+      UndoOp::UnfollowedArtist { name, .. } => format!("Unfollowed {}", name),
+      UndoOp::UnfollowedPlaylist { name, .. } => format!("Unfollowed playlist {}", name),
+      UndoOp::RemovedAlbum { name, .. } => format!("Removed album {}", name),
+      UndoOp::UnfollowedShow { name, .. } => format!("Unfollowed {}", name),
+    }
+  }
+
+  /// Uri of the track, artist, playlist, album or show that was changed
+  pub fn uri(&self) -> String {
+    match self {
+      UndoOp::RemovedFromPlaylist { track_id, .. } => track_id.uri(),
+      UndoOp::Unliked { id, .. } => id.uri(),
+      UndoOp::UnfollowedArtist { id, .. } => id.uri(),
+      UndoOp::UnfollowedPlaylist { id, .. } => id.uri(),
+      UndoOp::RemovedAlbum { id, .. } => id.uri(),
+      UndoOp::UnfollowedShow { id, .. } => id.uri(),
+    }
+  }
+}
+
+/// Undoable changes, newest last
+#[derive(Default)]
+pub struct UndoHistory {
+  entries: VecDeque<UndoOp>,
+}
+
+impl UndoHistory {
+  pub fn push(&mut self, op: UndoOp) {
+    if self.entries.len() == UNDO_LIMIT {
+      self.entries.pop_front();
+    }
+    self.entries.push_back(op);
+  }
+
+  /// Take out the entry `index` places from the newest one
+  pub fn take(&mut self, index: usize) -> Option<UndoOp> {
+    let position = self.entries.len().checked_sub(index + 1)?;
+    self.entries.remove(position)
+  }
+
+  /// Drop the newest entry for `uri`, used when the change it records did not go through
+  pub fn discard(&mut self, uri: &str) {
+    if let Some(position) = self.entries.iter().rposition(|op| op.uri() == uri) {
+      self.entries.remove(position);
+    }
+  }
+
+  /// Entries newest first
+  pub fn iter(&self) -> impl Iterator<Item = &UndoOp> {
+    self.entries.iter().rev()
+  }
+
+  pub fn len(&self) -> usize {
+    self.entries.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn removed_album(name: &str) -> UndoOp {
+    UndoOp::RemovedAlbum {
+      id: AlbumId::from_id("4aawyAB9vmqN3uQ7FjRGTy").unwrap(),
+      name: name.to_string(),
+    }
+  }
+
+  fn unfollowed_artist(name: &str) -> UndoOp {
+    UndoOp::UnfollowedArtist {
+      id: ArtistId::from_id("0OdUWJ0sBjDrqHygGUXeCF").unwrap(),
+      name: name.to_string(),
+    }
+  }
+
+  #[test]
+  fn entries_come_back_newest_first() {
+    let mut history = UndoHistory::default();
+    history.push(removed_album("First"));
+    history.push(unfollowed_artist("Second"));
+
+    let descriptions: Vec<String> = history.iter().map(UndoOp::description).collect();
+    assert_eq!(
+      descriptions,
+      vec!["Unfollowed Second", "Removed album First"]
+    );
+    assert_eq!(history.take(1), Some(removed_album("First")));
+    assert_eq!(history.take(0), Some(unfollowed_artist("Second")));
+    assert!(history.take(0).is_none());
+  }
+
+  #[test]
+  fn failed_changes_are_discarded_and_old_ones_dropped() {
+    let mut history = UndoHistory::default();
+    for i in 0..UNDO_LIMIT {
+      history.push(removed_album(&i.to_string()));
+    }
+    history.push(unfollowed_artist("Band"));
+    assert_eq!(history.len(), UNDO_LIMIT);
+
+    history.discard("spotify:artist:0OdUWJ0sBjDrqHygGUXeCF");
+    assert_eq!(history.len(), UNDO_LIMIT - 1);
+    assert_eq!(
+      history.take(0),
+      Some(removed_album(&(UNDO_LIMIT - 1).to_string()))
+    );
+  }
+}
@@ -1,10 +1,14 @@
+use crate::core::columns::{ColumnsConfig, ColumnsConfigString};
+use crate::core::keymap::{Action, KeyBindings, KeyBindingsString, RESERVED_KEYS};
+use crate::core::theme_files::{self, ThemeFile, THEMES_DIR};
 use crate::event::Key;
 use anyhow::{anyhow, Result};
 use ratatui::style::{Color, Style};
 use serde::{Deserialize, Serialize};
 use std::{
-  fs,
+  env, fs,
   path::{Path, PathBuf},
+  process::Command,
 };
 
 const FILE_NAME: &str = "config.yml";
@@ -104,6 +108,8 @@ pub enum ThemePreset {
   Gruvbox,
   GruvboxLight,
   CatppuccinMocha,
+  /// Recolored from the cover art of each track
+  Adaptive,
   Custom, // When user has manually customized colors
 }
 
@@ -120,6 +126,8 @@ impl ThemePreset {
       ThemePreset::Gruvbox,
       ThemePreset::GruvboxLight,
       ThemePreset::CatppuccinMocha,
+      #[cfg(feature = "cover-art")]
+      ThemePreset::Adaptive,
     ]
   }
 
@@ -135,6 +143,7 @@ impl ThemePreset {
       ThemePreset::Gruvbox => "Gruvbox",
       ThemePreset::GruvboxLight => "Gruvbox Light",
       ThemePreset::CatppuccinMocha => "Catppuccin Mocha",
+      ThemePreset::Adaptive => "Adaptive",
       ThemePreset::Custom => "Custom",
     }
   }
@@ -151,28 +160,11 @@ impl ThemePreset {
       "Gruvbox" => ThemePreset::Gruvbox,
       "Gruvbox Light" => ThemePreset::GruvboxLight,
       "Catppuccin Mocha" => ThemePreset::CatppuccinMocha,
+      "Adaptive" => ThemePreset::Adaptive,
       _ => ThemePreset::Custom,
     }
   }
 
-  pub fn next(&self) -> Self {
-    let presets = Self::all();
-    let current_idx = presets.iter().position(|p| p == self).unwrap_or(0);
-    let next_idx = (current_idx + 1) % presets.len();
-    presets[next_idx]
-  }
-
-  pub fn prev(&self) -> Self {
-    let presets = Self::all();
-    let current_idx = presets.iter().position(|p| p == self).unwrap_or(0);
-    let prev_idx = if current_idx == 0 {
-      presets.len() - 1
-    } else {
-      current_idx - 1
-    };
-    presets[prev_idx]
-  }
-
   /// Get the theme colors for this preset
   pub fn to_theme(self) -> Theme {
     match self {
@@ -357,6 +349,8 @@ impl ThemePreset {
         header: Color::Rgb(29, 185, 84),             // Spotify Green
         highlighted_lyrics: Color::Rgb(29, 185, 84), // Spotify Green
       },
+      // Starting point until the first cover has been analyzed
+      ThemePreset::Adaptive => Theme::default(),
       ThemePreset::Custom => Theme::default(), // Won't be used directly
     }
   }
@@ -395,6 +389,369 @@ impl VisualizerStyle {
   }
 }
 
+/// How cover art is drawn when the terminal has no image protocol
+#[cfg(feature = "cover-art")]
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub enum CoverArtFallback {
+  /// Two pixels per cell with `▀`, full color
+  #[default]
+  HalfBlock,
+  /// Eight dots per cell, two colors each; sharper edges, coarser color
+  Braille,
+}
+
+#[cfg(feature = "cover-art")]
+impl CoverArtFallback {
+  pub fn all() -> &'static [CoverArtFallback] {
+    &[CoverArtFallback::HalfBlock, CoverArtFallback::Braille]
+  }
+
+  pub fn name(&self) -> &'static str {
+    match self {
+      CoverArtFallback::HalfBlock => "Half Blocks",
+      CoverArtFallback::Braille => "Braille",
+    }
+  }
+
+  pub fn from_name(name: &str) -> Self {
+    Self::all()
+      .iter()
+      .find(|style| style.name().eq_ignore_ascii_case(name))
+      .copied()
+      .unwrap_or_default()
+  }
+
+  pub fn next(&self) -> Self {
+    let styles = Self::all();
+    let current_idx = styles.iter().position(|s| s == self).unwrap_or(0);
+    styles[(current_idx + 1) % styles.len()]
+  }
+
+  pub fn prev(&self) -> Self {
+    let styles = Self::all();
+    let current_idx = styles.iter().position(|s| s == self).unwrap_or(0);
+    styles[(current_idx + styles.len() - 1) % styles.len()]
+  }
+}
+
+/// How many colors the terminal can show. Anything below truecolor gets the theme's RGB colors
+/// mapped to the nearest palette entry
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub enum ColorSupport {
+  /// Detect from COLORTERM, TERM and terminfo
+  #[default]
+  Auto,
+  TrueColor,
+  Ansi256,
+  Ansi16,
+}
+
+impl ColorSupport {
+  pub fn all() -> &'static [ColorSupport] {
+    &[
+      ColorSupport::Auto,
+      ColorSupport::TrueColor,
+      ColorSupport::Ansi256,
+      ColorSupport::Ansi16,
+    ]
+  }
+
+  pub fn name(&self) -> &'static str {
+    match self {
+      ColorSupport::Auto => "Auto",
+      ColorSupport::TrueColor => "Truecolor",
+      ColorSupport::Ansi256 => "256 Colors",
+      ColorSupport::Ansi16 => "16 Colors",
+    }
+  }
+
+  pub fn from_name(name: &str) -> Self {
+    Self::all()
+      .iter()
+      .find(|support| support.name().eq_ignore_ascii_case(name))
+      .copied()
+      .unwrap_or_default()
+  }
+
+  pub fn next(&self) -> Self {
+    let supports = Self::all();
+    let current_idx = supports.iter().position(|s| s == self).unwrap_or(0);
+    supports[(current_idx + 1) % supports.len()]
+  }
+
+  pub fn prev(&self) -> Self {
+    let supports = Self::all();
+    let current_idx = supports.iter().position(|s| s == self).unwrap_or(0);
+    supports[(current_idx + supports.len() - 1) % supports.len()]
+  }
+
+  /// Resolve `Auto` by looking at the environment; explicit settings are kept
+  pub fn resolve(self) -> ColorSupport {
+    if self != ColorSupport::Auto {
+      return self;
+    }
+    let colorterm = env::var("COLORTERM").ok();
+    let term = env::var("TERM").ok();
+    detect_color_support(colorterm.as_deref(), term.as_deref(), || {
+      // Windows Terminal doesn't set COLORTERM
+      if env::var_os("WT_SESSION").is_some() {
+        return Some(u32::MAX);
+      }
+      terminfo_colors()
+    })
+  }
+}
+
+/// `terminfo` is only consulted when COLORTERM and TERM are inconclusive
+fn detect_color_support(
+  colorterm: Option<&str>,
+  term: Option<&str>,
+  terminfo: impl FnOnce() -> Option<u32>,
+) -> ColorSupport {
+  if matches!(colorterm, Some("truecolor" | "24bit")) {
+    return ColorSupport::TrueColor;
+  }
+  if let Some(term) = term {
+    if term.ends_with("-direct") {
+      return ColorSupport::TrueColor;
+    }
+    if term.contains("256color") {
+      return ColorSupport::Ansi256;
+    }
+    if term == "linux" || term == "dumb" {
+      return ColorSupport::Ansi16;
+    }
+  }
+  match terminfo() {
+    Some(colors) if colors >= 1 << 24 => ColorSupport::TrueColor,
+    Some(colors) if colors >= 256 => ColorSupport::Ansi256,
+    Some(_) => ColorSupport::Ansi16,
+    // Nothing to go on, keep the colors as they were configured
+    None => ColorSupport::TrueColor,
+  }
+}
+
+fn terminfo_colors() -> Option<u32> {
+  let output = Command::new("tput").arg("colors").output().ok()?;
+  if !output.status.success() {
+    return None;
+  }
+  String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+/// How notifications are delivered
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub enum NotificationMethod {
+  /// OSC 9 escape sequence (iTerm2, WezTerm, Windows Terminal, kitty, foot, ...)
+  #[default]
+  Osc9,
+  /// OSC 777 escape sequence with separate title and body (rxvt-unicode, Ghostty, foot, ...)
+  Osc777,
+  /// Freedesktop notification over D-Bus, using the cover art as icon
+  Desktop,
+  /// Plain terminal bell
+  Bell,
+}
+
+impl NotificationMethod {
+  pub fn all() -> &'static [NotificationMethod] {
+    &[
+      NotificationMethod::Osc9,
+      NotificationMethod::Osc777,
+      NotificationMethod::Desktop,
+      NotificationMethod::Bell,
+    ]
+  }
+
+  pub fn name(&self) -> &'static str {
+    match self {
+      NotificationMethod::Osc9 => "OSC 9",
+      NotificationMethod::Osc777 => "OSC 777",
+      NotificationMethod::Desktop => "Desktop",
+      NotificationMethod::Bell => "Bell",
+    }
+  }
+
+  pub fn from_name(name: &str) -> Self {
+    Self::all()
+      .iter()
+      .find(|method| method.name().eq_ignore_ascii_case(name))
+      .copied()
+      .unwrap_or_default()
+  }
+
+  pub fn next(&self) -> Self {
+    let methods = Self::all();
+    let current_idx = methods.iter().position(|m| m == self).unwrap_or(0);
+    methods[(current_idx + 1) % methods.len()]
+  }
+
+  pub fn prev(&self) -> Self {
+    let methods = Self::all();
+    let current_idx = methods.iter().position(|m| m == self).unwrap_or(0);
+    methods[(current_idx + methods.len() - 1) % methods.len()]
+  }
+}
+
+/// Number of bands in the native playback equalizer
+pub const EQ_BAND_COUNT: usize = 10;
+
+/// Centre frequencies of the equalizer bands in Hz. The outer bands are shelves, the rest peaking.
+pub const EQ_BAND_FREQUENCIES: [f32; EQ_BAND_COUNT] = [
+  32.0, 64.0, 125.0, 250.0, 500.0, 1000.0, 2000.0, 4000.0, 8000.0, 16000.0,
+];
+
+/// Band gains are limited to +/- this many dB
+pub const EQ_MAX_GAIN_DB: f32 = 12.0;
+
+/// Built-in equalizer curves
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub enum EqualizerPreset {
+  #[default]
+  Flat,
+  BassBoost,
+  Vocal,
+  Podcast,
+  /// Hand-tuned band gains
+  Custom,
+}
+
+impl EqualizerPreset {
+  pub fn all() -> &'static [EqualizerPreset] {
+    &[
+      EqualizerPreset::Flat,
+      EqualizerPreset::BassBoost,
+      EqualizerPreset::Vocal,
+      EqualizerPreset::Podcast,
+      EqualizerPreset::Custom,
+    ]
+  }
+
+  pub fn name(&self) -> &'static str {
+    match self {
+      EqualizerPreset::Flat => "Flat",
+      EqualizerPreset::BassBoost => "Bass Boost",
+      EqualizerPreset::Vocal => "Vocal",
+      EqualizerPreset::Podcast => "Podcast",
+      EqualizerPreset::Custom => "Custom",
+    }
+  }
+
+  pub fn from_name(name: &str) -> Self {
+    Self::all()
+      .iter()
+      .find(|preset| preset.name().eq_ignore_ascii_case(name))
+      .copied()
+      .unwrap_or_default()
+  }
+
+  pub fn next(&self) -> Self {
+    let presets = Self::all();
+    let current_idx = presets.iter().position(|p| p == self).unwrap_or(0);
+    presets[(current_idx + 1) % presets.len()]
+  }
+
+  pub fn prev(&self) -> Self {
+    let presets = Self::all();
+    let current_idx = presets.iter().position(|p| p == self).unwrap_or(0);
+    presets[(current_idx + presets.len() - 1) % presets.len()]
+  }
+
+  /// Band gains in dB, `None` for `Custom`
+  pub fn gains(&self) -> Option<[f32; EQ_BAND_COUNT]> {
+    match self {
+      EqualizerPreset::Flat => Some([0.0; EQ_BAND_COUNT]),
+      EqualizerPreset::BassBoost => Some([6.0, 5.0, 4.0, 2.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]),
+      EqualizerPreset::Vocal => Some([-2.0, -2.0, -1.0, 0.0, 2.0, 3.0, 3.0, 2.0, 0.0, -1.0]),
+      // Cut the boom of close-miked voices and lift intelligibility
+      EqualizerPreset::Podcast => Some([-6.0, -4.0, -2.0, 0.0, 1.0, 2.0, 3.0, 2.0, 0.0, -2.0]),
+      EqualizerPreset::Custom => None,
+    }
+  }
+}
+
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EqualizerConfigString {
+  pub enabled: Option<bool>,
+  pub preset: Option<EqualizerPreset>,
+  pub gains_db: Option<Vec<f32>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EqualizerConfig {
+  pub enabled: bool,
+  pub preset: EqualizerPreset,
+  pub gains_db: [f32; EQ_BAND_COUNT],
+}
+
+impl Default for EqualizerConfig {
+  fn default() -> Self {
+    Self {
+      enabled: false,
+      preset: EqualizerPreset::Flat,
+      gains_db: [0.0; EQ_BAND_COUNT],
+    }
+  }
+}
+
+impl EqualizerConfig {
+  /// Gains to hand to the player, or `None` to bypass the equalizer
+  pub fn active_gains(&self) -> Option<[f32; EQ_BAND_COUNT]> {
+    self.enabled.then_some(self.gains_db)
+  }
+
+  pub fn set_preset(&mut self, preset: EqualizerPreset) {
+    self.preset = preset;
+    if let Some(gains) = preset.gains() {
+      self.gains_db = gains;
+    }
+  }
+}
+
+/// Where lyrics can come from. Providers are tried in the configured order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LyricsProviderKind {
+  /// `.lrc`/`.txt` files in the local lyrics directory
+  Local,
+  /// Exact artist/title/duration match on LRCLIB
+  Lrclib,
+  /// LRCLIB full-text search, for titles that don't match exactly ("feat.", remasters)
+  LrclibSearch,
+}
+
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LyricsConfigString {
+  pub local_dir: Option<String>,
+  pub lrclib_url: Option<String>,
+  pub providers: Option<Vec<LyricsProviderKind>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LyricsConfig {
+  pub local_dir: Option<PathBuf>,
+  pub lrclib_url: String,
+  pub providers: Vec<LyricsProviderKind>,
+}
+
+impl Default for LyricsConfig {
+  fn default() -> Self {
+    Self {
+      local_dir: dirs::home_dir()
+        .map(|home| home.join(CONFIG_DIR).join(APP_CONFIG_DIR).join("lyrics")),
+      lrclib_url: "https://lrclib.net".to_string(),
+      providers: vec![LyricsProviderKind::Local, LyricsProviderKind::Lrclib],
+    }
+  }
+}
+
+/// Expand a leading `~/` so paths in the config can be written the way they are typed in a shell
+fn expand_home(path: &str) -> PathBuf {
+  match (path.strip_prefix("~/"), dirs::home_dir()) {
+    (Some(rest), Some(home)) => home.join(rest),
+    _ => PathBuf::from(path),
+  }
+}
+
 fn parse_key(key: String) -> Result<Key> {
   fn get_single_char(string: &str) -> char {
     match string.chars().next() {
@@ -458,37 +815,35 @@ pub fn parse_key_public(key: String) -> Result<Key> {
   parse_key(key)
 }
 
-fn check_reserved_keys(key: Key) -> Result<()> {
-  let reserved = [
-    Key::Char('h'),
-    Key::Char('j'),
-    Key::Char('k'),
-    Key::Char('l'),
-    Key::Char('H'),
-    Key::Char('M'),
-    Key::Char('L'),
-    Key::Up,
-    Key::Down,
-    Key::Left,
-    Key::Right,
-    Key::Backspace,
-    Key::Enter,
-  ];
-  for item in reserved.iter() {
-    if key == *item {
-      // TODO: Add pretty print for key
-      return Err(anyhow!(
-        "The key {:?} is reserved and cannot be remapped",
-        key
-      ));
+/// Reserved keys only go to the action they are kept for, `view` being `None` for the global
+/// keymap
+fn check_reserved_keys(key: Key, action: Action, view: Option<&str>) -> Result<()> {
+  for (reserved, reserved_for) in RESERVED_KEYS {
+    if key != *reserved || *reserved_for == Some(action) {
+      continue;
     }
+    return Err(match reserved_for {
+      Some(reserved_for) => anyhow!(
+        "The key {} is reserved for {} and cannot be bound to {} in {}",
+        key,
+        reserved_for.name(),
+        action.name(),
+        crate::core::keymap::scope_label(view)
+      ),
+      None => anyhow!(
+        "The key {} is reserved and cannot be bound to {} in {}",
+        key,
+        action.name(),
+        crate::core::keymap::scope_label(view)
+      ),
+    });
   }
   Ok(())
 }
 
 /// Public version of check_reserved_keys for use in handlers
-pub fn check_reserved_keys_public(key: Key) -> Result<()> {
-  check_reserved_keys(key)
+pub fn check_reserved_keys_public(key: Key, action: Action, view: Option<&str>) -> Result<()> {
+  check_reserved_keys(key, action, view)
 }
 
 #[derive(Clone)]
@@ -496,70 +851,6 @@ pub struct UserConfigPaths {
   pub config_file_path: PathBuf,
 }
 
-#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
-pub struct KeyBindingsString {
-  back: Option<String>,
-  next_page: Option<String>,
-  previous_page: Option<String>,
-  jump_to_start: Option<String>,
-  jump_to_end: Option<String>,
-  jump_to_album: Option<String>,
-  jump_to_artist_album: Option<String>,
-  jump_to_context: Option<String>,
-  manage_devices: Option<String>,
-  decrease_volume: Option<String>,
-  increase_volume: Option<String>,
-  toggle_playback: Option<String>,
-  seek_backwards: Option<String>,
-  seek_forwards: Option<String>,
-  next_track: Option<String>,
-  previous_track: Option<String>,
-  help: Option<String>,
-  shuffle: Option<String>,
-  repeat: Option<String>,
-  search: Option<String>,
-  submit: Option<String>,
-  copy_song_url: Option<String>,
-  copy_album_url: Option<String>,
-  audio_analysis: Option<String>,
-  basic_view: Option<String>,
-  add_item_to_queue: Option<String>,
-  open_settings: Option<String>,
-  save_settings: Option<String>,
-}
-
-#[derive(Clone)]
-pub struct KeyBindings {
-  pub back: Key,
-  pub next_page: Key,
-  pub previous_page: Key,
-  pub jump_to_start: Key,
-  pub jump_to_end: Key,
-  pub jump_to_album: Key,
-  pub jump_to_artist_album: Key,
-  pub jump_to_context: Key,
-  pub manage_devices: Key,
-  pub decrease_volume: Key,
-  pub increase_volume: Key,
-  pub toggle_playback: Key,
-  pub seek_backwards: Key,
-  pub seek_forwards: Key,
-  pub next_track: Key,
-  pub previous_track: Key,
-  pub help: Key,
-  pub shuffle: Key,
-  pub repeat: Key,
-  pub search: Key,
-  pub submit: Key,
-  pub copy_song_url: Key,
-  pub copy_album_url: Key,
-  pub audio_analysis: Key,
-  pub basic_view: Key,
-  pub add_item_to_queue: Key,
-  pub open_settings: Key,
-  pub save_settings: Key,
-}
-
 #[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct BehaviorConfigString {
   pub seek_milliseconds: Option<u32>,
@@ -583,12 +874,32 @@ pub struct BehaviorConfigString {
   pub playing_icon: Option<String>,
   pub paused_icon: Option<String>,
   pub set_window_title: Option<bool>,
+  pub restore_session: Option<bool>,
+  pub resume_playback_on_start: Option<bool>,
+  pub night_mode: Option<bool>,
   pub visualizer_style: Option<VisualizerStyle>,
   pub dismissed_announcements: Option<Vec<String>>,
+  pub enable_notifications: Option<bool>,
+  pub notification_method: Option<NotificationMethod>,
+  pub color_support: Option<ColorSupport>,
+  pub notify_on_track_change: Option<bool>,
+  pub notify_on_liked: Option<bool>,
+  pub notify_on_error: Option<bool>,
   #[cfg(feature = "cover-art")]
   pub draw_cover_art: Option<bool>,
   #[cfg(feature = "cover-art")]
   pub draw_cover_art_forced: Option<bool>,
+  #[cfg(feature = "cover-art")]
+  pub draw_cover_art_blocks: Option<bool>,
+  #[cfg(feature = "cover-art")]
+  pub cover_art_fallback: Option<CoverArtFallback>,
+  #[cfg(feature = "cover-art")]
+  pub cover_art_cache_mb: Option<u64>,
+  #[cfg(feature = "cover-art")]
+  pub draw_cover_thumbnails: Option<bool>,
+  #[cfg(feature = "cover-art")]
+  pub adaptive_theme: Option<bool>,
+  pub theme_file: Option<String>,
 }
 
 #[derive(Clone)]
@@ -614,12 +925,42 @@ pub struct BehaviorConfig {
   pub playing_icon: String,
   pub paused_icon: String,
   pub set_window_title: bool,
+  /// Reopen the last view and selection on start
+  pub restore_session: bool,
+  /// Load what was last playing, paused, once the native streaming device is up
+  pub resume_playback_on_start: bool,
+  /// Compress dynamics of native playback for quiet listening
+  pub night_mode: bool,
   pub visualizer_style: VisualizerStyle,
   pub dismissed_announcements: Vec<String>,
+  pub enable_notifications: bool,
+  pub notification_method: NotificationMethod,
+  /// `Auto` unless the detection gets the terminal wrong
+  pub color_support: ColorSupport,
+  pub notify_on_track_change: bool,
+  pub notify_on_liked: bool,
+  pub notify_on_error: bool,
   #[cfg(feature = "cover-art")]
   pub draw_cover_art: bool,
   #[cfg(feature = "cover-art")]
   pub draw_cover_art_forced: bool,
+  /// Draw covers with Unicode blocks when the terminal has no image protocol
+  #[cfg(feature = "cover-art")]
+  pub draw_cover_art_blocks: bool,
+  /// Renderer used for covers drawn with Unicode blocks
+  #[cfg(feature = "cover-art")]
+  pub cover_art_fallback: CoverArtFallback,
+  /// Size cap of the downloaded cover cache, 0 to not keep covers on disk
+  #[cfg(feature = "cover-art")]
+  pub cover_art_cache_mb: u64,
+  /// Small cover of the selected album in album lists and search results
+  #[cfg(feature = "cover-art")]
+  pub draw_cover_thumbnails: bool,
+  /// Set by the "Adaptive" theme preset, which recolors the theme from each track's cover
+  #[cfg(feature = "cover-art")]
+  pub adaptive_theme: bool,
+  /// Name of the theme from the themes directory that is in use, reapplied when the file changes
+  pub theme_file: Option<String>,
 }
 
 #[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -627,6 +968,9 @@ pub struct UserConfigString {
   keybindings: Option<KeyBindingsString>,
   behavior: Option<BehaviorConfigString>,
   theme: Option<UserTheme>,
+  equalizer: Option<EqualizerConfigString>,
+  lyrics: Option<LyricsConfigString>,
+  columns: Option<ColumnsConfigString>,
 }
 
 #[derive(Clone)]
@@ -634,55 +978,21 @@ pub struct UserConfig {
   pub keys: KeyBindings,
   pub theme: Theme,
   pub behavior: BehaviorConfig,
+  pub equalizer: EqualizerConfig,
+  pub lyrics: LyricsConfig,
+  pub columns: ColumnsConfig,
+  /// Themes from the themes directory, offered after the built-in presets
+  pub theme_files: Vec<ThemeFile>,
   pub path_to_config: Option<UserConfigPaths>,
+  /// `--tick-rate` from the command line, which wins over config.yml
+  pub tick_rate_override: Option<u64>,
 }
 
 impl UserConfig {
   pub fn new() -> UserConfig {
-    // Detect platform for platform-specific defaults
-    #[cfg(target_os = "macos")]
-    let is_macos = true;
-    #[cfg(not(target_os = "macos"))]
-    let is_macos = false;
-
     UserConfig {
       theme: Default::default(),
-      keys: KeyBindings {
-        back: Key::Char('q'),
-        next_page: Key::Ctrl('d'),
-        previous_page: Key::Ctrl('u'),
-        jump_to_start: Key::Ctrl('a'),
-        jump_to_end: Key::Ctrl('e'),
-        jump_to_album: Key::Char('a'),
-        jump_to_artist_album: Key::Char('A'),
-        jump_to_context: Key::Char('o'),
-        manage_devices: Key::Char('d'),
-        decrease_volume: Key::Char('-'),
-        increase_volume: Key::Char('+'),
-        toggle_playback: Key::Char(' '),
-        seek_backwards: Key::Char('<'),
-        seek_forwards: Key::Char('>'),
-        next_track: Key::Char('n'),
-        previous_track: Key::Char('p'),
-        help: Key::Char('?'),
-        shuffle: Key::Ctrl('s'),
-        repeat: Key::Ctrl('r'),
-        search: Key::Char('/'),
-        submit: Key::Enter,
-        copy_song_url: Key::Char('c'),
-        copy_album_url: Key::Char('C'),
-        audio_analysis: Key::Char('v'),
-        basic_view: Key::Char('B'),
-        add_item_to_queue: Key::Char('z'),
-        // On macOS, use Ctrl+, for settings since Alt+, produces ≤ on most keyboard layouts
-        // On other platforms, keep Alt+, for consistency with many apps
-        open_settings: if is_macos {
-          Key::Ctrl(',')
-        } else {
-          Key::Alt(',')
-        },
-        save_settings: Key::Alt('s'),
-      },
+      keys: KeyBindings::default(),
       behavior: BehaviorConfig {
         seek_milliseconds: 5 * 1000,
         volume_increment: 10,
@@ -705,14 +1015,39 @@ impl UserConfig {
         playing_icon: "▶".to_string(),
         paused_icon: "⏸".to_string(),
         set_window_title: true,
+        restore_session: true,
+        resume_playback_on_start: false,
+        night_mode: false,
         visualizer_style: VisualizerStyle::default(),
         dismissed_announcements: Vec::new(),
+        enable_notifications: false,
+        notification_method: NotificationMethod::default(),
+        color_support: ColorSupport::default(),
+        notify_on_track_change: true,
+        notify_on_liked: false,
+        notify_on_error: true,
         #[cfg(feature = "cover-art")]
         draw_cover_art: true,
         #[cfg(feature = "cover-art")]
         draw_cover_art_forced: false,
+        #[cfg(feature = "cover-art")]
+        draw_cover_art_blocks: false,
+        #[cfg(feature = "cover-art")]
+        cover_art_fallback: CoverArtFallback::default(),
+        #[cfg(feature = "cover-art")]
+        cover_art_cache_mb: 100,
+        #[cfg(feature = "cover-art")]
+        draw_cover_thumbnails: false,
+        #[cfg(feature = "cover-art")]
+        adaptive_theme: false,
+        theme_file: None,
       },
+      equalizer: EqualizerConfig::default(),
+      lyrics: LyricsConfig::default(),
+      columns: ColumnsConfig::default(),
+      theme_files: Vec::new(),
       path_to_config: None,
+      tick_rate_override: None,
     }
   }
 
@@ -744,72 +1079,11 @@ impl UserConfig {
   }
 
   pub fn load_keybindings(&mut self, keybindings: KeyBindingsString) -> Result<()> {
-    macro_rules! to_keys {
-      ($name: ident) => {
-        if let Some(key_string) = keybindings.$name {
-          self.keys.$name = parse_key(key_string)?;
-        }
-      };
-    }
-
-    to_keys!(back);
-    to_keys!(next_page);
-    to_keys!(previous_page);
-    to_keys!(jump_to_start);
-    to_keys!(jump_to_end);
-    to_keys!(jump_to_album);
-    to_keys!(jump_to_artist_album);
-    to_keys!(jump_to_context);
-    to_keys!(manage_devices);
-    to_keys!(decrease_volume);
-    to_keys!(increase_volume);
-    to_keys!(toggle_playback);
-    to_keys!(seek_backwards);
-    to_keys!(seek_forwards);
-    to_keys!(next_track);
-    to_keys!(previous_track);
-    to_keys!(help);
-    to_keys!(shuffle);
-    to_keys!(repeat);
-    to_keys!(search);
-    to_keys!(submit);
-    to_keys!(copy_song_url);
-    to_keys!(copy_album_url);
-    to_keys!(audio_analysis);
-    to_keys!(basic_view);
-    to_keys!(add_item_to_queue);
-    to_keys!(open_settings);
-    to_keys!(save_settings);
-
-    Ok(())
+    self.keys.load(keybindings)
   }
 
   pub fn load_theme(&mut self, theme: UserTheme) -> Result<()> {
-    macro_rules! to_theme_item {
-      ($name: ident) => {
-        if let Some(theme_item) = theme.$name {
-          self.theme.$name = parse_theme_item(&theme_item)?;
-        }
-      };
-    }
-
-    to_theme_item!(active);
-    to_theme_item!(banner);
-    to_theme_item!(error_border);
-    to_theme_item!(error_text);
-    to_theme_item!(hint);
-    to_theme_item!(hovered);
-    to_theme_item!(inactive);
-    to_theme_item!(playbar_background);
-    to_theme_item!(playbar_progress);
-    to_theme_item!(playbar_progress_text);
-    to_theme_item!(playbar_text);
-    to_theme_item!(selected);
-    to_theme_item!(text);
-    to_theme_item!(background);
-    to_theme_item!(header);
-    to_theme_item!(highlighted_lyrics);
-    Ok(())
+    apply_user_theme(&mut self.theme, theme)
   }
 
   pub fn load_behaviorconfig(&mut self, behavior_config: BehaviorConfigString) -> Result<()> {
@@ -879,6 +1153,18 @@ impl UserConfig {
       self.behavior.set_window_title = set_window_title;
     }
 
+    if let Some(restore_session) = behavior_config.restore_session {
+      self.behavior.restore_session = restore_session;
+    }
+
+    if let Some(resume_playback_on_start) = behavior_config.resume_playback_on_start {
+      self.behavior.resume_playback_on_start = resume_playback_on_start;
+    }
+
+    if let Some(night_mode) = behavior_config.night_mode {
+      self.behavior.night_mode = night_mode;
+    }
+
     if let Some(enable_global_song_count) = behavior_config.enable_global_song_count {
       self.behavior.enable_global_song_count = enable_global_song_count;
     }
@@ -928,6 +1214,30 @@ impl UserConfig {
         .collect();
     }
 
+    if let Some(enable_notifications) = behavior_config.enable_notifications {
+      self.behavior.enable_notifications = enable_notifications;
+    }
+
+    if let Some(notification_method) = behavior_config.notification_method {
+      self.behavior.notification_method = notification_method;
+    }
+
+    if let Some(color_support) = behavior_config.color_support {
+      self.behavior.color_support = color_support;
+    }
+
+    if let Some(notify_on_track_change) = behavior_config.notify_on_track_change {
+      self.behavior.notify_on_track_change = notify_on_track_change;
+    }
+
+    if let Some(notify_on_liked) = behavior_config.notify_on_liked {
+      self.behavior.notify_on_liked = notify_on_liked;
+    }
+
+    if let Some(notify_on_error) = behavior_config.notify_on_error {
+      self.behavior.notify_on_error = notify_on_error;
+    }
+
     #[cfg(feature = "cover-art")]
     if let Some(draw_cover_art) = behavior_config.draw_cover_art {
       self.behavior.draw_cover_art = draw_cover_art;
@@ -938,9 +1248,76 @@ impl UserConfig {
       self.behavior.draw_cover_art_forced = draw_cover_art_forced;
     }
 
+    #[cfg(feature = "cover-art")]
+    if let Some(draw_cover_art_blocks) = behavior_config.draw_cover_art_blocks {
+      self.behavior.draw_cover_art_blocks = draw_cover_art_blocks;
+    }
+
+    #[cfg(feature = "cover-art")]
+    if let Some(cover_art_fallback) = behavior_config.cover_art_fallback {
+      self.behavior.cover_art_fallback = cover_art_fallback;
+    }
+
+    #[cfg(feature = "cover-art")]
+    if let Some(cover_art_cache_mb) = behavior_config.cover_art_cache_mb {
+      self.behavior.cover_art_cache_mb = cover_art_cache_mb;
+    }
+
+    #[cfg(feature = "cover-art")]
+    if let Some(draw_cover_thumbnails) = behavior_config.draw_cover_thumbnails {
+      self.behavior.draw_cover_thumbnails = draw_cover_thumbnails;
+    }
+
+    #[cfg(feature = "cover-art")]
+    if let Some(adaptive_theme) = behavior_config.adaptive_theme {
+      self.behavior.adaptive_theme = adaptive_theme;
+    }
+
+    if let Some(theme_file) = behavior_config.theme_file {
+      self.behavior.theme_file = Some(theme_file);
+    }
+
     Ok(())
   }
 
+  pub fn load_equalizer(&mut self, equalizer: EqualizerConfigString) {
+    if let Some(enabled) = equalizer.enabled {
+      self.equalizer.enabled = enabled;
+    }
+
+    if let Some(preset) = equalizer.preset {
+      self.equalizer.set_preset(preset);
+    }
+
+    // Explicit gains only matter for a custom curve; named presets always use their own
+    if self.equalizer.preset == EqualizerPreset::Custom {
+      if let Some(gains_db) = equalizer.gains_db {
+        for (band, gain) in self.equalizer.gains_db.iter_mut().zip(gains_db) {
+          *band = gain.clamp(-EQ_MAX_GAIN_DB, EQ_MAX_GAIN_DB);
+        }
+      }
+    }
+  }
+
+  pub fn load_lyrics(&mut self, lyrics: LyricsConfigString) {
+    if let Some(local_dir) = lyrics.local_dir {
+      // An empty path turns the local directory off
+      self.lyrics.local_dir = if local_dir.trim().is_empty() {
+        None
+      } else {
+        Some(expand_home(&local_dir))
+      };
+    }
+
+    if let Some(lrclib_url) = lyrics.lrclib_url {
+      self.lyrics.lrclib_url = lrclib_url.trim_end_matches('/').to_string();
+    }
+
+    if let Some(providers) = lyrics.providers {
+      self.lyrics.providers = providers;
+    }
+  }
+
   pub fn load_config(&mut self) -> Result<()> {
     let paths = match &self.path_to_config {
       Some(path) => path,
@@ -952,36 +1329,120 @@ impl UserConfig {
     if paths.config_file_path.exists() {
       let config_string = fs::read_to_string(&paths.config_file_path)?;
       // serde fails if file is empty
-      if config_string.trim().is_empty() {
-        return Ok(());
+      if !config_string.trim().is_empty() {
+        self.load_config_string(&config_string)?;
       }
+    }
+
+    // A broken theme file shouldn't stop spotatui from starting, it is only skipped
+    self.load_theme_files();
+    Ok(())
+  }
 
-      let config_yml: UserConfigString = serde_yaml::from_str(&config_string)?;
+  /// Read config.yml again into a fresh config, keeping the paths and theme files of this one,
+  /// the command line overrides and the volume and shuffle state of the running session.
+  /// `self` is left alone so a broken file doesn't lose the running config
+  pub fn reload(&self) -> Result<UserConfig> {
+    let mut config = UserConfig::new();
+    config.path_to_config = self.path_to_config.clone();
+    config.theme_files = self.theme_files.clone();
+    config.tick_rate_override = self.tick_rate_override;
+
+    let paths = self
+      .path_to_config
+      .as_ref()
+      .ok_or_else(|| anyhow!("Config path not initialized"))?;
+    let config_string = fs::read_to_string(&paths.config_file_path)?;
+    if !config_string.trim().is_empty() {
+      config.load_config_string(&config_string)?;
+    }
+    config.apply_selected_theme_file();
 
-      if let Some(keybindings) = config_yml.keybindings.clone() {
-        self.load_keybindings(keybindings)?;
-      }
+    if let Some(tick_rate) = self.tick_rate_override {
+      config.behavior.tick_rate_milliseconds = tick_rate;
+    }
+    config.behavior.volume_percent = self.behavior.volume_percent;
+    config.behavior.shuffle_enabled = self.behavior.shuffle_enabled;
+    Ok(config)
+  }
 
-      if let Some(behavior) = config_yml.behavior {
-        self.load_behaviorconfig(behavior)?;
-      }
-      if let Some(theme) = config_yml.theme {
-        self.load_theme(theme)?;
-      }
+  /// Whether both configs would write the same config.yml
+  pub fn same_settings(&self, other: &UserConfig) -> bool {
+    self.to_config_string() == other.to_config_string()
+  }
+
+  fn load_config_string(&mut self, config_string: &str) -> Result<()> {
+    let config_yml: UserConfigString = serde_yaml::from_str(config_string)?;
+
+    if let Some(keybindings) = config_yml.keybindings.clone() {
+      self.load_keybindings(keybindings)?;
+    }
 
-      Ok(())
-    } else {
-      Ok(())
+    if let Some(behavior) = config_yml.behavior {
+      self.load_behaviorconfig(behavior)?;
     }
+    if let Some(theme) = config_yml.theme {
+      self.load_theme(theme)?;
+    }
+    if let Some(equalizer) = config_yml.equalizer {
+      self.load_equalizer(equalizer);
+    }
+    if let Some(lyrics) = config_yml.lyrics {
+      self.load_lyrics(lyrics);
+    }
+    if let Some(columns) = config_yml.columns {
+      self.columns.load(columns)?;
+    }
+    Ok(())
   }
 
-  /// Save the current configuration to the config file
-  pub fn save_config(&self) -> Result<()> {
-    let paths = match &self.path_to_config {
-      Some(path) => path,
-      None => return Err(anyhow!("Config path not initialized")),
+  pub fn themes_dir(&self) -> Option<PathBuf> {
+    let paths = self.path_to_config.as_ref()?;
+    Some(paths.config_file_path.parent()?.join(THEMES_DIR))
+  }
+
+  /// Reread the themes directory and reapply the selected theme file. Returns the errors of the
+  /// files that were skipped
+  pub fn load_theme_files(&mut self) -> Vec<String> {
+    let Some(dir) = self.themes_dir() else {
+      return Vec::new();
     };
+    let (themes, errors) = theme_files::load_dir(&dir);
+    self.theme_files = themes;
+    self.apply_selected_theme_file();
+    errors
+  }
+
+  /// Use the colors of the selected theme file, as long as it still exists
+  fn apply_selected_theme_file(&mut self) {
+    if let Some(file) = self
+      .behavior
+      .theme_file
+      .as_ref()
+      .and_then(|name| self.theme_files.iter().find(|file| &file.name == name))
+    {
+      self.theme = file.theme;
+    }
+  }
+
+  /// Built-in presets followed by the theme files, in the order Settings cycles through them.
+  /// A theme file named like a built-in preset replaces it
+  pub fn theme_names(&self) -> Vec<String> {
+    let mut names: Vec<String> = ThemePreset::all()
+      .iter()
+      .map(|preset| preset.name().to_string())
+      .collect();
+    for file in &self.theme_files {
+      if !names.contains(&file.name) {
+        names.push(file.name.clone());
+      }
+    }
+    names
+  }
 
+  /// Save the current configuration to the config file
+  /// The config as it is written to config.yml
+  fn to_config_string(&self) -> UserConfigString {
     // Helper to build behavior config from current values
     let build_behavior = || BehaviorConfigString {
       seek_milliseconds: Some(self.behavior.seek_milliseconds),
@@ -1005,82 +1466,32 @@ impl UserConfig {
       playing_icon: Some(self.behavior.playing_icon.clone()),
       paused_icon: Some(self.behavior.paused_icon.clone()),
       set_window_title: Some(self.behavior.set_window_title),
+      restore_session: Some(self.behavior.restore_session),
+      resume_playback_on_start: Some(self.behavior.resume_playback_on_start),
+      night_mode: Some(self.behavior.night_mode),
       visualizer_style: Some(self.behavior.visualizer_style),
       dismissed_announcements: Some(self.behavior.dismissed_announcements.clone()),
+      enable_notifications: Some(self.behavior.enable_notifications),
+      notification_method: Some(self.behavior.notification_method),
+      color_support: Some(self.behavior.color_support),
+      notify_on_track_change: Some(self.behavior.notify_on_track_change),
+      notify_on_liked: Some(self.behavior.notify_on_liked),
+      notify_on_error: Some(self.behavior.notify_on_error),
       #[cfg(feature = "cover-art")]
       draw_cover_art: Some(self.behavior.draw_cover_art),
       #[cfg(feature = "cover-art")]
       draw_cover_art_forced: Some(self.behavior.draw_cover_art_forced),
-    };
-
-    // Helper to convert Key to config string
-    let key_to_config_string = |key: Key| -> String {
-      match key {
-        Key::Char(' ') => "space".to_string(),
-        Key::Char(c) => c.to_string(),
-        Key::Ctrl(c) => format!("ctrl-{}", c),
-        Key::Alt(c) => format!("alt-{}", c),
-        Key::Enter => "enter".to_string(),
-        Key::Tab => "tab".to_string(),
-        Key::Esc => "esc".to_string(),
-        Key::Backspace => "backspace".to_string(),
-        Key::Delete => "del".to_string(),
-        Key::Left => "left".to_string(),
-        Key::Right => "right".to_string(),
-        Key::Up => "up".to_string(),
-        Key::Down => "down".to_string(),
-        Key::Home => "home".to_string(),
-        Key::End => "end".to_string(),
-        Key::Ins => "ins".to_string(),
-        Key::PageUp => "pageup".to_string(),
-        Key::PageDown => "pagedown".to_string(),
-        Key::F0 => "f0".to_string(),
-        Key::F1 => "f1".to_string(),
-        Key::F2 => "f2".to_string(),
-        Key::F3 => "f3".to_string(),
-        Key::F4 => "f4".to_string(),
-        Key::F5 => "f5".to_string(),
-        Key::F6 => "f6".to_string(),
-        Key::F7 => "f7".to_string(),
-        Key::F8 => "f8".to_string(),
-        Key::F9 => "f9".to_string(),
-        Key::F10 => "f10".to_string(),
-        Key::F11 => "f11".to_string(),
-        Key::F12 => "f12".to_string(),
-        _ => "unknown".to_string(),
-      }
-    };
-
-    // Helper to build keybindings config from current values
-    let build_keybindings = || KeyBindingsString {
-      back: Some(key_to_config_string(self.keys.back)),
-      next_page: Some(key_to_config_string(self.keys.next_page)),
-      previous_page: Some(key_to_config_string(self.keys.previous_page)),
-      jump_to_start: Some(key_to_config_string(self.keys.jump_to_start)),
-      jump_to_end: Some(key_to_config_string(self.keys.jump_to_end)),
-      jump_to_album: Some(key_to_config_string(self.keys.jump_to_album)),
-      jump_to_artist_album: Some(key_to_config_string(self.keys.jump_to_artist_album)),
-      jump_to_context: Some(key_to_config_string(self.keys.jump_to_context)),
-      manage_devices: Some(key_to_config_string(self.keys.manage_devices)),
-      decrease_volume: Some(key_to_config_string(self.keys.decrease_volume)),
-      increase_volume: Some(key_to_config_string(self.keys.increase_volume)),
-      toggle_playback: Some(key_to_config_string(self.keys.toggle_playback)),
-      seek_backwards: Some(key_to_config_string(self.keys.seek_backwards)),
-      seek_forwards: Some(key_to_config_string(self.keys.seek_forwards)),
-      next_track: Some(key_to_config_string(self.keys.next_track)),
-      previous_track: Some(key_to_config_string(self.keys.previous_track)),
-      help: Some(key_to_config_string(self.keys.help)),
-      shuffle: Some(key_to_config_string(self.keys.shuffle)),
-      repeat: Some(key_to_config_string(self.keys.repeat)),
-      search: Some(key_to_config_string(self.keys.search)),
-      submit: Some(key_to_config_string(self.keys.submit)),
-      copy_song_url: Some(key_to_config_string(self.keys.copy_song_url)),
-      copy_album_url: Some(key_to_config_string(self.keys.copy_album_url)),
-      audio_analysis: Some(key_to_config_string(self.keys.audio_analysis)),
-      basic_view: Some(key_to_config_string(self.keys.basic_view)),
-      add_item_to_queue: Some(key_to_config_string(self.keys.add_item_to_queue)),
-      open_settings: Some(key_to_config_string(self.keys.open_settings)),
-      save_settings: Some(key_to_config_string(self.keys.save_settings)),
+      #[cfg(feature = "cover-art")]
+      draw_cover_art_blocks: Some(self.behavior.draw_cover_art_blocks),
+      #[cfg(feature = "cover-art")]
+      cover_art_fallback: Some(self.behavior.cover_art_fallback),
+      #[cfg(feature = "cover-art")]
+      cover_art_cache_mb: Some(self.behavior.cover_art_cache_mb),
+      #[cfg(feature = "cover-art")]
+      draw_cover_thumbnails: Some(self.behavior.draw_cover_thumbnails),
+      #[cfg(feature = "cover-art")]
+      adaptive_theme: Some(self.behavior.adaptive_theme),
+      theme_file: self.behavior.theme_file.clone(),
     };
 
     // Helper to build theme config from current values
@@ -1103,30 +1514,49 @@ impl UserConfig {
       highlighted_lyrics: Some(color_to_string(self.theme.highlighted_lyrics)),
     };
 
-    // If the file exists, try to read it first to preserve keybindings
-    let final_config = if paths.config_file_path.exists() {
+    let build_equalizer = || EqualizerConfigString {
+      enabled: Some(self.equalizer.enabled),
+      preset: Some(self.equalizer.preset),
+      gains_db: Some(self.equalizer.gains_db.to_vec()),
+    };
+
+    let build_lyrics = || LyricsConfigString {
+      local_dir: Some(
+        self
+          .lyrics
+          .local_dir
+          .as_ref()
+          .map(|dir| dir.to_string_lossy().into_owned())
+          .unwrap_or_default(),
+      ),
+      lrclib_url: Some(self.lyrics.lrclib_url.clone()),
+      providers: Some(self.lyrics.providers.clone()),
+    };
+
+    UserConfigString {
+      keybindings: Some(self.keys.to_config()),
+      behavior: Some(build_behavior()),
+      theme: Some(build_theme()),
+      equalizer: Some(build_equalizer()),
+      lyrics: Some(build_lyrics()),
+      columns: Some(self.columns.to_config()),
+    }
+  }
+
+  pub fn save_config(&self) -> Result<()> {
+    let paths = match &self.path_to_config {
+      Some(path) => path,
+      None => return Err(anyhow!("Config path not initialized")),
+    };
+
+    // Don't overwrite a config file that no longer parses
+    if paths.config_file_path.exists() {
       let config_string = fs::read_to_string(&paths.config_file_path)?;
       if !config_string.trim().is_empty() {
-        let mut existing: UserConfigString = serde_yaml::from_str(&config_string)?;
-        // Update behavior, theme, and keybindings
-        existing.behavior = Some(build_behavior());
-        existing.theme = Some(build_theme());
-        existing.keybindings = Some(build_keybindings());
-        existing
-      } else {
-        UserConfigString {
-          keybindings: Some(build_keybindings()),
-          behavior: Some(build_behavior()),
-          theme: Some(build_theme()),
-        }
-      }
-    } else {
-      UserConfigString {
-        keybindings: Some(build_keybindings()),
-        behavior: Some(build_behavior()),
-        theme: Some(build_theme()),
+        serde_yaml::from_str::<UserConfigString>(&config_string)?;
       }
-    };
+    }
+    let final_config = self.to_config_string();
 
     let content_yml = serde_yaml::to_string(&final_config)?;
     let mut config_file = fs::File::create(&paths.config_file_path)?;
@@ -1157,10 +1587,51 @@ impl UserConfig {
 
   #[cfg(feature = "cover-art")]
   pub fn do_draw_cover_art(&self, full_image_support: bool) -> bool {
-    self.behavior.draw_cover_art && (self.behavior.draw_cover_art_forced || full_image_support)
+    self.behavior.draw_cover_art
+      && (self.behavior.draw_cover_art_forced
+        || self.behavior.draw_cover_art_blocks
+        || full_image_support)
+  }
+
+  /// Block renderer for covers in terminals without an image protocol, if it is turned on
+  #[cfg(feature = "cover-art")]
+  pub fn cover_art_blocks(&self) -> Option<CoverArtFallback> {
+    self
+      .behavior
+      .draw_cover_art_blocks
+      .then_some(self.behavior.cover_art_fallback)
   }
 }
 
+/// Overwrite the colors of `target` that are set in `theme`
+pub fn apply_user_theme(target: &mut Theme, theme: UserTheme) -> Result<()> {
+  macro_rules! to_theme_item {
+    ($name: ident) => {
+      if let Some(theme_item) = theme.$name {
+        target.$name = parse_theme_item(&theme_item)?;
+      }
+    };
+  }
+
+  to_theme_item!(active);
+  to_theme_item!(banner);
+  to_theme_item!(error_border);
+  to_theme_item!(error_text);
+  to_theme_item!(hint);
+  to_theme_item!(hovered);
+  to_theme_item!(inactive);
+  to_theme_item!(playbar_background);
+  to_theme_item!(playbar_progress);
+  to_theme_item!(playbar_progress_text);
+  to_theme_item!(playbar_text);
+  to_theme_item!(selected);
+  to_theme_item!(text);
+  to_theme_item!(background);
+  to_theme_item!(header);
+  to_theme_item!(highlighted_lyrics);
+  Ok(())
+}
+
 fn parse_theme_item(theme_item: &str) -> Result<Color> {
   let color = match theme_item {
     "Reset" => Color::Reset,
@@ -1287,14 +1758,127 @@ mod tests {
     );
   }
 
+  #[test]
+  fn load_equalizer_uses_gains_only_for_custom() {
+    use super::{EqualizerConfigString, EqualizerPreset, UserConfig, EQ_BAND_COUNT};
+
+    let mut config = UserConfig::new();
+    config.load_equalizer(EqualizerConfigString {
+      enabled: Some(true),
+      preset: Some(EqualizerPreset::BassBoost),
+      gains_db: Some(vec![-3.0; EQ_BAND_COUNT]),
+    });
+    assert_eq!(
+      config.equalizer.gains_db,
+      EqualizerPreset::BassBoost.gains().unwrap()
+    );
+
+    config.load_equalizer(EqualizerConfigString {
+      enabled: None,
+      preset: Some(EqualizerPreset::Custom),
+      gains_db: Some(vec![30.0, -3.0]),
+    });
+    assert!(config.equalizer.enabled);
+    assert_eq!(config.equalizer.gains_db[0], 12.0);
+    assert_eq!(config.equalizer.gains_db[1], -3.0);
+  }
+
+  #[cfg(feature = "cover-art")]
+  #[test]
+  fn block_covers_are_opt_in() {
+    use super::{CoverArtFallback, UserConfig};
+
+    let mut config = UserConfig::new();
+    assert!(config.do_draw_cover_art(true));
+    assert!(!config.do_draw_cover_art(false));
+    assert_eq!(config.cover_art_blocks(), None);
+
+    config.behavior.draw_cover_art_blocks = true;
+    config.behavior.cover_art_fallback = CoverArtFallback::Braille;
+    assert!(config.do_draw_cover_art(false));
+    assert_eq!(config.cover_art_blocks(), Some(CoverArtFallback::Braille));
+  }
+
+  #[test]
+  fn reload_keeps_running_config_on_error() {
+    use super::{UserConfig, UserConfigPaths};
+
+    let dir = std::env::temp_dir().join(format!("spotatui-reload-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut config = UserConfig::new();
+    config.path_to_config = Some(UserConfigPaths {
+      config_file_path: dir.join("config.yml"),
+    });
+
+    // A config saved by spotatui itself reads back unchanged
+    config.behavior.seek_milliseconds = 1234;
+    config.save_config().unwrap();
+    assert!(config.reload().unwrap().same_settings(&config));
+
+    std::fs::write(
+      dir.join("config.yml"),
+      "behavior:\n  seek_milliseconds: 4321\n",
+    )
+    .unwrap();
+    let reloaded = config.reload().unwrap();
+    assert_eq!(reloaded.behavior.seek_milliseconds, 4321);
+    assert!(!reloaded.same_settings(&config));
+
+    // The command line and the running session win over the file
+    config.tick_rate_override = Some(50);
+    config.behavior.tick_rate_milliseconds = 50;
+    config.behavior.volume_percent = 30;
+    let reloaded = config.reload().unwrap();
+    assert_eq!(reloaded.behavior.tick_rate_milliseconds, 50);
+    assert_eq!(reloaded.behavior.volume_percent, 30);
+
+    std::fs::write(
+      dir.join("config.yml"),
+      "behavior:\n  volume_increment: 200\n",
+    )
+    .unwrap();
+    assert!(config.reload().is_err());
+    assert_eq!(config.behavior.seek_milliseconds, 1234);
+
+    let _ = std::fs::remove_dir_all(dir);
+  }
+
   #[test]
   fn test_reserved_key() {
     use super::check_reserved_keys;
+    use crate::core::keymap::Action;
     use crate::event::Key;
 
     assert!(
-      check_reserved_keys(Key::Enter).is_err(),
+      check_reserved_keys(Key::Enter, Action::Search, None).is_err(),
       "Enter key should be reserved"
     );
+    assert!(check_reserved_keys(Key::Enter, Action::Submit, None).is_ok());
+  }
+
+  #[test]
+  fn detects_from_environment() {
+    use super::{detect_color_support, ColorSupport};
+    let no_terminfo = || None;
+    assert_eq!(
+      detect_color_support(Some("truecolor"), Some("xterm-256color"), no_terminfo),
+      ColorSupport::TrueColor
+    );
+    assert_eq!(
+      detect_color_support(None, Some("tmux-256color"), no_terminfo),
+      ColorSupport::Ansi256
+    );
+    assert_eq!(
+      detect_color_support(None, Some("linux"), no_terminfo),
+      ColorSupport::Ansi16
+    );
+    assert_eq!(
+      detect_color_support(None, Some("screen"), || Some(8)),
+      ColorSupport::Ansi16
+    );
+    assert_eq!(
+      detect_color_support(None, Some("xterm-kitty"), || Some(256)),
+      ColorSupport::Ansi256
+    );
   }
 }
@@ -1,11 +1,22 @@
 // Audio capture and analysis module for real-time visualization
 // This module provides cross-platform audio capture:
+// - Native streaming: decoded PCM tapped from the librespot sink (any backend)
 // - Linux: PipeWire native (via pipewire-rs)
 // - Windows/macOS: cpal (WASAPI/CoreAudio)
 
-#[cfg(any(feature = "audio-viz", feature = "audio-viz-cpal"))]
+#[cfg(any(
+  feature = "audio-viz",
+  feature = "audio-viz-cpal",
+  feature = "streaming"
+))]
 mod analyzer;
 
+#[cfg(feature = "streaming")]
+mod tap;
+
+#[cfg(feature = "streaming")]
+pub use tap::PlaybackTap;
+
 // Platform-specific capture backends
 #[cfg(all(feature = "audio-viz", target_os = "linux"))]
 mod pipewire_capture;
@@ -27,14 +38,19 @@ pub use pipewire_capture::PipeWireCapture as AudioCaptureManager;
 pub use capture::AudioCaptureManager;
 
 // Re-export SpectrumData
-#[cfg(any(feature = "audio-viz", feature = "audio-viz-cpal"))]
+#[cfg(any(
+  feature = "audio-viz",
+  feature = "audio-viz-cpal",
+  feature = "streaming"
+))]
 #[allow(unused_imports)]
 pub use analyzer::SpectrumData;
 
-// Fallback types when no audio-viz feature is enabled
+// Fallback types when no analyzer is compiled in
 #[cfg(not(any(
-  all(feature = "audio-viz", target_os = "linux"),
-  feature = "audio-viz-cpal"
+  feature = "audio-viz",
+  feature = "audio-viz-cpal",
+  feature = "streaming"
 )))]
 #[derive(Clone, Default)]
 #[allow(dead_code)]
@@ -43,6 +59,7 @@ pub struct SpectrumData {
   pub peak: f32,
 }
 
+// Loopback capture stub when no capture backend is available
 #[cfg(not(any(
   all(feature = "audio-viz", target_os = "linux"),
   feature = "audio-viz-cpal"
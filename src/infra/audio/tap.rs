@@ -0,0 +1,83 @@
+// Visualizer input tapped straight from the native playback pipeline
+// Unlike loopback capture this sees exactly what spotatui decodes, on any audio backend
+
+use super::analyzer::{create_shared_analyzer, SharedAnalyzer, SpectrumData};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// How long without new samples before we treat playback as paused and let the bars fall
+const IDLE_AFTER_MS: u64 = 150;
+
+/// Decoded PCM tap shared between the librespot sink thread and the UI
+#[derive(Clone)]
+pub struct PlaybackTap {
+  analyzer: SharedAnalyzer,
+  enabled: Arc<AtomicBool>,
+  created_at: Instant,
+  /// Milliseconds since `created_at` of the last packet fed in
+  last_feed_ms: Arc<AtomicU64>,
+}
+
+impl PlaybackTap {
+  pub fn new() -> Self {
+    Self {
+      analyzer: create_shared_analyzer(),
+      enabled: Arc::new(AtomicBool::new(false)),
+      created_at: Instant::now(),
+      last_feed_ms: Arc::new(AtomicU64::new(0)),
+    }
+  }
+
+  /// Only spend time on analysis while the Analysis view is open
+  pub fn set_enabled(&self, enabled: bool) {
+    self.enabled.store(enabled, Ordering::Relaxed);
+  }
+
+  /// Feed interleaved samples from the sink. `channels` is the interleave width.
+  pub fn feed(&self, samples: &[f64], channels: usize) {
+    if !self.enabled.load(Ordering::Relaxed) || samples.is_empty() {
+      return;
+    }
+
+    let channels = channels.max(1);
+    let mono: Vec<f32> = samples
+      .chunks(channels)
+      .map(|frame| (frame.iter().sum::<f64>() / frame.len() as f64) as f32)
+      .collect();
+
+    if let Ok(mut analyzer) = self.analyzer.lock() {
+      analyzer.push_samples(&mono);
+    }
+    self
+      .last_feed_ms
+      .store(self.elapsed_ms(), Ordering::Relaxed);
+  }
+
+  /// Get the current spectrum data
+  pub fn get_spectrum(&self) -> Option<SpectrumData> {
+    let mut analyzer = self.analyzer.lock().ok()?;
+    if !self.is_active() {
+      // The sink stops writing while paused; feed silence so the bars decay instead of freezing
+      analyzer.push_samples(&[0.0; 1024]);
+    }
+    Some(analyzer.process())
+  }
+
+  /// Whether samples have arrived recently
+  pub fn is_active(&self) -> bool {
+    let last = self.last_feed_ms.load(Ordering::Relaxed);
+    last > 0 && self.elapsed_ms().saturating_sub(last) < IDLE_AFTER_MS
+  }
+
+  fn elapsed_ms(&self) -> u64 {
+    // Offset by one so a packet in the first millisecond isn't mistaken for "never fed"
+    self.created_at.elapsed().as_millis() as u64 + 1
+  }
+}
+
+impl Default for PlaybackTap {
+  fn default() -> Self {
+    Self::new()
+  }
+}
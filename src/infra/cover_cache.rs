@@ -0,0 +1,133 @@
+// On-disk cache of downloaded cover art, keyed by image URL and trimmed to a size cap by
+// evicting the least recently used files
+
+use log::debug;
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+pub struct CoverCache {
+  dir: Option<PathBuf>,
+  max_bytes: u64,
+}
+
+impl CoverCache {
+  pub fn new(max_mb: u64) -> Self {
+    Self {
+      dir: dirs::cache_dir().map(|dir| dir.join("spotatui").join("covers")),
+      max_bytes: max_mb * 1024 * 1024,
+    }
+  }
+
+  #[cfg(test)]
+  fn with_dir(dir: PathBuf, max_bytes: u64) -> Self {
+    Self {
+      dir: Some(dir),
+      max_bytes,
+    }
+  }
+
+  fn path(&self, url: &str) -> Option<PathBuf> {
+    Some(self.dir.as_ref()?.join(format!("{:016x}", fnv1a(url))))
+  }
+
+  pub fn load(&self, url: &str) -> Option<Vec<u8>> {
+    let path = self.path(url)?;
+    let bytes = fs::read(&path).ok()?;
+    // Bump the modification time so eviction sees the file as recently used
+    if let Ok(file) = fs::File::options().append(true).open(&path) {
+      let _ = file.set_modified(SystemTime::now());
+    }
+    Some(bytes)
+  }
+
+  pub fn store(&self, url: &str, bytes: &[u8]) {
+    if self.max_bytes == 0 {
+      return;
+    }
+    let Some(path) = self.path(url) else {
+      return;
+    };
+    let written = path
+      .parent()
+      .map_or(Ok(()), fs::create_dir_all)
+      .and_then(|_| fs::write(&path, bytes));
+    if let Err(e) = written {
+      debug!("failed to cache cover {}: {}", url, e);
+      return;
+    }
+    self.evict();
+  }
+
+  /// Remove the least recently used covers until the cache fits its cap
+  fn evict(&self) {
+    let Some(entries) = self.dir.as_ref().and_then(|dir| fs::read_dir(dir).ok()) else {
+      return;
+    };
+    let mut files: Vec<(SystemTime, u64, PathBuf)> = entries
+      .filter_map(|entry| {
+        let entry = entry.ok()?;
+        let metadata = entry.metadata().ok()?;
+        Some((metadata.modified().ok()?, metadata.len(), entry.path()))
+      })
+      .collect();
+
+    let mut total: u64 = files.iter().map(|(_, len, _)| len).sum();
+    files.sort_by_key(|(modified, _, _)| *modified);
+    for (_, len, path) in files {
+      if total <= self.max_bytes {
+        break;
+      }
+      if fs::remove_file(&path).is_ok() {
+        total -= len;
+      }
+    }
+  }
+}
+
+/// Stable across runs and Rust versions, unlike `DefaultHasher`
+fn fnv1a(text: &str) -> u64 {
+  text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+    (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::time::Duration;
+
+  #[test]
+  fn evicts_least_recently_used_over_cap() {
+    let dir = std::env::temp_dir().join(format!("spotatui-cover-cache-{}", std::process::id()));
+    let cache = CoverCache::with_dir(dir.clone(), 10);
+
+    cache.store("https://i.scdn.co/image/a", &[1; 4]);
+    cache.store("https://i.scdn.co/image/b", &[2; 4]);
+    let a = cache.path("https://i.scdn.co/image/a").unwrap();
+    let old = SystemTime::now() - Duration::from_secs(60);
+    fs::File::options()
+      .append(true)
+      .open(&a)
+      .unwrap()
+      .set_modified(old)
+      .unwrap();
+
+    // Reading a cover marks it as used, so `b` is now the oldest
+    assert_eq!(cache.load("https://i.scdn.co/image/a"), Some(vec![1; 4]));
+    let b = cache.path("https://i.scdn.co/image/b").unwrap();
+    fs::File::options()
+      .append(true)
+      .open(&b)
+      .unwrap()
+      .set_modified(old)
+      .unwrap();
+
+    cache.store("https://i.scdn.co/image/c", &[3; 4]);
+    assert!(cache.load("https://i.scdn.co/image/b").is_none());
+    assert!(cache.load("https://i.scdn.co/image/a").is_some());
+    assert!(cache.load("https://i.scdn.co/image/c").is_some());
+
+    let _ = fs::remove_dir_all(dir);
+  }
+}
@@ -0,0 +1,183 @@
+// On-disk lyrics cache, one JSON file per track id under the user cache directory
+
+use super::FetchedLyrics;
+use log::debug;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// "No lyrics" answers are retried after a week; found lyrics are kept until removed
+const NOT_FOUND_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CachedLyrics {
+  /// `None` records that no provider had lyrics for the track
+  #[serde(default)]
+  pub lyrics: Option<FetchedLyrics>,
+  /// 0 when the entry only holds per-track settings and no lookup result
+  #[serde(default)]
+  pub fetched_at: u64,
+  /// Chosen by hand in the lyrics search; wins over every provider and never expires
+  #[serde(default)]
+  pub pinned: bool,
+  /// Timing correction in milliseconds, positive to show lines later
+  #[serde(default)]
+  pub offset_ms: i64,
+}
+
+pub struct LyricsCache {
+  dir: Option<PathBuf>,
+}
+
+impl LyricsCache {
+  pub fn new() -> Self {
+    Self {
+      dir: dirs::cache_dir().map(|dir| dir.join("spotatui").join("lyrics")),
+    }
+  }
+
+  #[cfg(test)]
+  fn with_dir(dir: PathBuf) -> Self {
+    Self { dir: Some(dir) }
+  }
+
+  fn path(&self, track_id: &str) -> Option<PathBuf> {
+    let name: String = track_id
+      .chars()
+      .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+      .collect();
+    if name.is_empty() {
+      return None;
+    }
+    Some(self.dir.as_ref()?.join(format!("{}.json", name)))
+  }
+
+  fn read(&self, track_id: &str) -> Option<CachedLyrics> {
+    let text = fs::read_to_string(self.path(track_id)?).ok()?;
+    serde_json::from_str(&text).ok()
+  }
+
+  /// A still valid lookup result for the track
+  pub fn load(&self, track_id: &str) -> Option<CachedLyrics> {
+    let entry = self.read(track_id)?;
+    if entry.fetched_at == 0 {
+      return None;
+    }
+    if entry.lyrics.is_none() && now_secs().saturating_sub(entry.fetched_at) > NOT_FOUND_TTL_SECS {
+      return None;
+    }
+    Some(entry)
+  }
+
+  pub fn store(&self, track_id: &str, lyrics: Option<&FetchedLyrics>) {
+    let existing = self.read(track_id).unwrap_or_default();
+    // Automatic lookups never replace a hand-picked result
+    if existing.pinned {
+      return;
+    }
+    let entry = CachedLyrics {
+      lyrics: lyrics.cloned(),
+      fetched_at: now_secs(),
+      ..existing
+    };
+    if let Err(e) = self.write(track_id, &entry) {
+      debug!("failed to cache lyrics for {}: {}", track_id, e);
+    }
+  }
+
+  pub fn pin(&self, track_id: &str, lyrics: &FetchedLyrics) -> anyhow::Result<()> {
+    let entry = CachedLyrics {
+      lyrics: Some(lyrics.clone()),
+      fetched_at: now_secs(),
+      pinned: true,
+      ..self.read(track_id).unwrap_or_default()
+    };
+    self.write(track_id, &entry)
+  }
+
+  pub fn offset_ms(&self, track_id: &str) -> i64 {
+    self
+      .read(track_id)
+      .map(|entry| entry.offset_ms)
+      .unwrap_or(0)
+  }
+
+  pub fn set_offset_ms(&self, track_id: &str, offset_ms: i64) -> anyhow::Result<()> {
+    let entry = CachedLyrics {
+      offset_ms,
+      ..self.read(track_id).unwrap_or_default()
+    };
+    self.write(track_id, &entry)
+  }
+
+  /// The hand-picked lyrics for a track, if any
+  pub fn pinned(&self, track_id: &str) -> Option<FetchedLyrics> {
+    self
+      .load(track_id)
+      .filter(|entry| entry.pinned)
+      .and_then(|entry| entry.lyrics)
+  }
+
+  fn write(&self, track_id: &str, entry: &CachedLyrics) -> anyhow::Result<()> {
+    let Some(path) = self.path(track_id) else {
+      return Ok(());
+    };
+    if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string(entry)?)?;
+    Ok(())
+  }
+}
+
+impl Default for LyricsCache {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+fn now_secs() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_secs())
+    .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn stores_and_loads_by_track_id() {
+    let dir = std::env::temp_dir().join(format!("spotatui-lyrics-cache-{}", std::process::id()));
+    let cache = LyricsCache::with_dir(dir.clone());
+    let lyrics = FetchedLyrics {
+      text: "[00:01.00]hello".to_string(),
+      synced: true,
+      source: "lrclib".to_string(),
+    };
+
+    assert!(cache.load("spotify:track:abc").is_none());
+    cache.store("spotify:track:abc", Some(&lyrics));
+    assert_eq!(
+      cache.load("spotify:track:abc").unwrap().lyrics,
+      Some(lyrics.clone())
+    );
+
+    cache.store("spotify:track:none", None);
+    assert!(cache.load("spotify:track:none").unwrap().lyrics.is_none());
+
+    cache.pin("spotify:track:none", &lyrics).unwrap();
+    cache.store("spotify:track:none", None);
+    assert_eq!(cache.pinned("spotify:track:none"), Some(lyrics));
+
+    // An offset alone is not a cached answer, and survives the lookup being stored later
+    cache.set_offset_ms("spotify:track:new", -300).unwrap();
+    assert!(cache.load("spotify:track:new").is_none());
+    cache.store("spotify:track:new", None);
+    assert_eq!(cache.offset_ms("spotify:track:new"), -300);
+
+    let _ = fs::remove_dir_all(dir);
+  }
+}
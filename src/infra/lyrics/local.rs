@@ -0,0 +1,187 @@
+// Lyrics from a local directory of `.lrc` and `.txt` files
+//
+// Files are matched by name (`Artist - Title.lrc` or `Title.lrc`) or, for LRC, by their
+// `[ar:]`/`[ti:]` tags. An LRC `[length:]` tag that disagrees with the track rules the file out.
+
+use super::{normalize, FetchedLyrics, LyricsProvider, LyricsQuery, ProviderFuture};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Allowed difference between a file's `[length:]` tag and the track duration
+const LENGTH_TOLERANCE_SECS: f64 = 5.0;
+
+pub struct LocalProvider {
+  dir: PathBuf,
+}
+
+impl LocalProvider {
+  pub fn new(dir: PathBuf) -> Self {
+    Self { dir }
+  }
+}
+
+impl LyricsProvider for LocalProvider {
+  fn name(&self) -> &'static str {
+    "local"
+  }
+
+  fn fetch<'a>(&'a self, query: &'a LyricsQuery) -> ProviderFuture<'a> {
+    let dir = self.dir.clone();
+    let query = query.clone();
+    Box::pin(async move {
+      // Scanning a large music folder blocks, keep it off the runtime threads
+      Ok(tokio::task::spawn_blocking(move || find(&dir, &query)).await?)
+    })
+  }
+
+  fn cacheable(&self) -> bool {
+    false
+  }
+}
+
+fn find(dir: &Path, query: &LyricsQuery) -> Option<FetchedLyrics> {
+  let title = normalize(&query.track);
+  let artist = normalize(query.primary_artist());
+  let names = [
+    format!("{} {}", artist, title),
+    format!("{} {}", title, artist),
+    title.clone(),
+  ];
+
+  let mut plain: Option<String> = None;
+  for entry in fs::read_dir(dir).ok()?.flatten() {
+    let path = entry.path();
+    let is_lrc = match extension(&path).as_deref() {
+      Some("lrc") => true,
+      Some("txt") => false,
+      _ => continue,
+    };
+
+    let stem = path
+      .file_stem()
+      .map(|stem| normalize(&stem.to_string_lossy()))
+      .unwrap_or_default();
+    let name_matches = names.contains(&stem);
+    if !name_matches && !is_lrc {
+      continue;
+    }
+
+    let Ok(text) = fs::read_to_string(&path) else {
+      continue;
+    };
+    let tags = LrcTags::parse(&text);
+    if !name_matches && !tags.matches(&title, &artist) {
+      continue;
+    }
+    if let Some(length) = tags.length_secs {
+      if (length - query.duration_secs).abs() > LENGTH_TOLERANCE_SECS {
+        continue;
+      }
+    }
+
+    // Prefer synced lyrics when both an .lrc and a .txt match
+    if is_lrc {
+      return Some(fetched(text, true));
+    }
+    plain.get_or_insert(text);
+  }
+
+  plain.map(|text| fetched(text, false))
+}
+
+fn fetched(text: String, is_lrc: bool) -> FetchedLyrics {
+  // An .lrc without a single timestamp is just plain text with a different extension
+  let synced = is_lrc && super::parse_lyrics(&text).1;
+  FetchedLyrics {
+    text,
+    synced,
+    source: "local".to_string(),
+  }
+}
+
+fn extension(path: &Path) -> Option<String> {
+  path
+    .extension()
+    .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+}
+
+#[derive(Default)]
+struct LrcTags {
+  title: Option<String>,
+  artist: Option<String>,
+  length_secs: Option<f64>,
+}
+
+impl LrcTags {
+  fn parse(text: &str) -> Self {
+    let mut tags = Self::default();
+    for line in text.lines() {
+      let Some((key, value)) = line
+        .trim()
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .and_then(|tag| tag.split_once(':'))
+      else {
+        continue;
+      };
+      match key.trim() {
+        "ti" => tags.title = Some(normalize(value)),
+        "ar" => tags.artist = Some(normalize(value)),
+        "length" => {
+          let (mins, secs) = value.trim().split_once(':').unwrap_or(("0", value.trim()));
+          if let (Ok(mins), Ok(secs)) = (mins.parse::<f64>(), secs.parse::<f64>()) {
+            tags.length_secs = Some(mins * 60.0 + secs);
+          }
+        }
+        _ => {}
+      }
+    }
+    tags
+  }
+
+  fn matches(&self, title: &str, artist: &str) -> bool {
+    self.title.as_deref() == Some(title)
+      && self
+        .artist
+        .as_deref()
+        .is_none_or(|tag_artist| tag_artist == artist)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn query(track: &str, artist: &str, duration_secs: f64) -> LyricsQuery {
+    LyricsQuery {
+      track_id: "id".to_string(),
+      track: track.to_string(),
+      artist: artist.to_string(),
+      duration_secs,
+    }
+  }
+
+  #[test]
+  fn matches_by_file_name_and_tags() {
+    let dir = std::env::temp_dir().join(format!("spotatui-lyrics-test-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("Band - Song.txt"), "plain words").unwrap();
+    fs::write(
+      dir.join("whatever.lrc"),
+      "[ti:Other Song]\n[ar:Band]\n[length:03:20]\n[00:01.00]synced words",
+    )
+    .unwrap();
+
+    let by_name = find(&dir, &query("Song", "Band, Guest", 200.0)).unwrap();
+    assert_eq!(by_name.text, "plain words");
+    assert!(!by_name.synced);
+
+    let by_tags = find(&dir, &query("Other Song", "Band", 201.0)).unwrap();
+    assert!(by_tags.synced);
+
+    // [length:] is 200s, far from this track's duration
+    assert!(find(&dir, &query("Other Song", "Band", 320.0)).is_none());
+
+    let _ = fs::remove_dir_all(dir);
+  }
+}
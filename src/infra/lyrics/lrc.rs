@@ -0,0 +1,239 @@
+// LRC parsing: `[mm:ss.xx] text` lines, including several timestamps per line and enhanced LRC
+// `<mm:ss.xx>` word timestamps, falling back to plain text
+
+/// One displayed line. `words` is only filled for enhanced LRC.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LyricsLine {
+  pub time_ms: u128,
+  pub text: String,
+  pub words: Vec<LyricsWord>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LyricsWord {
+  pub time_ms: u128,
+  /// Includes the whitespace that follows the word, so joining the words gives the line back
+  pub text: String,
+}
+
+/// Parse `[mm:ss.xx]` / `[mm:ss.xxx]` into milliseconds. Metadata tags like `[ar:Artist]` return `None`.
+fn parse_timestamp(timestamp: &str) -> Option<u128> {
+  let (mins, rest) = timestamp.split_once(':')?;
+  let (secs, fraction) = rest.split_once('.').unwrap_or((rest, ""));
+
+  let mins = mins.trim().parse::<u128>().ok()?;
+  let secs = secs.trim().parse::<u128>().ok()?;
+  let ms = match fraction.len() {
+    0 => 0,
+    // Hundredths, as most LRC files use
+    1 | 2 => fraction.parse::<u128>().ok()? * 10u128.pow(3 - fraction.len() as u32),
+    _ => fraction.get(..3)?.parse::<u128>().ok()?,
+  };
+
+  Some(mins * 60 * 1000 + secs * 1000 + ms)
+}
+
+/// Strip the leading `[mm:ss.xx]` tags off a line, e.g. `[00:12.00][01:30.00]chorus`
+fn split_line_timestamps(line: &str) -> (Vec<u128>, &str) {
+  let mut times = Vec::new();
+  let mut rest = line.trim();
+  while let Some(tag) = rest.strip_prefix('[') {
+    let Some((timestamp, after)) = tag.split_once(']') else {
+      break;
+    };
+    let Some(time) = parse_timestamp(timestamp) else {
+      break;
+    };
+    times.push(time);
+    rest = after;
+  }
+  (times, rest)
+}
+
+/// Split `<00:12.00>Hello <00:12.50>world` into timed words. Text before the first word tag
+/// starts with the line.
+fn parse_words(content: &str, line_time: u128) -> (String, Vec<LyricsWord>) {
+  if !content.contains('<') {
+    return (content.trim().to_string(), Vec::new());
+  }
+
+  let mut words = Vec::new();
+  let mut push = |time_ms: u128, text: &str| {
+    if !text.trim().is_empty() {
+      words.push(LyricsWord {
+        time_ms,
+        text: text.to_string(),
+      });
+    }
+  };
+
+  let mut segments = content.split('<');
+  push(line_time, segments.next().unwrap_or_default());
+  for segment in segments {
+    match segment
+      .split_once('>')
+      .and_then(|(timestamp, text)| Some((parse_timestamp(timestamp)?, text)))
+    {
+      Some((time, text)) => push(time, text),
+      // Not a timestamp after all, keep the text as it was
+      None => push(line_time, &format!("<{}", segment)),
+    }
+  }
+
+  if let Some(first) = words.first_mut() {
+    first.text = first.text.trim_start().to_string();
+  }
+  if let Some(last) = words.last_mut() {
+    last.text = last.text.trim_end().to_string();
+  }
+  let text = words.iter().map(|word| word.text.as_str()).collect();
+  (text, words)
+}
+
+/// `mm:ss.xx`, the form most LRC files use
+pub fn format_timestamp(ms: u128) -> String {
+  format!(
+    "{:02}:{:02}.{:02}",
+    ms / 60_000,
+    ms / 1000 % 60,
+    ms % 1000 / 10
+  )
+}
+
+/// Write `(start_ms, text)` lines as an LRC file with title, artist and length tags
+pub fn write_lrc(title: &str, artist: &str, length_ms: u128, lines: &[(u128, &str)]) -> String {
+  let mut lrc = format!(
+    "[ti:{}]\n[ar:{}]\n[length:{:02}:{:02}]\n",
+    title,
+    artist,
+    length_ms / 60_000,
+    length_ms / 1000 % 60
+  );
+  let mut lines = lines.to_vec();
+  lines.sort_by_key(|(time, _)| *time);
+  for (time, text) in lines {
+    lrc.push_str(&format!("[{}]{}\n", format_timestamp(time), text));
+  }
+  lrc
+}
+
+/// Returns lines sorted by time and whether the text carried timestamps.
+/// Plain text comes back line by line, all at 0.
+pub fn parse_lyrics(text: &str) -> (Vec<LyricsLine>, bool) {
+  let mut synced = Vec::new();
+  for line in text.lines() {
+    let (times, content) = split_line_timestamps(line);
+    let Some(&first_time) = times.first() else {
+      continue;
+    };
+    let (text, words) = parse_words(content, first_time);
+
+    // A repeated line (chorus) shares its word timings, shifted to each occurrence
+    for time in times {
+      let words = words
+        .iter()
+        .map(|word| LyricsWord {
+          time_ms: word.time_ms.saturating_sub(first_time) + time,
+          text: word.text.clone(),
+        })
+        .collect();
+      synced.push(LyricsLine {
+        time_ms: time,
+        text: text.clone(),
+        words,
+      });
+    }
+  }
+
+  if !synced.is_empty() {
+    synced.sort_by_key(|line| line.time_ms);
+    return (synced, true);
+  }
+
+  let plain = text
+    .lines()
+    .map(str::trim)
+    .filter(|line| !line.is_empty())
+    .map(|line| LyricsLine {
+      text: line.to_string(),
+      ..Default::default()
+    })
+    .collect();
+  (plain, false)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn times_and_text(lines: &[LyricsLine]) -> Vec<(u128, &str)> {
+    lines
+      .iter()
+      .map(|line| (line.time_ms, line.text.as_str()))
+      .collect()
+  }
+
+  #[test]
+  fn parses_synced_lines_and_skips_tags() {
+    let (lines, synced) = parse_lyrics("[ar:Someone]\n[00:01.50] first\n[01:02.345]second\n");
+    assert!(synced);
+    assert_eq!(
+      times_and_text(&lines),
+      vec![(1500, "first"), (62345, "second")]
+    );
+  }
+
+  #[test]
+  fn plain_text_falls_back_to_unsynced_lines() {
+    let (lines, synced) = parse_lyrics("first line\n\nsecond line\n");
+    assert!(!synced);
+    assert_eq!(
+      times_and_text(&lines),
+      vec![(0, "first line"), (0, "second line")]
+    );
+  }
+
+  #[test]
+  fn written_lrc_parses_back() {
+    let lrc = write_lrc(
+      "Song",
+      "Band",
+      201_000,
+      &[(65_430, "second"), (1_500, "first")],
+    );
+    assert!(lrc.starts_with("[ti:Song]\n[ar:Band]\n[length:03:21]\n[00:01.50]first\n"));
+    let (lines, synced) = parse_lyrics(&lrc);
+    assert!(synced);
+    assert_eq!(
+      times_and_text(&lines),
+      vec![(1500, "first"), (65430, "second")]
+    );
+  }
+
+  #[test]
+  fn repeats_lines_with_several_timestamps() {
+    let (lines, _) = parse_lyrics("[00:12.00][01:30.00]chorus\n[00:20.00]verse\n");
+    assert_eq!(
+      times_and_text(&lines),
+      vec![(12000, "chorus"), (20000, "verse"), (90000, "chorus")]
+    );
+  }
+
+  #[test]
+  fn parses_enhanced_word_timestamps() {
+    let (lines, _) = parse_lyrics(
+      "[00:10.00]<00:10.00>Hello <00:10.50>there<00:11.20>\n[00:30.00][00:40.00]<00:30.20>again",
+    );
+    assert_eq!(lines[0].text, "Hello there");
+    let words: Vec<(u128, &str)> = lines[0]
+      .words
+      .iter()
+      .map(|word| (word.time_ms, word.text.as_str()))
+      .collect();
+    assert_eq!(words, vec![(10000, "Hello "), (10500, "there")]);
+
+    // The repeat of a line keeps its word timing relative to the line
+    assert_eq!(lines[2].time_ms, 40000);
+    assert_eq!(lines[2].words[0].time_ms, 40200);
+  }
+}
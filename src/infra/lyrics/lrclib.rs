@@ -0,0 +1,196 @@
+// LRCLIB (https://lrclib.net): exact lookup by title/artist/duration, optionally falling back to
+// its fuzzy search endpoint
+
+use super::{FetchedLyrics, LyricsProvider, LyricsQuery, ProviderFuture};
+use anyhow::{anyhow, Result};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::time::Duration;
+
+/// A stalled request would otherwise leave the lyrics pane on "Loading..." indefinitely
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Search results further than this from the track duration are a different recording
+const DURATION_TOLERANCE_SECS: f64 = 5.0;
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LrclibRecord {
+  #[serde(default)]
+  pub track_name: String,
+  #[serde(default)]
+  pub artist_name: String,
+  #[serde(default)]
+  pub album_name: Option<String>,
+  #[serde(default)]
+  pub duration: f64,
+  #[serde(default)]
+  pub instrumental: bool,
+  pub plain_lyrics: Option<String>,
+  pub synced_lyrics: Option<String>,
+}
+
+impl LrclibRecord {
+  /// Synced lyrics when the record has them, plain otherwise
+  pub fn into_lyrics(self) -> Option<FetchedLyrics> {
+    let (text, synced) = match (self.synced_lyrics, self.plain_lyrics) {
+      (Some(text), _) if !text.trim().is_empty() => (text, true),
+      (_, Some(text)) if !text.trim().is_empty() => (text, false),
+      _ => return None,
+    };
+    Some(FetchedLyrics {
+      text,
+      synced,
+      source: "lrclib".to_string(),
+    })
+  }
+}
+
+pub struct LrclibProvider {
+  client: reqwest::Client,
+  base_url: String,
+  search: bool,
+}
+
+impl LrclibProvider {
+  pub fn new(base_url: &str) -> Self {
+    let client = reqwest::Client::builder()
+      .timeout(REQUEST_TIMEOUT)
+      .build()
+      .unwrap_or_default();
+    Self {
+      client,
+      base_url: base_url.trim_end_matches('/').to_string(),
+      search: false,
+    }
+  }
+
+  /// Use `/api/search` and pick the closest match instead of the exact `/api/get` lookup
+  pub fn with_search(mut self) -> Self {
+    self.search = true;
+    self
+  }
+
+  async fn get(&self, query: &LyricsQuery) -> Result<Option<LrclibRecord>> {
+    let resp = self
+      .client
+      .get(format!("{}/api/get", self.base_url))
+      .query(&[
+        ("track_name", query.track.clone()),
+        ("artist_name", query.primary_artist().to_string()),
+        ("duration", query.duration_secs.round().to_string()),
+      ])
+      .send()
+      .await?;
+
+    if resp.status() == StatusCode::NOT_FOUND {
+      return Ok(None);
+    }
+    if !resp.status().is_success() {
+      return Err(anyhow!("LRCLIB returned {}", resp.status()));
+    }
+    Ok(Some(resp.json::<LrclibRecord>().await?))
+  }
+
+  /// All candidates LRCLIB has for a free-form title/artist query
+  pub async fn search(&self, track: &str, artist: &str) -> Result<Vec<LrclibRecord>> {
+    let mut params = vec![("track_name", track.to_string())];
+    if !artist.trim().is_empty() {
+      params.push(("artist_name", artist.to_string()));
+    }
+
+    let resp = self
+      .client
+      .get(format!("{}/api/search", self.base_url))
+      .query(&params)
+      .send()
+      .await?;
+
+    if !resp.status().is_success() {
+      return Err(anyhow!("LRCLIB returned {}", resp.status()));
+    }
+    Ok(resp.json::<Vec<LrclibRecord>>().await?)
+  }
+
+  async fn search_best(&self, query: &LyricsQuery) -> Result<Option<LrclibRecord>> {
+    let records = self.search(&query.track, query.primary_artist()).await?;
+    Ok(best_match(records, query.duration_secs))
+  }
+}
+
+/// Closest duration within tolerance, preferring records with synced lyrics
+fn best_match(records: Vec<LrclibRecord>, duration_secs: f64) -> Option<LrclibRecord> {
+  records
+    .into_iter()
+    .filter(|record| !record.instrumental)
+    .filter(|record| (record.duration - duration_secs).abs() <= DURATION_TOLERANCE_SECS)
+    .min_by(|a, b| {
+      let key = |record: &LrclibRecord| {
+        (
+          record.synced_lyrics.is_none(),
+          (record.duration - duration_secs).abs(),
+        )
+      };
+      let (a_unsynced, a_diff) = key(a);
+      let (b_unsynced, b_diff) = key(b);
+      a_unsynced.cmp(&b_unsynced).then(a_diff.total_cmp(&b_diff))
+    })
+}
+
+impl LyricsProvider for LrclibProvider {
+  fn name(&self) -> &'static str {
+    if self.search {
+      "lrclib-search"
+    } else {
+      "lrclib"
+    }
+  }
+
+  fn fetch<'a>(&'a self, query: &'a LyricsQuery) -> ProviderFuture<'a> {
+    Box::pin(async move {
+      let record = if self.search {
+        self.search_best(query).await?
+      } else {
+        self.get(query).await?
+      };
+      Ok(record.and_then(LrclibRecord::into_lyrics))
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn record(duration: f64, synced: bool) -> LrclibRecord {
+    LrclibRecord {
+      duration,
+      plain_lyrics: Some("words".to_string()),
+      synced_lyrics: synced.then(|| "[00:01.00]words".to_string()),
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn best_match_prefers_synced_within_tolerance() {
+    let records = vec![
+      record(200.0, false),
+      record(203.0, true),
+      record(240.0, true),
+    ];
+    assert_eq!(best_match(records, 200.0).map(|r| r.duration), Some(203.0));
+    assert!(best_match(vec![record(240.0, true)], 200.0).is_none());
+  }
+
+  #[test]
+  fn parses_api_record() {
+    let json = r#"{"id":7,"trackName":"Song","artistName":"Band","albumName":null,
+      "duration":201.0,"instrumental":false,"plainLyrics":"a","syncedLyrics":"[00:01.00]a"}"#;
+    let lyrics = serde_json::from_str::<LrclibRecord>(json)
+      .unwrap()
+      .into_lyrics()
+      .unwrap();
+    assert!(lyrics.synced);
+    assert_eq!(lyrics.text, "[00:01.00]a");
+  }
+}
@@ -0,0 +1,155 @@
+//! Lyrics lookup: an ordered chain of providers in front of an on-disk cache
+
+mod cache;
+mod local;
+mod lrc;
+mod lrclib;
+
+pub use cache::LyricsCache;
+pub use local::LocalProvider;
+pub use lrc::{format_timestamp, parse_lyrics, write_lrc, LyricsLine};
+pub use lrclib::{LrclibProvider, LrclibRecord};
+
+use crate::core::user_config::{LyricsConfig, LyricsProviderKind};
+use anyhow::Result;
+use log::debug;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::pin::Pin;
+
+/// What we know about the track whose lyrics we want
+#[derive(Clone, Debug, PartialEq)]
+pub struct LyricsQuery {
+  pub track_id: String,
+  pub track: String,
+  /// Comma separated, as shown in the UI; providers use the first artist
+  pub artist: String,
+  pub duration_secs: f64,
+}
+
+impl LyricsQuery {
+  pub fn primary_artist(&self) -> &str {
+    self.artist.split(", ").next().unwrap_or(&self.artist)
+  }
+}
+
+/// Raw lyrics text as returned by a provider: LRC when `synced`, plain lines otherwise
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FetchedLyrics {
+  pub text: String,
+  pub synced: bool,
+  /// Provider name, for the lyrics pane title
+  pub source: String,
+}
+
+pub type ProviderFuture<'a> =
+  Pin<Box<dyn Future<Output = Result<Option<FetchedLyrics>>> + Send + 'a>>;
+
+pub trait LyricsProvider: Send + Sync {
+  fn name(&self) -> &'static str;
+
+  /// `Ok(None)` when the provider has nothing for this track. `Err` when it couldn't be asked
+  /// (offline, server error), which is never cached so the next play tries again.
+  fn fetch<'a>(&'a self, query: &'a LyricsQuery) -> ProviderFuture<'a>;
+
+  /// Remote results go through the disk cache; local files are cheap to check every time
+  fn cacheable(&self) -> bool {
+    true
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LookupOutcome {
+  Found(FetchedLyrics),
+  NotFound,
+  /// Every provider that could have had lyrics failed to answer
+  Failed,
+}
+
+pub fn build_providers(config: &LyricsConfig) -> Vec<Box<dyn LyricsProvider>> {
+  config
+    .providers
+    .iter()
+    .filter_map(|kind| -> Option<Box<dyn LyricsProvider>> {
+      match kind {
+        LyricsProviderKind::Local => config
+          .local_dir
+          .clone()
+          .map(|dir| Box::new(LocalProvider::new(dir)) as Box<dyn LyricsProvider>),
+        LyricsProviderKind::Lrclib => Some(Box::new(LrclibProvider::new(&config.lrclib_url))),
+        LyricsProviderKind::LrclibSearch => Some(Box::new(
+          LrclibProvider::new(&config.lrclib_url).with_search(),
+        )),
+      }
+    })
+    .collect()
+}
+
+/// Try each provider in order. A pinned result beats everything; otherwise the cache is consulted
+/// just before the first cacheable provider, so local files listed ahead of the remote ones still
+/// win over a cached result.
+pub async fn lookup(
+  providers: &[Box<dyn LyricsProvider>],
+  cache: &LyricsCache,
+  query: &LyricsQuery,
+) -> LookupOutcome {
+  if let Some(pinned) = cache.pinned(&query.track_id) {
+    return LookupOutcome::Found(pinned);
+  }
+
+  let mut cache_checked = false;
+  let mut failed = false;
+
+  for provider in providers {
+    if provider.cacheable() && !cache_checked {
+      cache_checked = true;
+      if let Some(entry) = cache.load(&query.track_id) {
+        return match entry.lyrics {
+          Some(lyrics) => LookupOutcome::Found(lyrics),
+          None => LookupOutcome::NotFound,
+        };
+      }
+    }
+
+    match provider.fetch(query).await {
+      Ok(Some(lyrics)) => {
+        if provider.cacheable() {
+          cache.store(&query.track_id, Some(&lyrics));
+        }
+        return LookupOutcome::Found(lyrics);
+      }
+      Ok(None) => {}
+      Err(e) => {
+        debug!("lyrics provider {} failed: {}", provider.name(), e);
+        if provider.cacheable() {
+          failed = true;
+        }
+      }
+    }
+  }
+
+  if failed {
+    return LookupOutcome::Failed;
+  }
+  if cache_checked {
+    cache.store(&query.track_id, None);
+  }
+  LookupOutcome::NotFound
+}
+
+/// Lowercase, drop punctuation and collapse whitespace so file names and tags compare loosely
+fn normalize(text: &str) -> String {
+  text
+    .chars()
+    .map(|c| {
+      if c.is_alphanumeric() {
+        c.to_ascii_lowercase()
+      } else {
+        ' '
+      }
+    })
+    .collect::<String>()
+    .split_whitespace()
+    .collect::<Vec<_>>()
+    .join(" ")
+}
@@ -1,11 +1,15 @@
 pub mod audio;
+#[cfg(feature = "cover-art")]
+pub mod cover_cache;
 #[cfg(feature = "discord-rpc")]
 pub mod discord_rpc;
+pub mod lyrics;
 #[cfg(all(feature = "macos-media", target_os = "macos"))]
 pub mod macos_media;
 #[cfg(all(feature = "mpris", target_os = "linux"))]
 pub mod mpris;
 pub mod network;
+pub mod notifications;
 #[cfg(feature = "streaming")]
 pub mod player;
 pub mod redirect_uri;
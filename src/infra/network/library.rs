@@ -2,8 +2,12 @@ use super::requests::{spotify_api_request_json_for, spotify_get_typed_compat_for
 use super::Network;
 use crate::core::app::{
   ActiveBlock, App, PlaylistFolder, PlaylistFolderItem, PlaylistFolderNode, PlaylistFolderNodeType,
-  RouteId, TrackTableContext,
+  RouteId, TrackAdded, TrackTableContext,
 };
+use crate::core::sort::{reorder, SortContext, Sorter, TrackRow};
+use crate::core::undo::UndoOp;
+use crate::infra::notifications::{Notification, NotificationKind};
+use crate::tui::ui::util::create_artist_string;
 use anyhow::anyhow;
 use reqwest::Method;
 use rspotify::model::{
@@ -22,6 +26,31 @@ use tokio::sync::Mutex;
 #[cfg(feature = "streaming")]
 use crate::infra::player::StreamingPlayer;
 
+/// "Title - Artists" for a track the user just liked, looked up from what's on screen
+fn liked_track_description(app: &App, track_id: &str) -> Option<String> {
+  let describe =
+    |track: &FullTrack| format!("{} - {}", track.name, create_artist_string(&track.artists));
+  let matches = |track: &FullTrack| track.id.as_ref().is_some_and(|id| id.id() == track_id);
+
+  let playing = app
+    .current_playback_context
+    .as_ref()
+    .and_then(|context| context.item.as_ref())
+    .and_then(|item| match item {
+      PlayableItem::Track(track) if matches(track) => Some(describe(track)),
+      _ => None,
+    });
+
+  playing.or_else(|| {
+    app
+      .track_table
+      .tracks
+      .iter()
+      .find(|track| matches(track))
+      .map(describe)
+  })
+}
+
 pub async fn prefetch_all_saved_tracks_task(
   spotify: AuthCodePkceSpotify,
   app: Arc<Mutex<App>>,
@@ -58,6 +87,11 @@ pub async fn prefetch_all_saved_tracks_task(
         // Also update track table if we are currently viewing saved tracks
         if let Some(TrackTableContext::SavedTracks) = app_guard.track_table.context {
           // Append to track table
+          let added = page.items.iter().map(|item| TrackAdded {
+            at: Some(item.added_at),
+            by: None,
+          });
+          app_guard.track_table.added.extend(added);
           let new_tracks: Vec<FullTrack> = page.items.into_iter().map(|item| item.track).collect();
           app_guard.track_table.tracks.extend(new_tracks);
         }
@@ -90,9 +124,11 @@ pub async fn prefetch_all_playlist_tracks_task(
         }
 
         let mut tracks: Vec<FullTrack> = Vec::new();
+        let mut added = Vec::new();
         for item in &page.items {
           if let Some(PlayableItem::Track(full_track)) = item.track.as_ref() {
             tracks.push(full_track.clone());
+            added.push(TrackAdded::from_playlist_item(item));
           }
         }
 
@@ -116,6 +152,7 @@ pub async fn prefetch_all_playlist_tracks_task(
           positions.extend(start..start + count);
         }
         app_guard.track_table.tracks.extend(tracks);
+        app_guard.track_table.added.extend(added);
 
         if page.next.is_none() {
           break;
@@ -161,6 +198,12 @@ pub trait LibraryNetwork {
     track_id: TrackId<'static>,
     position: usize,
   );
+  async fn restore_track_to_playlist(
+    &mut self,
+    playlist_id: PlaylistId<'static>,
+    track_id: TrackId<'static>,
+    position: usize,
+  );
   async fn toggle_save_track(&mut self, track_id: rspotify::model::idtypes::PlayableId<'static>);
   async fn current_user_saved_tracks_contains(&mut self, ids: Vec<TrackId<'static>>);
   async fn fetch_all_playlist_tracks_and_sort(&mut self, playlist_id: PlaylistId<'static>);
@@ -204,12 +247,72 @@ impl Network {
     Ok(())
   }
 
+  /// Fetch one page of a playlist into the track table, returning whether it worked
+  async fn load_playlist_page(&mut self, playlist_id: &PlaylistId<'static>, offset: u32) -> bool {
+    let path = format!("playlists/{}/items", playlist_id.id());
+    match spotify_get_typed_compat_for::<Page<PlaylistItem>>(
+      &self.spotify,
+      &path,
+      &[
+        ("limit", self.large_search_limit.to_string()),
+        ("offset", offset.to_string()),
+      ],
+    )
+    .await
+    {
+      Ok(playlist_tracks) => {
+        self.set_playlist_tracks_to_table(&playlist_tracks).await;
+        self.app.lock().await.playlist_tracks = Some(playlist_tracks);
+        true
+      }
+      Err(e) => {
+        self.handle_error(anyhow!(e)).await;
+        false
+      }
+    }
+  }
+
+  /// Reload the track table after the open playlist changed underneath it, so the rows and
+  /// their positions match Spotify again. Other views are left alone.
+  async fn reload_open_playlist(&mut self, playlist_id: PlaylistId<'static>) {
+    let (offset, selected_index) = {
+      let app = self.app.lock().await;
+      let is_open = app.track_table.context == Some(TrackTableContext::MyPlaylists)
+        && app.active_playlist_id().as_deref() == Some(playlist_id.id());
+      if !is_open {
+        return;
+      }
+      (app.playlist_offset, app.track_table.selected_index)
+    };
+
+    if !self.load_playlist_page(&playlist_id, offset).await {
+      return;
+    }
+
+    let sorted = !self
+      .app
+      .lock()
+      .await
+      .playlist_sorts
+      .get(playlist_id.id())
+      .is_default();
+    if sorted {
+      self.fetch_all_playlist_tracks_and_sort(playlist_id).await;
+    }
+
+    let mut app = self.app.lock().await;
+    let last = app.track_table.tracks.len().saturating_sub(1);
+    app.track_table.selected_index = selected_index.min(last);
+  }
+
   async fn set_playlist_tracks_to_table(&mut self, playlist_track_page: &Page<PlaylistItem>) {
     let mut tracks: Vec<FullTrack> = Vec::new();
     let mut positions: Vec<usize> = Vec::new();
+    let mut added = Vec::new();
 
     for (idx, item) in playlist_track_page.items.iter().enumerate() {
       if let Some(PlayableItem::Track(full_track)) = item.track.as_ref() {
+        added.push(TrackAdded::from_playlist_item(item));
         tracks.push(full_track.clone());
         positions.push(playlist_track_page.offset as usize + idx);
       }
@@ -219,6 +322,7 @@ impl Network {
 
     let mut app = self.app.lock().await;
     app.playlist_track_positions = Some(positions);
+    app.track_table.added = added;
   }
 }
 
@@ -293,27 +397,17 @@ impl LibraryNetwork for Network {
   }
 
   async fn get_playlist_tracks(&mut self, playlist_id: PlaylistId<'static>, playlist_offset: u32) {
-    let path = format!("playlists/{}/items", playlist_id.id());
-    match spotify_get_typed_compat_for::<Page<PlaylistItem>>(
-      &self.spotify,
-      &path,
-      &[
-        ("limit", self.large_search_limit.to_string()),
-        ("offset", playlist_offset.to_string()),
-      ],
-    )
-    .await
-    {
-      Ok(playlist_tracks) => {
-        self.set_playlist_tracks_to_table(&playlist_tracks).await;
+    if !self.load_playlist_page(&playlist_id, playlist_offset).await {
+      return;
+    }
 
-        let mut app = self.app.lock().await;
-        app.playlist_tracks = Some(playlist_tracks);
-        app.push_navigation_stack(RouteId::TrackTable, ActiveBlock::TrackTable);
-      }
-      Err(e) => {
-        self.handle_error(anyhow!(e)).await;
-      }
+    let mut app = self.app.lock().await;
+    app.push_navigation_stack(RouteId::TrackTable, ActiveBlock::TrackTable);
+    let sorted = !app.playlist_sorts.get(playlist_id.id()).is_default();
+    drop(app);
+
+    if sorted {
+      self.fetch_all_playlist_tracks_and_sort(playlist_id).await;
     }
   }
 
@@ -332,12 +426,21 @@ impl LibraryNetwork for Network {
     {
       Ok(saved_tracks) => {
         let mut app = self.app.lock().await;
+        app.visual_anchor = None;
         app.track_table.tracks = saved_tracks
           .items
           .clone()
           .into_iter()
           .map(|item| item.track)
           .collect::<Vec<FullTrack>>();
+        app.track_table.added = saved_tracks
+          .items
+          .iter()
+          .map(|item| TrackAdded {
+            at: Some(item.added_at),
+            by: None,
+          })
+          .collect();
 
         saved_tracks.items.iter().for_each(|item| {
           if let Some(track_id) = &item.track.id {
@@ -352,12 +455,16 @@ impl LibraryNetwork for Network {
             app.track_table.selected_index = match pending {
               crate::core::app::PendingTrackSelection::First => 0,
               crate::core::app::PendingTrackSelection::Last => track_count.saturating_sub(1),
+              crate::core::app::PendingTrackSelection::Index(index) => {
+                index.min(track_count.saturating_sub(1))
+              }
             };
           }
         }
 
         app.library.saved_tracks.add_pages(saved_tracks);
         app.track_table.context = Some(TrackTableContext::SavedTracks);
+        app.resort(SortContext::LikedSongs);
       }
       Err(e) => {
         self.handle_error(anyhow!(e)).await;
@@ -425,7 +532,10 @@ impl LibraryNetwork for Network {
         // dispatching event would require loop access, but we can't from here easily unless we return IoEvent
         // For now, assume optimistic update is handled or manually remove
       }
-      Err(e) => self.handle_error(anyhow!(e)).await,
+      Err(e) => {
+        self.app.lock().await.undo_history.discard(&album_id.uri());
+        self.handle_error(anyhow!(e)).await
+      }
     }
   }
 
@@ -471,7 +581,10 @@ impl LibraryNetwork for Network {
         let mut app = self.app.lock().await;
         app.saved_show_ids_set.remove(show_id.id());
       }
-      Err(e) => self.handle_error(anyhow!(e)).await,
+      Err(e) => {
+        self.app.lock().await.undo_history.discard(&show_id.uri());
+        self.handle_error(anyhow!(e)).await
+      }
     }
   }
 
@@ -534,11 +647,15 @@ impl LibraryNetwork for Network {
     _user_id: UserId<'static>,
     playlist_id: PlaylistId<'static>,
   ) {
+    let uri = playlist_id.uri();
     match self.spotify.playlist_unfollow(playlist_id).await {
       Ok(_) => {
         // Handled
       }
-      Err(e) => self.handle_error(anyhow!(e)).await,
+      Err(e) => {
+        self.app.lock().await.undo_history.discard(&uri);
+        self.handle_error(anyhow!(e)).await
+      }
     }
   }
 
@@ -583,11 +700,31 @@ impl LibraryNetwork for Network {
     )
     .await
     {
-      Ok(_) => {
-        self
-          .show_status_message("Removed from playlist".to_string(), 3)
-          .await;
+      // The status message offering to undo went up when the removal was confirmed
+      Ok(_) => self.reload_open_playlist(playlist_id).await,
+      Err(e) => {
+        self.app.lock().await.undo_history.discard(&track_id.uri());
+        self.handle_error(anyhow!(e)).await
       }
+    }
+  }
+
+  async fn restore_track_to_playlist(
+    &mut self,
+    playlist_id: PlaylistId<'static>,
+    track_id: TrackId<'static>,
+    position: usize,
+  ) {
+    match self
+      .spotify
+      .playlist_add_items(
+        playlist_id.clone(),
+        vec![PlayableId::Track(track_id)],
+        u32::try_from(position).ok(),
+      )
+      .await
+    {
+      Ok(_) => self.reload_open_playlist(playlist_id).await,
       Err(e) => self.handle_error(anyhow!(e)).await,
     }
   }
@@ -613,12 +750,26 @@ impl LibraryNetwork for Network {
       } else {
         let mut app = self.app.lock().await;
         app.liked_song_ids_set.remove(id_str);
+        let name = liked_track_description(&app, id_str).unwrap_or_else(|| match &track_id {
+          PlayableId::Track(_) => "track".to_string(),
+          PlayableId::Episode(_) => "episode".to_string(),
+        });
+        app.record_undo(UndoOp::Unliked {
+          id: track_id.clone(),
+          name,
+        });
       }
     } else if let Err(e) = self.library_save_uris(&[uri]).await {
       self.handle_error(anyhow!(e)).await;
     } else {
       let mut app = self.app.lock().await;
       app.liked_song_ids_set.insert(id_str.to_string());
+      let body = liked_track_description(&app, id_str).unwrap_or_default();
+      app.queue_notification(Notification::new(
+        NotificationKind::Liked,
+        "Added to Liked Songs",
+        body,
+      ));
     }
   }
 
@@ -657,6 +808,9 @@ impl LibraryNetwork for Network {
 
     let mut app = self.app.lock().await;
     app.playlist_track_positions = None;
+    app.track_table.added.clear();
+    // A range over the previous rows means nothing on the new ones
+    app.visual_anchor = None;
 
     let track_count = tracks.len();
     if track_count > 0 {
@@ -664,6 +818,9 @@ impl LibraryNetwork for Network {
         app.track_table.selected_index = match pending {
           crate::core::app::PendingTrackSelection::First => 0,
           crate::core::app::PendingTrackSelection::Last => track_count.saturating_sub(1),
+          crate::core::app::PendingTrackSelection::Index(index) => {
+            index.min(track_count.saturating_sub(1))
+          }
         };
       } else {
         let max_index = track_count.saturating_sub(1);
@@ -684,6 +841,8 @@ impl LibraryNetwork for Network {
 
   async fn fetch_all_playlist_tracks_and_sort(&mut self, playlist_id: PlaylistId<'static>) {
     let mut all_tracks = Vec::new();
+    let mut positions = Vec::new();
+    let mut added = Vec::new();
     let mut offset = 0u32;
     let limit = 50u32;
     let path = format!("playlists/{}/items", playlist_id.id());
@@ -696,9 +855,12 @@ impl LibraryNetwork for Network {
             break;
           }
 
-          for item in page.items {
+          for (idx, item) in page.items.into_iter().enumerate() {
+            let track_added = TrackAdded::from_playlist_item(&item);
             if let Some(PlayableItem::Track(full_track)) = item.track {
+              added.push(track_added);
               all_tracks.push(full_track);
+              positions.push(offset as usize + idx);
             }
           }
 
@@ -714,17 +876,27 @@ impl LibraryNetwork for Network {
       }
     }
 
-    // Apply sort if any
     let mut app = self.app.lock().await;
 
-    // Sort
-    use crate::core::sort::{SortContext, Sorter};
-    if let Some(SortContext::PlaylistTracks) = app.sort_context {
-      let sorter = Sorter::new(app.playlist_sort);
-      sorter.sort_tracks(&mut all_tracks);
-    }
+    // Sort by what was last picked for this playlist, the default being its own order. The
+    // positions move along so removals still hit the right entry.
+    let sorter = Sorter::new(app.playlist_sorts.get(playlist_id.id()));
+    let rows: Vec<TrackRow> = all_tracks
+      .iter()
+      .zip(&positions)
+      .zip(&added)
+      .map(|((track, position), entry)| TrackRow {
+        track,
+        added_at: entry.at,
+        natural_key: *position as i64,
+      })
+      .collect();
+    let order = sorter.order(&rows);
 
-    app.track_table.tracks = all_tracks;
+    app.visual_anchor = None;
+    app.track_table.tracks = reorder(all_tracks, &order);
+    app.playlist_track_positions = Some(reorder(positions, &order));
+    app.track_table.added = reorder(added, &order);
     // Reset selection
     app.track_table.selected_index = 0;
   }
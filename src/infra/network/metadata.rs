@@ -4,6 +4,7 @@ use crate::core::app::{
   ActiveBlock, Artist, ArtistBlock, EpisodeTableContext, RouteId, ScrollableResultPages,
   SelectedFullShow, SelectedShow,
 };
+use crate::core::sort::SortContext;
 use anyhow::anyhow;
 use futures::stream::StreamExt;
 use rspotify::model::{
@@ -120,6 +121,7 @@ impl MetadataNetwork for Network {
             selected_index: 0,
           });
           app.album_table_context = crate::core::app::AlbumTableContext::Simplified;
+          app.resort(SortContext::AlbumTracks);
           app.push_navigation_stack(RouteId::AlbumTracks, ActiveBlock::AlbumTracks);
         }
         Err(e) => self.handle_error(anyhow!(e)).await,
@@ -136,6 +138,7 @@ impl MetadataNetwork for Network {
           selected_index: 0,
         });
         app.album_table_context = crate::core::app::AlbumTableContext::Full;
+        app.resort(SortContext::AlbumTracks);
         app.push_navigation_stack(RouteId::AlbumTracks, ActiveBlock::AlbumTracks);
       }
       Err(e) => self.handle_error(anyhow!(e)).await,
@@ -244,11 +247,18 @@ impl MetadataNetwork for Network {
   }
 
   async fn user_unfollow_artists(&mut self, artist_ids: Vec<ArtistId<'static>>) {
+    let uris: Vec<String> = artist_ids.iter().map(|id| id.uri()).collect();
     match self.spotify.user_unfollow_artists(artist_ids).await {
       Ok(_) => {
         // Handled
       }
-      Err(e) => self.handle_error(anyhow!(e)).await,
+      Err(e) => {
+        let mut app = self.app.lock().await;
+        for uri in &uris {
+          app.undo_history.discard(uri);
+        }
+        app.handle_error(anyhow!(e));
+      }
     }
   }
 
@@ -9,6 +9,7 @@ pub mod utils;
 
 use crate::core::app::App;
 use crate::core::config::ClientConfig;
+use crate::core::session::SessionPlayback;
 use anyhow::anyhow;
 use rspotify::clients::BaseClient;
 use rspotify::model::{
@@ -80,6 +81,7 @@ pub enum IoEvent {
   UserUnfollowPlaylist(UserId<'static>, PlaylistId<'static>),
   AddTrackToPlaylist(PlaylistId<'static>, TrackId<'static>),
   RemoveTrackFromPlaylistAtPosition(PlaylistId<'static>, TrackId<'static>, usize),
+  RestoreTrackToPlaylist(PlaylistId<'static>, TrackId<'static>, usize),
   GetUser,
   ToggleSaveTrack(PlayableId<'static>),
   GetRecommendationsForTrackId(TrackId<'static>, Option<Country>),
@@ -91,6 +93,7 @@ pub enum IoEvent {
   TransferPlaybackToDevice(String, bool),
   #[allow(dead_code)]
   AutoSelectStreamingDevice(String, bool), // Auto-select a device by name (used for native streaming)
+  ResumeSession(SessionPlayback), // Load the last session's track paused on the native device
   GetAlbumForTrack(TrackId<'static>),
   CurrentUserSavedTracksContains(Vec<TrackId<'static>>),
   GetCurrentUserSavedShows(Option<u32>),
@@ -101,10 +104,20 @@ pub enum IoEvent {
   GetShow(ShowId<'static>),
   GetCurrentShowEpisodes(ShowId<'static>, Option<u32>),
   AddItemToQueue(PlayableId<'static>),
+  /// Upcoming items shown in the now playing view
+  GetQueue,
+  /// Cover image URL of an album selected in a list
+  #[cfg(feature = "cover-art")]
+  GetCoverThumbnail(String),
   IncrementGlobalSongCount,
   FetchGlobalSongCount,
   FetchAnnouncements,
-  GetLyrics(String, String, f64),
+  /// Track id, name, artists and duration in seconds
+  GetLyrics(String, String, String, f64),
+  /// Title and artist typed into the lyrics search popup
+  SearchLyrics(String, String),
+  /// Local outputs for the audio output picker
+  GetAudioOutputs,
   /// Start playback from the user's saved tracks collection (Liked Songs)
   /// Takes the absolute position in the collection to start from
   /// NOTE: Currently unused - Spotify Web API doesn't support collection context URI
@@ -270,6 +283,11 @@ impl Network {
           .remove_track_from_playlist_at_position(playlist_id, track_id, position)
           .await;
       }
+      IoEvent::RestoreTrackToPlaylist(playlist_id, track_id, position) => {
+        self
+          .restore_track_to_playlist(playlist_id, track_id, position)
+          .await;
+      }
 
       IoEvent::ToggleSaveTrack(track_id) => {
         self.toggle_save_track(track_id).await;
@@ -307,6 +325,12 @@ impl Network {
       }
       #[cfg(not(feature = "streaming"))]
       IoEvent::AutoSelectStreamingDevice(..) => {} // No-op without native streaming
+      #[cfg(feature = "streaming")]
+      IoEvent::ResumeSession(playback) => {
+        self.resume_session(playback).await;
+      }
+      #[cfg(not(feature = "streaming"))]
+      IoEvent::ResumeSession(..) => {}
       IoEvent::GetAlbumForTrack(track_id) => {
         self.get_album_for_track(track_id).await;
       }
@@ -337,6 +361,13 @@ impl Network {
       IoEvent::GetCurrentShowEpisodes(show_id, offset) => {
         self.get_current_show_episodes(show_id, offset).await;
       }
+      IoEvent::GetQueue => {
+        self.get_queue().await;
+      }
+      #[cfg(feature = "cover-art")]
+      IoEvent::GetCoverThumbnail(url) => {
+        self.get_cover_thumbnail(url).await;
+      }
       IoEvent::AddItemToQueue(item) => {
         self.add_item_to_queue(item).await;
       }
@@ -349,9 +380,25 @@ impl Network {
       IoEvent::FetchAnnouncements => {
         self.fetch_announcements().await;
       }
-      IoEvent::GetLyrics(track, artist, duration) => {
-        self.get_lyrics(track, artist, duration).await;
+      IoEvent::GetLyrics(track_id, track, artist, duration) => {
+        self.get_lyrics(track_id, track, artist, duration).await;
       }
+      IoEvent::SearchLyrics(track, artist) => {
+        self.search_lyrics(track, artist).await;
+      }
+      #[cfg(feature = "streaming")]
+      IoEvent::GetAudioOutputs => {
+        // Listing runs `aplay` and `pactl`, which can hang for a while on a busy sound server
+        let app = self.app.clone();
+        tokio::spawn(async move {
+          let choices = tokio::task::spawn_blocking(crate::infra::player::list_audio_outputs)
+            .await
+            .unwrap_or_default();
+          app.lock().await.set_audio_output_choices(choices);
+        });
+      }
+      #[cfg(not(feature = "streaming"))]
+      IoEvent::GetAudioOutputs => {}
       IoEvent::StartCollectionPlayback(offset) => {
         self.start_collection_playback(offset).await;
       }
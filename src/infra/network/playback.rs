@@ -1,5 +1,10 @@
 use super::requests::spotify_get_typed_compat_for;
 use super::{IoEvent, Network};
+use crate::core::app::ActiveBlock;
+#[cfg(feature = "streaming")]
+use crate::core::session::SessionPlayback;
+#[cfg(feature = "cover-art")]
+use crate::infra::cover_cache::CoverCache;
 use crate::tui::ui::util::create_artist_string;
 use anyhow::anyhow;
 use chrono::Duration as ChronoDuration;
@@ -33,9 +38,12 @@ pub trait PlaybackNetwork {
   async fn transfert_playback_to_device(&mut self, device_id: String, persist_device_id: bool);
   #[cfg(feature = "streaming")]
   async fn auto_select_streaming_device(&mut self, device_name: String, persist_device_id: bool);
+  #[cfg(feature = "streaming")]
+  async fn resume_session(&mut self, playback: SessionPlayback);
   async fn ensure_playback_continues(&mut self, previous_track_id: String);
   #[allow(dead_code)]
   async fn add_item_to_queue(&mut self, item: PlayableId<'static>);
+  async fn get_queue(&mut self);
   #[allow(dead_code)]
   async fn start_collection_playback(&mut self, offset: usize);
 }
@@ -170,6 +178,7 @@ impl PlaybackNetwork for Network {
                   // Trigger lyrics fetch
                   let duration_secs = track.duration.num_seconds() as f64;
                   app.dispatch(IoEvent::GetLyrics(
+                    track_id_str.clone(),
                     track.name.clone(),
                     create_artist_string(&track.artists),
                     duration_secs,
@@ -178,6 +187,10 @@ impl PlaybackNetwork for Network {
                   app.dispatch(IoEvent::CurrentUserSavedTracksContains(vec![track_id
                     .clone()
                     .into_static()]));
+
+                  if app.get_current_route().active_block == ActiveBlock::NowPlaying {
+                    app.dispatch(IoEvent::GetQueue);
+                  }
                 }
 
                 app.last_track_id = Some(track_id_str);
@@ -214,11 +227,14 @@ impl PlaybackNetwork for Network {
           }
         }
 
-        // Get album/episode cover art
+        // Get album/episode cover art. The now playing view draws it even without a graphics
+        // protocol, falling back to Unicode blocks, and the adaptive theme needs it to pick colors
         #[cfg(feature = "cover-art")]
-        if app
-          .user_config
-          .do_draw_cover_art(app.cover_art.full_image_support())
+        if app.user_config.behavior.adaptive_theme
+          || app.user_config.do_draw_cover_art(
+            app.cover_art.full_image_support()
+              || app.get_current_route().active_block == ActiveBlock::NowPlaying,
+          )
         {
           if let Some(playable) = &c.item {
             let image = match playable {
@@ -227,15 +243,30 @@ impl PlaybackNetwork for Network {
             };
 
             if let Some(image) = image {
-              if let anyhow::Result::Err(err) = app.cover_art.refresh(image).await {
-                drop(app);
-                self.handle_error(err).await;
-                return;
+              let cache = CoverCache::new(app.user_config.behavior.cover_art_cache_mb);
+              match app.cover_art.refresh(image, &cache).await {
+                Ok(true) => app.apply_adaptive_theme(),
+                Ok(false) => {}
+                Err(err) => {
+                  drop(app);
+                  self.handle_error(err).await;
+                  return;
+                }
               }
             }
           }
         }
 
+        // Playback updates are where track changes show up, so plays are counted here
+        let playing = match &c.item {
+          Some(PlayableItem::Track(track)) => track.id.as_ref().map(|id| {
+            let progress = c.progress.map_or(0, |p| p.num_milliseconds().max(0) as u64);
+            (id.id(), progress)
+          }),
+          _ => None,
+        };
+        app.play_counts.observe(playing);
+
         app.current_playback_context = Some(c);
 
         // Update is_streaming_active based on whether the current device matches native streaming
@@ -266,6 +297,7 @@ impl PlaybackNetwork for Network {
         }
       }
       Ok(None) => {
+        app.play_counts.observe(None);
         app.instant_since_last_current_playback_poll = Instant::now();
       }
       Err(e) => {
@@ -691,6 +723,48 @@ impl PlaybackNetwork for Network {
     }
   }
 
+  #[cfg(feature = "streaming")]
+  async fn resume_session(&mut self, playback: SessionPlayback) {
+    if !is_native_streaming_active_for_playback(self).await {
+      return;
+    }
+    let Some(ref player) = self.streaming_player else {
+      return;
+    };
+    {
+      // Whatever started playing in the meantime is left alone
+      let app = self.app.lock().await;
+      if app
+        .current_playback_context
+        .as_ref()
+        .is_some_and(|ctx| ctx.is_playing)
+      {
+        return;
+      }
+    }
+
+    let options = LoadRequestOptions {
+      start_playing: false,
+      seek_to: playback.position_ms,
+      context_options: None,
+      playing_track: Some(PlayingTrack::Uri(playback.track_uri.clone())),
+    };
+    let request = match playback.context_uri {
+      Some(context_uri) => LoadRequest::from_context_uri(context_uri, options),
+      None => LoadRequest::from_tracks(vec![playback.track_uri], options),
+    };
+
+    player.activate();
+    let mut app = self.app.lock().await;
+    match player.load(request) {
+      Ok(()) => {
+        app.is_streaming_active = true;
+        app.set_status_message("Resumed last session, paused", 4);
+      }
+      Err(e) => app.handle_error(anyhow!("Failed to resume last session: {}", e)),
+    }
+  }
+
   async fn ensure_playback_continues(&mut self, previous_track_id: String) {
     #[cfg(feature = "streaming")]
     if is_native_streaming_active_for_playback(self).await {
@@ -745,6 +819,17 @@ impl PlaybackNetwork for Network {
     }
   }
 
+  async fn get_queue(&mut self) {
+    match self.spotify.current_user_queue().await {
+      Ok(queue) => {
+        let mut app = self.app.lock().await;
+        app.up_next = queue.queue;
+      }
+      // Only decoration for the now playing view, not worth an error screen
+      Err(e) => log::debug!("failed to fetch queue: {}", e),
+    }
+  }
+
   async fn start_collection_playback(&mut self, _offset: usize) {
     // Placeholder - Spotify API doesn't support "My Music" as context
     let mut app = self.app.lock().await;
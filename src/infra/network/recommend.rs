@@ -74,6 +74,7 @@ impl RecommendationNetwork for Network {
         }
 
         app.track_table.tracks = full_tracks;
+        app.track_table.added.clear();
 
         // Prepend the seed track if available so user knows context
         if let Some(track) = *first_track {
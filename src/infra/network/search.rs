@@ -1,5 +1,6 @@
 use super::requests::spotify_get_typed_compat_for;
 use super::{IoEvent, Network};
+use crate::core::sort::SortContext;
 use anyhow::anyhow;
 use rspotify::model::{
   artist::FullArtist,
@@ -145,7 +146,14 @@ impl SearchNetwork for Network {
       app.dispatch(IoEvent::CurrentUserSavedShowsContains(show_ids));
     }
 
+    app.search_results.track_ranks = track_result
+      .iter()
+      .flat_map(|page| &page.items)
+      .enumerate()
+      .filter_map(|(rank, track)| Some((track.id.as_ref()?.id().to_string(), rank)))
+      .collect();
     app.search_results.tracks = track_result;
+    app.resort(SortContext::SearchResults);
     app.search_results.artists = artist_result;
     app.search_results.albums = album_result;
     app.search_results.playlists = playlist_result;
@@ -1,6 +1,7 @@
 use super::requests::{is_rate_limited_error, spotify_get_typed_compat_for};
 use super::Network;
 use crate::core::app::{ActiveBlock, DiscoverTimeRange, RouteId};
+use crate::core::sort::SortContext;
 use anyhow::anyhow;
 
 use rand::seq::SliceRandom;
@@ -147,6 +148,7 @@ impl UserNetwork for Network {
       Ok(recently_played) => {
         let mut app = self.app.lock().await;
         app.recently_played.result = Some(recently_played);
+        app.resort(SortContext::RecentlyPlayed);
         app.push_navigation_stack(RouteId::RecentlyPlayed, ActiveBlock::RecentlyPlayed);
       }
       Err(e) => {
@@ -1,16 +1,10 @@
 use super::Network;
 use crate::core::app::{Announcement, AnnouncementLevel, LyricsStatus};
+use crate::infra::lyrics::{self, LookupOutcome, LrclibProvider, LyricsCache, LyricsQuery};
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use std::time::{Duration, Instant};
 
-#[derive(Deserialize, Debug)]
-#[allow(non_snake_case)]
-struct LrcResponse {
-  syncedLyrics: Option<String>,
-  plainLyrics: Option<String>,
-}
-
 #[derive(Deserialize, Debug)]
 #[allow(dead_code)]
 struct GlobalSongCountResponse {
@@ -37,105 +31,91 @@ struct AnnouncementRecord {
 }
 
 pub trait UtilsNetwork {
-  async fn get_lyrics(&mut self, track: String, artist: String, duration: f64);
+  async fn get_lyrics(&mut self, track_id: String, track: String, artist: String, duration: f64);
+  async fn search_lyrics(&mut self, track: String, artist: String);
   async fn increment_global_song_count(&mut self);
   async fn fetch_global_song_count(&mut self);
   async fn fetch_announcements(&mut self);
+  #[cfg(feature = "cover-art")]
+  async fn get_cover_thumbnail(&mut self, url: String);
 }
 
 impl UtilsNetwork for Network {
-  async fn get_lyrics(&mut self, track: String, artist: String, duration: f64) {
-    let client = reqwest::Client::new();
-    let query = vec![
-      ("track_name", track.clone()),
-      ("artist_name", artist.clone()),
-      ("duration", duration.to_string()),
-    ];
-
-    // Update state to loading
-    {
+  async fn get_lyrics(&mut self, track_id: String, track: String, artist: String, duration: f64) {
+    let config = {
       let mut app = self.app.lock().await;
       app.lyrics_status = LyricsStatus::Loading;
       app.lyrics = None;
+      app.lyrics_synced = false;
+      app.lyrics_source = None;
+      app.lyrics_offset_ms = 0;
+      app.user_config.lyrics.clone()
+    };
+
+    let query = LyricsQuery {
+      track_id: track_id.clone(),
+      track,
+      artist,
+      duration_secs: duration,
+    };
+    let providers = lyrics::build_providers(&config);
+    let cache = LyricsCache::new();
+    let outcome = lyrics::lookup(&providers, &cache, &query).await;
+
+    let mut app = self.app.lock().await;
+    // The track changed while we were looking; its own request will fill the pane
+    if app.playing_track_id() != Some(track_id.as_str()) {
+      return;
     }
-
-    match client
-      .get("https://lrclib.net/api/get")
-      .query(&query)
-      .send()
-      .await
-    {
-      Ok(resp) => {
-        if resp.status().is_success() {
-          if let Ok(lrc_resp) = resp.json::<LrcResponse>().await {
-            let lyrics_text = lrc_resp
-              .syncedLyrics
-              .or(lrc_resp.plainLyrics)
-              .unwrap_or_default();
-
-            if !lyrics_text.is_empty() {
-              let mut app = self.app.lock().await;
-              // Simple LRC parser
-              let parsed: Vec<(u128, String)> = lyrics_text
-                .lines()
-                .filter_map(|line| {
-                  // [mm:ss.xx] text
-                  if let Some(idx) = line.find(']') {
-                    if idx > 1 && line.starts_with('[') {
-                      let timestamp = &line[1..idx];
-                      let content = line[idx + 1..].trim().to_string();
-
-                      // Parse timestamp
-                      let parts: Vec<&str> = timestamp.split(':').collect();
-                      if parts.len() == 2 {
-                        let mins = parts[0].parse::<u64>().unwrap_or(0);
-                        let secs_parts: Vec<&str> = parts[1].split('.').collect();
-                        let secs = secs_parts[0].parse::<u64>().unwrap_or(0);
-                        let ms = if secs_parts.len() > 1 {
-                          // Handle 2 or 3 digit ms
-                          let ms_str = secs_parts[1];
-                          let ms_val = ms_str.parse::<u64>().unwrap_or(0);
-                          if ms_str.len() == 2 {
-                            ms_val * 10
-                          } else {
-                            ms_val
-                          }
-                        } else {
-                          0
-                        };
-
-                        let total_ms = (mins * 60 * 1000) + (secs * 1000) + ms;
-                        return Some((total_ms as u128, content));
-                      }
-                    }
-                  }
-                  None
-                })
-                .collect();
-
-              if !parsed.is_empty() {
-                app.lyrics = Some(parsed);
-                app.lyrics_status = LyricsStatus::Found;
-              } else {
-                app.lyrics_status = LyricsStatus::NotFound;
-              }
-            } else {
-              let mut app = self.app.lock().await;
-              app.lyrics_status = LyricsStatus::NotFound;
-            }
-          }
-        } else {
-          let mut app = self.app.lock().await;
+    match outcome {
+      LookupOutcome::Found(fetched) => {
+        let (lines, synced) = lyrics::parse_lyrics(&fetched.text);
+        if lines.is_empty() {
           app.lyrics_status = LyricsStatus::NotFound;
+        } else {
+          app.lyrics = Some(lines);
+          app.lyrics_synced = synced;
+          app.lyrics_source = Some(fetched.source);
+          app.lyrics_offset_ms = cache.offset_ms(&track_id);
+          app.lyrics_status = LyricsStatus::Found;
         }
       }
-      Err(_) => {
-        let mut app = self.app.lock().await;
+      LookupOutcome::NotFound | LookupOutcome::Failed => {
         app.lyrics_status = LyricsStatus::NotFound;
       }
     }
   }
 
+  async fn search_lyrics(&mut self, track: String, artist: String) {
+    let base_url = self.app.lock().await.user_config.lyrics.lrclib_url.clone();
+    let result = LrclibProvider::new(&base_url).search(&track, &artist).await;
+
+    let mut app = self.app.lock().await;
+    let search = &mut app.lyrics_search;
+    search.searching = false;
+    // Superseded by an edited query
+    if search.title != track || search.artist != artist {
+      return;
+    }
+    search.selected_index = 0;
+    match result {
+      Ok(mut records) => {
+        // Closest duration first; that is almost always the recording being played
+        let duration = search.duration_secs;
+        records.sort_by(|a, b| {
+          (a.duration - duration)
+            .abs()
+            .total_cmp(&(b.duration - duration).abs())
+        });
+        search.results = records;
+      }
+      Err(e) => {
+        search.results.clear();
+        search.error = Some(e.to_string());
+      }
+    }
+  }
+
   async fn increment_global_song_count(&mut self) {
     let client = reqwest::Client::new();
     // Fire and forget
@@ -244,4 +224,27 @@ impl UtilsNetwork for Network {
       }
     }
   }
+
+  #[cfg(feature = "cover-art")]
+  async fn get_cover_thumbnail(&mut self, url: String) {
+    use crate::infra::cover_cache::CoverCache;
+    use crate::tui::cover_art::fetch_cover;
+
+    let cache_mb = self
+      .app
+      .lock()
+      .await
+      .user_config
+      .behavior
+      .cover_art_cache_mb;
+    // Downloaded without holding the app lock; the list stays responsive meanwhile
+    let image = match fetch_cover(&url, &CoverCache::new(cache_mb)).await {
+      Ok(image) => Some(image),
+      Err(e) => {
+        log::debug!("failed to load cover thumbnail {}: {}", url, e);
+        None
+      }
+    };
+    self.app.lock().await.cover_art.set_thumbnail(url, image);
+  }
 }
@@ -0,0 +1,207 @@
+use crate::core::user_config::NotificationMethod;
+use log::{debug, info};
+use std::io::{stdout, Write};
+use std::path::PathBuf;
+
+/// What caused a notification. Each kind can be toggled independently in the behavior config.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NotificationKind {
+  TrackChange,
+  Liked,
+  Error,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Notification {
+  pub kind: NotificationKind,
+  pub title: String,
+  pub body: String,
+  /// Cover art URL, only used by desktop notifications
+  pub image_url: Option<String>,
+}
+
+impl Notification {
+  pub fn new(kind: NotificationKind, title: impl Into<String>, body: impl Into<String>) -> Self {
+    Self {
+      kind,
+      title: title.into(),
+      body: body.into(),
+      image_url: None,
+    }
+  }
+
+  pub fn with_image_url(mut self, image_url: Option<String>) -> Self {
+    self.image_url = image_url;
+    self
+  }
+}
+
+/// Sends notifications through the configured method.
+///
+/// Terminal methods (OSC 9, OSC 777 and the bell) write straight to stdout, so `notify` must be
+/// called from the UI loop between draws. Desktop notifications are handed off to a background
+/// task since they need to download the cover and talk to D-Bus.
+pub struct NotificationManager {
+  method: NotificationMethod,
+  in_tmux: bool,
+}
+
+impl NotificationManager {
+  pub fn new(method: NotificationMethod) -> Self {
+    let in_tmux = std::env::var_os("TMUX").is_some();
+    info!(
+      "notification manager initialized with {} (tmux passthrough: {})",
+      method.name(),
+      in_tmux
+    );
+    Self { method, in_tmux }
+  }
+
+  pub fn set_method(&mut self, method: NotificationMethod) {
+    self.method = method;
+  }
+
+  pub fn notify(&self, notification: &Notification) {
+    match self.method {
+      NotificationMethod::Osc9 => {
+        let message = if notification.body.is_empty() {
+          notification.title.clone()
+        } else {
+          format!("{}: {}", notification.title, notification.body)
+        };
+        self.write_terminal(&format!("\x1b]9;{}\x07", sanitize(&message)));
+      }
+      NotificationMethod::Osc777 => {
+        self.write_terminal(&format!(
+          "\x1b]777;notify;{};{}\x07",
+          sanitize(&notification.title).replace(';', ","),
+          sanitize(&notification.body)
+        ));
+      }
+      NotificationMethod::Bell => {
+        // The bell needs no passthrough: tmux flags the window on its own
+        let mut out = stdout();
+        let _ = out.write_all(b"\x07");
+        let _ = out.flush();
+      }
+      NotificationMethod::Desktop => {
+        send_desktop_notification(notification.clone());
+      }
+    }
+  }
+
+  fn write_terminal(&self, sequence: &str) {
+    let payload = if self.in_tmux {
+      wrap_tmux_passthrough(sequence)
+    } else {
+      sequence.to_string()
+    };
+
+    let mut out = stdout();
+    let _ = out.write_all(payload.as_bytes());
+    let _ = out.flush();
+  }
+}
+
+/// Strip control characters so track metadata can't terminate or inject escape sequences
+fn sanitize(text: &str) -> String {
+  text.chars().filter(|c| !c.is_control()).collect()
+}
+
+/// tmux swallows OSC sequences unless they are wrapped in a DCS passthrough with every ESC doubled.
+/// Requires `set -g allow-passthrough on` in tmux 3.3+.
+fn wrap_tmux_passthrough(sequence: &str) -> String {
+  format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+}
+
+fn cover_cache_path() -> Option<PathBuf> {
+  dirs::cache_dir().map(|dir| dir.join("spotatui").join("notification-cover.jpg"))
+}
+
+async fn download_cover(url: &str) -> Option<PathBuf> {
+  let path = cover_cache_path()?;
+  let bytes = reqwest::get(url)
+    .await
+    .and_then(|res| res.error_for_status())
+    .ok()?
+    .bytes()
+    .await
+    .ok()?;
+
+  if let Some(parent) = path.parent() {
+    tokio::fs::create_dir_all(parent).await.ok()?;
+  }
+  tokio::fs::write(&path, &bytes).await.ok()?;
+  Some(path)
+}
+
+fn send_desktop_notification(notification: Notification) {
+  tokio::spawn(async move {
+    let icon = match notification.image_url.as_deref() {
+      Some(url) => download_cover(url).await,
+      None => None,
+    };
+
+    let result = tokio::task::spawn_blocking(move || show_desktop(&notification, icon)).await;
+    if let Ok(Err(e)) = result {
+      debug!("failed to show desktop notification: {}", e);
+    }
+  });
+}
+
+#[cfg(all(feature = "desktop-notifications", target_os = "linux"))]
+fn show_desktop(notification: &Notification, icon: Option<PathBuf>) -> anyhow::Result<()> {
+  let mut desktop = notify_rust::Notification::new();
+  desktop
+    .appname("spotatui")
+    .summary(&notification.title)
+    .body(&notification.body);
+
+  match icon {
+    Some(path) => desktop.icon(&path.to_string_lossy()),
+    None => desktop.icon("audio-x-generic"),
+  };
+
+  desktop.show()?;
+  Ok(())
+}
+
+/// Without the D-Bus client compiled in, fall back to `notify-send`, which ships with libnotify
+/// on most desktops and talks to the same freedesktop notification service.
+#[cfg(not(all(feature = "desktop-notifications", target_os = "linux")))]
+fn show_desktop(notification: &Notification, icon: Option<PathBuf>) -> anyhow::Result<()> {
+  let mut command = std::process::Command::new("notify-send");
+  command.arg("--app-name=spotatui");
+  if let Some(path) = icon {
+    command.arg(format!("--icon={}", path.display()));
+  }
+  command.arg(&notification.title).arg(&notification.body);
+
+  // Keep its output away from the TUI
+  let status = command
+    .stdout(std::process::Stdio::null())
+    .stderr(std::process::Stdio::null())
+    .status()?;
+  if !status.success() {
+    return Err(anyhow::anyhow!("notify-send exited with {}", status));
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn tmux_passthrough_doubles_escapes() {
+    assert_eq!(
+      wrap_tmux_passthrough("\x1b]9;hi\x07"),
+      "\x1bPtmux;\x1b\x1b]9;hi\x07\x1b\\"
+    );
+  }
+
+  #[test]
+  fn sanitize_strips_control_characters() {
+    assert_eq!(sanitize("a\x07b\x1b]c\n"), "ab]c");
+  }
+}
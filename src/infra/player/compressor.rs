@@ -0,0 +1,104 @@
+//! "Night mode": a gentle downward compressor with makeup gain, so quiet passages stay audible
+//! without loud ones jumping out at low listening levels
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+const THRESHOLD_DB: f64 = -24.0;
+const RATIO: f64 = 4.0;
+const MAKEUP_DB: f64 = 9.0;
+const ATTACK_MS: f64 = 10.0;
+const RELEASE_MS: f64 = 250.0;
+
+/// One-pole smoothing coefficient for a time constant in milliseconds
+fn time_coefficient(ms: f64, sample_rate: f64) -> f64 {
+  (-1.0 / (ms / 1000.0 * sample_rate)).exp()
+}
+
+fn db_to_gain(db: f64) -> f64 {
+  10f64.powf(db / 20.0)
+}
+
+/// Compressor state for the sink thread; toggled through a shared flag
+pub struct Compressor {
+  enabled: Arc<AtomicBool>,
+  channels: usize,
+  attack_cf: f64,
+  release_cf: f64,
+  /// Current gain reduction in dB (positive means quieter)
+  reduction_db: f64,
+}
+
+impl Compressor {
+  pub fn new(enabled: Arc<AtomicBool>, sample_rate: u32, channels: usize) -> Self {
+    let sample_rate = f64::from(sample_rate);
+    Self {
+      enabled,
+      channels: channels.max(1),
+      attack_cf: time_coefficient(ATTACK_MS, sample_rate),
+      release_cf: time_coefficient(RELEASE_MS, sample_rate),
+      reduction_db: 0.0,
+    }
+  }
+
+  /// Compress interleaved samples in place
+  pub fn process(&mut self, samples: &mut [f64]) {
+    if !self.enabled.load(Ordering::Relaxed) {
+      self.reduction_db = 0.0;
+      return;
+    }
+
+    for frame in samples.chunks_mut(self.channels) {
+      // Link the channels so the stereo image doesn't shift
+      let peak = frame.iter().fold(0.0f64, |peak, s| peak.max(s.abs()));
+      let level_db = 20.0 * peak.max(1e-9).log10();
+      let target_db = (level_db - THRESHOLD_DB).max(0.0) * (1.0 - 1.0 / RATIO);
+
+      let cf = if target_db > self.reduction_db {
+        self.attack_cf
+      } else {
+        self.release_cf
+      };
+      self.reduction_db = cf * self.reduction_db + (1.0 - cf) * target_db;
+
+      let gain = db_to_gain(MAKEUP_DB - self.reduction_db);
+      for sample in frame.iter_mut() {
+        *sample = (*sample * gain).clamp(-1.0, 1.0);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn settled_peak(compressor: &mut Compressor, amplitude: f64) -> f64 {
+    let mut samples: Vec<f64> = (0..44_100)
+      .map(|i| (2.0 * std::f64::consts::PI * 440.0 * i as f64 / 44_100.0).sin() * amplitude)
+      .collect();
+    compressor.process(&mut samples);
+    samples[22_050..]
+      .iter()
+      .fold(0.0f64, |peak, s| peak.max(s.abs()))
+  }
+
+  #[test]
+  fn disabled_is_a_no_op() {
+    let mut compressor = Compressor::new(Arc::new(AtomicBool::new(false)), 44_100, 1);
+    let mut samples = vec![0.9, -0.01, 0.5];
+    compressor.process(&mut samples);
+    assert_eq!(samples, vec![0.9, -0.01, 0.5]);
+  }
+
+  #[test]
+  fn narrows_the_gap_between_loud_and_quiet() {
+    let enabled = Arc::new(AtomicBool::new(true));
+    let loud = settled_peak(&mut Compressor::new(enabled.clone(), 44_100, 1), 0.9);
+    let quiet = settled_peak(&mut Compressor::new(enabled, 44_100, 1), 0.02);
+
+    // 0.9 vs 0.02 is a 45x (33 dB) difference going in
+    assert!(loud / quiet < 15.0, "loud {} quiet {}", loud, quiet);
+    assert!(quiet > 0.02);
+  }
+}
@@ -0,0 +1,258 @@
+//! Graphic equalizer applied to decoded samples before they reach the audio backend
+//!
+//! Each band is an RBJ "Audio EQ Cookbook" biquad: a low shelf for the lowest band, a high shelf
+//! for the highest and peaking filters in between.
+
+use crate::core::user_config::{EQ_BAND_COUNT, EQ_BAND_FREQUENCIES};
+use std::f64::consts::PI;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Bandwidth of the peaking bands; roughly one octave, matching the band spacing
+const PEAKING_Q: f64 = std::f64::consts::SQRT_2;
+/// Shelf slope (1.0 is the steepest slope without overshoot)
+const SHELF_SLOPE: f64 = 1.0;
+
+#[derive(Clone, Copy, Debug)]
+enum FilterKind {
+  LowShelf,
+  Peaking,
+  HighShelf,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Coefficients {
+  b0: f64,
+  b1: f64,
+  b2: f64,
+  a1: f64,
+  a2: f64,
+}
+
+impl Coefficients {
+  fn new(kind: FilterKind, frequency: f64, gain_db: f64, sample_rate: f64) -> Self {
+    let a = 10f64.powf(gain_db / 40.0);
+    // Keep the centre frequency below Nyquist for low sample rates
+    let w0 = 2.0 * PI * frequency.min(sample_rate * 0.45) / sample_rate;
+    let (sin_w0, cos_w0) = w0.sin_cos();
+
+    let (b0, b1, b2, a0, a1, a2) = match kind {
+      FilterKind::Peaking => {
+        let alpha = sin_w0 / (2.0 * PEAKING_Q);
+        (
+          1.0 + alpha * a,
+          -2.0 * cos_w0,
+          1.0 - alpha * a,
+          1.0 + alpha / a,
+          -2.0 * cos_w0,
+          1.0 - alpha / a,
+        )
+      }
+      FilterKind::LowShelf | FilterKind::HighShelf => {
+        let alpha = sin_w0 / 2.0 * ((a + 1.0 / a) * (1.0 / SHELF_SLOPE - 1.0) + 2.0).sqrt();
+        let two_sqrt_a_alpha = 2.0 * a.sqrt() * alpha;
+        if matches!(kind, FilterKind::LowShelf) {
+          (
+            a * ((a + 1.0) - (a - 1.0) * cos_w0 + two_sqrt_a_alpha),
+            2.0 * a * ((a - 1.0) - (a + 1.0) * cos_w0),
+            a * ((a + 1.0) - (a - 1.0) * cos_w0 - two_sqrt_a_alpha),
+            (a + 1.0) + (a - 1.0) * cos_w0 + two_sqrt_a_alpha,
+            -2.0 * ((a - 1.0) + (a + 1.0) * cos_w0),
+            (a + 1.0) + (a - 1.0) * cos_w0 - two_sqrt_a_alpha,
+          )
+        } else {
+          (
+            a * ((a + 1.0) + (a - 1.0) * cos_w0 + two_sqrt_a_alpha),
+            -2.0 * a * ((a - 1.0) + (a + 1.0) * cos_w0),
+            a * ((a + 1.0) + (a - 1.0) * cos_w0 - two_sqrt_a_alpha),
+            (a + 1.0) - (a - 1.0) * cos_w0 + two_sqrt_a_alpha,
+            2.0 * ((a - 1.0) - (a + 1.0) * cos_w0),
+            (a + 1.0) - (a - 1.0) * cos_w0 - two_sqrt_a_alpha,
+          )
+        }
+      }
+    };
+
+    Self {
+      b0: b0 / a0,
+      b1: b1 / a0,
+      b2: b2 / a0,
+      a1: a1 / a0,
+      a2: a2 / a0,
+    }
+  }
+}
+
+/// Filter memory for one channel of one band (transposed direct form II)
+#[derive(Clone, Copy, Debug, Default)]
+struct BiquadState {
+  z1: f64,
+  z2: f64,
+}
+
+impl BiquadState {
+  fn process(&mut self, c: &Coefficients, input: f64) -> f64 {
+    let output = c.b0 * input + self.z1;
+    self.z1 = c.b1 * input - c.a1 * output + self.z2;
+    self.z2 = c.b2 * input - c.a2 * output;
+    output
+  }
+}
+
+/// Equalizer parameters shared between the UI and the sink thread
+#[derive(Clone, Default)]
+pub struct EqualizerHandle {
+  gains: Arc<Mutex<Option<[f32; EQ_BAND_COUNT]>>>,
+  generation: Arc<AtomicU64>,
+}
+
+impl EqualizerHandle {
+  pub fn new(gains: Option<[f32; EQ_BAND_COUNT]>) -> Self {
+    Self {
+      gains: Arc::new(Mutex::new(gains)),
+      generation: Arc::new(AtomicU64::new(1)),
+    }
+  }
+
+  /// Set band gains in dB, or `None` to bypass. Takes effect on the next audio packet.
+  pub fn set_gains(&self, gains: Option<[f32; EQ_BAND_COUNT]>) {
+    if let Ok(mut current) = self.gains.lock() {
+      *current = gains;
+    }
+    self.generation.fetch_add(1, Ordering::Release);
+  }
+
+  fn snapshot(&self) -> Option<[f32; EQ_BAND_COUNT]> {
+    self.gains.lock().ok().and_then(|gains| *gains)
+  }
+}
+
+/// Per-sink equalizer state. Lives on the audio thread; picks up changes from its handle.
+pub struct Equalizer {
+  handle: EqualizerHandle,
+  seen_generation: u64,
+  sample_rate: f64,
+  /// `None` while bypassed
+  coefficients: Option<[Coefficients; EQ_BAND_COUNT]>,
+  /// Headroom so boosted bands don't clip
+  pregain: f64,
+  states: Vec<[BiquadState; EQ_BAND_COUNT]>,
+}
+
+impl Equalizer {
+  pub fn new(handle: EqualizerHandle, sample_rate: u32, channels: usize) -> Self {
+    Self {
+      handle,
+      seen_generation: 0,
+      sample_rate: f64::from(sample_rate),
+      coefficients: None,
+      pregain: 1.0,
+      states: vec![[BiquadState::default(); EQ_BAND_COUNT]; channels.max(1)],
+    }
+  }
+
+  fn refresh(&mut self) {
+    let generation = self.handle.generation.load(Ordering::Acquire);
+    if generation == self.seen_generation {
+      return;
+    }
+    self.seen_generation = generation;
+
+    let gains = self
+      .handle
+      .snapshot()
+      .filter(|gains| gains.iter().any(|gain| *gain != 0.0));
+
+    self.coefficients = gains.map(|gains| {
+      std::array::from_fn(|band| {
+        let kind = match band {
+          0 => FilterKind::LowShelf,
+          b if b == EQ_BAND_COUNT - 1 => FilterKind::HighShelf,
+          _ => FilterKind::Peaking,
+        };
+        Coefficients::new(
+          kind,
+          f64::from(EQ_BAND_FREQUENCIES[band]),
+          f64::from(gains[band]),
+          self.sample_rate,
+        )
+      })
+    });
+
+    let max_boost = gains
+      .map(|gains| gains.iter().cloned().fold(0.0f32, f32::max))
+      .unwrap_or(0.0);
+    self.pregain = 10f64.powf(-f64::from(max_boost) / 20.0);
+  }
+
+  /// Filter interleaved samples in place
+  pub fn process(&mut self, samples: &mut [f64]) {
+    self.refresh();
+    let Some(coefficients) = self.coefficients.as_ref() else {
+      return;
+    };
+
+    let channels = self.states.len();
+    for frame in samples.chunks_mut(channels) {
+      for (sample, state) in frame.iter_mut().zip(self.states.iter_mut()) {
+        let mut value = *sample * self.pregain;
+        for (band, coefficient) in state.iter_mut().zip(coefficients.iter()) {
+          value = band.process(coefficient, value);
+        }
+        *sample = value.clamp(-1.0, 1.0);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn run_sine(equalizer: &mut Equalizer, frequency: f64) -> f64 {
+    let sample_rate = 44_100.0;
+    let mut peak = 0.0f64;
+    let mut samples: Vec<f64> = (0..44_100)
+      .map(|i| (2.0 * PI * frequency * i as f64 / sample_rate).sin() * 0.25)
+      .collect();
+    equalizer.process(&mut samples);
+    // Skip the filter's settling time
+    for sample in &samples[22_050..] {
+      peak = peak.max(sample.abs());
+    }
+    peak
+  }
+
+  #[test]
+  fn bypass_leaves_samples_untouched() {
+    let mut equalizer = Equalizer::new(EqualizerHandle::new(None), 44_100, 1);
+    let mut samples = vec![0.1, -0.2, 0.3];
+    equalizer.process(&mut samples);
+    assert_eq!(samples, vec![0.1, -0.2, 0.3]);
+  }
+
+  #[test]
+  fn cut_band_attenuates_its_frequency() {
+    let mut gains = [0.0; EQ_BAND_COUNT];
+    gains[5] = -12.0; // 1 kHz
+    let mut equalizer = Equalizer::new(EqualizerHandle::new(Some(gains)), 44_100, 1);
+
+    let peak = run_sine(&mut equalizer, 1000.0);
+    // -12 dB is a factor of ~0.25
+    assert!(peak < 0.25 * 0.3, "peak was {}", peak);
+  }
+
+  #[test]
+  fn handle_changes_apply_live() {
+    let handle = EqualizerHandle::new(None);
+    let mut equalizer = Equalizer::new(handle.clone(), 44_100, 1);
+    let flat_peak = run_sine(&mut equalizer, 1000.0);
+
+    let mut gains = [0.0; EQ_BAND_COUNT];
+    gains[5] = -12.0;
+    handle.set_gains(Some(gains));
+    let cut_peak = run_sine(&mut equalizer, 1000.0);
+
+    assert!(cut_peak < flat_peak * 0.5);
+  }
+}
@@ -3,8 +3,16 @@
 //! This module provides native audio playback capabilities using the librespot library.
 //! It registers spotatui as a Spotify Connect device and handles audio streaming.
 
+#[cfg(feature = "streaming")]
+mod compressor;
+#[cfg(feature = "streaming")]
+mod equalizer;
+#[cfg(feature = "streaming")]
+mod output;
 #[cfg(feature = "streaming")]
 mod streaming;
 
+#[cfg(feature = "streaming")]
+pub use output::list_audio_outputs;
 #[cfg(feature = "streaming")]
 pub use streaming::*;
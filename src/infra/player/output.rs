@@ -0,0 +1,210 @@
+//! Local audio output selection for the native player
+//!
+//! librespot has no API for enumerating devices, so each backend is asked the same way its own
+//! tooling would: `aplay -L` for ALSA, `pactl` for PulseAudio/PipeWire and cpal for rodio.
+
+use crate::core::app::AudioOutputChoice;
+use anyhow::{anyhow, Result};
+use librespot_playback::{audio_backend, config::AudioFormat};
+use log::warn;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Backend and device to open; `None` means librespot's default for either
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AudioOutput {
+  pub backend: Option<String>,
+  pub device: Option<String>,
+}
+
+/// Output requested by the UI, picked up by the sink on its next start or write
+#[derive(Clone, Default)]
+pub struct OutputHandle {
+  target: Arc<Mutex<AudioOutput>>,
+  generation: Arc<AtomicU64>,
+}
+
+impl OutputHandle {
+  pub fn new(output: AudioOutput) -> Self {
+    Self {
+      target: Arc::new(Mutex::new(output)),
+      generation: Arc::new(AtomicU64::new(0)),
+    }
+  }
+
+  pub fn set(&self, output: AudioOutput) {
+    if let Ok(mut target) = self.target.lock() {
+      *target = output;
+    }
+    self.generation.fetch_add(1, Ordering::Release);
+  }
+
+  pub fn current(&self) -> AudioOutput {
+    self
+      .target
+      .lock()
+      .map(|target| target.clone())
+      .unwrap_or_default()
+  }
+
+  pub fn generation(&self) -> u64 {
+    self.generation.load(Ordering::Acquire)
+  }
+}
+
+/// Build a sink for `output`, falling back to the default backend if the configured one
+/// isn't compiled in (e.g. a config copied from another machine)
+pub fn open_sink(output: &AudioOutput) -> Result<Box<dyn audio_backend::Sink>> {
+  let builder = match audio_backend::find(output.backend.clone()) {
+    Some(builder) => builder,
+    None => {
+      warn!(
+        "audio backend {:?} is not available, using the default",
+        output.backend
+      );
+      audio_backend::find(None).ok_or_else(|| anyhow!("No audio backend available"))?
+    }
+  };
+
+  let device = output.device.clone();
+  std::panic::catch_unwind(move || builder(device, AudioFormat::default())).map_err(|_| {
+    anyhow!(
+      "Failed to open audio device {}",
+      output.device.as_deref().unwrap_or("default")
+    )
+  })
+}
+
+/// Every output of every compiled backend, each backend's default device first
+pub fn list_audio_outputs() -> Vec<AudioOutputChoice> {
+  let mut choices = Vec::new();
+  for (backend, _) in audio_backend::BACKENDS {
+    choices.push(AudioOutputChoice {
+      backend: backend.to_string(),
+      device: None,
+      label: format!("{}: default device", backend),
+    });
+    for (device, description) in list_devices(backend) {
+      let label = if description.is_empty() || description == device {
+        format!("{}: {}", backend, device)
+      } else {
+        format!("{}: {} ({})", backend, description, device)
+      };
+      choices.push(AudioOutputChoice {
+        backend: backend.to_string(),
+        device: Some(device),
+        label,
+      });
+    }
+  }
+  choices
+}
+
+/// `(device, description)` pairs for one backend
+fn list_devices(backend: &str) -> Vec<(String, String)> {
+  match backend {
+    "alsa" => command_output("aplay", &["-L"])
+      .map(|out| parse_aplay_list(&out))
+      .unwrap_or_default(),
+    // Also covers PipeWire through pipewire-pulse
+    "pulseaudio" => command_output("pactl", &["list", "short", "sinks"])
+      .map(|out| parse_pactl_sinks(&out))
+      .unwrap_or_default(),
+    #[cfg(feature = "audio-viz-cpal")]
+    "rodio" => list_cpal_devices(),
+    _ => Vec::new(),
+  }
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+  let output = Command::new(program)
+    .args(args)
+    .stdin(Stdio::null())
+    .stderr(Stdio::null())
+    .output()
+    .ok()?;
+  output
+    .status
+    .success()
+    .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// `aplay -L` prints each PCM name unindented, followed by indented description lines
+fn parse_aplay_list(output: &str) -> Vec<(String, String)> {
+  let mut devices: Vec<(String, String)> = Vec::new();
+  for line in output.lines() {
+    if line.trim().is_empty() {
+      continue;
+    }
+    if line.starts_with(char::is_whitespace) {
+      if let Some((_, description)) = devices.last_mut() {
+        if description.is_empty() {
+          *description = line.trim().to_string();
+        }
+      }
+    } else {
+      devices.push((line.trim().to_string(), String::new()));
+    }
+  }
+  devices.retain(|(name, _)| name != "null" && name != "default");
+  devices
+}
+
+/// `pactl list short sinks` columns: index, name, driver, sample spec, state
+fn parse_pactl_sinks(output: &str) -> Vec<(String, String)> {
+  output
+    .lines()
+    .filter_map(|line| line.split('\t').nth(1))
+    .map(|name| (name.to_string(), String::new()))
+    .collect()
+}
+
+#[cfg(feature = "audio-viz-cpal")]
+fn list_cpal_devices() -> Vec<(String, String)> {
+  use cpal::traits::{DeviceTrait, HostTrait};
+
+  let Ok(devices) = cpal::default_host().output_devices() else {
+    return Vec::new();
+  };
+  devices
+    .filter_map(|device| device.description().ok())
+    .map(|description| (description.name().to_string(), String::new()))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_aplay_device_list() {
+    let output = "null\n    Discard all samples (playback) or generate zero samples (capture)\n\
+default\n    Default ALSA Output (currently PipeWire Media Server)\n\
+hw:CARD=DAC,DEV=0\n    USB Audio DAC, USB Audio\n    Direct hardware device without any conversions\n";
+    assert_eq!(
+      parse_aplay_list(output),
+      vec![(
+        "hw:CARD=DAC,DEV=0".to_string(),
+        "USB Audio DAC, USB Audio".to_string()
+      )]
+    );
+  }
+
+  #[test]
+  fn parses_pactl_sink_names() {
+    let output = "47\talsa_output.usb-DAC.analog-stereo\tPipeWire\ts24le 2ch 48000Hz\tSUSPENDED\n\
+52\talsa_output.pci-0000_00_1f.3.analog-stereo\tPipeWire\ts32le 2ch 48000Hz\tRUNNING\n";
+    let names: Vec<String> = parse_pactl_sinks(output)
+      .into_iter()
+      .map(|(name, _)| name)
+      .collect();
+    assert_eq!(
+      names,
+      vec![
+        "alsa_output.usb-DAC.analog-stereo",
+        "alsa_output.pci-0000_00_1f.3.analog-stereo"
+      ]
+    );
+  }
+}
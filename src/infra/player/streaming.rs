@@ -2,6 +2,12 @@
 //!
 //! Handles authentication, session management, and audio playback with Spotify Connect.
 
+use super::compressor::Compressor;
+use super::equalizer::{Equalizer, EqualizerHandle};
+use super::output::{open_sink, AudioOutput, OutputHandle};
+use crate::core::config::NormalisationMode;
+use crate::core::user_config::EQ_BAND_COUNT;
+use crate::infra::audio::PlaybackTap;
 use anyhow::{anyhow, Context, Result};
 use librespot_connect::{ConnectConfig, LoadRequest, Spirc};
 use librespot_core::{
@@ -15,14 +21,16 @@ use librespot_core::{
 use librespot_oauth::OAuthClientBuilder;
 use librespot_playback::{
   audio_backend,
-  config::{AudioFormat, PlayerConfig},
+  config::{AudioFormat, NormalisationType, PlayerConfig},
   convert::Converter,
   decoder::AudioPacket,
   mixer::{softmixer::SoftMixer, Mixer, MixerConfig},
-  player::{Player, PlayerEventChannel},
+  player::{duration_to_coefficient, Player, PlayerEventChannel},
+  NUM_CHANNELS, SAMPLE_RATE,
 };
-use log::info;
+use log::{info, warn};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio::time::{timeout, Duration};
@@ -42,6 +50,80 @@ impl audio_backend::Sink for NullSink {
   }
 }
 
+/// Sits in front of the real sink: runs the equalizer and night mode compressor on decoded
+/// samples, then hands a copy of what will actually be heard to the visualizer.
+///
+/// Also owns the real sink, so switching outputs only swaps `inner` and librespot's `Player`
+/// (and with it the Connect session) keeps running.
+struct ProcessingSink {
+  inner: Box<dyn audio_backend::Sink>,
+  output: OutputHandle,
+  output_generation: u64,
+  running: bool,
+  equalizer: Equalizer,
+  compressor: Compressor,
+  tap: PlaybackTap,
+}
+
+impl ProcessingSink {
+  fn switch_output_if_requested(&mut self) {
+    let generation = self.output.generation();
+    if generation == self.output_generation {
+      return;
+    }
+    self.output_generation = generation;
+
+    let target = self.output.current();
+    let mut sink = match open_sink(&target) {
+      Ok(sink) => sink,
+      Err(e) => {
+        warn!("keeping current audio output: {}", e);
+        return;
+      }
+    };
+
+    if self.running {
+      // Release the old device first; some ALSA hardware devices only allow one client
+      let _ = self.inner.stop();
+      if let Err(e) = sink.start() {
+        warn!("failed to start audio output {:?}: {}", target, e);
+        let _ = self.inner.start();
+        return;
+      }
+    }
+    self.inner = sink;
+    info!("switched audio output to {:?}", target);
+  }
+}
+
+impl audio_backend::Sink for ProcessingSink {
+  fn start(&mut self) -> audio_backend::SinkResult<()> {
+    self.switch_output_if_requested();
+    self.inner.start()?;
+    self.running = true;
+    Ok(())
+  }
+
+  fn stop(&mut self) -> audio_backend::SinkResult<()> {
+    self.running = false;
+    self.inner.stop()
+  }
+
+  fn write(
+    &mut self,
+    mut packet: AudioPacket,
+    converter: &mut Converter,
+  ) -> audio_backend::SinkResult<()> {
+    self.switch_output_if_requested();
+    if let AudioPacket::Samples(ref mut samples) = packet {
+      self.equalizer.process(samples);
+      self.compressor.process(samples);
+      self.tap.feed(samples, NUM_CHANNELS as usize);
+    }
+    self.inner.write(packet, converter)
+  }
+}
+
 /// OAuth scopes required for streaming (based on spotify-player)
 const STREAMING_SCOPES: [&str; 6] = [
   "streaming",
@@ -120,6 +202,21 @@ pub struct StreamingConfig {
   pub cache_path: Option<PathBuf>,
   /// Initial volume (0-100)
   pub initial_volume: u8,
+  /// librespot audio backend, `None` for the first compiled one
+  pub audio_backend: Option<String>,
+  /// Backend-specific output device, `None` for the backend's default
+  pub audio_device: Option<String>,
+  /// Equalizer band gains in dB, `None` to bypass
+  pub equalizer_gains: Option<[f32; EQ_BAND_COUNT]>,
+  /// Start with the night mode compressor on
+  pub night_mode: bool,
+  /// librespot volume normalisation
+  pub normalisation: bool,
+  pub normalisation_mode: NormalisationMode,
+  pub normalisation_pregain_db: f64,
+  pub normalisation_threshold_dbfs: f64,
+  pub normalisation_attack_ms: u64,
+  pub normalisation_release_ms: u64,
 }
 
 impl Default for StreamingConfig {
@@ -130,6 +227,16 @@ impl Default for StreamingConfig {
       audio_cache: false,
       cache_path: None,
       initial_volume: 100,
+      audio_backend: None,
+      audio_device: None,
+      equalizer_gains: None,
+      night_mode: false,
+      normalisation: false,
+      normalisation_mode: NormalisationMode::default(),
+      normalisation_pregain_db: 0.0,
+      normalisation_threshold_dbfs: -2.0,
+      normalisation_attack_ms: 5,
+      normalisation_release_ms: 100,
     }
   }
 }
@@ -161,6 +268,10 @@ pub struct StreamingPlayer {
   config: StreamingConfig,
   #[allow(dead_code)]
   state: Arc<Mutex<PlayerState>>,
+  tap: PlaybackTap,
+  equalizer: EqualizerHandle,
+  night_mode: Arc<AtomicBool>,
+  output: OutputHandle,
 }
 
 #[allow(dead_code)]
@@ -225,6 +336,20 @@ impl StreamingPlayer {
       },
       // Enable periodic position updates for real-time playbar progress
       position_update_interval: Some(std::time::Duration::from_secs(1)),
+      normalisation: config.normalisation,
+      normalisation_type: match config.normalisation_mode {
+        NormalisationMode::Auto => NormalisationType::Auto,
+        NormalisationMode::Track => NormalisationType::Track,
+        NormalisationMode::Album => NormalisationType::Album,
+      },
+      normalisation_pregain_db: config.normalisation_pregain_db,
+      normalisation_threshold_dbfs: config.normalisation_threshold_dbfs,
+      normalisation_attack_cf: duration_to_coefficient(Duration::from_millis(
+        config.normalisation_attack_ms.max(1),
+      )),
+      normalisation_release_cf: duration_to_coefficient(Duration::from_millis(
+        config.normalisation_release_ms.max(1),
+      )),
       ..Default::default()
     };
 
@@ -236,23 +361,36 @@ impl StreamingPlayer {
     let volume_u16 = (f64::from(config.initial_volume.min(100)) / 100.0 * 65535.0).round() as u16;
     mixer.set_volume(volume_u16);
 
+    // Environment variables take precedence over the output picked in the UI
     let requested_backend = std::env::var("SPOTATUI_STREAMING_AUDIO_BACKEND").ok();
     let requested_device = std::env::var("SPOTATUI_STREAMING_AUDIO_DEVICE").ok();
 
-    // Create audio backend
-    let backend =
-      audio_backend::find(requested_backend.clone()).ok_or_else(|| match requested_backend {
-        Some(name) => anyhow!(
-          "Unknown audio backend '{}'. Available backends: {}",
-          name,
-          audio_backend::BACKENDS
-            .iter()
-            .map(|(n, _)| *n)
-            .collect::<Vec<_>>()
-            .join(", ")
-        ),
-        None => anyhow!("No audio backend available"),
-      })?;
+    // Check the audio backend up front so a typo in the environment fails loudly
+    audio_backend::find(requested_backend.clone()).ok_or_else(|| match &requested_backend {
+      Some(name) => anyhow!(
+        "Unknown audio backend '{}'. Available backends: {}",
+        name,
+        audio_backend::BACKENDS
+          .iter()
+          .map(|(n, _)| *n)
+          .collect::<Vec<_>>()
+          .join(", ")
+      ),
+      None => anyhow!("No audio backend available"),
+    })?;
+
+    let output = OutputHandle::new(AudioOutput {
+      backend: requested_backend.or_else(|| config.audio_backend.clone()),
+      device: requested_device.or_else(|| config.audio_device.clone()),
+    });
+    let sink_output = output.clone();
+
+    let tap = PlaybackTap::new();
+    let sink_tap = tap.clone();
+    let equalizer = EqualizerHandle::new(config.equalizer_gains);
+    let sink_equalizer = equalizer.clone();
+    let night_mode = Arc::new(AtomicBool::new(config.night_mode));
+    let sink_night_mode = night_mode.clone();
 
     // Create player
     let player = Player::new(
@@ -260,9 +398,8 @@ impl StreamingPlayer {
       session.clone(),
       mixer.get_soft_volume(),
       move || {
-        let result =
-          std::panic::catch_unwind(|| backend(requested_device.clone(), AudioFormat::default()));
-        match result {
+        let output_generation = sink_output.generation();
+        let inner: Box<dyn audio_backend::Sink> = match open_sink(&sink_output.current()) {
           Ok(sink) => sink,
           Err(_) => {
             eprintln!(
@@ -271,7 +408,16 @@ Set SPOTATUI_STREAMING_AUDIO_DEVICE to select an output device, or SPOTATUI_STRE
             );
             Box::new(NullSink)
           }
-        }
+        };
+        Box::new(ProcessingSink {
+          inner,
+          output: sink_output,
+          output_generation,
+          running: false,
+          equalizer: Equalizer::new(sink_equalizer, SAMPLE_RATE, NUM_CHANNELS as usize),
+          compressor: Compressor::new(sink_night_mode, SAMPLE_RATE, NUM_CHANNELS as usize),
+          tap: sink_tap,
+        })
       },
     );
 
@@ -352,9 +498,38 @@ Set SPOTATUI_STREAMING_AUDIO_DEVICE to select an output device, or SPOTATUI_STRE
       mixer,
       config,
       state: Arc::new(Mutex::new(PlayerState::default())),
+      tap,
+      equalizer,
+      night_mode,
+      output,
     })
   }
 
+  /// Move playback to another local output without interrupting the Connect session
+  pub fn set_audio_output(&self, backend: Option<String>, device: Option<String>) {
+    self.output.set(AudioOutput { backend, device });
+  }
+
+  /// Backend and device currently requested for local output
+  pub fn audio_output(&self) -> AudioOutput {
+    self.output.current()
+  }
+
+  /// Switch the night mode compressor on or off while playing
+  pub fn set_night_mode(&self, enabled: bool) {
+    self.night_mode.store(enabled, Ordering::Relaxed);
+  }
+
+  /// Update equalizer band gains (dB) while playing, `None` to bypass
+  pub fn set_equalizer(&self, gains: Option<[f32; EQ_BAND_COUNT]>) {
+    self.equalizer.set_gains(gains);
+  }
+
+  /// Decoded PCM tap for the visualizer; enable it with `PlaybackTap::set_enabled`
+  pub fn playback_tap(&self) -> &PlaybackTap {
+    &self.tap
+  }
+
   /// Get the device name
   pub fn device_name(&self) -> &str {
     &self.config.device_name
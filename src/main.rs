@@ -41,6 +41,7 @@ use crate::infra::macos_media;
 #[cfg(all(feature = "mpris", target_os = "linux"))]
 use crate::infra::mpris;
 use crate::infra::network::{IoEvent, Network};
+use crate::infra::notifications::{self, Notification, NotificationKind};
 #[cfg(feature = "streaming")]
 use crate::infra::player;
 use crate::infra::redirect_uri::redirect_uri_web_server;
@@ -127,6 +128,12 @@ struct DiscordPresenceState {
   last_progress_ms: u128,
 }
 
+#[derive(Default)]
+struct NotificationState {
+  /// Title and artist of the last track seen, `None` until the first track is known
+  last_track: Option<(String, String)>,
+}
+
 #[cfg(feature = "mpris")]
 #[derive(Default, PartialEq)]
 struct MprisMetadata {
@@ -293,6 +300,91 @@ fn update_discord_presence(
   }
 }
 
+fn build_track_notification(app: &App) -> Option<((String, String), Notification)> {
+  use crate::tui::ui::util::create_artist_string;
+  use rspotify::model::PlayableItem;
+
+  let api_item = app
+    .current_playback_context
+    .as_ref()
+    .and_then(|context| context.item.as_ref())
+    .map(|item| match item {
+      PlayableItem::Track(track) => (
+        track.name.clone(),
+        create_artist_string(&track.artists),
+        track.album.name.clone(),
+        track.album.images.first().map(|image| image.url.clone()),
+      ),
+      PlayableItem::Episode(episode) => (
+        episode.name.clone(),
+        episode.show.name.clone(),
+        String::new(),
+        episode.images.first().map(|image| image.url.clone()),
+      ),
+    });
+
+  // Native track info arrives before the API catches up, so only borrow the API's cover
+  // once it describes the same track
+  let (title, artist, album, image_url) = match (&app.native_track_info, api_item) {
+    (Some(native), Some((name, _, _, image_url))) if name == native.name => (
+      native.name.clone(),
+      native.artists_display.clone(),
+      native.album.clone(),
+      image_url,
+    ),
+    (Some(native), _) => (
+      native.name.clone(),
+      native.artists_display.clone(),
+      native.album.clone(),
+      None,
+    ),
+    (None, Some(api_item)) => api_item,
+    (None, None) => return None,
+  };
+
+  let body = if album.is_empty() {
+    artist.clone()
+  } else {
+    format!("{} - {}", artist, album)
+  };
+  let notification =
+    Notification::new(NotificationKind::TrackChange, title.clone(), body).with_image_url(image_url);
+
+  Some(((title, artist), notification))
+}
+
+fn update_notifications(
+  manager: &mut notifications::NotificationManager,
+  state: &mut NotificationState,
+  app: &mut App,
+) {
+  let behavior = &app.user_config.behavior;
+  let enabled = behavior.enable_notifications;
+  manager.set_method(behavior.notification_method);
+
+  if let Some((track, notification)) = build_track_notification(app) {
+    if state.last_track.as_ref() != Some(&track) {
+      // Don't announce whatever was already playing when spotatui started
+      let is_first_track = state.last_track.is_none();
+      if enabled && behavior.notify_on_track_change && !is_first_track {
+        manager.notify(&notification);
+      }
+      state.last_track = Some(track);
+    }
+  }
+
+  for notification in std::mem::take(&mut app.pending_notifications) {
+    let wanted = match notification.kind {
+      NotificationKind::TrackChange => app.user_config.behavior.notify_on_track_change,
+      NotificationKind::Liked => app.user_config.behavior.notify_on_liked,
+      NotificationKind::Error => app.user_config.behavior.notify_on_error,
+    };
+    if enabled && wanted {
+      manager.notify(&notification);
+    }
+  }
+}
+
 #[cfg(feature = "mpris")]
 fn update_mpris_metadata(
   manager: &mpris::MprisManager,
@@ -673,6 +765,12 @@ of the app. Beware that this comes at a CPU cost!",
         .action(clap::ArgAction::SetTrue)
         .help("Rerun client authentication setup wizard"),
     )
+    .arg(
+      Arg::new("mini")
+        .long("mini")
+        .action(clap::ArgAction::SetTrue)
+        .help("Start in the mini player layout, which only shows the playbar"),
+    )
     .arg(
       Arg::new("completions")
         .long("completions")
@@ -685,6 +783,7 @@ of the app. Beware that this comes at a CPU cost!",
     .subcommand(cli::play_subcommand())
     .subcommand(cli::list_subcommand())
     .subcommand(cli::search_subcommand())
+    .subcommand(cli::keys_subcommand())
     // Self-update command
     .subcommand(
       ClapApp::new("update")
@@ -729,6 +828,16 @@ of the app. Beware that this comes at a CPU cost!",
   }
   user_config.load_config()?;
   info!("user config loaded successfully");
+
+  // Listing the keybindings only needs the config
+  if let Some(keys_matches) = matches.subcommand_matches("keys") {
+    let table = match keys_matches.get_one::<String>("format").map(String::as_str) {
+      Some("json") => ui::help::help_json(&user_config.keys)?,
+      _ => ui::help::help_markdown(&user_config.keys),
+    };
+    println!("{}", table.trim_end());
+    return Ok(());
+  }
   let initial_shuffle_enabled = user_config.behavior.shuffle_enabled;
 
   if let Some(tick_rate) = matches
@@ -738,6 +847,7 @@ of the app. Beware that this comes at a CPU cost!",
     if tick_rate >= 1000 {
       panic!("Tick rate must be below 1000");
     } else {
+      user_config.tick_rate_override = Some(tick_rate);
       user_config.behavior.tick_rate_milliseconds = tick_rate;
     }
   }
@@ -924,11 +1034,10 @@ of the app. Beware that this comes at a CPU cost!",
   info!("app state initialized");
 
   // Initialise app state
-  let app = Arc::new(Mutex::new(App::new(
-    sync_io_tx,
-    user_config.clone(),
-    token_expiry,
-  )));
+  let mut app = App::new(sync_io_tx, user_config.clone(), token_expiry);
+  app.client_config = client_config.clone();
+  app.mini_mode = matches.get_flag("mini");
+  let app = Arc::new(Mutex::new(app));
 
   // Work with the cli (not really async)
   if let Some(cmd) = matches.subcommand_name() {
@@ -946,6 +1055,8 @@ of the app. Beware that this comes at a CPU cost!",
   // Launch the UI (async)
   } else {
     info!("launching interactive terminal ui");
+    let resume_playback = app.lock().await.start_session();
+
     #[cfg(feature = "streaming")]
     let (streaming_supported_for_account, streaming_startup_status_message) =
       if client_config.enable_streaming {
@@ -970,6 +1081,16 @@ of the app. Beware that this comes at a CPU cost!",
         audio_cache: client_config.streaming_audio_cache,
         cache_path: player::get_default_cache_path(),
         initial_volume: user_config.behavior.volume_percent,
+        audio_backend: client_config.streaming_audio_backend.clone(),
+        audio_device: client_config.streaming_audio_device.clone(),
+        equalizer_gains: user_config.equalizer.active_gains(),
+        night_mode: user_config.behavior.night_mode,
+        normalisation: client_config.streaming_normalisation,
+        normalisation_mode: client_config.streaming_normalisation_mode,
+        normalisation_pregain_db: client_config.streaming_normalisation_pregain_db,
+        normalisation_threshold_dbfs: client_config.streaming_normalisation_threshold_dbfs,
+        normalisation_attack_ms: client_config.streaming_normalisation_attack_ms,
+        normalisation_release_ms: client_config.streaming_normalisation_release_ms,
       };
 
       let client_id = client_config.client_id.clone();
@@ -1288,6 +1409,12 @@ of the app. Beware that this comes at a CPU cost!",
         .handle_network_event(IoEvent::Shuffle(initial_shuffle_enabled))
         .await;
 
+      if let Some(playback) = resume_playback {
+        network
+          .handle_network_event(IoEvent::ResumeSession(playback))
+          .await;
+      }
+
       start_tokio(sync_io_rx, &mut network).await;
     });
     // The UI must run in the "main" thread
@@ -1315,6 +1442,8 @@ of the app. Beware that this comes at a CPU cost!",
     .await?;
     #[cfg(not(feature = "streaming"))]
     start_ui(user_config, &cloned_app, None, None, discord_rpc_manager).await?;
+
+    cloned_app.lock().await.save_session();
   }
 
   Ok(())
@@ -1929,13 +2058,21 @@ async fn start_ui(
   // we set MPRIS to stopped so the external player's MPRIS interface takes precedence
   let mut prev_is_streaming_active = false;
 
-  // Lazy audio capture: only capture when in Analysis view
-  #[cfg(any(feature = "audio-viz", feature = "audio-viz-cpal"))]
+  // Lazy audio capture: only capture when a view shows the spectrum
+  #[cfg(any(
+    feature = "audio-viz",
+    feature = "audio-viz-cpal",
+    feature = "streaming"
+  ))]
   let mut audio_capture: Option<audio::AudioCaptureManager> = None;
 
   #[cfg(feature = "discord-rpc")]
   let mut discord_presence_state = DiscordPresenceState::default();
 
+  let mut notification_manager =
+    notifications::NotificationManager::new(user_config.behavior.notification_method);
+  let mut notification_state = NotificationState::default();
+
   #[cfg(feature = "mpris")]
   let mut mpris_metadata_state: Option<MprisMetadata> = None;
 
@@ -1994,35 +2131,48 @@ async fn start_ui(
         }
       };
 
+      #[cfg(feature = "cover-art")]
+      app.request_cover_thumbnails();
+
       let current_route = app.get_current_route();
-      terminal.draw(|f| match current_route.active_block {
-        ActiveBlock::HelpMenu => {
-          ui::draw_help_menu(f, &app);
-        }
-        ActiveBlock::Error => {
-          ui::draw_error_screen(f, &app);
-        }
-        ActiveBlock::SelectDevice => {
-          ui::draw_device_list(f, &app);
-        }
-        ActiveBlock::Analysis => {
-          ui::audio_analysis::draw(f, &app);
-        }
-        ActiveBlock::BasicView => {
-          ui::draw_basic_view(f, &app);
-        }
-        ActiveBlock::UpdatePrompt => {
-          ui::draw_update_prompt(f, &app);
-        }
-        ActiveBlock::AnnouncementPrompt => {
-          ui::draw_announcement_prompt(f, &app);
-        }
-        ActiveBlock::Settings => {
-          ui::settings::draw_settings(f, &app);
-        }
-        _ => {
-          ui::draw_main_layout(f, &app);
+      terminal.draw(|f| {
+        match current_route.active_block {
+          // The device picker stays reachable so playback can be started from the mini player
+          ActiveBlock::SelectDevice => {
+            ui::draw_device_list(f, &app);
+          }
+          _ if app.mini_mode => {
+            ui::draw_mini_layout(f, &app);
+          }
+          ActiveBlock::HelpMenu => {
+            ui::draw_help_menu(f, &app);
+          }
+          ActiveBlock::Error => {
+            ui::draw_error_screen(f, &app);
+          }
+          ActiveBlock::Analysis => {
+            ui::audio_analysis::draw(f, &app);
+          }
+          ActiveBlock::BasicView => {
+            ui::draw_basic_view(f, &app);
+          }
+          ActiveBlock::NowPlaying => {
+            ui::draw_now_playing(f, &app);
+          }
+          ActiveBlock::UpdatePrompt => {
+            ui::draw_update_prompt(f, &app);
+          }
+          ActiveBlock::AnnouncementPrompt => {
+            ui::draw_announcement_prompt(f, &app);
+          }
+          ActiveBlock::Settings => {
+            ui::settings::draw_settings(f, &app);
+          }
+          _ => {
+            ui::draw_main_layout(f, &app);
+          }
         }
+        ui::color::downsample_buffer(f.buffer_mut(), app.color_support);
       })?;
 
       if current_route.active_block == ActiveBlock::Input {
@@ -2057,42 +2207,10 @@ async fn start_ui(
           break;
         }
 
-        let current_active_block = app.get_current_route().active_block;
-
-        // To avoid swallowing the global key presses `q` and `-` make a special
-        // case for the input handler
-        if current_active_block == ActiveBlock::Input {
-          handlers::input_handler(key, &mut app);
-        } else if key == app.user_config.keys.back {
-          if app.get_current_route().active_block == ActiveBlock::AnnouncementPrompt {
-            if let Some(dismissed_id) = app.dismiss_active_announcement() {
-              app.user_config.mark_announcement_seen(dismissed_id);
-              if let Err(error) = app.user_config.save_config() {
-                app.handle_error(anyhow!(
-                  "Failed to persist dismissed announcement: {}",
-                  error
-                ));
-              }
-            }
-
-            if app.active_announcement.is_none() {
-              app.pop_navigation_stack();
-            }
-          } else if app.get_current_route().active_block != ActiveBlock::Input {
-            // Go back through navigation stack when not in search input mode and exit the app if there are no more places to back to
-
-            let pop_result = match app.pop_navigation_stack() {
-              Some(ref x) if x.id == RouteId::Search => app.pop_navigation_stack(),
-              Some(x) => Some(x),
-              None => None,
-            };
-            if pop_result.is_none() {
-              app.close_io_channel();
-              break; // Exit application
-            }
-          }
-        } else {
-          handlers::handle_app(key, &mut app);
+        handlers::handle_app(key, &mut app);
+        if app.should_quit {
+          app.close_io_channel();
+          break; // Exit application
         }
       }
       event::Event::Tick => {
@@ -2109,6 +2227,8 @@ async fn start_ui(
           update_discord_presence(manager, &mut discord_presence_state, &app);
         }
 
+        update_notifications(&mut notification_manager, &mut notification_state, &mut app);
+
         #[cfg(feature = "mpris")]
         if let Some(ref mpris) = mpris_manager {
           update_mpris_metadata(mpris, &mut mpris_metadata_state, &app);
@@ -2142,27 +2262,65 @@ async fn start_ui(
           }
         }
 
-        // Lazy audio capture: only capture when in Analysis view
-        #[cfg(any(feature = "audio-viz", feature = "audio-viz-cpal"))]
+        // Lazy audio capture: only capture when a view shows the spectrum
+        #[cfg(any(
+          feature = "audio-viz",
+          feature = "audio-viz-cpal",
+          feature = "streaming"
+        ))]
         {
-          let in_analysis_view = app.get_current_route().active_block == ActiveBlock::Analysis;
+          let in_analysis_view = matches!(
+            app.get_current_route().active_block,
+            ActiveBlock::Analysis | ActiveBlock::NowPlaying
+          );
 
-          if in_analysis_view {
-            if audio_capture.is_none() {
-              audio_capture = audio::AudioCaptureManager::new();
-              app.audio_capture_active = audio_capture.is_some();
-            }
+          // Native playback feeds the visualizer straight from the decoder, no loopback needed
+          #[cfg(feature = "streaming")]
+          let playback_tap = app
+            .streaming_player
+            .as_ref()
+            .filter(|_| app.is_streaming_active)
+            .map(|player| player.playback_tap().clone());
+          #[cfg(not(feature = "streaming"))]
+          let playback_tap: Option<()> = None;
+
+          #[cfg(feature = "streaming")]
+          if let Some(ref player) = app.streaming_player {
+            player
+              .playback_tap()
+              .set_enabled(in_analysis_view && playback_tap.is_some());
+          }
 
-            if let Some(ref capture) = audio_capture {
-              if let Some(spectrum) = capture.get_spectrum() {
+          if in_analysis_view {
+            #[cfg(feature = "streaming")]
+            if let Some(ref tap) = playback_tap {
+              audio_capture = None;
+              if let Some(spectrum) = tap.get_spectrum() {
                 app.spectrum_data = Some(app::SpectrumData {
                   bands: spectrum.bands,
                   peak: spectrum.peak,
                 });
-                app.audio_capture_active = capture.is_active();
+              }
+              app.audio_capture_active = tap.is_active();
+            }
+
+            if playback_tap.is_none() {
+              if audio_capture.is_none() {
+                audio_capture = audio::AudioCaptureManager::new();
+                app.audio_capture_active = audio_capture.is_some();
+              }
+
+              if let Some(ref capture) = audio_capture {
+                if let Some(spectrum) = capture.get_spectrum() {
+                  app.spectrum_data = Some(app::SpectrumData {
+                    bands: spectrum.bands,
+                    peak: spectrum.peak,
+                  });
+                  app.audio_capture_active = capture.is_active();
+                }
               }
             }
-          } else if audio_capture.is_some() {
+          } else if audio_capture.is_some() || app.spectrum_data.is_some() {
             audio_capture = None;
             app.audio_capture_active = false;
             app.spectrum_data = None;
@@ -2182,7 +2340,7 @@ async fn start_ui(
         app.dispatch(IoEvent::FetchGlobalSongCount);
       }
       app.dispatch(IoEvent::FetchAnnouncements);
-      app.help_docs_size = ui::help::get_help_docs(&app.user_config.keys).len() as u32;
+      app.update_help_docs_size();
 
       is_first_render = false;
     }
@@ -2256,13 +2414,21 @@ async fn start_ui(
     }
   }
 
-  // Lazy audio capture: only capture when in Analysis view
-  #[cfg(any(feature = "audio-viz", feature = "audio-viz-cpal"))]
+  // Lazy audio capture: only capture when a view shows the spectrum
+  #[cfg(any(
+    feature = "audio-viz",
+    feature = "audio-viz-cpal",
+    feature = "streaming"
+  ))]
   let mut audio_capture: Option<audio::AudioCaptureManager> = None;
 
   #[cfg(feature = "discord-rpc")]
   let mut discord_presence_state = DiscordPresenceState::default();
 
+  let mut notification_manager =
+    notifications::NotificationManager::new(user_config.behavior.notification_method);
+  let mut notification_state = NotificationState::default();
+
   let mut is_first_render = true;
 
   loop {
@@ -2295,6 +2461,9 @@ async fn start_ui(
         }
       };
 
+      #[cfg(feature = "cover-art")]
+      app.request_cover_thumbnails();
+
       let current_route = app.get_current_route();
       terminal.draw(|f| {
         f.render_widget(
@@ -2302,16 +2471,20 @@ async fn start_ui(
           f.area(),
         );
         match current_route.active_block {
+          // The device picker stays reachable so playback can be started from the mini player
+          ActiveBlock::SelectDevice => ui::draw_device_list(f, &app),
+          _ if app.mini_mode => ui::draw_mini_layout(f, &app),
           ActiveBlock::HelpMenu => ui::draw_help_menu(f, &app),
           ActiveBlock::Error => ui::draw_error_screen(f, &app),
-          ActiveBlock::SelectDevice => ui::draw_device_list(f, &app),
           ActiveBlock::Analysis => ui::audio_analysis::draw(f, &app),
           ActiveBlock::BasicView => ui::draw_basic_view(f, &app),
+          ActiveBlock::NowPlaying => ui::draw_now_playing(f, &app),
           ActiveBlock::UpdatePrompt => ui::draw_update_prompt(f, &app),
           ActiveBlock::AnnouncementPrompt => ui::draw_announcement_prompt(f, &app),
           ActiveBlock::Settings => ui::settings::draw_settings(f, &app),
           _ => ui::draw_main_layout(f, &app),
         }
+        ui::color::downsample_buffer(f.buffer_mut(), app.color_support);
       })?;
 
       if current_route.active_block == ActiveBlock::Input {
@@ -2343,38 +2516,10 @@ async fn start_ui(
           break;
         }
 
-        let current_active_block = app.get_current_route().active_block;
-
-        if current_active_block == ActiveBlock::Input {
-          handlers::input_handler(key, &mut app);
-        } else if key == app.user_config.keys.back {
-          if app.get_current_route().active_block == ActiveBlock::AnnouncementPrompt {
-            if let Some(dismissed_id) = app.dismiss_active_announcement() {
-              app.user_config.mark_announcement_seen(dismissed_id);
-              if let Err(error) = app.user_config.save_config() {
-                app.handle_error(anyhow!(
-                  "Failed to persist dismissed announcement: {}",
-                  error
-                ));
-              }
-            }
-
-            if app.active_announcement.is_none() {
-              app.pop_navigation_stack();
-            }
-          } else if app.get_current_route().active_block != ActiveBlock::Input {
-            let pop_result = match app.pop_navigation_stack() {
-              Some(ref x) if x.id == RouteId::Search => app.pop_navigation_stack(),
-              Some(x) => Some(x),
-              None => None,
-            };
-            if pop_result.is_none() {
-              app.close_io_channel();
-              break;
-            }
-          }
-        } else {
-          handlers::handle_app(key, &mut app);
+        handlers::handle_app(key, &mut app);
+        if app.should_quit {
+          app.close_io_channel();
+          break;
         }
       }
       event::Event::Tick => {
@@ -2391,6 +2536,8 @@ async fn start_ui(
           update_discord_presence(manager, &mut discord_presence_state, &app);
         }
 
+        update_notifications(&mut notification_manager, &mut notification_state, &mut app);
+
         // Read position from shared atomic if native streaming is active
         // Skip if we recently seeked - let the UI show our target position until the player catches up
         #[cfg(feature = "streaming")]
@@ -2416,27 +2563,65 @@ async fn start_ui(
           }
         }
 
-        // Lazy audio capture: only capture when in Analysis view
-        #[cfg(any(feature = "audio-viz", feature = "audio-viz-cpal"))]
+        // Lazy audio capture: only capture when a view shows the spectrum
+        #[cfg(any(
+          feature = "audio-viz",
+          feature = "audio-viz-cpal",
+          feature = "streaming"
+        ))]
         {
-          let in_analysis_view = app.get_current_route().active_block == ActiveBlock::Analysis;
+          let in_analysis_view = matches!(
+            app.get_current_route().active_block,
+            ActiveBlock::Analysis | ActiveBlock::NowPlaying
+          );
 
-          if in_analysis_view {
-            if audio_capture.is_none() {
-              audio_capture = audio::AudioCaptureManager::new();
-              app.audio_capture_active = audio_capture.is_some();
-            }
+          // Native playback feeds the visualizer straight from the decoder, no loopback needed
+          #[cfg(feature = "streaming")]
+          let playback_tap = app
+            .streaming_player
+            .as_ref()
+            .filter(|_| app.is_streaming_active)
+            .map(|player| player.playback_tap().clone());
+          #[cfg(not(feature = "streaming"))]
+          let playback_tap: Option<()> = None;
+
+          #[cfg(feature = "streaming")]
+          if let Some(ref player) = app.streaming_player {
+            player
+              .playback_tap()
+              .set_enabled(in_analysis_view && playback_tap.is_some());
+          }
 
-            if let Some(ref capture) = audio_capture {
-              if let Some(spectrum) = capture.get_spectrum() {
+          if in_analysis_view {
+            #[cfg(feature = "streaming")]
+            if let Some(ref tap) = playback_tap {
+              audio_capture = None;
+              if let Some(spectrum) = tap.get_spectrum() {
                 app.spectrum_data = Some(app::SpectrumData {
                   bands: spectrum.bands,
                   peak: spectrum.peak,
                 });
-                app.audio_capture_active = capture.is_active();
+              }
+              app.audio_capture_active = tap.is_active();
+            }
+
+            if playback_tap.is_none() {
+              if audio_capture.is_none() {
+                audio_capture = audio::AudioCaptureManager::new();
+                app.audio_capture_active = audio_capture.is_some();
+              }
+
+              if let Some(ref capture) = audio_capture {
+                if let Some(spectrum) = capture.get_spectrum() {
+                  app.spectrum_data = Some(app::SpectrumData {
+                    bands: spectrum.bands,
+                    peak: spectrum.peak,
+                  });
+                  app.audio_capture_active = capture.is_active();
+                }
               }
             }
-          } else if audio_capture.is_some() {
+          } else if audio_capture.is_some() || app.spectrum_data.is_some() {
             audio_capture = None;
             app.audio_capture_active = false;
             app.spectrum_data = None;
@@ -2454,7 +2639,7 @@ async fn start_ui(
         app.dispatch(IoEvent::FetchGlobalSongCount);
       }
       app.dispatch(IoEvent::FetchAnnouncements);
-      app.help_docs_size = ui::help::get_help_docs(&app.user_config.keys).len() as u32;
+      app.update_help_docs_size();
       is_first_render = false;
     }
   }
@@ -0,0 +1,221 @@
+// Theme colors derived from the current cover art, used by the "Adaptive" theme preset
+
+use crate::core::user_config::Theme;
+use image::RgbImage;
+use ratatui::style::Color;
+use std::collections::HashMap;
+
+/// WCAG AA minimum for normal sized text
+pub const MIN_TEXT_CONTRAST: f64 = 4.5;
+/// Bits kept per channel when bucketing pixels, 4 gives 4096 buckets
+const QUANTIZE_BITS: u8 = 4;
+/// Accent candidates closer than this (euclidean RGB) to the dominant color are skipped
+const MIN_ACCENT_DISTANCE: f64 = 80.0;
+/// How much of the dominant color is kept in the background
+const BACKGROUND_SHADE: f64 = 0.22;
+
+type Rgb = (u8, u8, u8);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Palette {
+  pub dominant: Rgb,
+  pub accent: Rgb,
+  /// Dark shade of the dominant color the rest of the palette is checked against
+  pub background: Rgb,
+  /// Text color tinted with the dominant color, readable on `background`
+  pub foreground: Rgb,
+}
+
+impl Palette {
+  pub fn from_image(image: &RgbImage) -> Option<Self> {
+    let buckets = bucket_pixels(image);
+    let (dominant, _) = buckets.iter().copied().max_by_key(|(_, count)| *count)?;
+
+    // Favor colorful buckets so a small splash of color beats a large grey area
+    let accent = buckets
+      .iter()
+      .filter(|(color, _)| distance(*color, dominant) >= MIN_ACCENT_DISTANCE)
+      .max_by(|(a, a_count), (b, b_count)| {
+        let score = |color: Rgb, count: u32| count as f64 * (0.2 + saturation(color));
+        score(*a, *a_count).total_cmp(&score(*b, *b_count))
+      })
+      .map_or(dominant, |(color, _)| *color);
+
+    let background = mix(dominant, (0, 0, 0), 1.0 - BACKGROUND_SHADE);
+    Some(Self {
+      dominant,
+      accent,
+      background,
+      foreground: ensure_contrast(mix(dominant, (255, 255, 255), 0.85), background),
+    })
+  }
+
+  pub fn to_theme(self) -> Theme {
+    let bg = self.background;
+    let readable = |color: Rgb| rgb(ensure_contrast(color, bg));
+    let default = Theme::default();
+    let accent = readable(self.accent);
+    let dominant = readable(self.dominant);
+    let muted = readable(mix(self.foreground, bg, 0.4));
+
+    Theme {
+      analysis_bar: accent,
+      analysis_bar_text: rgb(self.foreground),
+      active: accent,
+      banner: accent,
+      error_border: readable(to_rgb(default.error_border)),
+      error_text: readable(to_rgb(default.error_text)),
+      hint: readable(to_rgb(default.hint)),
+      hovered: dominant,
+      inactive: muted,
+      playbar_background: rgb(bg),
+      playbar_progress: accent,
+      playbar_progress_text: rgb(self.foreground),
+      playbar_text: rgb(self.foreground),
+      selected: accent,
+      text: rgb(self.foreground),
+      background: rgb(bg),
+      header: rgb(self.foreground),
+      highlighted_lyrics: accent,
+    }
+  }
+}
+
+/// Average color and pixel count of each quantized bucket
+fn bucket_pixels(image: &RgbImage) -> Vec<(Rgb, u32)> {
+  let mut buckets: HashMap<Rgb, (u64, u64, u64, u32)> = HashMap::new();
+  let shift = 8 - QUANTIZE_BITS;
+  for pixel in image.pixels() {
+    let [r, g, b] = pixel.0;
+    let entry = buckets
+      .entry((r >> shift, g >> shift, b >> shift))
+      .or_default();
+    entry.0 += r as u64;
+    entry.1 += g as u64;
+    entry.2 += b as u64;
+    entry.3 += 1;
+  }
+  buckets
+    .into_values()
+    .map(|(r, g, b, count)| {
+      let n = count as u64;
+      (((r / n) as u8, (g / n) as u8, (b / n) as u8), count)
+    })
+    .collect()
+}
+
+/// Move `color` toward white or black, whichever `background` contrasts with more, until the
+/// pair reaches the WCAG text minimum
+pub fn ensure_contrast(color: Rgb, background: Rgb) -> Rgb {
+  let target =
+    if contrast_ratio((255, 255, 255), background) >= contrast_ratio((0, 0, 0), background) {
+      (255, 255, 255)
+    } else {
+      (0, 0, 0)
+    };
+  (0..=20)
+    .map(|step| mix(color, target, step as f64 / 20.0))
+    .find(|candidate| contrast_ratio(*candidate, background) >= MIN_TEXT_CONTRAST)
+    .unwrap_or(target)
+}
+
+/// https://www.w3.org/TR/WCAG21/#dfn-relative-luminance
+pub fn relative_luminance((r, g, b): Rgb) -> f64 {
+  let channel = |c: u8| {
+    let c = c as f64 / 255.0;
+    if c <= 0.03928 {
+      c / 12.92
+    } else {
+      ((c + 0.055) / 1.055).powf(2.4)
+    }
+  };
+  0.2126 * channel(r) + 0.7152 * channel(g) + 0.0722 * channel(b)
+}
+
+/// https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio
+pub fn contrast_ratio(a: Rgb, b: Rgb) -> f64 {
+  let (la, lb) = (relative_luminance(a), relative_luminance(b));
+  (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+fn mix(from: Rgb, to: Rgb, amount: f64) -> Rgb {
+  let lerp = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * amount).round() as u8;
+  (lerp(from.0, to.0), lerp(from.1, to.1), lerp(from.2, to.2))
+}
+
+fn distance(a: Rgb, b: Rgb) -> f64 {
+  let d = |x: u8, y: u8| (x as f64 - y as f64).powi(2);
+  (d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)).sqrt()
+}
+
+/// HSV saturation in 0..=1
+fn saturation((r, g, b): Rgb) -> f64 {
+  let max = r.max(g).max(b);
+  if max == 0 {
+    return 0.0;
+  }
+  (max - r.min(g).min(b)) as f64 / max as f64
+}
+
+fn rgb((r, g, b): Rgb) -> Color {
+  Color::Rgb(r, g, b)
+}
+
+fn to_rgb(color: Color) -> Rgb {
+  match color {
+    Color::Rgb(r, g, b) => (r, g, b),
+    _ => (255, 255, 255),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use image::Rgb as Pixel;
+
+  #[test]
+  fn contrast_ratio_matches_wcag_extremes() {
+    assert!((contrast_ratio((0, 0, 0), (255, 255, 255)) - 21.0).abs() < 0.01);
+    assert!((contrast_ratio((120, 40, 200), (120, 40, 200)) - 1.0).abs() < 0.01);
+  }
+
+  #[test]
+  fn ensure_contrast_lifts_dim_colors() {
+    let background = (20, 20, 30);
+    let fixed = ensure_contrast((40, 40, 60), background);
+    assert!(contrast_ratio(fixed, background) >= MIN_TEXT_CONTRAST);
+
+    // Already readable colors are left alone
+    assert_eq!(
+      ensure_contrast((250, 250, 250), background),
+      (250, 250, 250)
+    );
+  }
+
+  #[test]
+  fn palette_picks_dominant_and_distinct_accent() {
+    // Mostly dark blue with a red stripe
+    let image = RgbImage::from_fn(10, 10, |x, _| {
+      if x < 2 {
+        Pixel([220, 30, 30])
+      } else {
+        Pixel([20, 30, 120])
+      }
+    });
+    let palette = Palette::from_image(&image).unwrap();
+    assert_eq!(palette.dominant, (20, 30, 120));
+    assert_eq!(palette.accent, (220, 30, 30));
+    assert!(contrast_ratio(palette.foreground, palette.background) >= MIN_TEXT_CONTRAST);
+  }
+
+  #[test]
+  fn theme_text_colors_are_readable() {
+    // A very light cover is the hard case for a dark background
+    let image = RgbImage::from_pixel(4, 4, Pixel([240, 235, 220]));
+    let theme = Palette::from_image(&image).unwrap().to_theme();
+    let background = to_rgb(theme.background);
+    for color in [theme.text, theme.selected, theme.inactive, theme.hint] {
+      assert!(contrast_ratio(to_rgb(color), background) >= MIN_TEXT_CONTRAST);
+    }
+  }
+}
@@ -1,29 +1,50 @@
+use crate::core::user_config::CoverArtFallback;
+use crate::infra::cover_cache::CoverCache;
+use crate::tui::adaptive_theme::Palette;
 use anyhow::anyhow;
+use image::{imageops::FilterType, DynamicImage, RgbImage};
 use log::{debug, info};
-use ratatui::{layout::Rect, Frame};
+use ratatui::{
+  buffer::Buffer,
+  layout::Rect,
+  style::{Color, Style},
+  widgets::Widget,
+  Frame,
+};
 use ratatui_image::{
   picker::{Picker, ProtocolType},
   protocol::StatefulProtocol,
   Resize, StatefulImage,
 };
 use rspotify::model::Image;
+use std::collections::HashMap;
 use std::sync::Mutex;
-use tokio::io::AsyncWriteExt;
+
+/// Larger covers are scaled down once on load; the block renderer never needs more pixels
+const BLOCK_IMAGE_MAX_PX: u32 = 256;
+const THUMBNAIL_PX: u32 = 64;
+/// Decoded thumbnails kept in memory before the map is cleared
+const MAX_THUMBNAILS: usize = 64;
 
 pub struct CoverArt {
   pub state: Mutex<Option<CoverArtState>>,
   picker: Picker,
+  /// `None` while a thumbnail is being fetched, or when it failed to load
+  thumbnails: Mutex<HashMap<String, Option<RgbImage>>>,
 }
 
 pub struct CoverArtState {
   url: String,
-  image: StatefulProtocol,
+  image: RgbImage,
+  protocol: StatefulProtocol,
+  fitted: Option<FittedImage>,
 }
 
-impl CoverArtState {
-  fn new(url: String, image: StatefulProtocol) -> Self {
-    Self { url, image }
-  }
+/// The block rendering of the cover for the last area it was drawn in
+struct FittedImage {
+  size: (u16, u16),
+  style: CoverArtFallback,
+  image: RgbImage,
 }
 
 impl CoverArt {
@@ -37,6 +58,7 @@ impl CoverArt {
     Self {
       picker,
       state: Mutex::new(None),
+      thumbnails: Mutex::new(HashMap::new()),
     }
   }
 
@@ -51,64 +73,304 @@ impl CoverArt {
     self.state.lock().unwrap().as_ref().map(|s| s.url.clone())
   }
 
-  pub fn set_state(&self, state: CoverArtState) {
+  /// Returns whether the cover changed
+  pub async fn refresh(&self, image: &Image, cache: &CoverCache) -> anyhow::Result<bool> {
+    if self.get_url().as_ref() == Some(&image.url) {
+      debug!("skipping image refresh: cover art already downloaded");
+      return Ok(false);
+    }
+
+    info!("getting new cover art image...");
+    let decoded = fetch_cover(&image.url, cache).await?;
+    let protocol = self.picker.new_resize_protocol(decoded.clone());
+    let state = CoverArtState {
+      url: image.url.clone(),
+      image: decoded
+        .thumbnail(BLOCK_IMAGE_MAX_PX, BLOCK_IMAGE_MAX_PX)
+        .to_rgb8(),
+      protocol,
+      fitted: None,
+    };
+    *self.state.lock().unwrap() = Some(state);
+    info!("got new cover art: {}", image.url);
+
+    Ok(true)
+  }
+
+  /// Colors of the current cover for the adaptive theme
+  pub fn palette(&self) -> Option<Palette> {
+    let lock = self.state.lock().unwrap();
+    Palette::from_image(&lock.as_ref()?.image)
+  }
+
+  pub fn available(&self) -> bool {
+    self.state.lock().unwrap().is_some()
+  }
+
+  /// Draw the cover through the image protocol, or with `blocks` when the terminal has none
+  pub fn render(&self, f: &mut Frame, area: Rect, blocks: Option<CoverArtFallback>) {
     let mut lock = self.state.lock().unwrap();
-    *lock = Some(state);
+    let Some(state) = lock.as_mut() else {
+      return;
+    };
+
+    let Some(fallback) = blocks.filter(|_| !self.full_image_support()) else {
+      f.render_stateful_widget(
+        StatefulImage::new().resize(Resize::Fit(None)),
+        area,
+        &mut state.protocol,
+      );
+      return;
+    };
+
+    // Scaling is the expensive part, so it is only redone when the area or style changes
+    let size = (area.width, area.height);
+    if !matches!(&state.fitted, Some(fitted) if fitted.size == size && fitted.style == fallback) {
+      state.fitted = Some(FittedImage {
+        size,
+        style: fallback,
+        image: fit_to_cells(&state.image, area, fallback),
+      });
+    }
+    if let Some(fitted) = &state.fitted {
+      f.render_widget(BlockImage::new(&fitted.image, fallback), area);
+    }
+  }
+
+  /// Marks the thumbnail as requested. False when it was already requested before.
+  pub fn request_thumbnail(&self, url: &str) -> bool {
+    let mut thumbnails = self.thumbnails.lock().unwrap();
+    if thumbnails.contains_key(url) {
+      return false;
+    }
+    if thumbnails.len() >= MAX_THUMBNAILS {
+      thumbnails.clear();
+    }
+    thumbnails.insert(url.to_string(), None);
+    true
+  }
+
+  pub fn set_thumbnail(&self, url: String, image: Option<DynamicImage>) {
+    let image = image.map(|image| image.thumbnail(THUMBNAIL_PX, THUMBNAIL_PX).to_rgb8());
+    self.thumbnails.lock().unwrap().insert(url, image);
+  }
+
+  pub fn has_thumbnail(&self, url: &str) -> bool {
+    matches!(self.thumbnails.lock().unwrap().get(url), Some(Some(_)))
+  }
+
+  /// Thumbnails are small enough to always use the block renderer
+  pub fn render_thumbnail(&self, f: &mut Frame, url: &str, area: Rect, style: CoverArtFallback) {
+    if let Some(Some(image)) = self.thumbnails.lock().unwrap().get(url) {
+      let fitted = fit_to_cells(image, area, style);
+      f.render_widget(BlockImage::new(&fitted, style), area);
+    }
+  }
+}
+
+/// The cover for `url`, from the disk cache when it was downloaded before
+pub async fn fetch_cover(url: &str, cache: &CoverCache) -> anyhow::Result<DynamicImage> {
+  let bytes = match cache.load(url) {
+    Some(bytes) => {
+      debug!("cover art cache hit: {}", url);
+      bytes
+    }
+    None => {
+      let res = reqwest::get(url).await?.error_for_status()?;
+      let bytes = res.bytes().await?.to_vec();
+      debug!("finished reading response: {} bytes", bytes.len());
+      cache.store(url, &bytes);
+      bytes
+    }
+  };
+  image::load_from_memory(&bytes).map_err(|e| anyhow!("failed to decode cover art: {}", e))
+}
+
+/// The smallest image that still looks fine as a thumbnail
+pub fn thumbnail_url(images: &[Image]) -> Option<&str> {
+  images
+    .iter()
+    .filter(|image| image.width.unwrap_or(0) >= THUMBNAIL_PX)
+    .min_by_key(|image| image.width)
+    .or_else(|| images.first())
+    .map(|image| image.url.as_str())
+}
+
+/// Pixels covered by one terminal cell in each renderer
+fn cell_pixels(style: CoverArtFallback) -> (u32, u32) {
+  match style {
+    CoverArtFallback::HalfBlock => (1, 2),
+    CoverArtFallback::Braille => (2, 4),
   }
+}
 
-  pub async fn refresh(&self, image: &Image) -> anyhow::Result<()> {
-    if self.get_url().as_ref() != Some(&image.url) {
-      info!("getting new cover art image...");
+/// Scale `image` to fit `area`, keeping its aspect ratio with cells twice as tall as wide
+pub fn fit_to_cells(image: &RgbImage, area: Rect, style: CoverArtFallback) -> RgbImage {
+  let (cell_w, cell_h) = cell_pixels(style);
+  let max_w = area.width as u32 * cell_w;
+  let max_h = area.height as u32 * cell_h;
+  if image.width() == 0 || image.height() == 0 || max_w == 0 || max_h == 0 {
+    return RgbImage::new(0, 0);
+  }
+  // A cell is about twice as tall as wide, so braille dots and half blocks are near square
+  let scale = f64::min(
+    max_w as f64 / image.width() as f64,
+    max_h as f64 / image.height() as f64,
+  );
+  let width = ((image.width() as f64 * scale).round() as u32).clamp(1, max_w);
+  let height = ((image.height() as f64 * scale).round() as u32).clamp(1, max_h);
+  image::imageops::resize(image, width, height, FilterType::Triangle)
+}
 
-      let res = match reqwest::get(&image.url).await {
-        Ok(r) => r.error_for_status(),
-        Err(e) => Result::Err(e),
-      };
+/// Truecolor picture drawn with Unicode blocks, for terminals without an image protocol.
+/// The image is expected to be fitted to the area already, see [`fit_to_cells`].
+pub struct BlockImage<'a> {
+  image: &'a RgbImage,
+  style: CoverArtFallback,
+}
 
-      let file = match res {
-        Ok(res) => {
-          // Allocate Vec "file" with capacity if content_length is provided
-          let mut file = match res.content_length() {
-            Some(s) => Vec::with_capacity(s as usize),
-            None => Vec::new(),
-          };
+impl<'a> BlockImage<'a> {
+  pub fn new(image: &'a RgbImage, style: CoverArtFallback) -> Self {
+    Self { image, style }
+  }
+}
 
-          file
-            .write_all(&res.bytes().await.unwrap())
-            .await
-            .expect("error while downloading album art");
+impl Widget for BlockImage<'_> {
+  fn render(self, area: Rect, buf: &mut Buffer) {
+    let (cell_w, cell_h) = cell_pixels(self.style);
+    let cols = self.image.width().div_ceil(cell_w) as u16;
+    let rows = self.image.height().div_ceil(cell_h) as u16;
+    // Center the picture in the area
+    let x0 = area.x + area.width.saturating_sub(cols) / 2;
+    let y0 = area.y + area.height.saturating_sub(rows) / 2;
 
-          debug!("finished reading response: {} bytes", file.len());
-          file
+    for row in 0..rows.min(area.height) {
+      for col in 0..cols.min(area.width) {
+        let pixels: Vec<Option<[u8; 3]>> = (0..cell_h)
+          .flat_map(|dy| (0..cell_w).map(move |dx| (dx, dy)))
+          .map(|(dx, dy)| {
+            self
+              .image
+              .get_pixel_checked(col as u32 * cell_w + dx, row as u32 * cell_h + dy)
+              .map(|pixel| pixel.0)
+          })
+          .collect();
+        let (symbol, style) = match self.style {
+          CoverArtFallback::HalfBlock => half_block_cell(&pixels),
+          CoverArtFallback::Braille => braille_cell(&pixels),
+        };
+        if let Some(cell) = buf.cell_mut((x0 + col, y0 + row)) {
+          cell.set_symbol(&symbol).set_style(style);
         }
-        Err(e) => return Err(anyhow!(e)),
-      };
+      }
+    }
+  }
+}
+
+fn rgb([r, g, b]: [u8; 3]) -> Color {
+  Color::Rgb(r, g, b)
+}
+
+/// `▀` with the top pixel as foreground and the bottom one as background
+fn half_block_cell(pixels: &[Option<[u8; 3]>]) -> (String, Style) {
+  let mut style = Style::default();
+  if let Some(top) = pixels[0] {
+    style = style.fg(rgb(top));
+  }
+  if let Some(bottom) = pixels[1] {
+    style = style.bg(rgb(bottom));
+  }
+  ("▀".to_string(), style)
+}
+
+/// Dots of a braille cell, column by column, in the order of `cell_pixels` rows
+const BRAILLE_DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
 
-      let image_protocol = self
-        .picker
-        .new_resize_protocol(image::load_from_memory(&file).unwrap());
+/// Dots brighter than the cell's average are raised in their mean color, the rest make the
+/// background
+fn braille_cell(pixels: &[Option<[u8; 3]>]) -> (String, Style) {
+  let luma = |[r, g, b]: [u8; 3]| 0.2126 * r as f64 + 0.7152 * g as f64 + 0.0722 * b as f64;
+  let present: Vec<[u8; 3]> = pixels.iter().flatten().copied().collect();
+  if present.is_empty() {
+    return (" ".to_string(), Style::default());
+  }
+  let mean = present.iter().map(|p| luma(*p)).sum::<f64>() / present.len() as f64;
 
-      self.set_state(CoverArtState::new(image.url.clone(), image_protocol));
-      info!("got new cover art: {}", image.url);
+  let mut bits = 0;
+  let (mut lit, mut unlit) = (Vec::new(), Vec::new());
+  for (index, pixel) in pixels.iter().enumerate() {
+    let Some(pixel) = pixel else {
+      continue;
+    };
+    if luma(*pixel) >= mean {
+      bits |= BRAILLE_DOTS[index / 2][index % 2];
+      lit.push(*pixel);
     } else {
-      debug!("skipping image refresh: cover art already downloaded");
+      unlit.push(*pixel);
     }
+  }
 
-    Ok(())
+  let average = |pixels: &[[u8; 3]]| {
+    let n = pixels.len().max(1) as u32;
+    let sum = pixels.iter().fold([0u32; 3], |acc, p| {
+      [
+        acc[0] + p[0] as u32,
+        acc[1] + p[1] as u32,
+        acc[2] + p[2] as u32,
+      ]
+    });
+    [(sum[0] / n) as u8, (sum[1] / n) as u8, (sum[2] / n) as u8]
+  };
+  let mut style = Style::default().fg(rgb(average(&lit)));
+  if !unlit.is_empty() {
+    style = style.bg(rgb(average(&unlit)));
   }
+  let symbol = char::from_u32(0x2800 + bits).unwrap_or(' ');
+  (symbol.to_string(), style)
+}
 
-  pub fn available(&self) -> bool {
-    self.state.lock().unwrap().is_some()
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use image::Rgb;
+
+  #[test]
+  fn half_blocks_use_top_and_bottom_pixels() {
+    let mut image = RgbImage::new(1, 2);
+    image.put_pixel(0, 0, Rgb([255, 0, 0]));
+    image.put_pixel(0, 1, Rgb([0, 0, 255]));
+    let mut buf = Buffer::empty(Rect::new(0, 0, 1, 1));
+    BlockImage::new(&image, CoverArtFallback::HalfBlock).render(buf.area, &mut buf);
+
+    let cell = &buf[(0, 0)];
+    assert_eq!(cell.symbol(), "▀");
+    assert_eq!(cell.fg, Color::Rgb(255, 0, 0));
+    assert_eq!(cell.bg, Color::Rgb(0, 0, 255));
   }
 
-  pub fn render(&self, f: &mut Frame, area: Rect) {
-    let mut lock = self.state.lock().unwrap();
-    if let Some(sp) = lock.as_mut() {
-      f.render_stateful_widget(
-        StatefulImage::new().resize(Resize::Fit(None)),
-        area,
-        &mut sp.image,
-      );
-    }
+  #[test]
+  fn braille_raises_the_bright_dots() {
+    // Left column white, right column black
+    let image = RgbImage::from_fn(2, 4, |x, _| {
+      if x == 0 {
+        Rgb([255, 255, 255])
+      } else {
+        Rgb([0, 0, 0])
+      }
+    });
+    let mut buf = Buffer::empty(Rect::new(0, 0, 1, 1));
+    BlockImage::new(&image, CoverArtFallback::Braille).render(buf.area, &mut buf);
+
+    let cell = &buf[(0, 0)];
+    assert_eq!(cell.symbol(), "⡇");
+    assert_eq!(cell.fg, Color::Rgb(255, 255, 255));
+    assert_eq!(cell.bg, Color::Rgb(0, 0, 0));
+  }
+
+  #[test]
+  fn fitting_keeps_the_aspect_ratio() {
+    let image = RgbImage::new(640, 640);
+    let fitted = fit_to_cells(&image, Rect::new(0, 0, 40, 10), CoverArtFallback::HalfBlock);
+    assert_eq!((fitted.width(), fitted.height()), (20, 20));
   }
 }
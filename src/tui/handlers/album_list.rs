@@ -1,45 +1,45 @@
 use super::common_key_events;
 use crate::{
   app::{ActiveBlock, AlbumTableContext, App, RouteId, SelectedFullAlbum},
-  event::Key,
+  core::{keymap::Action, sort::SortContext},
 };
 
-pub fn handler(key: Key, app: &mut App) {
-  match key {
-    k if common_key_events::left_event(k) => common_key_events::handle_left_event(app),
-    k if common_key_events::down_event(k) => {
+pub fn handler(action: Action, app: &mut App) {
+  match action {
+    Action::MoveLeft => common_key_events::handle_left_event(app),
+    Action::MoveDown => {
       if let Some(albums) = &mut app.library.saved_albums.get_results(None) {
         let next_index =
           common_key_events::on_down_press_handler(&albums.items, Some(app.album_list_index));
         app.album_list_index = next_index;
       }
     }
-    k if common_key_events::up_event(k) => {
+    Action::MoveUp => {
       if let Some(albums) = &mut app.library.saved_albums.get_results(None) {
         let next_index =
           common_key_events::on_up_press_handler(&albums.items, Some(app.album_list_index));
         app.album_list_index = next_index;
       }
     }
-    k if common_key_events::high_event(k) => {
+    Action::SelectTop => {
       if let Some(_albums) = app.library.saved_albums.get_results(None) {
         let next_index = common_key_events::on_high_press_handler();
         app.album_list_index = next_index;
       }
     }
-    k if common_key_events::middle_event(k) => {
+    Action::SelectMiddle => {
       if let Some(albums) = app.library.saved_albums.get_results(None) {
         let next_index = common_key_events::on_middle_press_handler(&albums.items);
         app.album_list_index = next_index;
       }
     }
-    k if common_key_events::low_event(k) => {
+    Action::SelectBottom => {
       if let Some(albums) = app.library.saved_albums.get_results(None) {
         let next_index = common_key_events::on_low_press_handler(&albums.items);
         app.album_list_index = next_index;
       }
     }
-    Key::Enter => {
+    Action::Submit => {
       if let Some(albums) = app.library.saved_albums.get_results(None) {
         if let Some(selected_album) = albums.items.get(app.album_list_index) {
           app.selected_album_full = Some(SelectedFullAlbum {
@@ -47,16 +47,17 @@ pub fn handler(key: Key, app: &mut App) {
             selected_index: 0,
           });
           app.album_table_context = AlbumTableContext::Full;
+          app.resort(SortContext::AlbumTracks);
           app.push_navigation_stack(RouteId::AlbumTracks, ActiveBlock::AlbumTracks);
         };
       }
     }
-    k if k == app.user_config.keys.next_page => app.get_current_user_saved_albums_next(),
-    k if k == app.user_config.keys.previous_page => app.get_current_user_saved_albums_previous(),
-    Key::Char('D') => app.current_user_saved_album_delete(ActiveBlock::AlbumList),
+    Action::NextPage => app.get_current_user_saved_albums_next(),
+    Action::PreviousPage => app.get_current_user_saved_albums_previous(),
+    Action::Remove => app.current_user_saved_album_delete(ActiveBlock::AlbumList),
     // Open sort menu
-    Key::Char(',') => {
-      super::sort_menu::open_sort_menu(app, crate::core::sort::SortContext::SavedAlbums);
+    Action::OpenSortMenu => {
+      super::sort_menu::open_sort_menu(app, SortContext::SavedAlbums);
     }
     _ => {}
   };
@@ -65,6 +66,8 @@ pub fn handler(key: Key, app: &mut App) {
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::handlers::handle_app;
+  use crate::tui::event::Key;
 
   #[test]
   fn on_left_press() {
@@ -74,7 +77,7 @@ mod tests {
       Some(ActiveBlock::AlbumTracks),
     );
 
-    handler(Key::Left, &mut app);
+    handler(Action::MoveLeft, &mut app);
     let current_route = app.get_current_route();
     assert_eq!(current_route.active_block, ActiveBlock::Empty);
     assert_eq!(current_route.hovered_block, ActiveBlock::Library);
@@ -84,7 +87,7 @@ mod tests {
   fn on_esc() {
     let mut app = App::default();
 
-    handler(Key::Esc, &mut app);
+    handle_app(Key::Esc, &mut app);
 
     let current_route = app.get_current_route();
     assert_eq!(current_route.active_block, ActiveBlock::Empty);
@@ -1,16 +1,16 @@
 use super::common_key_events;
 use crate::core::app::{AlbumTableContext, App, RecommendationsContext};
+use crate::core::keymap::Action;
 use crate::infra::network::IoEvent;
-use crate::tui::event::Key;
 use rspotify::{
   model::{PlayContextId, PlayableId},
   prelude::*,
 };
 
-pub fn handler(key: Key, app: &mut App) {
-  match key {
-    k if common_key_events::left_event(k) => common_key_events::handle_left_event(app),
-    k if common_key_events::down_event(k) => match app.album_table_context {
+pub fn handler(action: Action, app: &mut App) {
+  match action {
+    Action::MoveLeft => common_key_events::handle_left_event(app),
+    Action::MoveDown => match app.album_table_context {
       AlbumTableContext::Full => {
         if let Some(selected_album) = &app.selected_album_full {
           let next_index = common_key_events::on_down_press_handler(
@@ -30,7 +30,7 @@ pub fn handler(key: Key, app: &mut App) {
         }
       }
     },
-    k if common_key_events::up_event(k) => match app.album_table_context {
+    Action::MoveUp => match app.album_table_context {
       AlbumTableContext::Full => {
         if let Some(selected_album) = &app.selected_album_full {
           let next_index = common_key_events::on_up_press_handler(
@@ -50,12 +50,12 @@ pub fn handler(key: Key, app: &mut App) {
         }
       }
     },
-    k if common_key_events::high_event(k) => handle_high_event(app),
-    k if common_key_events::middle_event(k) => handle_middle_event(app),
-    k if common_key_events::low_event(k) => handle_low_event(app),
-    Key::Char('s') => handle_save_event(app),
-    Key::Char('w') => handle_save_album_event(app),
-    Key::Enter => match app.album_table_context {
+    Action::SelectTop => handle_high_event(app),
+    Action::SelectMiddle => handle_middle_event(app),
+    Action::SelectBottom => handle_low_event(app),
+    Action::Save => handle_save_event(app),
+    Action::Follow => handle_save_album_event(app),
+    Action::Submit => match app.album_table_context {
       AlbumTableContext::Full => {
         if let Some(selected_album) = app.selected_album_full.clone() {
           let context_id = Some(PlayContextId::Album(selected_album.album.id.into_static()));
@@ -82,10 +82,10 @@ pub fn handler(key: Key, app: &mut App) {
       }
     },
     //recommended playlist based on selected track
-    Key::Char('r') => {
+    Action::Recommendations => {
       handle_recommended_tracks(app);
     }
-    _ if key == app.user_config.keys.add_item_to_queue => match app.album_table_context {
+    Action::AddItemToQueue => match app.album_table_context {
       AlbumTableContext::Full => {
         if let Some(selected_album) = app.selected_album_full.clone() {
           if let Some(track) = selected_album
@@ -118,6 +118,9 @@ pub fn handler(key: Key, app: &mut App) {
         };
       }
     },
+    Action::OpenSortMenu => {
+      super::sort_menu::open_sort_menu(app, crate::core::sort::SortContext::AlbumTracks);
+    }
     _ => {}
   };
 }
@@ -271,6 +274,8 @@ fn handle_save_album_event(app: &mut App) {
 mod tests {
   use super::*;
   use crate::core::app::ActiveBlock;
+  use crate::handlers::handle_app;
+  use crate::tui::event::Key;
 
   #[test]
   fn on_left_press() {
@@ -280,7 +285,7 @@ mod tests {
       Some(ActiveBlock::AlbumTracks),
     );
 
-    handler(Key::Left, &mut app);
+    handler(Action::MoveLeft, &mut app);
     let current_route = app.get_current_route();
     assert_eq!(current_route.active_block, ActiveBlock::Empty);
     assert_eq!(current_route.hovered_block, ActiveBlock::Library);
@@ -290,7 +295,7 @@ mod tests {
   fn on_esc() {
     let mut app = App::default();
 
-    handler(Key::Esc, &mut app);
+    handle_app(Key::Esc, &mut app);
 
     let current_route = app.get_current_route();
     assert_eq!(current_route.active_block, ActiveBlock::Empty);
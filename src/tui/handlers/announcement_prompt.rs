@@ -1,9 +1,9 @@
 use crate::core::app::App;
-use crate::tui::event::Key;
+use crate::core::keymap::Action;
 
-pub fn handler(key: Key, app: &mut App) {
-  match key {
-    Key::Enter | Key::Esc | Key::Char('q') | Key::Char(' ') => {
+pub fn handler(action: Action, app: &mut App) {
+  match action {
+    Action::Submit | Action::Back => {
       if let Some(dismissed_id) = app.dismiss_active_announcement() {
         app.user_config.mark_announcement_seen(dismissed_id);
         if let Err(error) = app.user_config.save_config() {
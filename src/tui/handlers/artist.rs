@@ -1,6 +1,6 @@
 use super::common_key_events;
 use crate::core::app::{ActiveBlock, App, ArtistBlock, RecommendationsContext, TrackTableContext};
-use crate::tui::event::Key;
+use crate::core::keymap::Action;
 use crate::infra::network::IoEvent;
 use rspotify::{model::PlayableId, prelude::*};
 
@@ -242,27 +242,24 @@ fn handle_enter_event_on_hovered_block(app: &mut App) {
   }
 }
 
-pub fn handler(key: Key, app: &mut App) {
+pub fn handler(action: Action, app: &mut App) {
   if let Some(artist) = &mut app.artist {
-    match key {
-      Key::Esc => {
-        artist.artist_selected_block = ArtistBlock::Empty;
-      }
-      k if common_key_events::down_event(k) => {
+    match action {
+      Action::MoveDown => {
         if artist.artist_selected_block != ArtistBlock::Empty {
           handle_down_press_on_selected_block(app);
         } else {
           handle_down_press_on_hovered_block(app);
         }
       }
-      k if common_key_events::up_event(k) => {
+      Action::MoveUp => {
         if artist.artist_selected_block != ArtistBlock::Empty {
           handle_up_press_on_selected_block(app);
         } else {
           handle_up_press_on_hovered_block(app);
         }
       }
-      k if common_key_events::left_event(k) => {
+      Action::MoveLeft => {
         artist.artist_selected_block = ArtistBlock::Empty;
         match artist.artist_hovered_block {
           ArtistBlock::TopTracks => common_key_events::handle_left_event(app),
@@ -275,48 +272,48 @@ pub fn handler(key: Key, app: &mut App) {
           ArtistBlock::Empty => {}
         }
       }
-      k if common_key_events::right_event(k) => {
+      Action::MoveRight => {
         artist.artist_selected_block = ArtistBlock::Empty;
         handle_down_press_on_hovered_block(app);
       }
-      k if common_key_events::high_event(k) => {
+      Action::SelectTop => {
         if artist.artist_selected_block != ArtistBlock::Empty {
           handle_high_press_on_selected_block(app);
         }
       }
-      k if common_key_events::middle_event(k) => {
+      Action::SelectMiddle => {
         if artist.artist_selected_block != ArtistBlock::Empty {
           handle_middle_press_on_selected_block(app);
         }
       }
-      k if common_key_events::low_event(k) => {
+      Action::SelectBottom => {
         if artist.artist_selected_block != ArtistBlock::Empty {
           handle_low_press_on_selected_block(app);
         }
       }
-      Key::Enter => {
+      Action::Submit => {
         if artist.artist_selected_block != ArtistBlock::Empty {
           handle_enter_event_on_selected_block(app);
         } else {
           handle_enter_event_on_hovered_block(app);
         }
       }
-      Key::Char('r') => {
+      Action::Recommendations => {
         if artist.artist_selected_block != ArtistBlock::Empty {
           handle_recommend_event_on_selected_block(app);
         }
       }
-      Key::Char('w') => match artist.artist_selected_block {
+      Action::Follow => match artist.artist_selected_block {
         ArtistBlock::Albums => app.current_user_saved_album_add(ActiveBlock::ArtistBlock),
         ArtistBlock::RelatedArtists => app.user_follow_artists(ActiveBlock::ArtistBlock),
         _ => (),
       },
-      Key::Char('D') => match artist.artist_selected_block {
+      Action::Remove => match artist.artist_selected_block {
         ArtistBlock::Albums => app.current_user_saved_album_delete(ActiveBlock::ArtistBlock),
         ArtistBlock::RelatedArtists => app.user_unfollow_artists(ActiveBlock::ArtistBlock),
         _ => (),
       },
-      _ if key == app.user_config.keys.add_item_to_queue => {
+      Action::AddItemToQueue => {
         if let Some(artist) = &app.artist {
           if let ArtistBlock::TopTracks = artist.artist_selected_block {
             if let Some(track) = artist.top_tracks.get(artist.selected_top_track_index) {
@@ -338,12 +335,14 @@ pub fn handler(key: Key, app: &mut App) {
 mod tests {
   use super::*;
   use crate::core::app::ActiveBlock;
+  use crate::handlers::handle_app;
+  use crate::tui::event::Key;
 
   #[test]
   fn on_esc() {
     let mut app = App::default();
 
-    handler(Key::Esc, &mut app);
+    handle_app(Key::Esc, &mut app);
 
     let current_route = app.get_current_route();
     assert_eq!(current_route.active_block, ActiveBlock::Empty);
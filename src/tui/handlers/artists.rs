@@ -1,53 +1,53 @@
 use super::common_key_events;
 use crate::core::app::{ActiveBlock, App, RecommendationsContext};
+use crate::core::keymap::Action;
 use crate::infra::network::IoEvent;
-use crate::tui::event::Key;
 use rspotify::prelude::*;
 
-pub fn handler(key: Key, app: &mut App) {
-  match key {
-    k if common_key_events::left_event(k) => common_key_events::handle_left_event(app),
-    k if common_key_events::down_event(k) => {
+pub fn handler(action: Action, app: &mut App) {
+  match action {
+    Action::MoveLeft => common_key_events::handle_left_event(app),
+    Action::MoveDown => {
       if let Some(artists) = &mut app.library.saved_artists.get_results(None) {
         let next_index =
           common_key_events::on_down_press_handler(&artists.items, Some(app.artists_list_index));
         app.artists_list_index = next_index;
       }
     }
-    k if common_key_events::up_event(k) => {
+    Action::MoveUp => {
       if let Some(artists) = &mut app.library.saved_artists.get_results(None) {
         let next_index =
           common_key_events::on_up_press_handler(&artists.items, Some(app.artists_list_index));
         app.artists_list_index = next_index;
       }
     }
-    k if common_key_events::high_event(k) => {
+    Action::SelectTop => {
       if let Some(_artists) = &mut app.library.saved_artists.get_results(None) {
         let next_index = common_key_events::on_high_press_handler();
         app.artists_list_index = next_index;
       }
     }
-    k if common_key_events::middle_event(k) => {
+    Action::SelectMiddle => {
       if let Some(artists) = &mut app.library.saved_artists.get_results(None) {
         let next_index = common_key_events::on_middle_press_handler(&artists.items);
         app.artists_list_index = next_index;
       }
     }
-    k if common_key_events::low_event(k) => {
+    Action::SelectBottom => {
       if let Some(artists) = &mut app.library.saved_artists.get_results(None) {
         let next_index = common_key_events::on_low_press_handler(&artists.items);
         app.artists_list_index = next_index;
       }
     }
-    Key::Enter => {
+    Action::Submit => {
       let artists = app.artists.to_owned();
       if !artists.is_empty() {
         let artist = &artists[app.artists_list_index];
         app.get_artist(artist.id.as_ref().into_static(), artist.name.clone());
       }
     }
-    Key::Char('D') => app.user_unfollow_artists(ActiveBlock::AlbumList),
-    Key::Char('e') => {
+    Action::Remove => app.user_unfollow_artists(ActiveBlock::AlbumList),
+    Action::PlayArtist => {
       let artists = app.artists.to_owned();
       let artist = artists.get(app.artists_list_index);
       if let Some(artist) = artist {
@@ -60,7 +60,7 @@ pub fn handler(key: Key, app: &mut App) {
         ));
       }
     }
-    Key::Char('r') => {
+    Action::Recommendations => {
       let artists = app.artists.to_owned();
       let artist = artists.get(app.artists_list_index);
       if let Some(artist) = artist {
@@ -72,10 +72,10 @@ pub fn handler(key: Key, app: &mut App) {
         app.get_recommendations_for_seed(artist_id_list, None, None);
       }
     }
-    k if k == app.user_config.keys.next_page => app.get_current_user_saved_artists_next(),
-    k if k == app.user_config.keys.previous_page => app.get_current_user_saved_artists_previous(),
+    Action::NextPage => app.get_current_user_saved_artists_next(),
+    Action::PreviousPage => app.get_current_user_saved_artists_previous(),
     // Open sort menu
-    Key::Char(',') => {
+    Action::OpenSortMenu => {
       super::sort_menu::open_sort_menu(app, crate::core::sort::SortContext::SavedArtists);
     }
     _ => {}
@@ -1,28 +1,45 @@
+use super::lyrics_sync;
 use crate::core::app::App;
+use crate::core::keymap::Action;
 use crate::infra::network::IoEvent;
-use crate::tui::event::Key;
 use rspotify::model::{context::CurrentPlaybackContext, PlayableId, PlayableItem};
 
-pub fn handler(key: Key, app: &mut App) {
-  if let Key::Char('s') = key {
-    if let Some(CurrentPlaybackContext {
-      item: Some(item), ..
-    }) = app.current_playback_context.to_owned()
-    {
-      match item {
-        PlayableItem::Track(track) => {
-          if let Some(track_id) = track.id {
-            app.dispatch(IoEvent::ToggleSaveTrack(PlayableId::Track(
-              track_id.into_static(),
-            )));
-          }
-        }
-        PlayableItem::Episode(episode) => {
-          app.dispatch(IoEvent::ToggleSaveTrack(PlayableId::Episode(
-            episode.id.into_static(),
+const LYRICS_OFFSET_STEP_MS: i64 = 100;
+
+pub fn handler(action: Action, app: &mut App) {
+  if app.lyrics_sync.is_some() {
+    lyrics_sync::handler(action, app);
+    return;
+  }
+
+  match action {
+    Action::Save => toggle_save_current_item(app),
+    Action::LyricsSyncEditor => app.open_lyrics_sync_editor(),
+    Action::SearchLyrics => app.open_lyrics_search(),
+    Action::LyricsEarlier => app.adjust_lyrics_offset(-LYRICS_OFFSET_STEP_MS),
+    Action::LyricsLater => app.adjust_lyrics_offset(LYRICS_OFFSET_STEP_MS),
+    _ => {}
+  }
+}
+
+fn toggle_save_current_item(app: &mut App) {
+  if let Some(CurrentPlaybackContext {
+    item: Some(item), ..
+  }) = app.current_playback_context.to_owned()
+  {
+    match item {
+      PlayableItem::Track(track) => {
+        if let Some(track_id) = track.id {
+          app.dispatch(IoEvent::ToggleSaveTrack(PlayableId::Track(
+            track_id.into_static(),
           )));
         }
-      };
+      }
+      PlayableItem::Episode(episode) => {
+        app.dispatch(IoEvent::ToggleSaveTrack(PlayableId::Episode(
+          episode.id.into_static(),
+        )));
+      }
     };
-  }
+  };
 }
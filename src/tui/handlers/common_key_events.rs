@@ -1,33 +1,4 @@
 use crate::core::app::{ActiveBlock, App, RouteId};
-use crate::tui::event::Key;
-
-pub fn down_event(key: Key) -> bool {
-  matches!(key, Key::Down | Key::Char('j') | Key::Ctrl('n'))
-}
-
-pub fn up_event(key: Key) -> bool {
-  matches!(key, Key::Up | Key::Char('k') | Key::Ctrl('p'))
-}
-
-pub fn left_event(key: Key) -> bool {
-  matches!(key, Key::Left | Key::Char('h') | Key::Ctrl('b'))
-}
-
-pub fn right_event(key: Key) -> bool {
-  matches!(key, Key::Right | Key::Char('l') | Key::Ctrl('f'))
-}
-
-pub fn high_event(key: Key) -> bool {
-  matches!(key, Key::Char('H'))
-}
-
-pub fn middle_event(key: Key) -> bool {
-  matches!(key, Key::Char('M'))
-}
-
-pub fn low_event(key: Key) -> bool {
-  matches!(key, Key::Char('L'))
-}
 
 pub fn on_down_press_handler<T>(selection_data: &[T], selection_index: Option<usize>) -> usize {
   match selection_index {
@@ -134,6 +105,7 @@ pub fn handle_right_event(app: &mut App) {
       RouteId::Error => {}
       RouteId::Analysis => {}
       RouteId::BasicView => {}
+      RouteId::NowPlaying => {}
       RouteId::Dialog => {}
       RouteId::UpdatePrompt => {}
       RouteId::AnnouncementPrompt => {}
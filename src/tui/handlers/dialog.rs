@@ -1,27 +1,32 @@
 use super::common_key_events;
-use crate::core::app::{ActiveBlock, App, DialogContext};
+use crate::core::app::{ActiveBlock, App, DialogContext, LyricsSearchFocus};
+use crate::core::keymap::Action;
+use crate::core::undo::UndoOp;
 use crate::infra::network::IoEvent;
 use crate::tui::event::Key;
 
-pub fn handler(key: Key, app: &mut App) {
+pub fn handler(action: Action, app: &mut App) {
   let dialog_context = match app.get_current_route().active_block {
     ActiveBlock::Dialog(context) => context,
     _ => return,
   };
 
   match dialog_context {
-    DialogContext::AddTrackToPlaylistPicker => handle_add_to_playlist_picker(key, app),
+    DialogContext::AddTrackToPlaylistPicker => handle_add_to_playlist_picker(action, app),
+    DialogContext::AudioOutputPicker => handle_audio_output_picker(action, app),
+    DialogContext::LyricsSearch => handle_lyrics_search_results(action, app),
+    DialogContext::UndoHistory => handle_undo_history(action, app),
     DialogContext::PlaylistWindow
     | DialogContext::PlaylistSearch
     | DialogContext::RemoveTrackFromPlaylistConfirm => {
-      handle_confirmation_dialog(key, app, dialog_context)
+      handle_confirmation_dialog(action, app, dialog_context)
     }
   }
 }
 
-fn handle_confirmation_dialog(key: Key, app: &mut App, dialog_context: DialogContext) {
-  match key {
-    Key::Enter => {
+fn handle_confirmation_dialog(action: Action, app: &mut App, dialog_context: DialogContext) {
+  match action {
+    Action::Submit => {
       if app.confirm {
         match dialog_context {
           DialogContext::PlaylistWindow => handle_playlist_dialog(app),
@@ -29,24 +34,26 @@ fn handle_confirmation_dialog(key: Key, app: &mut App, dialog_context: DialogCon
           DialogContext::RemoveTrackFromPlaylistConfirm => {
             handle_remove_track_from_playlist_confirm(app);
           }
-          DialogContext::AddTrackToPlaylistPicker => {}
+          DialogContext::AddTrackToPlaylistPicker
+          | DialogContext::AudioOutputPicker
+          | DialogContext::LyricsSearch
+          | DialogContext::UndoHistory => {}
         }
       }
       close_dialog(app);
     }
-    Key::Char('q') => {
+    Action::Back => {
       close_dialog(app);
     }
-    k if common_key_events::right_event(k) => app.confirm = !app.confirm,
-    k if common_key_events::left_event(k) => app.confirm = !app.confirm,
+    Action::MoveRight | Action::MoveLeft => app.confirm = !app.confirm,
     _ => {}
   }
 }
 
-fn handle_add_to_playlist_picker(key: Key, app: &mut App) {
+fn handle_add_to_playlist_picker(action: Action, app: &mut App) {
   let playlist_count = app.all_playlists.len();
-  match key {
-    k if common_key_events::down_event(k) => {
+  match action {
+    Action::MoveDown => {
       if playlist_count > 0 {
         let next = common_key_events::on_down_press_handler(
           &app.all_playlists,
@@ -55,7 +62,7 @@ fn handle_add_to_playlist_picker(key: Key, app: &mut App) {
         app.playlist_picker_selected_index = next;
       }
     }
-    k if common_key_events::up_event(k) => {
+    Action::MoveUp => {
       if playlist_count > 0 {
         let next = common_key_events::on_up_press_handler(
           &app.all_playlists,
@@ -64,24 +71,24 @@ fn handle_add_to_playlist_picker(key: Key, app: &mut App) {
         app.playlist_picker_selected_index = next;
       }
     }
-    k if common_key_events::high_event(k) => {
+    Action::SelectTop => {
       if playlist_count > 0 {
         app.playlist_picker_selected_index = common_key_events::on_high_press_handler();
       }
     }
-    k if common_key_events::middle_event(k) => {
+    Action::SelectMiddle => {
       if playlist_count > 0 {
         app.playlist_picker_selected_index =
           common_key_events::on_middle_press_handler(&app.all_playlists);
       }
     }
-    k if common_key_events::low_event(k) => {
+    Action::SelectBottom => {
       if playlist_count > 0 {
         app.playlist_picker_selected_index =
           common_key_events::on_low_press_handler(&app.all_playlists);
       }
     }
-    Key::Enter => {
+    Action::Submit => {
       if let Some(pending_add) = app.pending_playlist_track_add.clone() {
         if let Some(playlist) = app.all_playlists.get(
           app
@@ -96,13 +103,132 @@ fn handle_add_to_playlist_picker(key: Key, app: &mut App) {
       }
       close_dialog(app);
     }
-    Key::Char('q') => {
+    Action::Back => {
       close_dialog(app);
     }
     _ => {}
   }
 }
 
+fn handle_audio_output_picker(action: Action, app: &mut App) {
+  if app.audio_output_choices.is_empty() {
+    if matches!(action, Action::Submit | Action::Back) {
+      close_dialog(app);
+    }
+    return;
+  }
+
+  let choices = &app.audio_output_choices;
+  let selected = Some(app.audio_output_selected_index);
+  match action {
+    Action::MoveDown => {
+      app.audio_output_selected_index = common_key_events::on_down_press_handler(choices, selected);
+    }
+    Action::MoveUp => {
+      app.audio_output_selected_index = common_key_events::on_up_press_handler(choices, selected);
+    }
+    Action::SelectTop => {
+      app.audio_output_selected_index = common_key_events::on_high_press_handler();
+    }
+    Action::SelectMiddle => {
+      app.audio_output_selected_index = common_key_events::on_middle_press_handler(choices);
+    }
+    Action::SelectBottom => {
+      app.audio_output_selected_index = common_key_events::on_low_press_handler(choices);
+    }
+    Action::Submit => {
+      let index = app.audio_output_selected_index;
+      close_dialog(app);
+      app.select_audio_output(index);
+    }
+    Action::Back => {
+      close_dialog(app);
+    }
+    _ => {}
+  }
+}
+
+fn handle_undo_history(action: Action, app: &mut App) {
+  let len = app.undo_history.len();
+  if len == 0 {
+    close_dialog(app);
+    return;
+  }
+  let selected = app.undo_history_selected_index.min(len - 1);
+  match action {
+    Action::MoveDown => app.undo_history_selected_index = (selected + 1) % len,
+    Action::MoveUp => app.undo_history_selected_index = (selected + len - 1) % len,
+    Action::SelectTop => app.undo_history_selected_index = 0,
+    Action::SelectMiddle => app.undo_history_selected_index = (len - 1) / 2,
+    Action::SelectBottom => app.undo_history_selected_index = len - 1,
+    Action::Submit => {
+      app.undo(selected);
+      if app.undo_history.is_empty() {
+        close_dialog(app);
+      } else {
+        app.undo_history_selected_index = selected.min(app.undo_history.len() - 1);
+      }
+    }
+    Action::Back => close_dialog(app),
+    _ => {}
+  }
+}
+
+/// Text fields of the lyrics search take raw keys, the global ones included
+pub fn text_handler(key: Key, app: &mut App) {
+  let search = &mut app.lyrics_search;
+  let field = match search.focus {
+    LyricsSearchFocus::Artist => &mut search.artist,
+    _ => &mut search.title,
+  };
+  match key {
+    Key::Esc => close_dialog(app),
+    Key::Enter => app.search_lyrics(),
+    Key::Tab => {
+      search.focus = match search.focus {
+        LyricsSearchFocus::Title => LyricsSearchFocus::Artist,
+        _ => LyricsSearchFocus::Results,
+      };
+    }
+    Key::Backspace => {
+      field.pop();
+    }
+    Key::Char(c) => field.push(c),
+    _ => {}
+  }
+}
+
+fn handle_lyrics_search_results(action: Action, app: &mut App) {
+  let search = &mut app.lyrics_search;
+  let results = &search.results;
+  let selected = Some(search.selected_index);
+  match action {
+    Action::NextField => search.focus = LyricsSearchFocus::Title,
+    Action::Submit if results.is_empty() => search.focus = LyricsSearchFocus::Title,
+    Action::MoveDown => {
+      search.selected_index = common_key_events::on_down_press_handler(results, selected);
+    }
+    Action::MoveUp => {
+      search.selected_index = common_key_events::on_up_press_handler(results, selected);
+    }
+    Action::SelectTop => {
+      search.selected_index = common_key_events::on_high_press_handler();
+    }
+    Action::SelectMiddle => {
+      search.selected_index = common_key_events::on_middle_press_handler(results);
+    }
+    Action::SelectBottom => {
+      search.selected_index = common_key_events::on_low_press_handler(results);
+    }
+    Action::Submit => {
+      close_dialog(app);
+      app.pin_lyrics_search_result();
+    }
+    Action::Back => close_dialog(app),
+    _ => {}
+  }
+}
+
 fn handle_playlist_dialog(app: &mut App) {
   app.user_unfollow_playlist()
 }
@@ -114,10 +240,17 @@ fn handle_playlist_search_dialog(app: &mut App) {
 fn handle_remove_track_from_playlist_confirm(app: &mut App) {
   if let Some(pending_remove) = app.pending_playlist_track_removal.clone() {
     app.dispatch(IoEvent::RemoveTrackFromPlaylistAtPosition(
-      pending_remove.playlist_id,
-      pending_remove.track_id,
+      pending_remove.playlist_id.clone(),
+      pending_remove.track_id.clone(),
       pending_remove.position,
     ));
+    app.record_undo(UndoOp::RemovedFromPlaylist {
+      playlist_id: pending_remove.playlist_id,
+      playlist_name: pending_remove.playlist_name,
+      track_id: pending_remove.track_id,
+      track_name: pending_remove.track_name,
+      position: pending_remove.position,
+    });
   }
 }
 
@@ -142,10 +275,73 @@ mod tests {
     );
     app.confirm = false;
 
-    handler(Key::Char('l'), &mut app);
+    handler(Action::MoveRight, &mut app);
     assert!(app.confirm);
 
-    handler(Key::Char('h'), &mut app);
+    handler(Action::MoveLeft, &mut app);
     assert!(!app.confirm);
   }
+
+  #[test]
+  fn lyrics_search_fields_take_global_keys_as_text() {
+    let mut app = App::default();
+    app.push_navigation_stack(
+      RouteId::Dialog,
+      ActiveBlock::Dialog(DialogContext::LyricsSearch),
+    );
+    assert!(app.is_editing_lyrics_search());
+
+    for c in "q a".chars() {
+      text_handler(Key::Char(c), &mut app);
+    }
+    text_handler(Key::Backspace, &mut app);
+    assert_eq!(app.lyrics_search.title, "q ");
+
+    text_handler(Key::Tab, &mut app);
+    text_handler(Key::Char('x'), &mut app);
+    assert_eq!(app.lyrics_search.artist, "x");
+
+    text_handler(Key::Enter, &mut app);
+    assert_eq!(app.lyrics_search.focus, LyricsSearchFocus::Results);
+    assert!(app.lyrics_search.searching);
+    assert!(!app.is_editing_lyrics_search());
+  }
+
+  #[test]
+  fn confirmed_removals_can_be_undone_from_the_history() {
+    use crate::core::app::PendingPlaylistTrackRemoval;
+    use rspotify::model::idtypes::{PlaylistId, TrackId};
+
+    let mut app = App::default();
+    app.pending_playlist_track_removal = Some(PendingPlaylistTrackRemoval {
+      playlist_id: PlaylistId::from_id("37i9dQZF1DXcBWIGoYBM5M").unwrap(),
+      playlist_name: "Mix".to_string(),
+      track_id: TrackId::from_id("4uLU6hMCjMI75M1A2tKUQC").unwrap(),
+      track_name: "Song".to_string(),
+      position: 7,
+    });
+    app.push_navigation_stack(
+      RouteId::Dialog,
+      ActiveBlock::Dialog(DialogContext::RemoveTrackFromPlaylistConfirm),
+    );
+    app.confirm = true;
+    handler(Action::Submit, &mut app);
+    assert_eq!(
+      app.status_message.as_deref(),
+      Some("Removed Song from Mix — press u to undo")
+    );
+
+    app.open_undo_history();
+    assert_eq!(
+      app.get_current_route().active_block,
+      ActiveBlock::Dialog(DialogContext::UndoHistory)
+    );
+    handler(Action::Submit, &mut app);
+    assert!(app.undo_history.is_empty());
+    assert_eq!(app.get_current_route().id, RouteId::Home);
+    assert_eq!(
+      app.status_message.as_deref(),
+      Some("Undone: Removed Song from Mix")
+    );
+  }
 }
@@ -1,15 +1,15 @@
 use super::common_key_events;
 use crate::core::app::{ActiveBlock, App, RouteId, TrackTableContext};
+use crate::core::keymap::Action;
 use crate::infra::network::IoEvent;
-use crate::tui::event::Key;
 use rspotify::model::PlayableId;
 
 const DISCOVER_OPTIONS_COUNT: usize = 2;
 
-pub fn handler(key: Key, app: &mut App) {
-  match key {
-    k if common_key_events::left_event(k) => common_key_events::handle_left_event(app),
-    k if common_key_events::down_event(k) => {
+pub fn handler(action: Action, app: &mut App) {
+  match action {
+    Action::MoveLeft => common_key_events::handle_left_event(app),
+    Action::MoveDown => {
       let next_index = if app.discover_selected_index >= DISCOVER_OPTIONS_COUNT - 1 {
         0
       } else {
@@ -17,7 +17,7 @@ pub fn handler(key: Key, app: &mut App) {
       };
       app.discover_selected_index = next_index;
     }
-    k if common_key_events::up_event(k) => {
+    Action::MoveUp => {
       let next_index = if app.discover_selected_index == 0 {
         DISCOVER_OPTIONS_COUNT - 1
       } else {
@@ -26,7 +26,7 @@ pub fn handler(key: Key, app: &mut App) {
       app.discover_selected_index = next_index;
     }
     // Left/Right to cycle time range (for Top Tracks)
-    k if common_key_events::right_event(k) => {
+    Action::MoveRight => {
       if app.discover_selected_index == 1 {
         // Only cycle time range when Top Tracks is selected
         app.discover_time_range = app.discover_time_range.next();
@@ -34,19 +34,19 @@ pub fn handler(key: Key, app: &mut App) {
         app.discover_top_tracks.clear();
       }
     }
-    Key::Char('[') => {
+    Action::PreviousTimeRange => {
       if app.discover_selected_index == 1 {
         app.discover_time_range = app.discover_time_range.prev();
         app.discover_top_tracks.clear();
       }
     }
-    Key::Char(']') => {
+    Action::NextTimeRange => {
       if app.discover_selected_index == 1 {
         app.discover_time_range = app.discover_time_range.next();
         app.discover_top_tracks.clear();
       }
     }
-    Key::Enter => {
+    Action::Submit => {
       if app.discover_loading {
         return; // Don't process Enter while loading
       }
@@ -58,6 +58,7 @@ pub fn handler(key: Key, app: &mut App) {
           } else {
             // Mix already loaded, show it
             app.track_table.tracks = app.discover_artists_mix.clone();
+            app.track_table.added.clear();
             app.track_table.context = Some(TrackTableContext::DiscoverPlaylist);
             app.track_table.selected_index = 0;
             app.push_navigation_stack(RouteId::TrackTable, ActiveBlock::TrackTable);
@@ -70,6 +71,7 @@ pub fn handler(key: Key, app: &mut App) {
           } else {
             // Tracks already loaded, show them
             app.track_table.tracks = app.discover_top_tracks.clone();
+            app.track_table.added.clear();
             app.track_table.context = Some(TrackTableContext::DiscoverPlaylist);
             app.track_table.selected_index = 0;
             app.push_navigation_stack(RouteId::TrackTable, ActiveBlock::TrackTable);
@@ -78,7 +80,7 @@ pub fn handler(key: Key, app: &mut App) {
         _ => {}
       }
     }
-    _ if key == app.user_config.keys.add_item_to_queue => {
+    Action::AddItemToQueue => {
       // Add selected track from top tracks to queue if available
       let tracks = match app.discover_selected_index {
         0 => &app.discover_artists_mix,
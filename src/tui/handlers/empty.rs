@@ -1,17 +1,17 @@
 use super::common_key_events;
 use crate::{
   app::{ActiveBlock, App},
-  event::Key,
+  core::keymap::Action,
 };
 
 // When no block is actively selected, just handle regular event
-pub fn handler(key: Key, app: &mut App) {
-  match key {
-    Key::Enter => {
+pub fn handler(action: Action, app: &mut App) {
+  match action {
+    Action::Submit => {
       let current_hovered = app.get_current_route().hovered_block;
       app.set_current_route_state(Some(current_hovered), None);
     }
-    k if common_key_events::down_event(k) => match app.get_current_route().hovered_block {
+    Action::MoveDown => match app.get_current_route().hovered_block {
       ActiveBlock::Library => {
         app.set_current_route_state(None, Some(ActiveBlock::MyPlaylists));
       }
@@ -30,7 +30,7 @@ pub fn handler(key: Key, app: &mut App) {
       }
       _ => {}
     },
-    k if common_key_events::up_event(k) => match app.get_current_route().hovered_block {
+    Action::MoveUp => match app.get_current_route().hovered_block {
       ActiveBlock::MyPlaylists => {
         app.set_current_route_state(None, Some(ActiveBlock::Library));
       }
@@ -39,7 +39,7 @@ pub fn handler(key: Key, app: &mut App) {
       }
       _ => {}
     },
-    k if common_key_events::left_event(k) => match app.get_current_route().hovered_block {
+    Action::MoveLeft => match app.get_current_route().hovered_block {
       ActiveBlock::ArtistBlock
       | ActiveBlock::AlbumList
       | ActiveBlock::AlbumTracks
@@ -54,10 +54,10 @@ pub fn handler(key: Key, app: &mut App) {
       }
       _ => {}
     },
-    k if common_key_events::right_event(k) => common_key_events::handle_right_event(app),
-    Key::Char('w') => {
+    Action::MoveRight => common_key_events::handle_right_event(app),
+    Action::AddToPlaylist => {
       if app.get_current_route().hovered_block == ActiveBlock::PlayBar {
-        super::playbar::handler(key, app);
+        super::playbar::handler(action, app);
       }
     }
     _ => (),
@@ -75,7 +75,7 @@ mod tests {
 
     app.set_current_route_state(Some(ActiveBlock::Empty), Some(ActiveBlock::Library));
 
-    handler(Key::Enter, &mut app);
+    handler(Action::Submit, &mut app);
     let current_route = app.get_current_route();
 
     assert_eq!(current_route.active_block, ActiveBlock::Library);
@@ -88,7 +88,7 @@ mod tests {
 
     app.set_current_route_state(Some(ActiveBlock::Empty), Some(ActiveBlock::Library));
 
-    handler(Key::Down, &mut app);
+    handler(Action::MoveDown, &mut app);
     let current_route = app.get_current_route();
 
     assert_eq!(current_route.active_block, ActiveBlock::Empty);
@@ -103,7 +103,7 @@ mod tests {
 
     app.set_current_route_state(Some(ActiveBlock::Empty), Some(ActiveBlock::MyPlaylists));
 
-    handler(Key::Up, &mut app);
+    handler(Action::MoveUp, &mut app);
     let current_route = app.get_current_route();
 
     assert_eq!(current_route.active_block, ActiveBlock::Empty);
@@ -115,18 +115,18 @@ mod tests {
     let mut app = App::default();
     app.set_current_route_state(Some(ActiveBlock::Empty), Some(ActiveBlock::AlbumTracks));
 
-    handler(Key::Left, &mut app);
+    handler(Action::MoveLeft, &mut app);
     let current_route = app.get_current_route();
     assert_eq!(current_route.active_block, ActiveBlock::Empty);
     assert_eq!(current_route.hovered_block, ActiveBlock::Library);
 
     app.set_current_route_state(None, Some(ActiveBlock::Home));
-    handler(Key::Left, &mut app);
+    handler(Action::MoveLeft, &mut app);
     let current_route = app.get_current_route();
     assert_eq!(current_route.hovered_block, ActiveBlock::Library);
 
     app.set_current_route_state(None, Some(ActiveBlock::TrackTable));
-    handler(Key::Left, &mut app);
+    handler(Action::MoveLeft, &mut app);
     let current_route = app.get_current_route();
     assert_eq!(current_route.hovered_block, ActiveBlock::Library);
   }
@@ -137,7 +137,7 @@ mod tests {
 
     app.set_current_route_state(Some(ActiveBlock::Empty), Some(ActiveBlock::Library));
     app.push_navigation_stack(RouteId::AlbumTracks, ActiveBlock::AlbumTracks);
-    handler(Key::Right, &mut app);
+    handler(Action::MoveRight, &mut app);
     let current_route = app.get_current_route();
 
     assert_eq!(current_route.active_block, ActiveBlock::AlbumTracks);
@@ -145,7 +145,7 @@ mod tests {
 
     app.push_navigation_stack(RouteId::Search, ActiveBlock::Empty);
     app.set_current_route_state(None, Some(ActiveBlock::MyPlaylists));
-    handler(Key::Right, &mut app);
+    handler(Action::MoveRight, &mut app);
     let current_route = app.get_current_route();
 
     assert_eq!(current_route.active_block, ActiveBlock::SearchResultBlock);
@@ -153,7 +153,7 @@ mod tests {
 
     app.set_current_route_state(None, Some(ActiveBlock::Library));
     app.push_navigation_stack(RouteId::TrackTable, ActiveBlock::TrackTable);
-    handler(Key::Right, &mut app);
+    handler(Action::MoveRight, &mut app);
     let current_route = app.get_current_route();
 
     assert_eq!(current_route.active_block, ActiveBlock::TrackTable);
@@ -161,14 +161,14 @@ mod tests {
 
     app.set_current_route_state(None, Some(ActiveBlock::Library));
     app.push_navigation_stack(RouteId::TrackTable, ActiveBlock::TrackTable);
-    handler(Key::Right, &mut app);
+    handler(Action::MoveRight, &mut app);
     let current_route = app.get_current_route();
     assert_eq!(current_route.active_block, ActiveBlock::TrackTable);
     assert_eq!(current_route.hovered_block, ActiveBlock::TrackTable);
 
     app.push_navigation_stack(RouteId::Home, ActiveBlock::Home);
     app.set_current_route_state(Some(ActiveBlock::Empty), Some(ActiveBlock::Library));
-    handler(Key::Right, &mut app);
+    handler(Action::MoveRight, &mut app);
     let current_route = app.get_current_route();
     assert_eq!(current_route.active_block, ActiveBlock::Home);
     assert_eq!(current_route.hovered_block, ActiveBlock::Home);
@@ -179,7 +179,7 @@ mod tests {
     let mut app = App::default();
     app.set_current_route_state(Some(ActiveBlock::Empty), Some(ActiveBlock::PlayBar));
 
-    handler(Key::Char('w'), &mut app);
+    handler(Action::AddToPlaylist, &mut app);
 
     assert_eq!(
       app.status_message.as_deref(),
@@ -1,57 +1,57 @@
 use super::common_key_events;
 use crate::core::app::ActiveBlock;
 use crate::core::app::{App, EpisodeTableContext};
+use crate::core::keymap::Action;
 use crate::infra::network::IoEvent;
-use crate::tui::event::Key;
 use rspotify::{model::PlayableId, prelude::*};
 
-pub fn handler(key: Key, app: &mut App) {
-  match key {
-    k if common_key_events::left_event(k) => common_key_events::handle_left_event(app),
-    k if common_key_events::down_event(k) => {
+pub fn handler(action: Action, app: &mut App) {
+  match action {
+    Action::MoveLeft => common_key_events::handle_left_event(app),
+    Action::MoveDown => {
       if let Some(episodes) = &mut app.library.show_episodes.get_results(None) {
         let next_index =
           common_key_events::on_down_press_handler(&episodes.items, Some(app.episode_list_index));
         app.episode_list_index = next_index;
       }
     }
-    k if common_key_events::up_event(k) => {
+    Action::MoveUp => {
       if let Some(episodes) = &mut app.library.show_episodes.get_results(None) {
         let next_index =
           common_key_events::on_up_press_handler(&episodes.items, Some(app.episode_list_index));
         app.episode_list_index = next_index;
       }
     }
-    k if common_key_events::high_event(k) => {
+    Action::SelectTop => {
       if let Some(_episodes) = app.library.show_episodes.get_results(None) {
         let next_index = common_key_events::on_high_press_handler();
         app.episode_list_index = next_index;
       }
     }
-    k if common_key_events::middle_event(k) => {
+    Action::SelectMiddle => {
       if let Some(episodes) = app.library.show_episodes.get_results(None) {
         let next_index = common_key_events::on_middle_press_handler(&episodes.items);
         app.episode_list_index = next_index;
       }
     }
-    k if common_key_events::low_event(k) => {
+    Action::SelectBottom => {
       if let Some(episodes) = app.library.show_episodes.get_results(None) {
         let next_index = common_key_events::on_low_press_handler(&episodes.items);
         app.episode_list_index = next_index;
       }
     }
-    Key::Enter => {
+    Action::Submit => {
       on_enter(app);
     }
     // Scroll down
-    k if k == app.user_config.keys.next_page => handle_next_event(app),
+    Action::NextPage => handle_next_event(app),
     // Scroll up
-    k if k == app.user_config.keys.previous_page => handle_prev_event(app),
-    Key::Char('S') => toggle_sort_by_date(app),
-    Key::Char('s') => handle_follow_event(app),
-    Key::Char('D') => handle_unfollow_event(app),
-    Key::Ctrl('e') => jump_to_end(app),
-    Key::Ctrl('a') => jump_to_start(app),
+    Action::PreviousPage => handle_prev_event(app),
+    Action::ReverseOrder => toggle_sort_by_date(app),
+    Action::Follow => handle_follow_event(app),
+    Action::Remove => handle_unfollow_event(app),
+    Action::JumpToEnd => jump_to_end(app),
+    Action::JumpToStart => jump_to_start(app),
     _ => {}
   }
 }
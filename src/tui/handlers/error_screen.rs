@@ -1,3 +1,3 @@
-use crate::{app::App, event::Key};
+use crate::{app::App, core::keymap::Action};
 
-pub fn handler(_key: Key, _app: &mut App) {}
+pub fn handler(_action: Action, _app: &mut App) {}
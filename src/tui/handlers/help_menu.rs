@@ -1,5 +1,4 @@
-use super::common_key_events;
-use crate::{app::App, event::Key};
+use crate::{app::App, core::keymap::Action, tui::event::Key};
 
 #[derive(PartialEq)]
 enum Direction {
@@ -7,24 +6,56 @@ enum Direction {
   Down,
 }
 
-pub fn handler(key: Key, app: &mut App) {
-  match key {
-    k if common_key_events::down_event(k) => {
+pub fn handler(action: Action, app: &mut App) {
+  match action {
+    Action::MoveDown => {
       move_page(Direction::Down, app);
     }
-    k if common_key_events::up_event(k) => {
+    Action::MoveUp => {
       move_page(Direction::Up, app);
     }
-    Key::Ctrl('d') => {
+    Action::NextPage => {
       move_page(Direction::Down, app);
     }
-    Key::Ctrl('u') => {
+    Action::PreviousPage => {
       move_page(Direction::Up, app);
     }
+    Action::FilterHelp => {
+      app.help_filter_editing = true;
+    }
     _ => {}
   };
 }
 
+/// Typing into the help search, the rows narrow down with every key
+pub fn text_handler(key: Key, app: &mut App) {
+  match key {
+    Key::Char(c) => app.help_filter.push(c),
+    Key::Backspace => {
+      app.help_filter.pop();
+    }
+    Key::Ctrl('u') => app.help_filter.clear(),
+    // Keep the results and go back to scrolling them
+    Key::Enter => {
+      app.help_filter_editing = false;
+      return;
+    }
+    Key::Esc => {
+      app.help_filter.clear();
+      app.help_filter_editing = false;
+    }
+    _ => return,
+  }
+  reset_help_menu(app);
+}
+
+/// Back to the first page of whatever the current search matches
+pub fn reset_help_menu(app: &mut App) {
+  app.update_help_docs_size();
+  app.help_menu_page = 0;
+  app.help_menu_offset = 0;
+}
+
 fn move_page(direction: Direction, app: &mut App) {
   if direction == Direction::Up {
     if app.help_menu_page > 0 {
@@ -41,6 +72,8 @@ mod tests {
   use super::*;
   use crate::core::app::{ActiveBlock, RouteId};
   use crate::handlers::handle_app;
+  use crate::tui::event::Key;
+  use crate::tui::ui::help::visible_help_docs;
 
   #[test]
   fn test_help_menu_pagination() {
@@ -49,28 +82,28 @@ mod tests {
     app.help_menu_max_lines = 10;
 
     // Test down navigation
-    handler(Key::Down, &mut app);
+    handler(Action::MoveDown, &mut app);
     assert_eq!(app.help_menu_page, 1);
     assert_eq!(app.help_menu_offset, 10);
 
-    handler(Key::Char('j'), &mut app);
+    handler(Action::MoveDown, &mut app);
     assert_eq!(app.help_menu_page, 2);
     assert_eq!(app.help_menu_offset, 20);
 
-    handler(Key::Ctrl('d'), &mut app);
+    handler(Action::NextPage, &mut app);
     assert_eq!(app.help_menu_page, 3);
     assert_eq!(app.help_menu_offset, 30);
 
     // Test up navigation
-    handler(Key::Up, &mut app);
+    handler(Action::MoveUp, &mut app);
     assert_eq!(app.help_menu_page, 2);
     assert_eq!(app.help_menu_offset, 20);
 
-    handler(Key::Char('k'), &mut app);
+    handler(Action::MoveUp, &mut app);
     assert_eq!(app.help_menu_page, 1);
     assert_eq!(app.help_menu_offset, 10);
 
-    handler(Key::Ctrl('u'), &mut app);
+    handler(Action::PreviousPage, &mut app);
     assert_eq!(app.help_menu_page, 0);
     assert_eq!(app.help_menu_offset, 0);
   }
@@ -96,12 +129,43 @@ mod tests {
     handle_app(Key::Char('?'), &mut app);
     assert_eq!(app.get_current_route().id, RouteId::HelpMenu);
 
-    // Close help menu with 'q' (simulating the back key handling in main.rs)
-    let back_key = app.user_config.keys.back;
-    assert_eq!(back_key, Key::Char('q'));
+    // Close help menu with 'q', the default back key
+    assert_eq!(
+      app.user_config.keys.action("help_menu", Key::Char('q')),
+      Some(Action::Back)
+    );
 
-    let pop_result = app.pop_navigation_stack();
-    assert!(pop_result.is_some());
+    handle_app(Key::Char('q'), &mut app);
+    assert!(!app.should_quit);
     assert_eq!(app.get_current_route().id, RouteId::Home);
   }
+
+  #[test]
+  fn search_narrows_down_and_esc_clears_it() {
+    let mut app = App::default();
+    handle_app(Key::Char('?'), &mut app);
+    app.help_menu_page = 2;
+
+    handle_app(Key::Char('/'), &mut app);
+    assert!(app.help_filter_editing);
+    for c in "qu".chars() {
+      handle_app(Key::Char(c), &mut app);
+    }
+    assert_eq!(app.help_filter, "qu");
+    assert_eq!(app.help_menu_page, 0);
+    assert_eq!(app.help_docs_size as usize, visible_help_docs(&app).len());
+    assert!(visible_help_docs(&app).iter().all(|doc| doc.matches("qu")));
+    assert_eq!(app.get_current_route().id, RouteId::HelpMenu);
+
+    // Enter keeps the filter, so j scrolls instead of typing
+    handle_app(Key::Enter, &mut app);
+    handle_app(Key::Char('j'), &mut app);
+    assert_eq!(app.help_filter, "qu");
+
+    handle_app(Key::Char('/'), &mut app);
+    handle_app(Key::Esc, &mut app);
+    assert!(app.help_filter.is_empty());
+    assert!(!app.help_filter_editing);
+    assert_eq!(app.get_current_route().id, RouteId::HelpMenu);
+  }
 }
@@ -1,25 +1,25 @@
 use super::common_key_events;
 use crate::core::app::App;
-use crate::tui::event::Key;
+use crate::core::keymap::Action;
 
 const LARGE_SCROLL: u16 = 10;
 const SMALL_SCROLL: u16 = 1;
 
-pub fn handler(key: Key, app: &mut App) {
-  match key {
-    k if common_key_events::left_event(k) => common_key_events::handle_left_event(app),
-    k if common_key_events::down_event(k) => {
+pub fn handler(action: Action, app: &mut App) {
+  match action {
+    Action::MoveLeft => common_key_events::handle_left_event(app),
+    Action::MoveDown => {
       app.home_scroll += SMALL_SCROLL;
     }
-    k if common_key_events::up_event(k) => {
+    Action::MoveUp => {
       if app.home_scroll > 0 {
         app.home_scroll -= SMALL_SCROLL;
       }
     }
-    k if k == app.user_config.keys.next_page => {
+    Action::NextPage => {
       app.home_scroll += LARGE_SCROLL;
     }
-    k if k == app.user_config.keys.previous_page => {
+    Action::PreviousPage => {
       if app.home_scroll > LARGE_SCROLL {
         app.home_scroll -= LARGE_SCROLL;
       } else {
@@ -38,10 +38,10 @@ mod tests {
   fn on_small_down_press() {
     let mut app = App::default();
 
-    handler(Key::Down, &mut app);
+    handler(Action::MoveDown, &mut app);
     assert_eq!(app.home_scroll, SMALL_SCROLL);
 
-    handler(Key::Down, &mut app);
+    handler(Action::MoveDown, &mut app);
     assert_eq!(app.home_scroll, SMALL_SCROLL * 2);
   }
 
@@ -49,18 +49,18 @@ mod tests {
   fn on_small_up_press() {
     let mut app = App::default();
 
-    handler(Key::Up, &mut app);
+    handler(Action::MoveUp, &mut app);
     assert_eq!(app.home_scroll, 0);
 
     app.home_scroll = 1;
 
-    handler(Key::Up, &mut app);
+    handler(Action::MoveUp, &mut app);
     assert_eq!(app.home_scroll, 0);
 
     // Check that smashing the up button doesn't go to negative scroll (which would cause a crash)
-    handler(Key::Up, &mut app);
-    handler(Key::Up, &mut app);
-    handler(Key::Up, &mut app);
+    handler(Action::MoveUp, &mut app);
+    handler(Action::MoveUp, &mut app);
+    handler(Action::MoveUp, &mut app);
     assert_eq!(app.home_scroll, 0);
   }
 
@@ -68,10 +68,10 @@ mod tests {
   fn on_large_down_press() {
     let mut app = App::default();
 
-    handler(Key::Ctrl('d'), &mut app);
+    handler(Action::NextPage, &mut app);
     assert_eq!(app.home_scroll, LARGE_SCROLL);
 
-    handler(Key::Ctrl('d'), &mut app);
+    handler(Action::NextPage, &mut app);
     assert_eq!(app.home_scroll, LARGE_SCROLL * 2);
   }
 
@@ -82,16 +82,16 @@ mod tests {
     let scroll = 37;
     app.home_scroll = scroll;
 
-    handler(Key::Ctrl('u'), &mut app);
+    handler(Action::PreviousPage, &mut app);
     assert_eq!(app.home_scroll, scroll - LARGE_SCROLL);
 
-    handler(Key::Ctrl('u'), &mut app);
+    handler(Action::PreviousPage, &mut app);
     assert_eq!(app.home_scroll, scroll - LARGE_SCROLL * 2);
 
     // Check that smashing the up button doesn't go to negative scroll (which would cause a crash)
-    handler(Key::Ctrl('u'), &mut app);
-    handler(Key::Ctrl('u'), &mut app);
-    handler(Key::Ctrl('u'), &mut app);
+    handler(Action::PreviousPage, &mut app);
+    handler(Action::PreviousPage, &mut app);
+    handler(Action::PreviousPage, &mut app);
     assert_eq!(app.home_scroll, 0);
   }
 }
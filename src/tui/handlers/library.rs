@@ -1,38 +1,38 @@
 use super::common_key_events;
 use crate::core::app::{ActiveBlock, App, RouteId, LIBRARY_OPTIONS};
+use crate::core::keymap::Action;
 use crate::infra::network::IoEvent;
-use crate::tui::event::Key;
 
-pub fn handler(key: Key, app: &mut App) {
-  match key {
-    k if common_key_events::right_event(k) => common_key_events::handle_right_event(app),
-    k if common_key_events::down_event(k) => {
+pub fn handler(action: Action, app: &mut App) {
+  match action {
+    Action::MoveRight => common_key_events::handle_right_event(app),
+    Action::MoveDown => {
       let next_index = common_key_events::on_down_press_handler(
         &LIBRARY_OPTIONS,
         Some(app.library.selected_index),
       );
       app.library.selected_index = next_index;
     }
-    k if common_key_events::up_event(k) => {
+    Action::MoveUp => {
       let next_index =
         common_key_events::on_up_press_handler(&LIBRARY_OPTIONS, Some(app.library.selected_index));
       app.library.selected_index = next_index;
     }
-    k if common_key_events::high_event(k) => {
+    Action::SelectTop => {
       let next_index = common_key_events::on_high_press_handler();
       app.library.selected_index = next_index;
     }
-    k if common_key_events::middle_event(k) => {
+    Action::SelectMiddle => {
       let next_index = common_key_events::on_middle_press_handler(&LIBRARY_OPTIONS);
       app.library.selected_index = next_index;
     }
-    k if common_key_events::low_event(k) => {
+    Action::SelectBottom => {
       let next_index = common_key_events::on_low_press_handler(&LIBRARY_OPTIONS);
       app.library.selected_index = next_index
     }
     // `library` should probably be an array of structs with enums rather than just using indexes
     // like this
-    Key::Enter => match app.library.selected_index {
+    Action::Submit => match app.library.selected_index {
       0 => {
         app.push_navigation_stack(RouteId::Discover, ActiveBlock::Discover);
       }
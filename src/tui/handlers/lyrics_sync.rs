@@ -0,0 +1,111 @@
+use super::common_key_events;
+use crate::core::app::App;
+use crate::core::keymap::Action;
+
+const NUDGE_STEP_MS: i64 = 100;
+
+/// Actions the editor acts on, taken before the global bindings while it is open
+pub fn handles(action: Action) -> bool {
+  matches!(
+    action,
+    Action::Submit
+      | Action::MoveDown
+      | Action::MoveUp
+      | Action::LyricsEarlier
+      | Action::LyricsLater
+      | Action::ClearLyricsStamp
+      | Action::PreviewLyricsSync
+      | Action::SaveLyricsSync
+      | Action::LyricsSyncEditor
+      | Action::Back
+  )
+}
+
+pub fn handler(action: Action, app: &mut App) {
+  let Some(editor) = app.lyrics_sync.as_mut() else {
+    return;
+  };
+
+  match action {
+    Action::Submit => app.stamp_lyrics_line(),
+    Action::MoveDown => {
+      editor.selected_index =
+        common_key_events::on_down_press_handler(&editor.lines, Some(editor.selected_index));
+    }
+    Action::MoveUp => {
+      editor.selected_index =
+        common_key_events::on_up_press_handler(&editor.lines, Some(editor.selected_index));
+    }
+    Action::LyricsEarlier => editor.nudge(-NUDGE_STEP_MS),
+    Action::LyricsLater => editor.nudge(NUDGE_STEP_MS),
+    Action::ClearLyricsStamp => editor.clear(),
+    Action::PreviewLyricsSync => editor.previewing = !editor.previewing,
+    Action::SaveLyricsSync => app.save_lyrics_sync(),
+    // Closing the editor goes back to the lyrics it was opened from
+    Action::LyricsSyncEditor | Action::Back => app.lyrics_sync = None,
+    _ => {}
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::app::LyricsSyncEditor;
+
+  #[test]
+  fn stamps_advance_and_can_be_fixed() {
+    let mut app = App::default();
+    app.last_track_id = Some("track".to_string());
+    app.lyrics_sync = Some(LyricsSyncEditor {
+      track_id: "track".to_string(),
+      lines: vec!["one".to_string(), "two".to_string()],
+      stamps: vec![None, None],
+      ..Default::default()
+    });
+
+    app.song_progress_ms = 1_000;
+    handler(Action::Submit, &mut app);
+    app.song_progress_ms = 2_500;
+    handler(Action::Submit, &mut app);
+    handler(Action::LyricsLater, &mut app);
+
+    let editor = app.lyrics_sync.as_ref().unwrap();
+    assert_eq!(editor.stamps, vec![Some(1_000), Some(2_600)]);
+    assert_eq!(editor.unstamped_count(), 0);
+
+    handler(Action::MoveUp, &mut app);
+    handler(Action::ClearLyricsStamp, &mut app);
+    assert_eq!(app.lyrics_sync.as_ref().unwrap().stamps[0], None);
+  }
+
+  #[test]
+  fn stamps_with_native_track_uris() {
+    let mut app = App::default();
+    app.last_track_id = Some("spotify:track:track".to_string());
+    app.lyrics_sync = Some(LyricsSyncEditor {
+      track_id: "track".to_string(),
+      lines: vec!["one".to_string()],
+      stamps: vec![None],
+      ..Default::default()
+    });
+
+    app.song_progress_ms = 1_000;
+    handler(Action::Submit, &mut app);
+    assert_eq!(app.lyrics_sync.as_ref().unwrap().stamps, vec![Some(1_000)]);
+  }
+
+  #[test]
+  fn does_not_stamp_another_track() {
+    let mut app = App::default();
+    app.last_track_id = Some("other".to_string());
+    app.lyrics_sync = Some(LyricsSyncEditor {
+      track_id: "track".to_string(),
+      lines: vec!["one".to_string()],
+      stamps: vec![None],
+      ..Default::default()
+    });
+
+    handler(Action::Submit, &mut app);
+    assert_eq!(app.lyrics_sync.as_ref().unwrap().stamps, vec![None]);
+  }
+}
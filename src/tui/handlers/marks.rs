@@ -0,0 +1,195 @@
+use crate::core::app::{
+  ActiveBlock, App, Mark, MarkPrompt, PendingTrackSelection, TrackTableContext, LIBRARY_OPTIONS,
+};
+use crate::infra::network::IoEvent;
+use crate::tui::event::Key;
+
+/// Takes the letter typed after `m` or `'`, anything else gives up on the mark
+pub fn handler(key: Key, app: &mut App) {
+  let prompt = app.mark_prompt.take();
+  app.status_message = None;
+  let letter = match key {
+    Key::Char(letter) if letter.is_ascii_alphabetic() => letter,
+    _ => return,
+  };
+  match prompt {
+    Some(MarkPrompt::Set) => set_mark(app, letter),
+    Some(MarkPrompt::Jump) => jump_to_mark(app, letter),
+    None => {}
+  }
+}
+
+fn set_mark(app: &mut App, letter: char) {
+  let route = app.get_current_route().clone();
+  let row = match selected_row(app, route.active_block) {
+    Some(row) => row,
+    None => {
+      app.set_status_message("Nothing to mark here".to_string(), 3);
+      return;
+    }
+  };
+  let playlist = match (route.active_block, &app.track_table.context) {
+    (ActiveBlock::TrackTable, Some(TrackTableContext::MyPlaylists)) => app
+      .active_playlist_index
+      .and_then(|index| app.all_playlists.get(index))
+      .map(|playlist| (playlist.id.clone().into_static(), app.playlist_offset)),
+    _ => None,
+  };
+  app.marks.insert(
+    letter,
+    Mark {
+      route,
+      row,
+      playlist,
+    },
+  );
+  app.set_status_message(format!("Marked row {} as '{}", row + 1, letter), 3);
+}
+
+fn jump_to_mark(app: &mut App, letter: char) {
+  let mark = match app.marks.get(&letter) {
+    Some(mark) => mark.clone(),
+    None => {
+      app.set_status_message(format!("Mark '{} is not set", letter), 3);
+      return;
+    }
+  };
+
+  if let Some((playlist_id, offset)) = mark.playlist {
+    let index = match app
+      .all_playlists
+      .iter()
+      .position(|playlist| playlist.id == playlist_id)
+    {
+      Some(index) => index,
+      None => {
+        app.set_status_message(format!("The playlist of mark '{} is gone", letter), 3);
+        return;
+      }
+    };
+    let showing = app.track_table.context == Some(TrackTableContext::MyPlaylists)
+      && app.active_playlist_index == Some(index)
+      && app.playlist_offset == offset;
+    if !showing {
+      // Loading the page opens the track table and selects the row once it arrives
+      app.active_playlist_index = Some(index);
+      app.track_table.context = Some(TrackTableContext::MyPlaylists);
+      app.playlist_offset = offset;
+      app.pending_track_table_selection = Some(PendingTrackSelection::Index(mark.row));
+      app.dispatch(IoEvent::GetPlaylistItems(playlist_id, offset));
+      return;
+    }
+  }
+
+  if app.get_current_route().id != mark.route.id {
+    app.push_navigation_stack(mark.route.id.clone(), mark.route.active_block);
+  }
+  app.set_current_route_state(
+    Some(mark.route.active_block),
+    Some(mark.route.hovered_block),
+  );
+  select_row(app, mark.route.active_block, mark.row);
+}
+
+/// Number of rows in the list shown by `block`, `None` for blocks that are not plain lists
+fn list_len(app: &App, block: ActiveBlock) -> Option<usize> {
+  match block {
+    ActiveBlock::TrackTable => Some(app.track_table.tracks.len()),
+    ActiveBlock::MyPlaylists => Some(app.get_playlist_display_count()),
+    ActiveBlock::Library => Some(LIBRARY_OPTIONS.len()),
+    ActiveBlock::EpisodeTable => app
+      .library
+      .show_episodes
+      .get_results(None)
+      .map(|episodes| episodes.items.len()),
+    ActiveBlock::Artists => Some(app.artists.len()),
+    ActiveBlock::AlbumList => app
+      .library
+      .saved_albums
+      .get_results(None)
+      .map(|albums| albums.items.len()),
+    ActiveBlock::Podcasts => app
+      .library
+      .saved_shows
+      .get_results(None)
+      .map(|shows| shows.items.len()),
+    ActiveBlock::RecentlyPlayed => app
+      .recently_played
+      .result
+      .as_ref()
+      .map(|result| result.items.len()),
+    _ => None,
+  }
+}
+
+pub fn selected_row(app: &App, block: ActiveBlock) -> Option<usize> {
+  list_len(app, block)?;
+  match block {
+    ActiveBlock::TrackTable => Some(app.track_table.selected_index),
+    ActiveBlock::MyPlaylists => app.selected_playlist_index,
+    ActiveBlock::Library => Some(app.library.selected_index),
+    ActiveBlock::EpisodeTable => Some(app.episode_list_index),
+    ActiveBlock::Artists => Some(app.artists_list_index),
+    ActiveBlock::AlbumList => Some(app.album_list_index),
+    ActiveBlock::Podcasts => Some(app.shows_list_index),
+    ActiveBlock::RecentlyPlayed => Some(app.recently_played.index),
+    _ => None,
+  }
+}
+
+/// Select `row` of the list shown by `block`, or its last row when the list got shorter
+pub fn select_row(app: &mut App, block: ActiveBlock, row: usize) {
+  let row = match list_len(app, block).and_then(|len| len.checked_sub(1)) {
+    Some(last) => row.min(last),
+    None => return,
+  };
+  match block {
+    ActiveBlock::TrackTable => app.track_table.selected_index = row,
+    ActiveBlock::MyPlaylists => app.selected_playlist_index = Some(row),
+    ActiveBlock::Library => app.library.selected_index = row,
+    ActiveBlock::EpisodeTable => app.episode_list_index = row,
+    ActiveBlock::Artists => app.artists_list_index = row,
+    ActiveBlock::AlbumList => app.album_list_index = row,
+    ActiveBlock::Podcasts => app.shows_list_index = row,
+    ActiveBlock::RecentlyPlayed => app.recently_played.index = row,
+    _ => {}
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::app::RouteId;
+
+  #[test]
+  fn jumping_back_to_a_mark_restores_view_and_row() {
+    let mut app = App::default();
+    app.set_current_route_state(Some(ActiveBlock::Library), Some(ActiveBlock::Library));
+    app.library.selected_index = 3;
+    app.mark_prompt = Some(MarkPrompt::Set);
+    handler(Key::Char('a'), &mut app);
+
+    app.library.selected_index = 0;
+    app.push_navigation_stack(RouteId::HelpMenu, ActiveBlock::HelpMenu);
+    app.mark_prompt = Some(MarkPrompt::Jump);
+    handler(Key::Char('a'), &mut app);
+
+    assert_eq!(app.get_current_route().id, RouteId::Home);
+    assert_eq!(app.get_current_route().active_block, ActiveBlock::Library);
+    assert_eq!(app.library.selected_index, 3);
+    assert!(app.mark_prompt.is_none());
+  }
+
+  #[test]
+  fn unknown_marks_and_other_keys_do_nothing() {
+    let mut app = App::default();
+    app.mark_prompt = Some(MarkPrompt::Jump);
+    handler(Key::Char('z'), &mut app);
+    assert_eq!(app.status_message.as_deref(), Some("Mark 'z is not set"));
+
+    app.mark_prompt = Some(MarkPrompt::Set);
+    handler(Key::Char('1'), &mut app);
+    assert!(app.marks.is_empty());
+    assert!(app.mark_prompt.is_none());
+  }
+}
@@ -15,6 +15,8 @@ mod help_menu;
 mod home;
 mod input;
 mod library;
+mod lyrics_sync;
+mod marks;
 mod playbar;
 mod playlist;
 mod podcasts;
@@ -26,177 +28,334 @@ mod sort_menu;
 mod track_table;
 mod update_prompt;
 
-use crate::core::app::{ActiveBlock, App, ArtistBlock, RouteId, SearchResultBlock};
+use crate::core::app::{ActiveBlock, App, ArtistBlock, MarkPrompt, RouteId, SearchResultBlock};
+use crate::core::keymap::{Action, Lookup};
 use crate::infra::network::IoEvent;
 use crate::tui::event::Key;
 use rspotify::model::idtypes::PlaylistId;
 use rspotify::model::{context::CurrentPlaybackContext, PlayableItem};
 
-pub use input::handler as input_handler;
-
 pub fn handle_app(key: Key, app: &mut App) {
-  // First handle any global event and then move to block event
-  match key {
-    Key::Esc => {
-      handle_escape(app);
-    }
-    _ if key == app.user_config.keys.jump_to_album => {
-      handle_jump_to_album(app);
-    }
-    _ if key == app.user_config.keys.jump_to_artist_album => {
-      handle_jump_to_artist_album(app);
+  // Text entry and key capture take raw keys before anything is looked up
+  match app.get_current_route().active_block {
+    ActiveBlock::Input => {
+      input::handler(key, app);
+      return;
     }
-    _ if key == app.user_config.keys.jump_to_context => {
-      handle_jump_to_context(app);
+    ActiveBlock::Settings if app.settings_edit_mode => {
+      settings::edit_handler(key, app);
+      return;
     }
-    _ if key == app.user_config.keys.manage_devices => {
-      app.dispatch(IoEvent::GetDevices);
+    ActiveBlock::HelpMenu if app.help_filter_editing => {
+      help_menu::text_handler(key, app);
+      return;
     }
-    _ if key == app.user_config.keys.decrease_volume => {
-      app.decrease_volume();
+    _ if app.is_editing_lyrics_search() => {
+      dialog::text_handler(key, app);
+      return;
     }
-    _ if key == app.user_config.keys.increase_volume => {
-      app.increase_volume();
+    _ if app.mark_prompt.is_some() => {
+      marks::handler(key, app);
+      return;
     }
-    // Press space to toggle playback
-    _ if key == app.user_config.keys.toggle_playback => {
-      app.toggle_playback();
+    _ => {}
+  }
+
+  if key == Key::Esc {
+    app.pending_keys.clear();
+    app.count_prefix = None;
+    // Esc leaves a visual range before it leaves the track table
+    let in_visual_range = app.visual_anchor.take().is_some()
+      && app.get_current_route().active_block == ActiveBlock::TrackTable;
+    // Like Back, Esc leaves mini mode before it touches the routes hidden behind it
+    if app.mini_mode && app.get_current_route().active_block != ActiveBlock::SelectDevice {
+      app.mini_mode = false;
+    } else if !in_visual_range {
+      handle_escape(app);
     }
-    _ if key == app.user_config.keys.seek_backwards => {
-      app.seek_backwards();
+    return;
+  }
+
+  let view = app.key_view();
+  // Digits that are not bound to anything build up a count for the next action
+  if let Key::Char(digit @ '0'..='9') = key {
+    if app.pending_keys.is_empty()
+      && (digit != '0' || app.count_prefix.is_some())
+      && app.user_config.keys.lookup(view, &[key]) == Lookup::None
+    {
+      let count = app.count_prefix.unwrap_or(0) * 10 + digit.to_digit(10).unwrap_or(0) as usize;
+      app.count_prefix = Some(count.min(MAX_COUNT));
+      app.set_status_message(format!("{} …", count.min(MAX_COUNT)), 2);
+      return;
     }
-    _ if key == app.user_config.keys.seek_forwards => {
-      app.seek_forwards();
+  }
+
+  app.pending_keys.push(key);
+  let mut lookup = app.user_config.keys.lookup(view, &app.pending_keys);
+  // A key that does not continue the chord starts over on its own
+  if lookup == Lookup::None && app.pending_keys.len() > 1 {
+    app.pending_keys = vec![key];
+    lookup = app.user_config.keys.lookup(view, &app.pending_keys);
+  }
+
+  match lookup {
+    Lookup::Action(action) => {
+      app.pending_keys.clear();
+      match app.count_prefix.take() {
+        Some(count) => handle_counted_action(action, count, app),
+        None => handle_action(action, app),
+      }
     }
-    _ if key == app.user_config.keys.next_track => {
-      app.next_track();
+    Lookup::Pending => {
+      let typed = app
+        .pending_keys
+        .iter()
+        .map(|key| key.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+      app.set_status_message(format!("{} …", typed), 2);
     }
-    _ if key == app.user_config.keys.previous_track => {
-      app.previous_track();
+    Lookup::None => {
+      app.pending_keys.clear();
+      app.count_prefix = None;
     }
-    _ if key == app.user_config.keys.help => {
-      app.push_navigation_stack(RouteId::HelpMenu, ActiveBlock::HelpMenu);
+  }
+}
+
+/// Counts above this are most likely typos
+const MAX_COUNT: usize = 999;
+
+/// Run an action typed with a count: the top and bottom jumps go to that row and list movement
+/// repeats. `3n`/`3p` page rather than skip tracks, and everything else runs once, so a count
+/// never turns into a burst of playback requests.
+fn handle_counted_action(action: Action, count: usize, app: &mut App) {
+  let action = match action {
+    Action::NextTrack if !app.mini_mode => Action::NextPage,
+    Action::PreviousTrack if !app.mini_mode => Action::PreviousPage,
+    action => action,
+  };
+  match action {
+    Action::SelectTop | Action::SelectBottom if !app.mini_mode => {
+      let block = app.get_current_route().active_block;
+      marks::select_row(app, block, count.saturating_sub(1));
+    }
+    Action::MoveUp | Action::MoveDown | Action::NextPage | Action::PreviousPage => {
+      for _ in 0..count {
+        handle_action(action, app);
+        // Stepping past the end of the page loads the next one, the rest of the count is
+        // dropped rather than run against rows that are about to be replaced
+        if app.pending_track_table_selection.is_some() {
+          break;
+        }
+      }
     }
+    _ => handle_action(action, app),
+  }
+}
+
+/// Run an action, the global ones first and then whatever the active block does with it
+fn handle_action(action: Action, app: &mut App) {
+  // Nothing but the playbar is visible, so only the playback actions and the device picker act.
+  // Back leaves mini mode rather than popping routes nobody can see, and so does Esc.
+  if app.mini_mode
+    && action == Action::Back
+    && app.get_current_route().active_block != ActiveBlock::SelectDevice
+  {
+    app.mini_mode = false;
+    return;
+  }
+  if app.mini_mode
+    && app.get_current_route().active_block != ActiveBlock::SelectDevice
+    && !matches!(
+      action,
+      Action::ManageDevices
+        | Action::DecreaseVolume
+        | Action::IncreaseVolume
+        | Action::TogglePlayback
+        | Action::SeekBackwards
+        | Action::SeekForwards
+        | Action::NextTrack
+        | Action::PreviousTrack
+        | Action::Shuffle
+        | Action::Repeat
+        | Action::ToggleMiniMode
+    )
+  {
+    return;
+  }
 
-    _ if key == app.user_config.keys.shuffle => {
-      app.shuffle();
+  // The sync editor sits over the lyrics, so its keys come before the global ones
+  if app.key_view() == "lyrics_sync" && lyrics_sync::handles(action) {
+    lyrics_sync::handler(action, app);
+    return;
+  }
+
+  match action {
+    Action::Back => handle_back(app),
+    Action::Forward => {
+      if !app.navigate_forward() {
+        app.set_status_message("Nowhere to go forward to".to_string(), 3);
+      }
     }
-    _ if key == app.user_config.keys.repeat => {
-      app.repeat();
+    Action::SetMark => {
+      app.mark_prompt = Some(MarkPrompt::Set);
+      app.set_status_message("Mark the row as …".to_string(), 3);
+    }
+    Action::JumpToMark => {
+      app.mark_prompt = Some(MarkPrompt::Jump);
+      app.set_status_message("Jump to mark …".to_string(), 3);
+    }
+    Action::JumpToAlbum => handle_jump_to_album(app),
+    Action::JumpToArtistAlbum => handle_jump_to_artist_album(app),
+    Action::JumpToContext => handle_jump_to_context(app),
+    Action::ManageDevices => app.dispatch(IoEvent::GetDevices),
+    Action::DecreaseVolume => app.decrease_volume(),
+    Action::IncreaseVolume => app.increase_volume(),
+    Action::TogglePlayback => app.toggle_playback(),
+    Action::SeekBackwards => app.seek_backwards(),
+    Action::SeekForwards => app.seek_forwards(),
+    Action::NextTrack => app.next_track(),
+    Action::PreviousTrack => app.previous_track(),
+    Action::Shuffle => app.shuffle(),
+    Action::Repeat => app.repeat(),
+    Action::ToggleMiniMode => app.mini_mode = !app.mini_mode,
+    Action::Undo => app.undo(0),
+    Action::UndoHistory => app.open_undo_history(),
+    Action::Help => {
+      app.help_filter.clear();
+      help_menu::reset_help_menu(app);
+      app.push_navigation_stack(RouteId::HelpMenu, ActiveBlock::HelpMenu);
     }
-    _ if key == app.user_config.keys.search => {
+    Action::Search => {
       app.set_current_route_state(Some(ActiveBlock::Input), Some(ActiveBlock::Input));
     }
-    _ if key == app.user_config.keys.copy_song_url => {
-      app.copy_song_url();
-    }
-    _ if key == app.user_config.keys.copy_album_url => {
-      app.copy_album_url();
-    }
-    _ if key == app.user_config.keys.audio_analysis => {
-      app.get_audio_analysis();
-    }
-    _ if key == app.user_config.keys.basic_view => {
+    Action::CopySongUrl => app.copy_song_url(),
+    Action::CopyAlbumUrl => app.copy_album_url(),
+    Action::AudioAnalysis => app.get_audio_analysis(),
+    Action::BasicView => {
       app.push_navigation_stack(RouteId::BasicView, ActiveBlock::BasicView);
     }
-    _ if key == app.user_config.keys.open_settings => {
+    Action::NowPlaying => {
+      if app.get_current_route().active_block != ActiveBlock::NowPlaying {
+        app.push_navigation_stack(RouteId::NowPlaying, ActiveBlock::NowPlaying);
+        app.dispatch(IoEvent::GetQueue);
+      }
+    }
+    Action::ToggleNightMode => app.toggle_night_mode(),
+    Action::OpenSettings => {
       app.load_settings_for_category();
       app.push_navigation_stack(RouteId::Settings, ActiveBlock::Settings);
     }
-    Key::Char('W') => match app.get_current_route().active_block {
-      ActiveBlock::Input
-      | ActiveBlock::Dialog(_)
-      | ActiveBlock::UpdatePrompt
-      | ActiveBlock::AnnouncementPrompt => {
-        handle_block_events(key, app);
-      }
+    Action::AddPlayingToPlaylist => match app.get_current_route().active_block {
+      ActiveBlock::Dialog(_) | ActiveBlock::UpdatePrompt | ActiveBlock::AnnouncementPrompt => {}
       _ => playbar::add_currently_playing_track_to_playlist(app),
     },
-    _ => handle_block_events(key, app),
+    _ => handle_block_action(action, app),
   }
 }
 
-// Handle event for the current active block
-fn handle_block_events(key: Key, app: &mut App) {
+// Handle action for the current active block
+fn handle_block_action(action: Action, app: &mut App) {
   let current_route = app.get_current_route();
   match current_route.active_block {
     ActiveBlock::Analysis => {
-      analysis::handler(key, app);
+      analysis::handler(action, app);
     }
     ActiveBlock::ArtistBlock => {
-      artist::handler(key, app);
-    }
-    ActiveBlock::Input => {
-      input::handler(key, app);
+      artist::handler(action, app);
     }
+    // Input takes raw keys in `handle_app`
+    ActiveBlock::Input => {}
     ActiveBlock::MyPlaylists => {
-      playlist::handler(key, app);
+      playlist::handler(action, app);
     }
     ActiveBlock::TrackTable => {
-      track_table::handler(key, app);
+      track_table::handler(action, app);
     }
     ActiveBlock::EpisodeTable => {
-      episode_table::handler(key, app);
+      episode_table::handler(action, app);
     }
     ActiveBlock::HelpMenu => {
-      help_menu::handler(key, app);
+      help_menu::handler(action, app);
     }
     ActiveBlock::Error => {
-      error_screen::handler(key, app);
+      error_screen::handler(action, app);
     }
     ActiveBlock::SelectDevice => {
-      select_device::handler(key, app);
+      select_device::handler(action, app);
     }
     ActiveBlock::SearchResultBlock => {
-      search_results::handler(key, app);
+      search_results::handler(action, app);
     }
     ActiveBlock::Home => {
-      home::handler(key, app);
+      home::handler(action, app);
     }
     ActiveBlock::AlbumList => {
-      album_list::handler(key, app);
+      album_list::handler(action, app);
     }
     ActiveBlock::AlbumTracks => {
-      album_tracks::handler(key, app);
+      album_tracks::handler(action, app);
     }
     ActiveBlock::Library => {
-      library::handler(key, app);
+      library::handler(action, app);
     }
     ActiveBlock::Empty => {
-      empty::handler(key, app);
+      empty::handler(action, app);
     }
     ActiveBlock::RecentlyPlayed => {
-      recently_played::handler(key, app);
+      recently_played::handler(action, app);
     }
     ActiveBlock::Artists => {
-      artists::handler(key, app);
+      artists::handler(action, app);
     }
     ActiveBlock::Discover => {
-      discover::handler(key, app);
+      discover::handler(action, app);
     }
     ActiveBlock::Podcasts => {
-      podcasts::handler(key, app);
+      podcasts::handler(action, app);
     }
     ActiveBlock::PlayBar => {
-      playbar::handler(key, app);
+      playbar::handler(action, app);
     }
-    ActiveBlock::BasicView => {
-      basic_view::handler(key, app);
+    // Same lyrics and save actions as the basic view
+    ActiveBlock::BasicView | ActiveBlock::NowPlaying => {
+      basic_view::handler(action, app);
     }
     ActiveBlock::Dialog(_) => {
-      dialog::handler(key, app);
+      dialog::handler(action, app);
     }
     ActiveBlock::UpdatePrompt => {
-      update_prompt::handler(key, app);
+      update_prompt::handler(action, app);
     }
     ActiveBlock::AnnouncementPrompt => {
-      announcement_prompt::handler(key, app);
+      announcement_prompt::handler(action, app);
     }
     ActiveBlock::Settings => {
-      settings::handler(key, app);
+      settings::handler(action, app);
     }
     ActiveBlock::SortMenu => {
-      sort_menu::handler(key, app);
+      sort_menu::handler(action, app);
+    }
+  }
+}
+
+/// Go back through the navigation stack, and quit when there is nowhere left to go back to
+fn handle_back(app: &mut App) {
+  match app.get_current_route().active_block {
+    // Dialogs and prompts clean up after themselves when dismissed
+    ActiveBlock::Dialog(_) | ActiveBlock::UpdatePrompt | ActiveBlock::AnnouncementPrompt => {
+      handle_block_action(Action::Back, app);
+    }
+    ActiveBlock::SortMenu => handle_escape(app),
+    _ => {
+      let pop_result = match app.navigate_back() {
+        Some(ref x) if x.id == RouteId::Search => app.navigate_back(),
+        Some(x) => Some(x),
+        None => None,
+      };
+      if pop_result.is_none() {
+        app.should_quit = true;
+      }
     }
   }
 }
@@ -220,7 +379,14 @@ fn handle_escape(app: &mut App) {
       app.confirm = false;
       app.clear_playlist_track_dialog_state();
     }
-    ActiveBlock::HelpMenu => {
+    ActiveBlock::HelpMenu | ActiveBlock::Settings => {
+      app.pop_navigation_stack();
+    }
+    // Leave the lyrics sync editor without leaving the lyrics view
+    ActiveBlock::BasicView | ActiveBlock::NowPlaying if app.lyrics_sync.is_some() => {
+      app.lyrics_sync = None;
+    }
+    ActiveBlock::NowPlaying => {
       app.pop_navigation_stack();
     }
     // These are global views that have no active/inactive distinction so do nothing
@@ -298,6 +464,7 @@ fn handle_jump_to_artist_album(app: &mut App) {
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::core::app::LyricsSyncEditor;
 
   #[test]
   fn global_shift_w_adds_current_track_from_anywhere() {
@@ -322,4 +489,154 @@ mod tests {
     assert_eq!(app.input, vec!['W']);
     assert!(app.status_message.is_none());
   }
+
+  #[test]
+  fn mini_mode_only_passes_playback_keys() {
+    let mut app = App::default();
+    app.set_current_route_state(Some(ActiveBlock::Empty), Some(ActiveBlock::Library));
+
+    handle_app(Key::Alt('m'), &mut app);
+    assert!(app.mini_mode);
+
+    handle_app(Key::Char('?'), &mut app);
+    handle_app(Key::Char('W'), &mut app);
+    assert_ne!(app.get_current_route().active_block, ActiveBlock::HelpMenu);
+    assert!(app.status_message.is_none());
+
+    handle_app(Key::Alt('m'), &mut app);
+    assert!(!app.mini_mode);
+  }
+
+  #[test]
+  fn sync_editor_keys_come_before_global_ones() {
+    let mut app = App::default();
+    app.push_navigation_stack(RouteId::BasicView, ActiveBlock::BasicView);
+    app.lyrics_sync = Some(LyricsSyncEditor::default());
+
+    handle_app(Key::Char('q'), &mut app);
+
+    assert!(app.lyrics_sync.is_none());
+    assert_eq!(app.get_current_route().active_block, ActiveBlock::BasicView);
+  }
+
+  #[test]
+  fn leaving_the_lyrics_view_closes_the_sync_editor() {
+    let mut app = App::default();
+    app.push_navigation_stack(RouteId::NowPlaying, ActiveBlock::NowPlaying);
+    app.lyrics_sync = Some(LyricsSyncEditor::default());
+
+    app.navigate_back();
+    app.push_navigation_stack(RouteId::BasicView, ActiveBlock::BasicView);
+
+    assert!(app.lyrics_sync.is_none());
+    assert_eq!(app.key_view(), "basic_view");
+  }
+
+  #[test]
+  fn back_leaves_mini_mode_first() {
+    let mut app = App::default();
+    app.set_current_route_state(Some(ActiveBlock::Empty), Some(ActiveBlock::Library));
+    app.push_navigation_stack(RouteId::HelpMenu, ActiveBlock::HelpMenu);
+    app.mini_mode = true;
+
+    handle_app(Key::Char('q'), &mut app);
+
+    assert!(!app.mini_mode);
+    assert_eq!(app.get_current_route().active_block, ActiveBlock::HelpMenu);
+
+    app.mini_mode = true;
+    handle_app(Key::Esc, &mut app);
+    assert!(!app.mini_mode);
+    assert_eq!(app.get_current_route().active_block, ActiveBlock::HelpMenu);
+
+    app.mini_mode = true;
+    handle_app(Key::Char('a'), &mut app);
+    assert_eq!(app.get_current_route().active_block, ActiveBlock::HelpMenu);
+  }
+
+  #[test]
+  fn chords_wait_for_their_last_key() {
+    let mut app = App::default();
+    app
+      .user_config
+      .keys
+      .bind(
+        None,
+        Action::Help,
+        vec![vec![Key::Char('g'), Key::Char('h')]],
+      )
+      .unwrap();
+    app.set_current_route_state(Some(ActiveBlock::Empty), Some(ActiveBlock::Library));
+
+    handle_app(Key::Char('g'), &mut app);
+    assert_eq!(app.pending_keys, vec![Key::Char('g')]);
+    assert_eq!(app.get_current_route().active_block, ActiveBlock::Empty);
+
+    handle_app(Key::Char('h'), &mut app);
+    assert!(app.pending_keys.is_empty());
+    assert_eq!(app.get_current_route().active_block, ActiveBlock::HelpMenu);
+  }
+
+  #[test]
+  fn back_with_nowhere_to_go_quits() {
+    let mut app = App::default();
+
+    handle_app(Key::Char('q'), &mut app);
+
+    assert!(app.should_quit);
+  }
+
+  #[test]
+  fn counts_repeat_steps_and_pick_rows() {
+    let mut app = App::default();
+    app.set_current_route_state(Some(ActiveBlock::Library), Some(ActiveBlock::Library));
+
+    handle_app(Key::Char('3'), &mut app);
+    handle_app(Key::Char('j'), &mut app);
+    assert_eq!(app.library.selected_index, 3);
+    assert!(app.count_prefix.is_none());
+
+    for key in ['2', 'g', 'g'] {
+      handle_app(Key::Char(key), &mut app);
+    }
+    assert_eq!(app.library.selected_index, 1);
+
+    // A lone 0 is not a count
+    handle_app(Key::Char('0'), &mut app);
+    assert!(app.count_prefix.is_none());
+  }
+
+  #[test]
+  fn counted_next_track_pages() {
+    let mut app = App::default();
+    app.push_navigation_stack(RouteId::HelpMenu, ActiveBlock::HelpMenu);
+    app.help_docs_size = 100;
+    app.help_menu_max_lines = 10;
+
+    handle_app(Key::Char('3'), &mut app);
+    handle_app(Key::Char('n'), &mut app);
+    assert_eq!(app.help_menu_page, 3);
+
+    handle_app(Key::Char('2'), &mut app);
+    handle_app(Key::Char('p'), &mut app);
+    assert_eq!(app.help_menu_page, 1);
+  }
+
+  #[test]
+  fn forward_returns_to_where_back_came_from() {
+    let mut app = App::default();
+    app.push_navigation_stack(RouteId::Artists, ActiveBlock::Artists);
+
+    handle_app(Key::Char('q'), &mut app);
+    assert_eq!(app.get_current_route().id, RouteId::Home);
+
+    handle_app(Key::Tab, &mut app);
+    assert_eq!(app.get_current_route().id, RouteId::Artists);
+
+    handle_app(Key::Tab, &mut app);
+    assert_eq!(
+      app.status_message.as_deref(),
+      Some("Nowhere to go forward to")
+    );
+  }
 }
@@ -1,15 +1,14 @@
-use super::common_key_events;
 use crate::core::app::{ActiveBlock, App};
+use crate::core::keymap::Action;
 use crate::infra::network::IoEvent;
-use crate::tui::event::Key;
 use rspotify::model::{context::CurrentPlaybackContext, PlayableId, PlayableItem};
 
-pub fn handler(key: Key, app: &mut App) {
-  match key {
-    k if common_key_events::up_event(k) => {
+pub fn handler(action: Action, app: &mut App) {
+  match action {
+    Action::MoveUp => {
       app.set_current_route_state(Some(ActiveBlock::Empty), Some(ActiveBlock::MyPlaylists));
     }
-    Key::Char('s') => {
+    Action::Save => {
       if let Some(CurrentPlaybackContext {
         item: Some(item), ..
       }) = app.current_playback_context.to_owned()
@@ -30,7 +29,7 @@ pub fn handler(key: Key, app: &mut App) {
         };
       };
     }
-    Key::Char('w') => {
+    Action::AddToPlaylist => {
       add_currently_playing_track_to_playlist(app);
     }
     _ => {}
@@ -65,7 +64,7 @@ mod tests {
     let mut app = App::default();
     app.set_current_route_state(Some(ActiveBlock::PlayBar), Some(ActiveBlock::PlayBar));
 
-    handler(Key::Up, &mut app);
+    handler(Action::MoveUp, &mut app);
     let current_route = app.get_current_route();
     assert_eq!(current_route.active_block, ActiveBlock::Empty);
     assert_eq!(current_route.hovered_block, ActiveBlock::MyPlaylists);
@@ -76,7 +75,7 @@ mod tests {
     let mut app = App::default();
     app.set_current_route_state(Some(ActiveBlock::PlayBar), Some(ActiveBlock::PlayBar));
 
-    handler(Key::Char('w'), &mut app);
+    handler(Action::AddToPlaylist, &mut app);
 
     assert_eq!(
       app.status_message.as_deref(),
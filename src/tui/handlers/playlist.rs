@@ -1,32 +1,32 @@
 use super::common_key_events;
 use crate::core::app::{ActiveBlock, RouteId};
 use crate::core::app::{App, DialogContext, PlaylistFolderItem, TrackTableContext};
+use crate::core::keymap::Action;
 use crate::infra::network::IoEvent;
-use crate::tui::event::Key;
 
-pub fn handler(key: Key, app: &mut App) {
-  match key {
-    k if common_key_events::right_event(k) => common_key_events::handle_right_event(app),
-    k if common_key_events::down_event(k) => {
+pub fn handler(action: Action, app: &mut App) {
+  match action {
+    Action::MoveRight => common_key_events::handle_right_event(app),
+    Action::MoveDown => {
       let count = app.get_playlist_display_count();
       if count > 0 {
         let current = app.selected_playlist_index.unwrap_or(0);
         app.selected_playlist_index = Some((current + 1) % count);
       }
     }
-    k if common_key_events::up_event(k) => {
+    Action::MoveUp => {
       let count = app.get_playlist_display_count();
       if count > 0 {
         let current = app.selected_playlist_index.unwrap_or(0);
         app.selected_playlist_index = Some(if current == 0 { count - 1 } else { current - 1 });
       }
     }
-    k if common_key_events::high_event(k) => {
+    Action::SelectTop => {
       if app.get_playlist_display_count() > 0 {
         app.selected_playlist_index = Some(0);
       }
     }
-    k if common_key_events::middle_event(k) => {
+    Action::SelectMiddle => {
       let count = app.get_playlist_display_count();
       if count > 0 {
         let next_index = if count.is_multiple_of(2) {
@@ -37,13 +37,13 @@ pub fn handler(key: Key, app: &mut App) {
         app.selected_playlist_index = Some(next_index);
       }
     }
-    k if common_key_events::low_event(k) => {
+    Action::SelectBottom => {
       let count = app.get_playlist_display_count();
       if count > 0 {
         app.selected_playlist_index = Some(count - 1);
       }
     }
-    Key::Enter => {
+    Action::Submit => {
       if let Some(selected_idx) = app.selected_playlist_index {
         if let Some(item) = app.get_playlist_display_item_at(selected_idx) {
           match item {
@@ -71,7 +71,7 @@ pub fn handler(key: Key, app: &mut App) {
         }
       }
     }
-    Key::Char('D') => {
+    Action::Remove => {
       if let Some(selected_idx) = app.selected_playlist_index {
         if let Some(PlaylistFolderItem::Playlist { index, .. }) =
           app.get_playlist_display_item_at(selected_idx)
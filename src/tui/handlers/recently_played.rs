@@ -1,15 +1,15 @@
 use super::common_key_events;
 use crate::core::app::App;
 use crate::core::app::RecommendationsContext;
+use crate::core::keymap::Action;
 use crate::infra::network::IoEvent;
-use crate::tui::event::Key;
 use rspotify::model::idtypes::PlayableId;
 use rspotify::prelude::Id;
 
-pub fn handler(key: Key, app: &mut App) {
-  match key {
-    k if common_key_events::left_event(k) => common_key_events::handle_left_event(app),
-    k if common_key_events::down_event(k) => {
+pub fn handler(action: Action, app: &mut App) {
+  match action {
+    Action::MoveLeft => common_key_events::handle_left_event(app),
+    Action::MoveDown => {
       if let Some(recently_played_result) = &app.recently_played.result {
         let next_index = common_key_events::on_down_press_handler(
           &recently_played_result.items,
@@ -18,7 +18,7 @@ pub fn handler(key: Key, app: &mut App) {
         app.recently_played.index = next_index;
       }
     }
-    k if common_key_events::up_event(k) => {
+    Action::MoveUp => {
       if let Some(recently_played_result) = &app.recently_played.result {
         let next_index = common_key_events::on_up_press_handler(
           &recently_played_result.items,
@@ -27,25 +27,25 @@ pub fn handler(key: Key, app: &mut App) {
         app.recently_played.index = next_index;
       }
     }
-    k if common_key_events::high_event(k) => {
+    Action::SelectTop => {
       if let Some(_recently_played_result) = &app.recently_played.result {
         let next_index = common_key_events::on_high_press_handler();
         app.recently_played.index = next_index;
       }
     }
-    k if common_key_events::middle_event(k) => {
+    Action::SelectMiddle => {
       if let Some(recently_played_result) = &app.recently_played.result {
         let next_index = common_key_events::on_middle_press_handler(&recently_played_result.items);
         app.recently_played.index = next_index;
       }
     }
-    k if common_key_events::low_event(k) => {
+    Action::SelectBottom => {
       if let Some(recently_played_result) = &app.recently_played.result {
         let next_index = common_key_events::on_low_press_handler(&recently_played_result.items);
         app.recently_played.index = next_index;
       }
     }
-    Key::Char('s') => {
+    Action::Save => {
       if let Some(recently_played_result) = &app.recently_played.result.clone() {
         if let Some(selected_track) = recently_played_result.items.get(app.recently_played.index) {
           if let Some(track_id) = &selected_track.track.id {
@@ -56,7 +56,7 @@ pub fn handler(key: Key, app: &mut App) {
         };
       };
     }
-    Key::Enter => {
+    Action::Submit => {
       if let Some(recently_played_result) = &app.recently_played.result.clone() {
         let track_uris: Vec<PlayableId<'static>> = recently_played_result
           .items
@@ -77,7 +77,7 @@ pub fn handler(key: Key, app: &mut App) {
         ));
       };
     }
-    Key::Char('r') => {
+    Action::Recommendations => {
       if let Some(recently_played_result) = &app.recently_played.result.clone() {
         if let Some(selected_track) = recently_played_result.items.get(app.recently_played.index) {
           if let Some(track_id) = &selected_track.track.id {
@@ -88,7 +88,7 @@ pub fn handler(key: Key, app: &mut App) {
         };
       };
     }
-    _ if key == app.user_config.keys.add_item_to_queue => {
+    Action::AddItemToQueue => {
       if let Some(recently_played_result) = &app.recently_played.result.clone() {
         if let Some(selected_track) = recently_played_result.items.get(app.recently_played.index) {
           if let Some(track_id) = &selected_track.track.id {
@@ -99,6 +99,9 @@ pub fn handler(key: Key, app: &mut App) {
         };
       };
     }
+    Action::OpenSortMenu => {
+      super::sort_menu::open_sort_menu(app, crate::core::sort::SortContext::RecentlyPlayed);
+    }
     _ => {}
   };
 }
@@ -107,6 +110,8 @@ pub fn handler(key: Key, app: &mut App) {
 mod tests {
   use super::*;
   use crate::core::app::ActiveBlock;
+  use crate::handlers::handle_app;
+  use crate::tui::event::Key;
 
   #[test]
   fn on_left_press() {
@@ -116,7 +121,7 @@ mod tests {
       Some(ActiveBlock::AlbumTracks),
     );
 
-    handler(Key::Left, &mut app);
+    handler(Action::MoveLeft, &mut app);
     let current_route = app.get_current_route();
     assert_eq!(current_route.active_block, ActiveBlock::Empty);
     assert_eq!(current_route.hovered_block, ActiveBlock::Library);
@@ -126,7 +131,7 @@ mod tests {
   fn on_esc() {
     let mut app = App::default();
 
-    handler(Key::Esc, &mut app);
+    handle_app(Key::Esc, &mut app);
 
     let current_route = app.get_current_route();
     assert_eq!(current_route.active_block, ActiveBlock::Empty);
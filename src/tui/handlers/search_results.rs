@@ -3,8 +3,8 @@ use crate::core::app::{
   ActiveBlock, App, DialogContext, RecommendationsContext, RouteId, SearchResultBlock,
   TrackTableContext,
 };
+use crate::core::keymap::Action;
 use crate::infra::network::IoEvent;
-use crate::tui::event::Key;
 use rspotify::{model::PlayableId, prelude::*};
 
 fn handle_down_press_on_selected_block(app: &mut App) {
@@ -418,26 +418,23 @@ fn handle_recommended_tracks(app: &mut App) {
   }
 }
 
-pub fn handler(key: Key, app: &mut App) {
-  match key {
-    Key::Esc => {
-      app.search_results.selected_block = SearchResultBlock::Empty;
-    }
-    k if common_key_events::down_event(k) => {
+pub fn handler(action: Action, app: &mut App) {
+  match action {
+    Action::MoveDown => {
       if app.search_results.selected_block != SearchResultBlock::Empty {
         handle_down_press_on_selected_block(app);
       } else {
         handle_down_press_on_hovered_block(app);
       }
     }
-    k if common_key_events::up_event(k) => {
+    Action::MoveUp => {
       if app.search_results.selected_block != SearchResultBlock::Empty {
         handle_up_press_on_selected_block(app);
       } else {
         handle_up_press_on_hovered_block(app);
       }
     }
-    k if common_key_events::left_event(k) => {
+    Action::MoveLeft => {
       app.search_results.selected_block = SearchResultBlock::Empty;
       match app.search_results.hovered_block {
         SearchResultBlock::AlbumSearch => {
@@ -458,7 +455,7 @@ pub fn handler(key: Key, app: &mut App) {
         SearchResultBlock::Empty => {}
       }
     }
-    k if common_key_events::right_event(k) => {
+    Action::MoveRight => {
       app.search_results.selected_block = SearchResultBlock::Empty;
       match app.search_results.hovered_block {
         SearchResultBlock::AlbumSearch => {
@@ -477,23 +474,23 @@ pub fn handler(key: Key, app: &mut App) {
         SearchResultBlock::Empty => {}
       }
     }
-    k if common_key_events::high_event(k) => {
+    Action::SelectTop => {
       if app.search_results.selected_block != SearchResultBlock::Empty {
         handle_high_press_on_selected_block(app);
       }
     }
-    k if common_key_events::middle_event(k) => {
+    Action::SelectMiddle => {
       if app.search_results.selected_block != SearchResultBlock::Empty {
         handle_middle_press_on_selected_block(app);
       }
     }
-    k if common_key_events::low_event(k) => {
+    Action::SelectBottom => {
       if app.search_results.selected_block != SearchResultBlock::Empty {
         handle_low_press_on_selected_block(app)
       }
     }
     // Handle pressing enter when block is selected to start playing track
-    Key::Enter => match app.search_results.selected_block {
+    Action::Submit => match app.search_results.selected_block {
       SearchResultBlock::Empty => handle_enter_event_on_hovered_block(app),
       SearchResultBlock::PlaylistSearch => {
         app.playlist_offset = 0;
@@ -501,7 +498,7 @@ pub fn handler(key: Key, app: &mut App) {
       }
       _ => handle_enter_event_on_selected_block(app),
     },
-    Key::Char('w') => match app.search_results.selected_block {
+    Action::Follow => match app.search_results.selected_block {
       SearchResultBlock::AlbumSearch => {
         app.current_user_saved_album_add(ActiveBlock::SearchResultBlock)
       }
@@ -513,7 +510,7 @@ pub fn handler(key: Key, app: &mut App) {
       SearchResultBlock::ShowSearch => app.user_follow_show(ActiveBlock::SearchResultBlock),
       SearchResultBlock::Empty => {}
     },
-    Key::Char('D') => match app.search_results.selected_block {
+    Action::Remove => match app.search_results.selected_block {
       SearchResultBlock::AlbumSearch => {
         app.current_user_saved_album_delete(ActiveBlock::SearchResultBlock)
       }
@@ -537,8 +534,17 @@ pub fn handler(key: Key, app: &mut App) {
       SearchResultBlock::ShowSearch => app.user_unfollow_show(ActiveBlock::SearchResultBlock),
       SearchResultBlock::Empty => {}
     },
-    Key::Char('r') => handle_recommended_tracks(app),
-    _ if key == app.user_config.keys.add_item_to_queue => handle_add_item_to_queue(app),
+    Action::Recommendations => handle_recommended_tracks(app),
+    Action::AddItemToQueue => handle_add_item_to_queue(app),
+    Action::OpenSortMenu => {
+      let block = match &app.search_results.selected_block {
+        SearchResultBlock::Empty => &app.search_results.hovered_block,
+        block => block,
+      };
+      if *block == SearchResultBlock::SongSearch {
+        super::sort_menu::open_sort_menu(app, crate::core::sort::SortContext::SearchResults);
+      }
+    }
     // Add `s` to "see more" on each option
     _ => {}
   }
@@ -1,14 +1,11 @@
 use super::common_key_events;
-use crate::core::app::{ActiveBlock, App};
+use crate::core::app::App;
+use crate::core::keymap::Action;
 use crate::infra::network::IoEvent;
-use crate::tui::event::Key;
 
-pub fn handler(key: Key, app: &mut App) {
-  match key {
-    Key::Esc => {
-      app.set_current_route_state(Some(ActiveBlock::Library), None);
-    }
-    k if common_key_events::down_event(k) => {
+pub fn handler(action: Action, app: &mut App) {
+  match action {
+    Action::MoveDown => {
       if let Some(p) = &app.devices {
         if let Some(selected_device_index) = app.selected_device_index {
           let next_index =
@@ -17,7 +14,7 @@ pub fn handler(key: Key, app: &mut App) {
         }
       };
     }
-    k if common_key_events::up_event(k) => {
+    Action::MoveUp => {
       if let Some(p) = &app.devices {
         if let Some(selected_device_index) = app.selected_device_index {
           let next_index =
@@ -26,7 +23,7 @@ pub fn handler(key: Key, app: &mut App) {
         }
       };
     }
-    k if common_key_events::high_event(k) => {
+    Action::SelectTop => {
       if let Some(_p) = &app.devices {
         if let Some(_selected_device_index) = app.selected_device_index {
           let next_index = common_key_events::on_high_press_handler();
@@ -34,7 +31,7 @@ pub fn handler(key: Key, app: &mut App) {
         }
       };
     }
-    k if common_key_events::middle_event(k) => {
+    Action::SelectMiddle => {
       if let Some(p) = &app.devices {
         if let Some(_selected_device_index) = app.selected_device_index {
           let next_index = common_key_events::on_middle_press_handler(&p.devices);
@@ -42,7 +39,7 @@ pub fn handler(key: Key, app: &mut App) {
         }
       };
     }
-    k if common_key_events::low_event(k) => {
+    Action::SelectBottom => {
       if let Some(p) = &app.devices {
         if let Some(_selected_device_index) = app.selected_device_index {
           let next_index = common_key_events::on_low_press_handler(&p.devices);
@@ -50,7 +47,9 @@ pub fn handler(key: Key, app: &mut App) {
         }
       };
     }
-    Key::Enter => {
+    // Local output of the native player (speakers, USB DAC, ...), as opposed to Connect devices
+    Action::AudioOutput => app.open_audio_output_picker(),
+    Action::Submit => {
       if let Some(index) = app.selected_device_index {
         if let Some(devices) = &app.devices {
           if let Some(device) = devices.devices.get(index) {
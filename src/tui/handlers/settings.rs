@@ -1,40 +1,47 @@
-use crate::core::app::{App, SettingValue, SettingsCategory};
+use crate::core::app::{
+  App, KeyCapture, KeyCaptureIssue, SettingItem, SettingValue, SettingsCategory,
+};
+use crate::core::keymap::{self, Action};
+use crate::core::user_config::check_reserved_keys_public;
 use crate::tui::event::Key;
-use crate::handlers::common_key_events::{down_event, left_event, right_event, up_event};
 
-pub fn handler(key: Key, app: &mut App) {
-  if app.settings_edit_mode {
-    handle_edit_mode(key, app);
-  } else {
-    handle_navigation(key, app);
-  }
-}
-
-fn handle_navigation(key: Key, app: &mut App) {
-  match key {
+pub fn handler(action: Action, app: &mut App) {
+  match action {
     // Category switching with left/right (only when not in edit mode)
-    key if left_event(key) => switch_category_left(app),
-    key if right_event(key) => switch_category_right(app),
+    Action::MoveLeft => switch_category_left(app),
+    Action::MoveRight => switch_category_right(app),
 
     // Item selection with up/down
-    key if down_event(key) => select_next_item(app),
-    key if up_event(key) => select_previous_item(app),
+    Action::MoveDown => select_next_item(app),
+    Action::MoveUp => select_previous_item(app),
 
     // Enter edit mode
-    Key::Enter => enter_edit_mode(app),
+    Action::Submit => enter_edit_mode(app),
 
     // Save settings
-    key if key == app.user_config.keys.save_settings => save_settings(app),
+    Action::SaveSettings => save_settings(app),
 
-    // Exit settings
-    Key::Esc => {
-      app.pop_navigation_stack();
+    Action::ResetBinding if app.settings_category == SettingsCategory::Keybindings => {
+      reset_key_bindings(app, false)
     }
-    key if key == app.user_config.keys.back => {
-      app.pop_navigation_stack();
+    Action::ResetAllBindings if app.settings_category == SettingsCategory::Keybindings => {
+      reset_key_bindings(app, true)
     }
     _ => {}
   }
+  // Equalizer edits are heard straight away rather than on save
+  app.preview_equalizer_settings();
+}
+
+/// Edit mode reads raw keys, a key binding being edited can be any key
+pub fn edit_handler(key: Key, app: &mut App) {
+  handle_edit_mode(key, app);
+  app.preview_equalizer_settings();
+}
+
+/// Movement the settings keys map to while editing a value
+fn edit_action(key: Key, app: &App) -> Option<Action> {
+  app.user_config.keys.action("settings", key)
 }
 
 fn handle_edit_mode(key: Key, app: &mut App) {
@@ -63,7 +70,12 @@ fn handle_bool_edit(key: Key, app: &mut App) {
     Key::Esc => {
       app.settings_edit_mode = false;
     }
-    key if left_event(key) || right_event(key) => {
+    key
+      if matches!(
+        edit_action(key, app),
+        Some(Action::MoveLeft | Action::MoveRight)
+      ) =>
+    {
       // Toggle on left/right as well for better UX
       if let Some(setting) = app.settings_items.get_mut(app.settings_selected_index) {
         if let SettingValue::Bool(v) = setting.value {
@@ -80,8 +92,8 @@ fn handle_number_edit(key: Key, app: &mut App) {
     Key::Enter => {
       // Parse and apply the edited number
       if let Ok(num) = app.settings_edit_buffer.parse::<i64>() {
-        if let Some(setting) = app.settings_items.get_mut(app.settings_selected_index) {
-          setting.value = SettingValue::Number(num);
+        if !confirm_value(app, SettingValue::Number(num)) {
+          return;
         }
       }
       app.settings_edit_mode = false;
@@ -90,6 +102,7 @@ fn handle_number_edit(key: Key, app: &mut App) {
     Key::Esc => {
       app.settings_edit_mode = false;
       app.settings_edit_buffer.clear();
+      app.settings_edit_error = None;
     }
     Key::Char(c) if c.is_ascii_digit() || c == '-' => {
       app.settings_edit_buffer.push(c);
@@ -97,7 +110,7 @@ fn handle_number_edit(key: Key, app: &mut App) {
     Key::Backspace => {
       app.settings_edit_buffer.pop();
     }
-    key if up_event(key) => {
+    key if edit_action(key, app) == Some(Action::MoveUp) => {
       // Increment value
       if let Some(setting) = app.settings_items.get_mut(app.settings_selected_index) {
         if let SettingValue::Number(v) = setting.value {
@@ -107,7 +120,7 @@ fn handle_number_edit(key: Key, app: &mut App) {
         }
       }
     }
-    key if down_event(key) => {
+    key if edit_action(key, app) == Some(Action::MoveDown) => {
       // Decrement value
       if let Some(setting) = app.settings_items.get_mut(app.settings_selected_index) {
         if let SettingValue::Number(v) = setting.value {
@@ -125,17 +138,16 @@ fn handle_string_edit(key: Key, app: &mut App) {
   match key {
     Key::Enter => {
       // Apply the edited string
-      if let Some(setting) = app.settings_items.get_mut(app.settings_selected_index) {
-        let new_value = app.settings_edit_buffer.clone();
-        match &setting.value {
-          SettingValue::String(_) => {
-            setting.value = SettingValue::String(new_value);
-          }
-          SettingValue::Color(_) => {
-            setting.value = SettingValue::Color(new_value);
-          }
-          _ => {}
-        }
+      let new_value = app.settings_edit_buffer.clone();
+      let new_value = match app.settings_items.get(app.settings_selected_index) {
+        Some(SettingItem {
+          value: SettingValue::Color(_),
+          ..
+        }) => SettingValue::Color(new_value),
+        _ => SettingValue::String(new_value),
+      };
+      if !confirm_value(app, new_value) {
+        return;
       }
       app.settings_edit_mode = false;
       app.settings_edit_buffer.clear();
@@ -143,6 +155,7 @@ fn handle_string_edit(key: Key, app: &mut App) {
     Key::Esc => {
       app.settings_edit_mode = false;
       app.settings_edit_buffer.clear();
+      app.settings_edit_error = None;
     }
     Key::Char(c) => {
       app.settings_edit_buffer.push(c);
@@ -154,120 +167,131 @@ fn handle_string_edit(key: Key, app: &mut App) {
   }
 }
 
-/// Check if a keybinding conflicts with another action
-/// Returns Some(action_name) if conflict found, None otherwise
-fn check_keybinding_conflict(app: &App, new_key: Key, current_setting_id: &str) -> Option<String> {
-  // Iterate through all settings items
-  for setting in &app.settings_items {
-    // Skip if it's the same setting we're editing
-    if setting.id == current_setting_id {
-      continue;
+/// Replace the selected setting's value unless it fails validation, which stays on screen
+fn confirm_value(app: &mut App, value: SettingValue) -> bool {
+  let Some(setting) = app.settings_items.get_mut(app.settings_selected_index) else {
+    return false;
+  };
+  match setting.validate(&value) {
+    Ok(()) => {
+      setting.value = value;
+      app.settings_edit_error = None;
+      true
     }
-
-    // Only check keybinding settings
-    if !setting.id.starts_with("keys.") {
-      continue;
+    Err(e) => {
+      app.settings_edit_error = Some(e.to_string());
+      false
     }
+  }
+}
 
-    // Get the key value from this setting
-    if let SettingValue::Key(key_string) = &setting.value {
-      // Parse the key string to compare
-      if let Ok(existing_key) = crate::core::user_config::parse_key_public(key_string.clone()) {
-        // Check if keys match (case-sensitive comparison)
-        if existing_key == new_key {
-          // Return the friendly name of the conflicting action
-          return Some(setting.name.clone());
-        }
-      }
-    }
+/// Action of the Keybindings tab setting at `index`
+fn key_setting_action(app: &App, index: usize) -> Option<Action> {
+  let setting = app.settings_items.get(index)?;
+  Action::from_name(setting.id.strip_prefix("keys.")?)
+}
+
+/// What stands in the way of binding the recorded keys to the selected action
+fn key_capture_issue(app: &App, keys: &[Key]) -> Option<KeyCaptureIssue> {
+  let action = key_setting_action(app, app.settings_selected_index)?;
+  if let Err(e) = check_reserved_keys_public(*keys.first()?, action, None) {
+    return Some(KeyCaptureIssue::Reserved(e.to_string()));
   }
+  app
+    .settings_items
+    .iter()
+    .enumerate()
+    .filter(|(index, _)| *index != app.settings_selected_index)
+    .find_map(|(index, setting)| match &setting.value {
+      SettingValue::Key(value) if setting.id.starts_with("keys.") => {
+        let existing = keymap::parse_sequence(value).ok()?;
+        keymap::overlaps(&existing, keys).then(|| KeyCaptureIssue::Conflict {
+          index,
+          name: setting.name.clone(),
+        })
+      }
+      _ => None,
+    })
+}
 
-  None
+fn finish_key_edit(app: &mut App) {
+  app.settings_edit_mode = false;
+  app.settings_edit_buffer.clear();
+  app.key_capture = KeyCapture::default();
 }
 
 fn handle_key_edit(key: Key, app: &mut App) {
+  if app.key_capture.resolving {
+    handle_key_conflict(key, app);
+    return;
+  }
+
   match key {
     // Escape cancels the key binding edit
-    Key::Esc => {
-      app.settings_edit_mode = false;
-      app.settings_edit_buffer.clear();
+    Key::Esc => finish_key_edit(app),
+    Key::Enter if app.key_capture.keys.is_empty() => finish_key_edit(app),
+    Key::Enter => match app.key_capture.issue {
+      // Nothing to confirm until the reserved key is deleted again
+      Some(KeyCaptureIssue::Reserved(_)) => {}
+      Some(KeyCaptureIssue::Conflict { .. }) => app.key_capture.resolving = true,
+      None => {
+        set_captured_keys(app);
+        finish_key_edit(app);
+      }
+    },
+    Key::Backspace => {
+      app.key_capture.keys.pop();
+      app.key_capture.issue = key_capture_issue(app, &app.key_capture.keys);
     }
-    // Any other key press is captured as the new keybinding
+    // Any other key press is recorded, several make a chord
     _ => {
-      // Check if this is a reserved key
-      if let Err(e) = crate::core::user_config::check_reserved_keys_public(key) {
-        // Show error but don't apply the reserved key
-        app.handle_error(anyhow::anyhow!("{}", e));
-        app.settings_edit_mode = false;
-        app.settings_edit_buffer.clear();
-        return;
-      }
-
-      // Check for keybinding conflicts
-      if let Some(setting) = app.settings_items.get(app.settings_selected_index) {
-        if let Some(conflict_name) = check_keybinding_conflict(app, key, &setting.id) {
-          // Show error and don't apply the conflicting key
-          let key_display = key_to_config_string(&key);
-          app.handle_error(anyhow::anyhow!(
-            "Key {} is already assigned to {}",
-            key_display,
-            conflict_name
-          ));
-          app.settings_edit_mode = false;
-          app.settings_edit_buffer.clear();
-          return;
-        }
-      }
-
-      // Convert the key to string representation
-      let key_string = key_to_config_string(&key);
-
-      // Apply the new keybinding
-      if let Some(setting) = app.settings_items.get_mut(app.settings_selected_index) {
-        setting.value = SettingValue::Key(key_string);
-      }
+      app.key_capture.keys.push(key);
+      app.key_capture.issue = key_capture_issue(app, &app.key_capture.keys);
+    }
+  }
+}
 
-      app.settings_edit_mode = false;
-      app.settings_edit_buffer.clear();
+/// Swap keys with the conflicting action or take them away from it
+fn handle_key_conflict(key: Key, app: &mut App) {
+  let Some(KeyCaptureIssue::Conflict { index, .. }) = app.key_capture.issue else {
+    app.key_capture.resolving = false;
+    return;
+  };
+  let other_value = match key {
+    // The edit buffer still holds the keys the edited action had before
+    Key::Char('s') => app.settings_edit_buffer.clone(),
+    Key::Char('u') => String::new(),
+    Key::Esc => {
+      app.key_capture.resolving = false;
+      return;
     }
+    _ => return,
+  };
+  if let Some(other) = app.settings_items.get_mut(index) {
+    other.value = SettingValue::Key(other_value);
   }
+  set_captured_keys(app);
+  finish_key_edit(app);
 }
 
-/// Convert a Key to its config file string representation
-fn key_to_config_string(key: &Key) -> String {
-  match key {
-    Key::Char(c) if *c == ' ' => "space".to_string(),
-    Key::Char(c) => c.to_string(),
-    Key::Ctrl(c) => format!("ctrl-{}", c),
-    Key::Alt(c) => format!("alt-{}", c),
-    Key::Enter => "enter".to_string(),
-    Key::Esc => "esc".to_string(),
-    Key::Backspace => "backspace".to_string(),
-    Key::Delete => "del".to_string(),
-    Key::Left => "left".to_string(),
-    Key::Right => "right".to_string(),
-    Key::Up => "up".to_string(),
-    Key::Down => "down".to_string(),
-    Key::PageUp => "pageup".to_string(),
-    Key::PageDown => "pagedown".to_string(),
-    Key::Home => "home".to_string(),
-    Key::End => "end".to_string(),
-    Key::Tab => "tab".to_string(),
-    Key::Ins => "ins".to_string(),
-    Key::F0 => "f0".to_string(),
-    Key::F1 => "f1".to_string(),
-    Key::F2 => "f2".to_string(),
-    Key::F3 => "f3".to_string(),
-    Key::F4 => "f4".to_string(),
-    Key::F5 => "f5".to_string(),
-    Key::F6 => "f6".to_string(),
-    Key::F7 => "f7".to_string(),
-    Key::F8 => "f8".to_string(),
-    Key::F9 => "f9".to_string(),
-    Key::F10 => "f10".to_string(),
-    Key::F11 => "f11".to_string(),
-    Key::F12 => "f12".to_string(),
-    Key::Unknown => "unknown".to_string(),
+fn set_captured_keys(app: &mut App) {
+  let value = keymap::sequence_to_config_string(&app.key_capture.keys);
+  if let Some(setting) = app.settings_items.get_mut(app.settings_selected_index) {
+    setting.value = SettingValue::Key(value);
+  }
+}
+
+/// Put the selected binding, or all of them, back to the defaults; applied on save like any edit
+fn reset_key_bindings(app: &mut App, all: bool) {
+  let indices: Vec<usize> = if all {
+    (0..app.settings_items.len()).collect()
+  } else {
+    vec![app.settings_selected_index]
+  };
+  for index in indices {
+    if let Some(action) = key_setting_action(app, index) {
+      app.settings_items[index].value = SettingValue::Key(keymap::default_binding(action));
+    }
   }
 }
 
@@ -319,18 +343,15 @@ fn enter_edit_mode(app: &mut App) {
     }
 
     // For presets, cycle to next preset directly
-    if let SettingValue::Preset(ref preset_name) = setting.value {
-      use crate::core::user_config::ThemePreset;
-      let current = ThemePreset::from_name(preset_name);
-      let next = current.next();
-      if let Some(setting_mut) = app.settings_items.get_mut(app.settings_selected_index) {
-        setting_mut.value = SettingValue::Preset(next.name().to_string());
-      }
+    if let SettingValue::Preset(_) = setting.value {
+      cycle_preset(app, true);
       return;
     }
 
     // For other types, enter edit mode
     app.settings_edit_mode = true;
+    app.settings_edit_error = None;
+    app.key_capture = KeyCapture::default();
     // Pre-populate the edit buffer with current value
     app.settings_edit_buffer = match &setting.value {
       SettingValue::Bool(_) => String::new(), // Shouldn't reach here
@@ -344,50 +365,98 @@ fn enter_edit_mode(app: &mut App) {
 }
 
 fn handle_preset_edit(key: Key, app: &mut App) {
-  use crate::core::user_config::ThemePreset;
-
   match key {
     Key::Enter | Key::Char(' ') => {
-      // Cycle to next preset
-      if let Some(setting) = app.settings_items.get(app.settings_selected_index) {
-        if let SettingValue::Preset(ref preset_name) = setting.value {
-          let current = ThemePreset::from_name(preset_name);
-          let next = current.next();
-          if let Some(setting_mut) = app.settings_items.get_mut(app.settings_selected_index) {
-            setting_mut.value = SettingValue::Preset(next.name().to_string());
-          }
-        }
-      }
+      cycle_preset(app, true);
       app.settings_edit_mode = false;
     }
     Key::Esc => {
       app.settings_edit_mode = false;
     }
-    key if right_event(key) => {
-      // Next preset
-      if let Some(setting) = app.settings_items.get(app.settings_selected_index) {
-        if let SettingValue::Preset(ref preset_name) = setting.value {
-          let current = ThemePreset::from_name(preset_name);
-          let next = current.next();
-          if let Some(setting_mut) = app.settings_items.get_mut(app.settings_selected_index) {
-            setting_mut.value = SettingValue::Preset(next.name().to_string());
-          }
+    key if edit_action(key, app) == Some(Action::MoveRight) => cycle_preset(app, true),
+    key if edit_action(key, app) == Some(Action::MoveLeft) => cycle_preset(app, false),
+    _ => {}
+  }
+}
+
+/// Move the selected preset setting to the next (or previous) option of its own kind
+fn cycle_preset(app: &mut App, forward: bool) {
+  use crate::core::config::NormalisationMode;
+  use crate::core::user_config::{EqualizerPreset, NotificationMethod};
+
+  if let Some(setting) = app.settings_items.get_mut(app.settings_selected_index) {
+    if let SettingValue::Preset(ref preset_name) = setting.value {
+      let name = match setting.id.as_str() {
+        "behavior.notification_method" => {
+          let current = NotificationMethod::from_name(preset_name);
+          let next = if forward {
+            current.next()
+          } else {
+            current.prev()
+          };
+          next.name().to_string()
         }
-      }
-    }
-    key if left_event(key) => {
-      // Previous preset
-      if let Some(setting) = app.settings_items.get(app.settings_selected_index) {
-        if let SettingValue::Preset(ref preset_name) = setting.value {
-          let current = ThemePreset::from_name(preset_name);
-          let prev = current.prev();
-          if let Some(setting_mut) = app.settings_items.get_mut(app.settings_selected_index) {
-            setting_mut.value = SettingValue::Preset(prev.name().to_string());
-          }
+        "behavior.color_support" => {
+          let current = crate::core::user_config::ColorSupport::from_name(preset_name);
+          let next = if forward {
+            current.next()
+          } else {
+            current.prev()
+          };
+          next.name().to_string()
         }
-      }
+        "streaming.normalisation_mode" => {
+          let current = NormalisationMode::from_name(preset_name);
+          let next = if forward {
+            current.next()
+          } else {
+            current.prev()
+          };
+          next.name().to_string()
+        }
+        #[cfg(feature = "cover-art")]
+        "behavior.cover_art_fallback" => {
+          let current = crate::core::user_config::CoverArtFallback::from_name(preset_name);
+          let next = if forward {
+            current.next()
+          } else {
+            current.prev()
+          };
+          next.name().to_string()
+        }
+        "streaming.bitrate" => {
+          const BITRATES: [&str; 3] = ["96", "160", "320"];
+          let current = BITRATES.iter().position(|b| b == preset_name).unwrap_or(2);
+          let next = if forward {
+            (current + 1) % BITRATES.len()
+          } else {
+            (current + BITRATES.len() - 1) % BITRATES.len()
+          };
+          BITRATES[next].to_string()
+        }
+        "eq.preset" => {
+          let current = EqualizerPreset::from_name(preset_name);
+          let next = if forward {
+            current.next()
+          } else {
+            current.prev()
+          };
+          next.name().to_string()
+        }
+        // Theme presets, including the ones loaded from the themes directory
+        _ => {
+          let names = app.user_config.theme_names();
+          let current = names.iter().position(|n| n == preset_name).unwrap_or(0);
+          let next = if forward {
+            (current + 1) % names.len()
+          } else {
+            (current + names.len() - 1) % names.len()
+          };
+          names[next].clone()
+        }
+      };
+      setting.value = SettingValue::Preset(name);
     }
-    _ => {}
   }
 }
 
@@ -397,4 +466,225 @@ fn save_settings(app: &mut App) {
   if let Err(e) = app.user_config.save_config() {
     app.handle_error(anyhow::anyhow!("Failed to save settings: {}", e));
   }
+  if matches!(
+    app.settings_category,
+    SettingsCategory::Streaming | SettingsCategory::Account
+  ) {
+    if let Err(e) = app.client_config.save_ui_settings() {
+      app.handle_error(anyhow::anyhow!("Failed to save client settings: {}", e));
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn keybindings_settings() -> App {
+    let mut app = App::default();
+    app.settings_category = SettingsCategory::Keybindings;
+    app.load_settings_for_category();
+    app
+  }
+
+  fn select(app: &mut App, id: &str) -> usize {
+    let index = app
+      .settings_items
+      .iter()
+      .position(|setting| setting.id == id)
+      .unwrap();
+    app.settings_selected_index = index;
+    index
+  }
+
+  fn setting_value(app: &App, index: usize) -> &SettingValue {
+    &app.settings_items[index].value
+  }
+
+  #[test]
+  fn chords_are_recorded_until_enter() {
+    let mut app = keybindings_settings();
+    let search = select(&mut app, "keys.search");
+
+    handler(Action::Submit, &mut app);
+    edit_handler(Key::Char('g'), &mut app);
+    edit_handler(Key::Char('s'), &mut app);
+    assert!(app.settings_edit_mode);
+    assert_eq!(app.key_capture.issue, None);
+    edit_handler(Key::Enter, &mut app);
+
+    assert!(!app.settings_edit_mode);
+    assert_eq!(
+      setting_value(&app, search),
+      &SettingValue::Key("g s".to_string())
+    );
+  }
+
+  #[test]
+  fn conflicts_show_up_straight_away_and_can_be_swapped() {
+    let mut app = keybindings_settings();
+    let previous = select(&mut app, "keys.previous_track");
+    let next = select(&mut app, "keys.next_track");
+
+    handler(Action::Submit, &mut app);
+    edit_handler(Key::Char('p'), &mut app);
+    assert!(matches!(
+      app.key_capture.issue,
+      Some(KeyCaptureIssue::Conflict { index, .. }) if index == previous
+    ));
+
+    edit_handler(Key::Enter, &mut app);
+    assert!(app.key_capture.resolving);
+    edit_handler(Key::Char('s'), &mut app);
+
+    assert!(!app.settings_edit_mode);
+    assert_eq!(
+      setting_value(&app, next),
+      &SettingValue::Key("p".to_string())
+    );
+    assert_eq!(
+      setting_value(&app, previous),
+      &SettingValue::Key("n".to_string())
+    );
+
+    app.apply_settings_changes();
+    assert_eq!(
+      app.user_config.keys.action("home", Key::Char('p')),
+      Some(Action::NextTrack)
+    );
+    assert_eq!(
+      app.user_config.keys.action("home", Key::Char('n')),
+      Some(Action::PreviousTrack)
+    );
+  }
+
+  #[test]
+  fn unbind_takes_the_keys_from_the_other_action() {
+    let mut app = keybindings_settings();
+    let previous = select(&mut app, "keys.previous_track");
+    let next = select(&mut app, "keys.next_track");
+
+    handler(Action::Submit, &mut app);
+    edit_handler(Key::Char('p'), &mut app);
+    edit_handler(Key::Enter, &mut app);
+    edit_handler(Key::Char('u'), &mut app);
+    app.apply_settings_changes();
+
+    assert_eq!(
+      setting_value(&app, previous),
+      &SettingValue::Key(String::new())
+    );
+    assert_eq!(
+      setting_value(&app, next),
+      &SettingValue::Key("p".to_string())
+    );
+    assert_eq!(
+      app.user_config.keys.first_binding(Action::PreviousTrack),
+      ""
+    );
+  }
+
+  #[test]
+  fn reserved_keys_cannot_be_confirmed() {
+    let mut app = keybindings_settings();
+    let search = select(&mut app, "keys.search");
+
+    handler(Action::Submit, &mut app);
+    edit_handler(Key::Up, &mut app);
+    assert!(matches!(
+      app.key_capture.issue,
+      Some(KeyCaptureIssue::Reserved(_))
+    ));
+    edit_handler(Key::Enter, &mut app);
+    assert!(app.settings_edit_mode);
+
+    edit_handler(Key::Backspace, &mut app);
+    edit_handler(Key::Esc, &mut app);
+    assert!(!app.settings_edit_mode);
+    assert_eq!(
+      setting_value(&app, search),
+      &SettingValue::Key("/".to_string())
+    );
+  }
+
+  #[test]
+  fn reset_puts_the_defaults_back() {
+    let mut app = keybindings_settings();
+    let search = select(&mut app, "keys.search");
+    app.settings_items[search].value = SettingValue::Key("g s".to_string());
+    let help = select(&mut app, "keys.help");
+    app.settings_items[help].value = SettingValue::Key("g h".to_string());
+
+    handler(Action::ResetBinding, &mut app);
+    assert_eq!(
+      setting_value(&app, help),
+      &SettingValue::Key("?".to_string())
+    );
+    assert_eq!(
+      setting_value(&app, search),
+      &SettingValue::Key("g s".to_string())
+    );
+
+    handler(Action::ResetAllBindings, &mut app);
+    assert_eq!(
+      setting_value(&app, search),
+      &SettingValue::Key("/".to_string())
+    );
+  }
+
+  #[test]
+  fn unbinding_a_key_resizes_the_help() {
+    let mut app = keybindings_settings();
+    app.update_help_docs_size();
+    let before = app.help_docs_size;
+    let search = select(&mut app, "keys.search");
+    app.settings_items[search].value = SettingValue::Key(String::new());
+
+    app.apply_settings_changes();
+
+    assert_eq!(app.help_docs_size, before - 1);
+  }
+
+  #[test]
+  fn invalid_client_ids_stay_in_edit_mode() {
+    let mut app = App::default();
+    app.settings_category = SettingsCategory::Account;
+    app.load_settings_for_category();
+    let client_id = select(&mut app, "account.client_id");
+
+    handler(Action::Submit, &mut app);
+    app.settings_edit_buffer = "not-a-client-id".to_string();
+    edit_handler(Key::Enter, &mut app);
+    assert!(app.settings_edit_mode);
+    assert!(app.settings_edit_error.is_some());
+
+    app.settings_edit_buffer = "0123456789abcdef0123456789ABCDEF".to_string();
+    edit_handler(Key::Enter, &mut app);
+    assert!(!app.settings_edit_mode);
+    assert_eq!(app.settings_edit_error, None);
+    assert!(app.settings_items[client_id].requires_restart());
+
+    app.apply_settings_changes();
+    assert_eq!(
+      app.client_config.client_id,
+      "0123456789abcdef0123456789ABCDEF"
+    );
+  }
+
+  #[test]
+  fn bitrate_cycles_through_the_supported_values() {
+    let mut app = App::default();
+    app.settings_category = SettingsCategory::Streaming;
+    app.load_settings_for_category();
+    let bitrate = select(&mut app, "streaming.bitrate");
+
+    handler(Action::Submit, &mut app);
+    assert_eq!(
+      setting_value(&app, bitrate),
+      &SettingValue::Preset("96".to_string())
+    );
+
+    app.apply_settings_changes();
+    assert_eq!(app.client_config.streaming_bitrate, 96);
+  }
 }
@@ -3,18 +3,19 @@ use crate::core::app::{
   ActiveBlock, App, DialogContext, PendingPlaylistTrackRemoval, PendingTrackSelection,
   RecommendationsContext, RouteId, TrackTable, TrackTableContext,
 };
+use crate::core::keymap::Action;
 use crate::infra::network::IoEvent;
-use crate::tui::event::Key;
 use rand::{thread_rng, Rng};
 use rspotify::model::{
   idtypes::{PlayContextId, PlaylistId, TrackId},
   PlayableId,
 };
+use rspotify::prelude::Id;
 
-pub fn handler(key: Key, app: &mut App) {
-  match key {
-    k if common_key_events::left_event(k) => common_key_events::handle_left_event(app),
-    k if common_key_events::down_event(k) => {
+pub fn handler(action: Action, app: &mut App) {
+  match action {
+    Action::MoveLeft => common_key_events::handle_left_event(app),
+    Action::MoveDown => {
       let current_index = app.track_table.selected_index;
       let tracks_len = app.track_table.tracks.len();
 
@@ -61,7 +62,7 @@ pub fn handler(key: Key, app: &mut App) {
       );
       app.track_table.selected_index = next_index;
     }
-    k if common_key_events::up_event(k) => {
+    Action::MoveUp => {
       let current_index = app.track_table.selected_index;
 
       // Check if we're at the first track and there are previous tracks to load
@@ -100,23 +101,23 @@ pub fn handler(key: Key, app: &mut App) {
       );
       app.track_table.selected_index = next_index;
     }
-    k if common_key_events::high_event(k) => {
+    Action::SelectTop => {
       let next_index = common_key_events::on_high_press_handler();
       app.track_table.selected_index = next_index;
     }
-    k if common_key_events::middle_event(k) => {
+    Action::SelectMiddle => {
       let next_index = common_key_events::on_middle_press_handler(&app.track_table.tracks);
       app.track_table.selected_index = next_index;
     }
-    k if common_key_events::low_event(k) => {
+    Action::SelectBottom => {
       let next_index = common_key_events::on_low_press_handler(&app.track_table.tracks);
       app.track_table.selected_index = next_index;
     }
-    Key::Enter => {
+    Action::Submit => {
       on_enter(app);
     }
     // Scroll down
-    k if k == app.user_config.keys.next_page => {
+    Action::NextPage => {
       if let Some(context) = &app.track_table.context {
         match context {
           TrackTableContext::MyPlaylists => {
@@ -140,7 +141,7 @@ pub fn handler(key: Key, app: &mut App) {
       };
     }
     // Scroll up
-    k if k == app.user_config.keys.previous_page => {
+    Action::PreviousPage => {
       if let Some(context) = &app.track_table.context {
         match context {
           TrackTableContext::MyPlaylists => {
@@ -161,21 +162,33 @@ pub fn handler(key: Key, app: &mut App) {
         }
       };
     }
-    Key::Char('w') => open_add_to_playlist_dialog(app),
-    Key::Char('x') => open_remove_from_playlist_dialog(app),
-    Key::Char('s') => handle_save_track_event(app),
-    Key::Char('S') => play_random_song(app),
-    k if k == app.user_config.keys.jump_to_end => jump_to_end(app),
-    k if k == app.user_config.keys.jump_to_start => jump_to_start(app),
+    Action::AddToPlaylist => open_add_to_playlist_dialog(app),
+    Action::RemoveFromPlaylist => open_remove_from_playlist_dialog(app),
+    Action::Save => handle_save_track_event(app),
+    Action::VisualSelect => {
+      app.visual_anchor = match app.visual_anchor {
+        Some(_) => None,
+        None => Some(app.track_table.selected_index),
+      };
+    }
+    Action::PlayRandom => play_random_song(app),
+    Action::JumpToEnd => jump_to_end(app),
+    Action::JumpToStart => jump_to_start(app),
     //recommended song radio
-    Key::Char('r') => {
+    Action::Recommendations => {
       handle_recommended_tracks(app);
     }
-    _ if key == app.user_config.keys.add_item_to_queue => on_queue(app),
+    Action::AddItemToQueue => on_queue(app),
     // Open sort menu
-    Key::Char(',') => {
-      super::sort_menu::open_sort_menu(app, crate::core::sort::SortContext::PlaylistTracks);
-    }
+    Action::OpenSortMenu => match app.track_table.context {
+      Some(TrackTableContext::MyPlaylists) => {
+        super::sort_menu::open_sort_menu(app, crate::core::sort::SortContext::PlaylistTracks);
+      }
+      Some(TrackTableContext::SavedTracks) => {
+        super::sort_menu::open_sort_menu(app, crate::core::sort::SortContext::LikedSongs);
+      }
+      _ => {}
+    },
     _ => {}
   }
 }
@@ -331,6 +344,10 @@ fn play_random_song(app: &mut App) {
 }
 
 fn handle_save_track_event(app: &mut App) {
+  if let Some(range) = app.visual_range() {
+    save_range(app, range);
+    return;
+  }
   let (selected_index, tracks) = (&app.track_table.selected_index, &app.track_table.tracks);
   if let Some(track) = tracks.get(*selected_index) {
     if let Some(playable_id) = track_playable_id(track.id.clone()) {
@@ -379,6 +396,7 @@ fn on_enter(app: &mut App) {
     context,
     selected_index,
     tracks,
+    ..
   } = &app.track_table;
   if let Some(context) = &context {
     match context {
@@ -515,11 +533,50 @@ fn on_enter(app: &mut App) {
   };
 }
 
+/// Save every track of the range, or unsave them all when they are all saved already
+fn save_range(app: &mut App, range: std::ops::RangeInclusive<usize>) {
+  let ids: Vec<TrackId<'static>> = app.track_table.tracks[range]
+    .iter()
+    .filter_map(|track| track.id.clone().map(|id| id.into_static()))
+    .collect();
+  let all_saved = ids
+    .iter()
+    .all(|id| app.liked_song_ids_set.contains(id.id()));
+  let mut count = 0;
+  for id in ids {
+    if all_saved || !app.liked_song_ids_set.contains(id.id()) {
+      app.dispatch(IoEvent::ToggleSaveTrack(PlayableId::Track(id)));
+      count += 1;
+    }
+  }
+  app.visual_anchor = None;
+  let verb = if all_saved { "Unsaved" } else { "Saved" };
+  app.set_status_message(format!("{} {} tracks", verb, count), 4);
+}
+
+fn queue_range(app: &mut App, range: std::ops::RangeInclusive<usize>) {
+  let ids: Vec<PlayableId<'static>> = app.track_table.tracks[range]
+    .iter()
+    .filter_map(|track| track_playable_id(track.id.clone()))
+    .collect();
+  let count = ids.len();
+  for id in ids {
+    app.dispatch(IoEvent::AddItemToQueue(id));
+  }
+  app.visual_anchor = None;
+  app.set_status_message(format!("Queued {} tracks", count), 4);
+}
+
 fn on_queue(app: &mut App) {
+  if let Some(range) = app.visual_range() {
+    queue_range(app, range);
+    return;
+  }
   let TrackTable {
     context,
     selected_index,
     tracks,
+    ..
   } = &app.track_table;
   if let Some(context) = &context {
     match context {
@@ -1,9 +1,9 @@
 use crate::core::app::App;
-use crate::tui::event::Key;
+use crate::core::keymap::Action;
 
-pub fn handler(key: Key, app: &mut App) {
-  match key {
-    Key::Enter | Key::Esc | Key::Char('q') | Key::Char(' ') => {
+pub fn handler(action: Action, app: &mut App) {
+  match action {
+    Action::Submit | Action::Back => {
       app.update_prompt_acknowledged = true;
       app.pop_navigation_stack();
     }
@@ -1,3 +1,5 @@
+#[cfg(feature = "cover-art")]
+pub mod adaptive_theme;
 pub mod banner;
 #[cfg(feature = "cover-art")]
 pub mod cover_art;
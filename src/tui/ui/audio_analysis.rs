@@ -81,7 +81,7 @@ pub fn draw(f: &mut Frame<'_>, app: &App) {
       }
       VisualizerStyle::BarGraph => {
         f.render_widget(bar_chart_block, visualizer_area);
-        render_bar_graph(f, &spectrum.bands, inner_area);
+        render_bar_graph(f, app, &spectrum.bands, inner_area);
       }
     }
   } else {
@@ -112,6 +112,14 @@ pub fn draw(f: &mut Frame<'_>, app: &App) {
   }
 }
 
+/// Borderless spectrum in the configured style, for embedding in other views
+pub fn draw_spectrum_strip(f: &mut Frame<'_>, app: &App, bands: &[f32], area: Rect) {
+  match app.user_config.behavior.visualizer_style {
+    VisualizerStyle::Equalizer => render_equalizer(f, bands, area),
+    VisualizerStyle::BarGraph => render_bar_graph(f, app, bands, area),
+  }
+}
+
 /// Render equalizer-style visualization using tui-equalizer
 /// https://github.com/joshka/tui-equalizer
 ///
@@ -220,7 +228,7 @@ fn render_equalizer(f: &mut Frame<'_>, bands: &[f32], area: Rect) {
 /// https://github.com/joshka/tui-widgets/tree/main/tui-bar-graph
 ///
 /// The tui-bar-graph widget fills the entire area with one bar per column.
-fn render_bar_graph(f: &mut Frame<'_>, bands: &[f32], area: Rect) {
+fn render_bar_graph(f: &mut Frame<'_>, app: &App, bands: &[f32], area: Rect) {
   if bands.is_empty() || area.width == 0 || area.height == 0 {
     return;
   }
@@ -229,8 +237,25 @@ fn render_bar_graph(f: &mut Frame<'_>, bands: &[f32], area: Rect) {
   let target_width = (area.width as usize) * 2;
   let data = interpolate_bands(bands, target_width);
 
-  let bar_graph = BarGraph::new(data)
-    .with_gradient(colorgrad::preset::turbo())
+  let bar_graph = BarGraph::new(data);
+  // The adaptive theme carries the cover's colors into the bars
+  #[cfg(feature = "cover-art")]
+  let bar_graph = if app.user_config.behavior.adaptive_theme {
+    let theme = &app.user_config.theme;
+    bar_graph.with_gradient(util::build_gradient(&[
+      theme.hovered,
+      theme.analysis_bar,
+      theme.text,
+    ]))
+  } else {
+    bar_graph.with_gradient(colorgrad::preset::turbo())
+  };
+  #[cfg(not(feature = "cover-art"))]
+  let bar_graph = {
+    let _ = app;
+    bar_graph.with_gradient(colorgrad::preset::turbo())
+  };
+  let bar_graph = bar_graph
     .with_bar_style(BarStyle::Braille) // Braille for high-res, Solid for blocks
     .with_color_mode(ColorMode::VerticalGradient)
     .with_max(1.0);
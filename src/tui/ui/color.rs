@@ -0,0 +1,160 @@
+// The mapping of RGB colors onto the 256 or 16 color palettes for terminals without truecolor.
+// The mapping runs over the whole frame buffer after drawing, so themes, the banner gradient,
+// the visualizers and cover art are all covered
+
+use crate::core::user_config::ColorSupport;
+use ratatui::{buffer::Buffer, style::Color};
+
+/// The 16 ANSI colors with the xterm default values, used to find the nearest match
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+  (Color::Black, (0, 0, 0)),
+  (Color::Red, (205, 0, 0)),
+  (Color::Green, (0, 205, 0)),
+  (Color::Yellow, (205, 205, 0)),
+  (Color::Blue, (0, 0, 238)),
+  (Color::Magenta, (205, 0, 205)),
+  (Color::Cyan, (0, 205, 205)),
+  (Color::Gray, (229, 229, 229)),
+  (Color::DarkGray, (127, 127, 127)),
+  (Color::LightRed, (255, 0, 0)),
+  (Color::LightGreen, (0, 255, 0)),
+  (Color::LightYellow, (255, 255, 0)),
+  (Color::LightBlue, (92, 92, 255)),
+  (Color::LightMagenta, (255, 0, 255)),
+  (Color::LightCyan, (0, 255, 255)),
+  (Color::White, (255, 255, 255)),
+];
+
+/// Channel values of the 6x6x6 cube in the 256 color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+pub fn downsample(color: Color, support: ColorSupport) -> Color {
+  match (support, color) {
+    (ColorSupport::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_256((r, g, b))),
+    (ColorSupport::Ansi16, Color::Rgb(r, g, b)) => nearest_16((r, g, b)),
+    (ColorSupport::Ansi16, Color::Indexed(index)) if index >= 16 => {
+      nearest_16(indexed_to_rgb(index))
+    }
+    _ => color,
+  }
+}
+
+/// Map every cell of a drawn frame onto the palette of the terminal
+pub fn downsample_buffer(buf: &mut Buffer, support: ColorSupport) {
+  if matches!(support, ColorSupport::TrueColor | ColorSupport::Auto) {
+    return;
+  }
+  for cell in buf.content.iter_mut() {
+    cell.fg = downsample(cell.fg, support);
+    cell.bg = downsample(cell.bg, support);
+  }
+}
+
+/// Closest entry of the color cube or the grayscale ramp. The first 16 entries are left out since
+/// terminals remap them
+fn nearest_256(rgb: (u8, u8, u8)) -> u8 {
+  let level = |c: u8| match c {
+    0..=47 => 0,
+    48..=114 => 1,
+    _ => (c - 35) / 40,
+  };
+  let (r, g, b) = (level(rgb.0), level(rgb.1), level(rgb.2));
+  let cube = (
+    CUBE_LEVELS[r as usize],
+    CUBE_LEVELS[g as usize],
+    CUBE_LEVELS[b as usize],
+  );
+  let cube_index = 16 + 36 * r + 6 * g + b;
+
+  let average = (rgb.0 as u16 + rgb.1 as u16 + rgb.2 as u16) / 3;
+  let gray_step = (average.saturating_sub(3) / 10).min(23) as u8;
+  let gray_value = 8 + 10 * gray_step;
+  let gray_index = 232 + gray_step;
+
+  if distance(rgb, (gray_value, gray_value, gray_value)) < distance(rgb, cube) {
+    gray_index
+  } else {
+    cube_index
+  }
+}
+
+fn nearest_16(rgb: (u8, u8, u8)) -> Color {
+  ANSI16
+    .iter()
+    .min_by_key(|(_, candidate)| distance(rgb, *candidate))
+    .map_or(Color::Reset, |(color, _)| *color)
+}
+
+fn indexed_to_rgb(index: u8) -> (u8, u8, u8) {
+  match index {
+    0..=15 => ANSI16[index as usize].1,
+    16..=231 => {
+      let i = index - 16;
+      (
+        CUBE_LEVELS[(i / 36) as usize],
+        CUBE_LEVELS[(i / 6 % 6) as usize],
+        CUBE_LEVELS[(i % 6) as usize],
+      )
+    }
+    _ => {
+      let value = 8 + 10 * (index - 232);
+      (value, value, value)
+    }
+  }
+}
+
+/// Squared distance weighted by how sensitive the eye is to each channel ("redmean")
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+  let mean_red = (a.0 as i32 + b.0 as i32) / 2;
+  let dr = a.0 as i32 - b.0 as i32;
+  let dg = a.1 as i32 - b.1 as i32;
+  let db = a.2 as i32 - b.2 as i32;
+  ((((512 + mean_red) * dr * dr) >> 8) + 4 * dg * dg + (((767 - mean_red) * db * db) >> 8)) as u32
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use ratatui::layout::Rect;
+
+  #[test]
+  fn maps_to_the_256_color_palette() {
+    assert_eq!(
+      downsample(Color::Rgb(255, 0, 0), ColorSupport::Ansi256),
+      Color::Indexed(196)
+    );
+    assert_eq!(
+      downsample(Color::Rgb(0, 200, 200), ColorSupport::Ansi256),
+      Color::Indexed(44)
+    );
+    // Near-grays land on the grayscale ramp rather than the coarser cube
+    assert_eq!(
+      downsample(Color::Rgb(128, 128, 130), ColorSupport::Ansi256),
+      Color::Indexed(244)
+    );
+  }
+
+  #[test]
+  fn maps_to_the_16_color_palette() {
+    assert_eq!(
+      downsample(Color::Rgb(0, 200, 200), ColorSupport::Ansi16),
+      Color::Cyan
+    );
+    assert_eq!(
+      downsample(Color::Indexed(196), ColorSupport::Ansi16),
+      Color::LightRed
+    );
+    // Named colors are already in every palette
+    assert_eq!(downsample(Color::Reset, ColorSupport::Ansi16), Color::Reset);
+  }
+
+  #[test]
+  fn downsamples_every_cell() {
+    let mut buf = Buffer::empty(Rect::new(0, 0, 2, 1));
+    buf[(0, 0)].set_fg(Color::Rgb(255, 0, 0));
+    buf[(1, 0)].set_bg(Color::Rgb(0, 0, 0));
+    downsample_buffer(&mut buf, ColorSupport::Ansi16);
+    assert_eq!(buf[(0, 0)].fg, Color::LightRed);
+    assert_eq!(buf[(1, 0)].bg, Color::Black);
+  }
+}
@@ -1,306 +1,190 @@
-use crate::core::user_config::KeyBindings;
+use crate::core::app::App;
+use crate::core::keymap::{KeyBindings, VIEWS};
+use anyhow::Result;
+use serde::Serialize;
 
-pub fn get_help_docs(key_bindings: &KeyBindings) -> Vec<Vec<String>> {
-  vec![
-    vec![
-      String::from("Scroll down to next result page"),
-      key_bindings.next_page.to_string(),
-      String::from("Pagination"),
-    ],
-    vec![
-      String::from("Scroll up to previous result page"),
-      key_bindings.previous_page.to_string(),
-      String::from("Pagination"),
-    ],
-    vec![
-      String::from("Jump to start of playlist"),
-      key_bindings.jump_to_start.to_string(),
-      String::from("Pagination"),
-    ],
-    vec![
-      String::from("Jump to end of playlist"),
-      key_bindings.jump_to_end.to_string(),
-      String::from("Pagination"),
-    ],
-    vec![
-      String::from("Jump to currently playing album"),
-      key_bindings.jump_to_album.to_string(),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Jump to currently playing artist's album list"),
-      key_bindings.jump_to_artist_album.to_string(),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Jump to current play context"),
-      key_bindings.jump_to_context.to_string(),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Increase volume by 10%"),
-      key_bindings.increase_volume.to_string(),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Decrease volume by 10%"),
-      key_bindings.decrease_volume.to_string(),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Skip to next track"),
-      key_bindings.next_track.to_string(),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Skip to previous track"),
-      key_bindings.previous_track.to_string(),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Seek backwards 5 seconds"),
-      key_bindings.seek_backwards.to_string(),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Seek forwards 5 seconds"),
-      key_bindings.seek_forwards.to_string(),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Toggle shuffle"),
-      key_bindings.shuffle.to_string(),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Copy url to currently playing song/episode"),
-      key_bindings.copy_song_url.to_string(),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Copy url to currently playing album/show"),
-      key_bindings.copy_album_url.to_string(),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Cycle repeat mode"),
-      key_bindings.repeat.to_string(),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Move selection left"),
-      String::from("h | <Left Arrow Key> | <Ctrl+b>"),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Move selection down"),
-      String::from("j | <Down Arrow Key> | <Ctrl+n>"),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Move selection up"),
-      String::from("k | <Up Arrow Key> | <Ctrl+p>"),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Move selection right"),
-      String::from("l | <Right Arrow Key> | <Ctrl+f>"),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Move selection to top of list"),
-      String::from("H"),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Move selection to middle of list"),
-      String::from("M"),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Move selection to bottom of list"),
-      String::from("L"),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Enter input for search"),
-      key_bindings.search.to_string(),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Pause/Resume playback"),
-      key_bindings.toggle_playback.to_string(),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Enter active mode"),
-      String::from("<Enter>"),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Go to audio analysis screen"),
-      key_bindings.audio_analysis.to_string(),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Go to lyrics view"),
-      key_bindings.basic_view.to_string(),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Go back or exit when nowhere left to back to"),
-      key_bindings.back.to_string(),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Select device to play music on"),
-      key_bindings.manage_devices.to_string(),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Open settings"),
-      key_bindings.open_settings.to_string(),
-      String::from("General"),
-    ],
-    vec![
-      String::from("Save settings"),
-      key_bindings.save_settings.to_string(),
-      String::from("Settings"),
-    ],
-    vec![
-      String::from("Enter hover mode"),
-      String::from("<Esc>"),
-      String::from("Selected block"),
-    ],
-    vec![
-      String::from("Save track in list or table"),
-      String::from("s"),
-      String::from("Selected block"),
-    ],
-    vec![
-      String::from("Add selected track to playlist"),
-      String::from("w"),
-      String::from("Track table"),
-    ],
-    vec![
-      String::from("Add currently playing track to playlist"),
-      String::from("w"),
-      String::from("Playbar"),
-    ],
-    vec![
-      String::from("Quick-add currently playing track to playlist"),
-      String::from("W"),
-      String::from("Global"),
-    ],
-    vec![
-      String::from("Remove selected track from current playlist"),
-      String::from("x"),
-      String::from("Track table (playlist views)"),
-    ],
-    vec![
-      String::from("Start playback or enter album/artist/playlist"),
-      key_bindings.submit.to_string(),
-      String::from("Selected block"),
-    ],
-    vec![
-      String::from("Play recommendations for song/artist"),
-      String::from("r"),
-      String::from("Selected block"),
-    ],
-    vec![
-      String::from("Play all tracks for artist"),
-      String::from("e"),
-      String::from("Library -> Artists"),
-    ],
-    vec![
-      String::from("Search with input text"),
-      String::from("<Enter>"),
-      String::from("Search input"),
-    ],
-    vec![
-      String::from("Move cursor one space left"),
-      String::from("<Left Arrow Key>"),
-      String::from("Search input"),
-    ],
-    vec![
-      String::from("Move cursor one space right"),
-      String::from("<Right Arrow Key>"),
-      String::from("Search input"),
-    ],
-    vec![
-      String::from("Delete entire input"),
-      String::from("<Ctrl+l>"),
-      String::from("Search input"),
-    ],
-    vec![
-      String::from("Delete text from cursor to start of input"),
-      String::from("<Ctrl+u>"),
-      String::from("Search input"),
-    ],
-    vec![
-      String::from("Delete text from cursor to end of input"),
-      String::from("<Ctrl+k>"),
-      String::from("Search input"),
-    ],
-    vec![
-      String::from("Delete previous word"),
-      String::from("<Ctrl+w>"),
-      String::from("Search input"),
-    ],
-    vec![
-      String::from("Jump to start of input"),
-      String::from("<Ctrl+a>"),
-      String::from("Search input"),
-    ],
-    vec![
-      String::from("Jump to end of input"),
-      String::from("<Ctrl+e>"),
-      String::from("Search input"),
-    ],
-    vec![
-      String::from("Escape from the input back to hovered block"),
-      String::from("<Esc>"),
-      String::from("Search input"),
-    ],
-    vec![
-      String::from("Delete saved album"),
-      String::from("D"),
-      String::from("Library -> Albums"),
-    ],
-    vec![
-      String::from("Delete saved playlist"),
-      String::from("D"),
-      String::from("Playlist"),
-    ],
-    vec![
-      String::from("Follow an artist/playlist"),
-      String::from("w"),
-      String::from("Search result"),
-    ],
-    vec![
-      String::from("Save (like) album to library"),
-      String::from("w"),
-      String::from("Search result"),
-    ],
-    vec![
-      String::from("Play random song in playlist"),
-      String::from("S"),
-      String::from("Selected Playlist"),
-    ],
-    vec![
-      String::from("Toggle sort order of podcast episodes"),
-      String::from("S"),
-      String::from("Selected Show"),
-    ],
-    vec![
-      String::from("Add track to queue"),
-      key_bindings.add_item_to_queue.to_string(),
-      String::from("Hovered over track"),
-    ],
-    vec![
-      String::from("Open sort menu"),
-      String::from(","),
-      String::from("Track/Album/Artist list"),
-    ],
-  ]
+/// One line of the help menu
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct HelpDoc {
+  pub context: String,
+  pub keys: String,
+  pub description: String,
+  /// Name of the action in config.yml, `None` for keys that cannot be rebound
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub action: Option<&'static str>,
+}
+
+impl HelpDoc {
+  /// Case-insensitive match of `filter` against any column
+  pub fn matches(&self, filter: &str) -> bool {
+    let filter = filter.to_lowercase();
+    [
+      self.context.as_str(),
+      self.keys.as_str(),
+      self.description.as_str(),
+      self.action.unwrap_or_default(),
+    ]
+    .iter()
+    .any(|column| column.to_lowercase().contains(&filter))
+  }
+}
+
+// Keys handled outside the registry, listed after the bindings of their view
+const FIXED_KEYS: &[(&str, &str, &str)] = &[
+  (
+    "global",
+    "<Esc>",
+    "Leave the selected block, close popups or go back",
+  ),
+  (
+    "global",
+    "<0-9>",
+    "Count for the next move, page, seek or skip, or the row to select with G/gg",
+  ),
+  ("input", "<Enter>", "Search with input text"),
+  ("input", "<Left Arrow Key>", "Move cursor one space left"),
+  ("input", "<Right Arrow Key>", "Move cursor one space right"),
+  ("input", "<Ctrl+l>", "Delete entire input"),
+  (
+    "input",
+    "<Ctrl+u>",
+    "Delete text from cursor to start of input",
+  ),
+  (
+    "input",
+    "<Ctrl+k>",
+    "Delete text from cursor to end of input",
+  ),
+  ("input", "<Ctrl+w>", "Delete previous word"),
+  ("input", "<Ctrl+a>", "Jump to start of input"),
+  ("input", "<Ctrl+e>", "Jump to end of input"),
+  (
+    "input",
+    "<Esc>",
+    "Escape from the input back to hovered block",
+  ),
+];
+
+/// "track_table" reads as "Track table"
+fn view_title(view: &str) -> String {
+  let mut title = view.replace('_', " ");
+  if let Some(first) = title.get_mut(0..1) {
+    first.make_ascii_uppercase();
+  }
+  title
+}
+
+/// The help built from the live key bindings, the global keymap first and then each view
+pub fn get_help_docs(key_bindings: &KeyBindings) -> Vec<HelpDoc> {
+  let mut docs = Vec::new();
+  for view in std::iter::once(None).chain(VIEWS.iter().copied().map(Some)) {
+    let context = view_title(view.unwrap_or("global"));
+    for (action, keys) in key_bindings.bound_in(view) {
+      docs.push(HelpDoc {
+        context: context.clone(),
+        keys,
+        description: action.description().to_string(),
+        action: Some(action.name()),
+      });
+    }
+    for (_, keys, description) in FIXED_KEYS
+      .iter()
+      .filter(|(fixed_view, _, _)| *fixed_view == view.unwrap_or("global"))
+    {
+      docs.push(HelpDoc {
+        context: context.clone(),
+        keys: keys.to_string(),
+        description: description.to_string(),
+        action: None,
+      });
+    }
+  }
+  docs
+}
+
+/// What the help menu shows, narrowed down by the search typed into it
+pub fn visible_help_docs(app: &App) -> Vec<HelpDoc> {
+  get_help_docs(&app.user_config.keys)
+    .into_iter()
+    .filter(|doc| doc.matches(&app.help_filter))
+    .collect()
+}
+
+/// The help as Markdown, one table per view
+pub fn help_markdown(key_bindings: &KeyBindings) -> String {
+  let mut markdown = String::new();
+  let mut context = None;
+  for doc in get_help_docs(key_bindings) {
+    if context.as_ref() != Some(&doc.context) {
+      if context.is_some() {
+        markdown.push('\n');
+      }
+      markdown.push_str(&format!(
+        "## {}\n\n| Keys | Description | Action |\n| --- | --- | --- |\n",
+        doc.context
+      ));
+      context = Some(doc.context.clone());
+    }
+    markdown.push_str(&format!(
+      "| `{}` | {} | {} |\n",
+      doc.keys.replace('|', "\\|"),
+      doc.description.replace('|', "\\|"),
+      doc.action.map(|a| format!("`{}`", a)).unwrap_or_default()
+    ));
+  }
+  markdown
+}
+
+pub fn help_json(key_bindings: &KeyBindings) -> Result<String> {
+  Ok(serde_json::to_string_pretty(&get_help_docs(key_bindings))?)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::keymap::{parse_sequence, Action};
+
+  #[test]
+  fn help_follows_remapped_keys() {
+    let mut keys = KeyBindings::default();
+    keys
+      .bind(
+        Some("track_table"),
+        Action::Save,
+        vec![parse_sequence("space s").unwrap()],
+      )
+      .unwrap();
+
+    let docs = get_help_docs(&keys);
+    let save = docs
+      .iter()
+      .find(|doc| doc.context == "Track table" && doc.action == Some("save"))
+      .unwrap();
+    assert_eq!(save.keys, "<Space> s");
+    assert_eq!(docs[0].context, "Global");
+  }
+
+  #[test]
+  fn filter_matches_any_column() {
+    let docs = get_help_docs(&KeyBindings::default());
+    let shuffle: Vec<_> = docs
+      .iter()
+      .filter(|doc| doc.matches("TOGGLE SHUFFLE"))
+      .collect();
+    assert_eq!(shuffle.len(), 1);
+    assert!(docs
+      .iter()
+      .filter(|doc| doc.matches("lyrics sync"))
+      .all(|doc| doc.context == "Lyrics sync" || doc.description.contains("lyrics sync")));
+    assert!(docs.iter().any(|doc| doc.matches("ctrl+w")));
+  }
+
+  #[test]
+  fn markdown_escapes_alternative_keys() {
+    let markdown = help_markdown(&KeyBindings::default());
+    assert!(markdown.starts_with("## Global\n\n| Keys | Description | Action |"));
+    assert!(
+      markdown.contains("| `k \\| <Up Arrow Key> \\| <Ctrl+p>` | Move selection up | `move_up` |")
+    );
+  }
 }
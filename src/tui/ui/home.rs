@@ -1,6 +1,6 @@
 use crate::core::app::{ActiveBlock, App};
 use crate::tui::banner::BANNER;
-use colorgrad::{self, Gradient};
+use colorgrad::Gradient;
 use ratatui::{
   layout::{Constraint, Layout, Rect},
   style::{Color, Modifier, Style},
@@ -11,7 +11,7 @@ use ratatui::{
 use std::sync::{Mutex, OnceLock};
 use unicode_width::UnicodeWidthStr;
 
-use super::util::get_color;
+use super::util::{build_gradient, get_color};
 
 #[derive(Clone, PartialEq)]
 struct HomeThemeKey {
@@ -148,41 +148,7 @@ fn build_home_cache(theme: &crate::core::user_config::Theme, changelog_width: u1
 }
 
 fn build_banner_gradient_lines(theme: &crate::core::user_config::Theme) -> Vec<Line<'static>> {
-  fn to_rgba(color: ratatui::style::Color) -> (u8, u8, u8, u8) {
-    match color {
-      ratatui::style::Color::Rgb(r, g, b) => (r, g, b, 255),
-      ratatui::style::Color::Black => (0, 0, 0, 255),
-      ratatui::style::Color::Red => (255, 0, 0, 255),
-      ratatui::style::Color::Green => (0, 255, 0, 255),
-      ratatui::style::Color::Yellow => (255, 255, 0, 255),
-      ratatui::style::Color::Blue => (0, 0, 255, 255),
-      ratatui::style::Color::Magenta => (255, 0, 255, 255),
-      ratatui::style::Color::Cyan => (0, 255, 255, 255),
-      ratatui::style::Color::Gray => (128, 128, 128, 255),
-      ratatui::style::Color::DarkGray => (64, 64, 64, 255),
-      ratatui::style::Color::LightRed => (255, 128, 128, 255),
-      ratatui::style::Color::LightGreen => (128, 255, 128, 255),
-      ratatui::style::Color::LightYellow => (255, 255, 128, 255),
-      ratatui::style::Color::LightBlue => (128, 128, 255, 255),
-      ratatui::style::Color::LightMagenta => (255, 128, 255, 255),
-      ratatui::style::Color::LightCyan => (128, 255, 255, 255),
-      ratatui::style::Color::White => (255, 255, 255, 255),
-      _ => (255, 255, 255, 255),
-    }
-  }
-
-  let c1 = to_rgba(theme.banner);
-  let c2 = to_rgba(theme.active);
-  let c3 = to_rgba(theme.hovered);
-
-  let grad = colorgrad::GradientBuilder::new()
-    .colors(&[
-      colorgrad::Color::from_rgba8(c1.0, c1.1, c1.2, c1.3),
-      colorgrad::Color::from_rgba8(c2.0, c2.1, c2.2, c2.3),
-      colorgrad::Color::from_rgba8(c3.0, c3.1, c3.2, c3.3),
-    ])
-    .build::<colorgrad::LinearGradient>()
-    .unwrap();
+  let grad = build_gradient(&[theme.banner, theme.active, theme.hovered]);
 
   BANNER
     .lines()
@@ -1,9 +1,11 @@
 pub mod artist;
 pub mod audio_analysis;
+pub mod color;
 pub mod discover;
 pub mod help;
 pub mod home;
 pub mod library;
+pub mod now_playing;
 pub mod player;
 pub mod popups;
 pub mod search;
@@ -21,7 +23,8 @@ pub use self::artist::draw_artist_albums;
 pub use self::discover::draw_discover;
 pub use self::home::draw_home;
 pub use self::library::draw_user_block;
-pub use self::player::{draw_basic_view, draw_device_list, draw_playbar};
+pub use self::now_playing::draw_now_playing;
+pub use self::player::{draw_basic_view, draw_device_list, draw_mini_playbar, draw_playbar};
 pub use self::popups::{
   draw_announcement_prompt, draw_dialog, draw_error_screen, draw_help_menu, draw_sort_menu,
   draw_update_prompt,
@@ -73,6 +76,12 @@ pub fn draw_main_layout(f: &mut Frame<'_>, app: &App) {
   draw_sort_menu(f, app);
 }
 
+/// Only the playbar, at most three rows, for terminals split down to a few lines
+pub fn draw_mini_layout(f: &mut Frame<'_>, app: &App) {
+  let [playbar_area] = f.area().layout(&Layout::vertical([Constraint::Max(3)]));
+  draw_mini_playbar(f, app, playbar_area);
+}
+
 pub fn draw_routes(f: &mut Frame<'_>, app: &App, layout_chunk: Rect) {
   let [user_area, content_area] = layout_chunk.layout(&Layout::horizontal([
     Constraint::Percentage(20),
@@ -124,6 +133,7 @@ pub fn draw_routes(f: &mut Frame<'_>, app: &App, layout_chunk: Rect) {
     RouteId::SelectedDevice => {} // This is handled as a "full screen" route in main.rs
     RouteId::Analysis => {} // This is handled as a "full screen" route in main.rs
     RouteId::BasicView => {} // This is handled as a "full screen" route in main.rs
+    RouteId::NowPlaying => {} // This is handled as a "full screen" route in main.rs
     RouteId::Dialog => {} // This is handled in the draw_dialog function in mod.rs
     RouteId::UpdatePrompt => {} // This is handled as a "full screen" route in main.rs
     RouteId::AnnouncementPrompt => {} // This is handled as a "full screen" route in main.rs
@@ -0,0 +1,217 @@
+use super::audio_analysis;
+use super::player::{draw_lyrics, now_playing_info, NowPlayingInfo};
+use super::util::{
+  create_artist_string, display_track_progress, get_main_layout_margin,
+  get_track_progress_percentage, SMALL_TERMINAL_WIDTH,
+};
+use crate::core::app::App;
+use ratatui::{
+  layout::{Alignment, Constraint, Layout, Rect},
+  style::{Modifier, Style},
+  text::{Line, Span},
+  widgets::{Block, Borders, LineGauge, List, ListItem, Paragraph},
+  Frame,
+};
+use rspotify::model::PlayableItem;
+use tui_big_text::{BigText, PixelSize};
+use unicode_width::UnicodeWidthStr;
+
+/// Rows taken by one line of quadrant-sized big text
+const BIG_TEXT_HEIGHT: u16 = 4;
+/// Columns taken by one character of quadrant-sized big text
+const BIG_TEXT_CHAR_WIDTH: u16 = 4;
+const UP_NEXT_LEN: usize = 5;
+const VISUALIZER_HEIGHT: u16 = 6;
+
+pub fn draw_now_playing(f: &mut Frame<'_>, app: &App) {
+  let margin = get_main_layout_margin(app);
+  let visualizer_height = if app.spectrum_data.is_some() {
+    VISUALIZER_HEIGHT
+  } else {
+    0
+  };
+  let [main_area, visualizer_area, progress_area] = f.area().layout(
+    &Layout::vertical([
+      Constraint::Min(BIG_TEXT_HEIGHT * 2),
+      Constraint::Length(visualizer_height),
+      Constraint::Length(2),
+    ])
+    .margin(margin),
+  );
+
+  let Some(info) = now_playing_info(app) else {
+    let p = Paragraph::new("Nothing is playing")
+      .style(Style::default().fg(app.user_config.theme.inactive))
+      .alignment(Alignment::Center);
+    let [_, centered, _] = main_area.layout(&Layout::vertical([
+      Constraint::Fill(1),
+      Constraint::Length(1),
+      Constraint::Fill(1),
+    ]));
+    f.render_widget(p, centered);
+    return;
+  };
+
+  // The cover column only fits next to the text on wide terminals
+  let text_area = if app.size.width >= SMALL_TERMINAL_WIDTH / 2 {
+    let [side_area, _, text_area] = main_area.layout(&Layout::horizontal([
+      Constraint::Percentage(40),
+      Constraint::Length(2),
+      Constraint::Fill(1),
+    ]));
+    draw_side_column(f, app, &info, side_area);
+    text_area
+  } else {
+    main_area
+  };
+
+  let [clock_area, title_area, artist_area, lyrics_area] = text_area.layout(&Layout::vertical([
+    Constraint::Length(BIG_TEXT_HEIGHT),
+    Constraint::Length(BIG_TEXT_HEIGHT),
+    Constraint::Length(BIG_TEXT_HEIGHT),
+    Constraint::Min(3),
+  ]));
+
+  let clock = chrono::Local::now().format("%H:%M").to_string();
+  draw_big_text(
+    f,
+    &clock,
+    Style::default().fg(app.user_config.theme.inactive),
+    clock_area,
+  );
+  draw_big_text(
+    f,
+    &info.name,
+    Style::default()
+      .fg(app.user_config.theme.selected)
+      .add_modifier(Modifier::BOLD),
+    title_area,
+  );
+  draw_big_text(
+    f,
+    &info.artists,
+    Style::default().fg(app.user_config.theme.text),
+    artist_area,
+  );
+  draw_lyrics(f, app, lyrics_area);
+
+  if let Some(spectrum) = &app.spectrum_data {
+    audio_analysis::draw_spectrum_strip(f, app, &spectrum.bands, visualizer_area);
+  }
+
+  draw_progress(f, app, &info, progress_area);
+}
+
+/// Cover on top, the next few queue items below it
+fn draw_side_column(f: &mut Frame<'_>, app: &App, info: &NowPlayingInfo, area: Rect) {
+  let up_next_height = if app.up_next.is_empty() {
+    0
+  } else {
+    app.up_next.len().min(UP_NEXT_LEN) as u16 + 2
+  };
+  let [cover_area, album_area, up_next_area] = area.layout(&Layout::vertical([
+    Constraint::Min(1),
+    Constraint::Length(1),
+    Constraint::Length(up_next_height),
+  ]));
+
+  #[cfg(feature = "cover-art")]
+  if app.user_config.behavior.draw_cover_art && app.cover_art.available() {
+    // The wall display always gets a cover, drawn with blocks when there is no image protocol
+    let blocks = Some(app.user_config.behavior.cover_art_fallback);
+    app.cover_art.render(f, cover_area, blocks);
+  }
+  #[cfg(not(feature = "cover-art"))]
+  let _ = cover_area;
+
+  let album = Paragraph::new(Span::styled(
+    info.album.as_str(),
+    Style::default()
+      .fg(app.user_config.theme.inactive)
+      .add_modifier(Modifier::ITALIC),
+  ))
+  .alignment(Alignment::Center);
+  f.render_widget(album, album_area);
+
+  if up_next_height == 0 {
+    return;
+  }
+
+  let items: Vec<ListItem> = app
+    .up_next
+    .iter()
+    .take(UP_NEXT_LEN)
+    .map(|item| {
+      let (name, artists) = match item {
+        PlayableItem::Track(track) => (track.name.as_str(), create_artist_string(&track.artists)),
+        PlayableItem::Episode(episode) => (episode.name.as_str(), episode.show.name.clone()),
+      };
+      ListItem::new(Line::from(vec![
+        Span::styled(name, Style::default().fg(app.user_config.theme.text)),
+        Span::styled(
+          format!(" - {}", artists),
+          Style::default().fg(app.user_config.theme.inactive),
+        ),
+      ]))
+    })
+    .collect();
+
+  let list = List::new(items).block(
+    Block::default()
+      .borders(Borders::TOP)
+      .title(Span::styled(
+        "Up Next",
+        Style::default().fg(app.user_config.theme.header),
+      ))
+      .border_style(Style::default().fg(app.user_config.theme.inactive)),
+  );
+  f.render_widget(list, up_next_area);
+}
+
+/// Big text when it fits the area, bold plain text otherwise
+fn draw_big_text(f: &mut Frame<'_>, text: &str, style: Style, area: Rect) {
+  let width = text.width() as u16;
+  // The big text font only covers ASCII
+  if text.is_ascii() && width * BIG_TEXT_CHAR_WIDTH <= area.width && area.height >= BIG_TEXT_HEIGHT
+  {
+    let big_text = BigText::builder()
+      .pixel_size(PixelSize::Quadrant)
+      .style(style)
+      .lines(vec![Line::from(text.to_string())])
+      .centered()
+      .build();
+    f.render_widget(big_text, area);
+    return;
+  }
+
+  let [_, line_area, _] = area.layout(&Layout::vertical([
+    Constraint::Fill(1),
+    Constraint::Length(1),
+    Constraint::Fill(1),
+  ]));
+  let p = Paragraph::new(Span::styled(text, style.add_modifier(Modifier::BOLD)))
+    .alignment(Alignment::Center);
+  f.render_widget(p, line_area);
+}
+
+fn draw_progress(f: &mut Frame<'_>, app: &App, info: &NowPlayingInfo, area: Rect) {
+  let progress_ms = app.seek_ms.unwrap_or(app.song_progress_ms);
+  let duration = std::time::Duration::from_millis(info.duration_ms);
+  let perc = get_track_progress_percentage(progress_ms, duration);
+
+  let gauge = LineGauge::default()
+    .filled_style(Style::default().fg(app.user_config.theme.playbar_progress))
+    .unfilled_style(Style::default().fg(app.user_config.theme.inactive))
+    .ratio(perc as f64 / 100.0)
+    .filled_symbol("⣿")
+    .unfilled_symbol("⣉")
+    .label(Span::styled(
+      display_track_progress(progress_ms, duration),
+      Style::default().fg(app.user_config.theme.playbar_progress_text),
+    ));
+  let [_, gauge_area] = area.layout(&Layout::vertical([
+    Constraint::Length(1),
+    Constraint::Length(1),
+  ]));
+  f.render_widget(gauge, gauge_area);
+}
@@ -1,4 +1,6 @@
-use crate::core::app::{ActiveBlock, App};
+use crate::core::app::{ActiveBlock, App, LyricsSyncEditor};
+use crate::core::keymap::Action;
+use crate::infra::lyrics::{format_timestamp, LyricsLine};
 use ratatui::{
   layout::{Alignment, Constraint, Direction, Layout, Rect},
   style::{Color, Modifier, Style},
@@ -31,13 +33,30 @@ pub fn draw_basic_view(f: &mut Frame<'_>, app: &App) {
   draw_playbar(f, app, chunks[1]);
 }
 
-fn draw_lyrics(f: &mut Frame<'_>, app: &App, area: Rect) {
+pub fn draw_lyrics(f: &mut Frame<'_>, app: &App, area: Rect) {
   use crate::core::app::LyricsStatus;
 
+  if let Some(editor) = &app.lyrics_sync {
+    draw_lyrics_sync_editor(f, app, editor, area);
+    return;
+  }
+
+  let title = match &app.lyrics_source {
+    Some(source) if app.lyrics_status == LyricsStatus::Found && app.lyrics_offset_ms != 0 => {
+      format!(
+        " Lyrics ({}, {:+.1}s) ",
+        source,
+        app.lyrics_offset_ms as f64 / 1000.0
+      )
+    }
+    Some(source) if app.lyrics_status == LyricsStatus::Found => format!(" Lyrics ({}) ", source),
+    _ => " Lyrics ".to_string(),
+  };
+
   // Draw bordered block first
   let block = Block::default()
     .borders(Borders::ALL)
-    .title(" Lyrics ")
+    .title(title)
     .style(Style::default().fg(Color::Rgb(100, 100, 100))); // RGB for cross-terminal compat
   f.render_widget(block.clone(), area);
 
@@ -70,66 +89,244 @@ fn draw_lyrics(f: &mut Frame<'_>, app: &App, area: Rect) {
   }
 
   if let Some(lyrics) = &app.lyrics {
-    if lyrics.is_empty() {
-      return;
-    }
+    draw_lyrics_lines(
+      f,
+      app,
+      inner_area,
+      lyrics,
+      app.lyrics_synced,
+      app.lyrics_position_ms(),
+    );
+  }
+}
 
-    let current_time = app.song_progress_ms;
-    let mut active_idx = 0;
-    for (i, (time, _)) in lyrics.iter().enumerate() {
-      if *time <= current_time {
+/// Scroll `lyrics` so the line playing at `current_time` sits in the middle of `inner_area`
+fn draw_lyrics_lines(
+  f: &mut Frame<'_>,
+  app: &App,
+  inner_area: Rect,
+  lyrics: &[LyricsLine],
+  synced: bool,
+  current_time: u128,
+) {
+  if lyrics.is_empty() {
+    return;
+  }
+
+  let mut active_idx = 0;
+  if synced {
+    for (i, line) in lyrics.iter().enumerate() {
+      if line.time_ms <= current_time {
         active_idx = i;
       } else {
         break;
       }
     }
+  } else if let Some(duration_ms) = current_duration_ms(app).filter(|ms| *ms > 0) {
+    // No timestamps: scroll through the text at the pace of the track
+    let progress = current_time.min(duration_ms) as f64 / duration_ms as f64;
+    active_idx = ((lyrics.len() - 1) as f64 * progress) as usize;
+  }
 
-    // Target position for active line: Vertical center of inner_area
-    let target_row = inner_area.y + (inner_area.height / 2);
+  // Target position for active line: Vertical center of inner_area
+  let target_row = inner_area.y + (inner_area.height / 2);
 
-    let area_height = inner_area.height as i32;
-    let area_y = inner_area.y as i32;
+  let area_height = inner_area.height as i32;
+  let area_y = inner_area.y as i32;
 
-    // Loop through all visible rows of the screen area
-    for row in 0..area_height {
-      let screen_y = area_y + row;
+  // Loop through all visible rows of the screen area
+  for row in 0..area_height {
+    let screen_y = area_y + row;
 
-      // screen_y = target_row + (line_idx - active_idx)
-      // line_idx = screen_y - target_row + active_idx
+    // screen_y = target_row + (line_idx - active_idx)
+    // line_idx = screen_y - target_row + active_idx
 
-      let offset_from_target = screen_y - (target_row as i32);
-      let line_idx = active_idx as i32 + offset_from_target;
+    let offset_from_target = screen_y - (target_row as i32);
+    let line_idx = active_idx as i32 + offset_from_target;
 
-      if line_idx >= 0 && line_idx < lyrics.len() as i32 {
-        let (_, text) = &lyrics[line_idx as usize];
-        let is_active = line_idx == active_idx as i32;
+    if line_idx >= 0 && line_idx < lyrics.len() as i32 {
+      let line = &lyrics[line_idx as usize];
+      let is_active = synced && line_idx == active_idx as i32;
 
-        // Use explicit RGB colors for cross-terminal compatibility
-        // Some terminals (like Kitty with custom themes) remap ANSI colors
-        let style = if is_active {
-          Style::default()
-            .fg(app.user_config.theme.highlighted_lyrics) // Use theme color for highlighted lyrics
-            .add_modifier(Modifier::BOLD)
-        } else {
-          Style::default().fg(Color::Rgb(100, 100, 100)) // Dim gray for inactive lines
-        };
+      // Use explicit RGB colors for cross-terminal compatibility
+      // Some terminals (like Kitty with custom themes) remap ANSI colors
+      let highlighted = Style::default()
+        .fg(app.user_config.theme.highlighted_lyrics) // Use theme color for highlighted lyrics
+        .add_modifier(Modifier::BOLD);
+      let dim = Style::default().fg(Color::Rgb(100, 100, 100)); // Dim gray for inactive lines
 
-        let p = Paragraph::new(text.clone())
-          .style(style)
-          .alignment(Alignment::Center);
+      let text = if is_active && !line.words.is_empty() {
+        karaoke_line(line, current_time, highlighted, dim)
+      } else if is_active {
+        Line::styled(line.text.clone(), highlighted)
+      } else {
+        Line::styled(line.text.clone(), dim)
+      };
 
-        let line_rect = Rect {
-          x: inner_area.x,
-          y: screen_y as u16,
-          width: inner_area.width,
-          height: 1,
-        };
-        f.render_widget(p, line_rect);
-      }
+      let p = Paragraph::new(text).alignment(Alignment::Center);
+
+      let line_rect = Rect {
+        x: inner_area.x,
+        y: screen_y as u16,
+        width: inner_area.width,
+        height: 1,
+      };
+      f.render_widget(p, line_rect);
     }
   }
 }
 
+/// Every line with its time (or a placeholder), the selected one in the middle
+fn draw_lyrics_sync_editor(f: &mut Frame<'_>, app: &App, editor: &LyricsSyncEditor, area: Rect) {
+  let stamped = editor.lines.len() - editor.unstamped_count();
+  let title = format!(
+    " Sync lyrics: {}/{} timed | {}{} ",
+    stamped,
+    editor.lines.len(),
+    format_timestamp(app.song_progress_ms),
+    if editor.previewing { " | preview" } else { "" }
+  );
+  let block = Block::default()
+    .borders(Borders::ALL)
+    .title(title)
+    .style(Style::default().fg(Color::Rgb(100, 100, 100))); // RGB for cross-terminal compat
+  f.render_widget(block.clone(), area);
+
+  let [body, hint] = block.inner(area).layout(&Layout::vertical([
+    Constraint::Min(1),
+    Constraint::Length(1),
+  ]));
+
+  let hint_text =
+    "Enter time line | j/k select | [ ] nudge | x clear | P preview | S save .lrc | Esc leave";
+  f.render_widget(
+    Paragraph::new(hint_text)
+      .style(Style::default().fg(app.user_config.theme.hint))
+      .alignment(Alignment::Center),
+    hint,
+  );
+
+  if editor.previewing {
+    let lines = editor.stamped_lines();
+    draw_lyrics_lines(f, app, body, &lines, true, app.song_progress_ms);
+    return;
+  }
+
+  let target_row = (body.height / 2) as i32;
+  for row in 0..body.height as i32 {
+    let line_idx = editor.selected_index as i32 + row - target_row;
+    if line_idx < 0 || line_idx >= editor.lines.len() as i32 {
+      continue;
+    }
+    let line_idx = line_idx as usize;
+
+    let stamp = editor.stamps[line_idx]
+      .map(format_timestamp)
+      .unwrap_or_else(|| "--:--.--".to_string());
+    let style = if line_idx == editor.selected_index {
+      Style::default()
+        .fg(app.user_config.theme.highlighted_lyrics)
+        .add_modifier(Modifier::BOLD)
+    } else if editor.stamps[line_idx].is_some() {
+      Style::default().fg(app.user_config.theme.text)
+    } else {
+      Style::default().fg(Color::Rgb(100, 100, 100))
+    };
+
+    let line_rect = Rect {
+      x: body.x,
+      y: body.y + row as u16,
+      width: body.width,
+      height: 1,
+    };
+    f.render_widget(
+      Paragraph::new(format!("{}  {}", stamp, editor.lines[line_idx])).style(style),
+      line_rect,
+    );
+  }
+}
+
+/// Enhanced LRC: words already sung in the highlight color, the current one underlined
+fn karaoke_line(line: &LyricsLine, current_time: u128, sung: Style, upcoming: Style) -> Line<'_> {
+  let current_idx = line
+    .words
+    .iter()
+    .rposition(|word| word.time_ms <= current_time);
+  let spans: Vec<Span> = line
+    .words
+    .iter()
+    .enumerate()
+    .flat_map(|(i, word)| {
+      let style = match current_idx {
+        Some(current) if i == current => sung.add_modifier(Modifier::UNDERLINED),
+        Some(current) if i < current => sung,
+        _ => upcoming.add_modifier(Modifier::BOLD),
+      };
+      // Keep the underline off the space after the word
+      let text = word.text.trim_end();
+      [
+        Span::styled(text, style),
+        Span::raw(&word.text[text.len()..]),
+      ]
+    })
+    .collect();
+  Line::from(spans)
+}
+
+/// What the playbar shows for the current item
+pub struct NowPlayingInfo {
+  pub name: String,
+  pub artists: String,
+  pub album: String,
+  pub duration_ms: u64,
+}
+
+pub fn now_playing_info(app: &App) -> Option<NowPlayingInfo> {
+  let item = app.current_playback_context.as_ref()?.item.as_ref()?;
+  let (name, artists, album, duration_ms) = match item {
+    PlayableItem::Track(track) => (
+      track.name.clone(),
+      create_artist_string(&track.artists),
+      track.album.name.clone(),
+      track.duration.num_milliseconds().max(0) as u64,
+    ),
+    PlayableItem::Episode(episode) => (
+      episode.name.clone(),
+      episode.show.publisher.clone(),
+      episode.show.name.clone(),
+      episode.duration.num_milliseconds().max(0) as u64,
+    ),
+  };
+
+  // Native playback knows about a skip before the API does
+  Some(match &app.native_track_info {
+    Some(native) => NowPlayingInfo {
+      name: native.name.clone(),
+      artists: native.artists_display.clone(),
+      album: if native.album.is_empty() {
+        album
+      } else {
+        native.album.clone()
+      },
+      duration_ms: native.duration_ms as u64,
+    },
+    None => NowPlayingInfo {
+      name,
+      artists,
+      album,
+      duration_ms,
+    },
+  })
+}
+
+fn current_duration_ms(app: &App) -> Option<u128> {
+  let context = app.current_playback_context.as_ref()?;
+  match context.item.as_ref()? {
+    PlayableItem::Track(track) => Some(track.duration.num_milliseconds().max(0) as u128),
+    PlayableItem::Episode(episode) => Some(episode.duration.num_milliseconds().max(0) as u128),
+  }
+}
+
 pub fn draw_playbar(f: &mut Frame<'_>, app: &App, layout_chunk: Rect) {
   #[cfg(feature = "cover-art")]
   let (artist_area, progress_area, cover_art) = {
@@ -217,6 +414,10 @@ pub fn draw_playbar(f: &mut Frame<'_>, app: &App, layout_chunk: Rect) {
         current_playback_context.device.volume_percent.unwrap_or(0)
       );
 
+      if app.user_config.behavior.night_mode && app.is_streaming_active {
+        title.push_str(" | Night");
+      }
+
       if let Some(message) = app.status_message.as_ref() {
         title = format!("{} | {}", title, message);
       }
@@ -370,7 +571,9 @@ pub fn draw_playbar(f: &mut Frame<'_>, app: &App, layout_chunk: Rect) {
         .do_draw_cover_art(app.cover_art.full_image_support())
       {
         if let Some(cover_art) = cover_art {
-          app.cover_art.render(f, cover_art);
+          app
+            .cover_art
+            .render(f, cover_art, app.user_config.cover_art_blocks());
         }
       }
 
@@ -394,6 +597,129 @@ pub fn draw_playbar(f: &mut Frame<'_>, app: &App, layout_chunk: Rect) {
   }
 }
 
+/// Narrowest the title column gets before the status on the right is dropped
+const MINI_MIN_TITLE_WIDTH: u16 = 16;
+
+/// Borderless playbar for the mini layout. Uses up to three rows (title, artist, progress) and
+/// drops the shuffle/repeat/volume status first when the width runs out.
+pub fn draw_mini_playbar(f: &mut Frame<'_>, app: &App, area: Rect) {
+  let theme = app.user_config.theme;
+  let Some(context) = &app.current_playback_context else {
+    let text = app.status_message.clone().unwrap_or_else(|| {
+      format!(
+        "Nothing playing, press {} to pick a device",
+        app.user_config.keys.display(Action::ManageDevices)
+      )
+    });
+    f.render_widget(
+      Paragraph::new(Span::styled(text, Style::default().fg(theme.inactive))),
+      area,
+    );
+    return;
+  };
+  let Some(info) = now_playing_info(app) else {
+    return;
+  };
+
+  let is_playing = app
+    .native_is_playing
+    .filter(|_| app.is_streaming_active)
+    .unwrap_or(context.is_playing);
+  let dim = Style::default().fg(theme.inactive);
+  let on = Style::default().fg(theme.selected);
+
+  let repeat = match context.repeat_state {
+    RepeatState::Off => Span::styled("↻ ", dim),
+    RepeatState::Track => Span::styled("↻1", on),
+    RepeatState::Context => Span::styled("↻ ", on),
+  };
+  let status = Line::from(vec![
+    Span::styled("⇄ ", if context.shuffle_state { on } else { dim }),
+    repeat,
+    Span::styled(
+      format!(" {:>3}%", context.device.volume_percent.unwrap_or(0)),
+      Style::default().fg(theme.playbar_text),
+    ),
+  ]);
+
+  let progress_ms = app.seek_ms.unwrap_or(app.song_progress_ms);
+  let duration = std::time::Duration::from_millis(info.duration_ms);
+
+  let title = Span::styled(
+    format!("{} {}", if is_playing { "▶" } else { "⏸" }, info.name),
+    Style::default()
+      .fg(theme.selected)
+      .add_modifier(Modifier::BOLD),
+  );
+  // An error or status message is more useful than the artist while it lasts
+  let subtitle = if app.get_current_route().active_block == ActiveBlock::Error {
+    Span::styled(app.api_error.clone(), Style::default().fg(theme.error_text))
+  } else if let Some(message) = &app.status_message {
+    Span::styled(message.clone(), Style::default().fg(theme.hint))
+  } else {
+    Span::styled(info.artists, Style::default().fg(theme.playbar_text))
+  };
+
+  match area.height {
+    0 => {}
+    1 => {
+      let line = Line::from(vec![
+        title,
+        Span::styled(" - ", dim),
+        subtitle,
+        Span::styled(
+          format!("  {}", display_track_progress(progress_ms, duration)),
+          dim,
+        ),
+      ]);
+      mini_row_with_status(f, line, &status, area);
+    }
+    height => {
+      let [title_area, subtitle_area, progress_area] = area.layout(&Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(if height >= 3 { 1 } else { 0 }),
+        Constraint::Length(1),
+      ]));
+      if height >= 3 {
+        mini_row_with_status(f, Line::from(title), &status, title_area);
+        f.render_widget(Paragraph::new(Line::from(subtitle)), subtitle_area);
+      } else {
+        let line = Line::from(vec![title, Span::styled(" - ", dim), subtitle]);
+        mini_row_with_status(f, line, &status, title_area);
+      }
+
+      let perc = get_track_progress_percentage(progress_ms, duration);
+      let gauge = LineGauge::default()
+        .filled_style(Style::default().fg(theme.playbar_progress))
+        .unfilled_style(dim)
+        .ratio(perc as f64 / 100.0)
+        .filled_symbol("⣿")
+        .unfilled_symbol("⣉")
+        .label(Span::styled(
+          display_track_progress(progress_ms, duration),
+          Style::default().fg(theme.playbar_progress_text),
+        ));
+      f.render_widget(gauge, progress_area);
+    }
+  }
+}
+
+/// `line` on the left with `status` right-aligned, or `line` alone when both don't fit
+fn mini_row_with_status(f: &mut Frame<'_>, line: Line<'_>, status: &Line<'_>, row: Rect) {
+  let status_width = status.width() as u16;
+  if row.width >= status_width + 1 + MINI_MIN_TITLE_WIDTH {
+    let [left, _, right] = row.layout(&Layout::horizontal([
+      Constraint::Fill(1),
+      Constraint::Length(1),
+      Constraint::Length(status_width),
+    ]));
+    f.render_widget(Paragraph::new(line), left);
+    f.render_widget(Paragraph::new(status.clone()), right);
+  } else {
+    f.render_widget(Paragraph::new(line), row);
+  }
+}
+
 pub fn draw_device_list(f: &mut Frame<'_>, app: &App) {
   let [instructions_area, list_area] = f
     .area()
@@ -404,6 +730,7 @@ pub fn draw_device_list(f: &mut Frame<'_>, app: &App) {
         "Use `j/k` or up/down arrow keys to move up and down and <Enter> to select. ",
         "Your choice here will be cached so you can jump straight back in when you next open `spotatui`. ",
         "You can change the playback device at any time by pressing `d`.",
+        "Press `O` to choose which speakers or audio device native playback uses.",
     ].into_iter().map(|instruction| Line::from(Span::raw(instruction))).collect();
 
   let instructions = Paragraph::new(device_instructions)
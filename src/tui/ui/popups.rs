@@ -1,4 +1,5 @@
-use crate::core::app::{ActiveBlock, AnnouncementLevel, App, DialogContext};
+use crate::core::app::{ActiveBlock, AnnouncementLevel, App, DialogContext, LyricsSearchFocus};
+use crate::core::keymap::Action;
 use ratatui::{
   layout::{Alignment, Constraint, Direction, Layout, Rect},
   style::{Modifier, Style},
@@ -7,7 +8,7 @@ use ratatui::{
   Frame,
 };
 
-use super::help::get_help_docs;
+use super::help::visible_help_docs;
 
 pub fn draw_help_menu(f: &mut Frame<'_>, app: &App) {
   let [area] = f
@@ -16,23 +17,37 @@ pub fn draw_help_menu(f: &mut Frame<'_>, app: &App) {
 
   // Create a one-column table to avoid flickering due to non-determinism when
   // resolving constraints on widths of table columns.
-  let format_row =
-    |r: Vec<String>| -> Vec<String> { vec![format!("{:50}{:40}{:20}", r[0], r[1], r[2])] };
+  let format_row = |description: &str, keys: &str, context: &str| -> Vec<String> {
+    vec![format!("{:50}{:40}{:20}", description, keys, context)]
+  };
 
   let help_menu_style = app.user_config.theme.base_style();
-  let header = ["Description", "Event", "Context"];
-  let header = format_row(header.iter().map(|s| s.to_string()).collect());
-
-  let help_docs = get_help_docs(&app.user_config.keys);
-  let help_docs = help_docs
-    .into_iter()
-    .map(format_row)
-    .collect::<Vec<Vec<String>>>();
-  let help_docs = &help_docs[app.help_menu_offset as usize..];
+  let header = format_row("Description", "Event", "Context");
 
+  let help_docs = visible_help_docs(app);
   let rows = help_docs
     .iter()
-    .map(|item| Row::new(item.clone()).style(help_menu_style));
+    .skip(app.help_menu_offset as usize)
+    .map(|doc| {
+      Row::new(format_row(&doc.description, &doc.keys, &doc.context)).style(help_menu_style)
+    });
+
+  let title = if app.help_filter_editing || !app.help_filter.is_empty() {
+    format!(
+      "Help - search: {}{} ({} matches)",
+      app.help_filter,
+      if app.help_filter_editing { "_" } else { "" },
+      help_docs.len()
+    )
+  } else {
+    format!(
+      "Help (press {} to search, <Esc> to go back)",
+      app
+        .user_config
+        .keys
+        .display_in("help_menu", Action::FilterHelp)
+    )
+  };
 
   let help_menu = Table::new(rows, &[Constraint::Percentage(100)])
     .header(Row::new(header))
@@ -40,10 +55,7 @@ pub fn draw_help_menu(f: &mut Frame<'_>, app: &App) {
       Block::default()
         .borders(Borders::ALL)
         .style(help_menu_style)
-        .title(Span::styled(
-          "Help (press <Esc> to go back)",
-          help_menu_style,
-        ))
+        .title(Span::styled(title, help_menu_style))
         .border_style(help_menu_style),
     )
     .style(help_menu_style);
@@ -148,6 +160,15 @@ pub fn draw_dialog(f: &mut Frame<'_>, app: &App) {
     DialogContext::AddTrackToPlaylistPicker => {
       draw_add_track_to_playlist_picker_dialog(f, app);
     }
+    DialogContext::AudioOutputPicker => {
+      draw_audio_output_picker_dialog(f, app);
+    }
+    DialogContext::LyricsSearch => {
+      draw_lyrics_search_dialog(f, app);
+    }
+    DialogContext::UndoHistory => {
+      draw_undo_history_dialog(f, app);
+    }
   }
 }
 
@@ -290,6 +311,231 @@ fn draw_add_track_to_playlist_picker_dialog(f: &mut Frame<'_>, app: &App) {
   f.render_widget(footer, vchunks[2]);
 }
 
+fn draw_audio_output_picker_dialog(f: &mut Frame<'_>, app: &App) {
+  let rect = centered_modal_rect(f.area(), 80, 20);
+  f.render_widget(Clear, rect);
+
+  let block = Block::default()
+    .title(Span::styled(
+      "Audio Output",
+      Style::default()
+        .fg(app.user_config.theme.header)
+        .add_modifier(Modifier::BOLD),
+    ))
+    .borders(Borders::ALL)
+    .style(app.user_config.theme.base_style())
+    .border_style(Style::default().fg(app.user_config.theme.inactive));
+  f.render_widget(block, rect);
+
+  let vchunks = Layout::default()
+    .direction(Direction::Vertical)
+    .margin(1)
+    .constraints([Constraint::Min(3), Constraint::Length(1)])
+    .split(rect);
+
+  if app.audio_output_choices.is_empty() {
+    let message = if app.audio_outputs_loading {
+      "Looking for audio outputs…"
+    } else {
+      "No audio backends compiled in"
+    };
+    let empty_text = Paragraph::new(message)
+      .style(Style::default().fg(app.user_config.theme.inactive))
+      .alignment(Alignment::Center);
+    f.render_widget(empty_text, vchunks[0]);
+  } else {
+    let items: Vec<ListItem> = app
+      .audio_output_choices
+      .iter()
+      .map(|choice| ListItem::new(Span::raw(choice.label.as_str())))
+      .collect();
+    let mut list_state = ListState::default();
+    list_state.select(Some(
+      app
+        .audio_output_selected_index
+        .min(app.audio_output_choices.len() - 1),
+    ));
+
+    let list = List::new(items)
+      .style(app.user_config.theme.base_style())
+      .highlight_style(Style::default().fg(app.user_config.theme.hovered))
+      .highlight_symbol("▶ ");
+
+    f.render_stateful_widget(list, vchunks[0], &mut list_state);
+  }
+
+  let footer = Paragraph::new("Enter switch | q cancel | j/k or arrows move | H/M/L jump")
+    .style(Style::default().fg(app.user_config.theme.inactive))
+    .alignment(Alignment::Center);
+  f.render_widget(footer, vchunks[1]);
+}
+
+fn draw_undo_history_dialog(f: &mut Frame<'_>, app: &App) {
+  let rect = centered_modal_rect(f.area(), 80, 20);
+  f.render_widget(Clear, rect);
+
+  let block = Block::default()
+    .title(Span::styled(
+      "Undo History",
+      Style::default()
+        .fg(app.user_config.theme.header)
+        .add_modifier(Modifier::BOLD),
+    ))
+    .borders(Borders::ALL)
+    .style(app.user_config.theme.base_style())
+    .border_style(Style::default().fg(app.user_config.theme.inactive));
+  f.render_widget(block, rect);
+
+  let vchunks = Layout::default()
+    .direction(Direction::Vertical)
+    .margin(1)
+    .constraints([Constraint::Min(3), Constraint::Length(1)])
+    .split(rect);
+
+  let items: Vec<ListItem> = app
+    .undo_history
+    .iter()
+    .map(|op| ListItem::new(Span::raw(op.description())))
+    .collect();
+  let mut list_state = ListState::default();
+  if !items.is_empty() {
+    list_state.select(Some(app.undo_history_selected_index.min(items.len() - 1)));
+  }
+
+  let list = List::new(items)
+    .style(app.user_config.theme.base_style())
+    .highlight_style(Style::default().fg(app.user_config.theme.hovered))
+    .highlight_symbol("▶ ");
+  f.render_stateful_widget(list, vchunks[0], &mut list_state);
+
+  let keys = &app.user_config.keys;
+  let footer = Paragraph::new(format!(
+    "{} undo | {} close | ↑/↓ move | newest first",
+    keys.display_in("dialog", Action::Submit),
+    keys.display_in("dialog", Action::Back)
+  ))
+  .style(Style::default().fg(app.user_config.theme.inactive))
+  .alignment(Alignment::Center);
+  f.render_widget(footer, vchunks[1]);
+}
+
+fn draw_lyrics_search_dialog(f: &mut Frame<'_>, app: &App) {
+  let search = &app.lyrics_search;
+  let theme = &app.user_config.theme;
+  let rect = centered_modal_rect(f.area(), 90, 22);
+  f.render_widget(Clear, rect);
+
+  let block = Block::default()
+    .title(Span::styled(
+      "Search Lyrics (LRCLIB)",
+      Style::default()
+        .fg(theme.header)
+        .add_modifier(Modifier::BOLD),
+    ))
+    .borders(Borders::ALL)
+    .style(theme.base_style())
+    .border_style(Style::default().fg(theme.inactive));
+  f.render_widget(block, rect);
+
+  let vchunks = Layout::default()
+    .direction(Direction::Vertical)
+    .margin(1)
+    .constraints([
+      Constraint::Length(1),
+      Constraint::Length(1),
+      Constraint::Length(1),
+      Constraint::Min(3),
+      Constraint::Length(1),
+    ])
+    .split(rect);
+
+  let field = |label: &str, value: &str, focus: LyricsSearchFocus| {
+    let focused = search.focus == focus;
+    let style = if focused {
+      Style::default().fg(theme.selected)
+    } else {
+      Style::default().fg(theme.inactive)
+    };
+    let cursor = if focused { "_" } else { "" };
+    Paragraph::new(Line::from(vec![
+      Span::styled(format!("{:<8}", label), style),
+      Span::styled(format!("{}{}", value, cursor), theme.base_style()),
+    ]))
+  };
+  f.render_widget(
+    field("Title", &search.title, LyricsSearchFocus::Title),
+    vchunks[0],
+  );
+  f.render_widget(
+    field("Artist", &search.artist, LyricsSearchFocus::Artist),
+    vchunks[1],
+  );
+
+  let message = if search.searching {
+    Some("Searching...".to_string())
+  } else if let Some(error) = &search.error {
+    Some(format!("Search failed: {}", error))
+  } else if search.results.is_empty() {
+    Some("No results".to_string())
+  } else {
+    None
+  };
+
+  if let Some(message) = message {
+    let text = Paragraph::new(message)
+      .style(Style::default().fg(theme.inactive))
+      .alignment(Alignment::Center);
+    f.render_widget(text, vchunks[3]);
+  } else {
+    let items: Vec<ListItem> = search
+      .results
+      .iter()
+      .map(|record| {
+        let kind = if record.synced_lyrics.is_some() {
+          "synced"
+        } else if record.instrumental {
+          "instr."
+        } else {
+          "plain"
+        };
+        let duration = record.duration.round() as u64;
+        let mut label = format!(
+          "[{:<6}] {:>2}:{:02}  {} - {}",
+          kind,
+          duration / 60,
+          duration % 60,
+          record.track_name,
+          record.artist_name
+        );
+        if let Some(album) = record.album_name.as_deref().filter(|a| !a.is_empty()) {
+          label.push_str(&format!(" ({})", album));
+        }
+        ListItem::new(Span::raw(label))
+      })
+      .collect();
+    let mut list_state = ListState::default();
+    if search.focus == LyricsSearchFocus::Results {
+      list_state.select(Some(search.selected_index.min(search.results.len() - 1)));
+    }
+
+    let list = List::new(items)
+      .style(theme.base_style())
+      .highlight_style(Style::default().fg(theme.hovered))
+      .highlight_symbol("▶ ");
+    f.render_stateful_widget(list, vchunks[3], &mut list_state);
+  }
+
+  let help = if search.focus == LyricsSearchFocus::Results {
+    "Enter pin to track | Tab edit query | q cancel | j/k or arrows move"
+  } else {
+    "Enter search | Tab next field | Esc cancel"
+  };
+  let footer = Paragraph::new(help)
+    .style(Style::default().fg(theme.inactive))
+    .alignment(Alignment::Center);
+  f.render_widget(footer, vchunks[4]);
+}
+
 /// Draw the mandatory update prompt modal
 pub fn draw_update_prompt(f: &mut Frame<'_>, app: &App) {
   if let Some(update_info) = &app.update_available {
@@ -411,12 +657,7 @@ pub fn draw_sort_menu(f: &mut Frame<'_>, app: &App) {
   };
 
   let available_fields = context.available_fields();
-  let current_sort = match context {
-    crate::core::sort::SortContext::PlaylistTracks => &app.playlist_sort,
-    crate::core::sort::SortContext::SavedAlbums => &app.album_sort,
-    crate::core::sort::SortContext::SavedArtists => &app.artist_sort,
-    crate::core::sort::SortContext::RecentlyPlayed => &app.playlist_sort,
-  };
+  let current_sort = app.sort_state(context);
 
   let width = std::cmp::min(f.area().width.saturating_sub(4), 35);
   let height = (available_fields.len() + 4) as u16; // +4 for borders/padding
@@ -431,12 +672,16 @@ pub fn draw_sort_menu(f: &mut Frame<'_>, app: &App) {
     .iter()
     .enumerate()
     .map(|(i, field)| {
-      let shortcut = field
-        .shortcut()
-        .map(|c| format!(" ({})", c))
-        .unwrap_or_default();
+      let keys = app.user_config.keys.display_in("sort_menu", field.action());
+      let shortcut = if keys.is_empty() {
+        String::new()
+      } else {
+        format!(" ({})", keys)
+      };
       let indicator = if *field == current_sort.field {
         format!(" {}", current_sort.order.indicator())
+      } else if let Some(position) = current_sort.then_position(*field) {
+        format!(" +{}", position)
       } else {
         String::new()
       };
@@ -456,12 +701,7 @@ pub fn draw_sort_menu(f: &mut Frame<'_>, app: &App) {
     })
     .collect();
 
-  let title = match context {
-    crate::core::sort::SortContext::PlaylistTracks => "Sort Tracks",
-    crate::core::sort::SortContext::SavedAlbums => "Sort Albums",
-    crate::core::sort::SortContext::SavedArtists => "Sort Artists",
-    crate::core::sort::SortContext::RecentlyPlayed => "Sort",
-  };
+  let title = context.title();
 
   let list = List::new(items)
     .block(
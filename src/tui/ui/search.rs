@@ -11,8 +11,8 @@ use rspotify::model::PlayableItem;
 use rspotify::prelude::Id;
 
 use super::util::{
-  create_artist_string, draw_selectable_list, get_color, get_search_results_highlight_state,
-  SMALL_TERMINAL_WIDTH,
+  create_artist_string, draw_selectable_list, draw_with_cover_thumbnail, get_color,
+  get_search_results_highlight_state, SMALL_TERMINAL_WIDTH,
 };
 
 pub fn draw_input_and_help_box(f: &mut Frame<'_>, app: &App, layout_chunk: Rect) {
@@ -138,15 +138,24 @@ pub fn draw_search_results(f: &mut Frame<'_>, app: &App, layout_chunk: Rect) {
       None => vec![],
     };
 
-    draw_selectable_list(
-      f,
-      app,
-      songs_area,
-      "Songs",
-      &songs,
-      get_search_results_highlight_state(app, SearchResultBlock::SongSearch),
-      app.search_results.selected_tracks_index,
-    );
+    let selected_track_images = app
+      .search_results
+      .tracks
+      .as_ref()
+      .zip(app.search_results.selected_tracks_index)
+      .and_then(|(tracks, index)| tracks.items.get(index))
+      .map(|track| track.album.images.as_slice());
+    draw_with_cover_thumbnail(f, app, selected_track_images, songs_area, |f, area| {
+      draw_selectable_list(
+        f,
+        app,
+        area,
+        "Songs",
+        &songs,
+        get_search_results_highlight_state(app, SearchResultBlock::SongSearch),
+        app.search_results.selected_tracks_index,
+      )
+    });
 
     let artists = match &app.search_results.artists {
       Some(artists) => artists
@@ -204,15 +213,24 @@ pub fn draw_search_results(f: &mut Frame<'_>, app: &App, layout_chunk: Rect) {
       None => vec![],
     };
 
-    draw_selectable_list(
-      f,
-      app,
-      albums_area,
-      "Albums",
-      &albums,
-      get_search_results_highlight_state(app, SearchResultBlock::AlbumSearch),
-      app.search_results.selected_album_index,
-    );
+    let selected_album_images = app
+      .search_results
+      .albums
+      .as_ref()
+      .zip(app.search_results.selected_album_index)
+      .and_then(|(albums, index)| albums.items.get(index))
+      .map(|album| album.images.as_slice());
+    draw_with_cover_thumbnail(f, app, selected_album_images, albums_area, |f, area| {
+      draw_selectable_list(
+        f,
+        app,
+        area,
+        "Albums",
+        &albums,
+        get_search_results_highlight_state(app, SearchResultBlock::AlbumSearch),
+        app.search_results.selected_album_index,
+      )
+    });
 
     let playlists = match &app.search_results.playlists {
       Some(playlists) => playlists
@@ -1,4 +1,6 @@
-use crate::app::{App, SettingValue, SettingsCategory};
+use crate::app::{App, KeyCaptureIssue, SettingValue, SettingsCategory};
+use crate::core::keymap::Action;
+use crate::core::user_config::{EQ_BAND_COUNT, EQ_MAX_GAIN_DB};
 use ratatui::{
   layout::{Constraint, Layout, Rect},
   style::{Modifier, Style},
@@ -6,6 +8,7 @@ use ratatui::{
   widgets::{Block, Borders, List, ListItem, Paragraph, Tabs},
   Frame,
 };
+use tui_equalizer::{Band, Equalizer};
 
 pub fn draw_settings(f: &mut Frame<'_>, app: &App) {
   let [tabs_area, list_area, help_area] = f.area().layout(
@@ -18,7 +21,18 @@ pub fn draw_settings(f: &mut Frame<'_>, app: &App) {
   );
 
   draw_category_tabs(f, app, tabs_area);
-  draw_settings_list(f, app, list_area);
+  if app.settings_category == SettingsCategory::Equalizer {
+    // Two cells per band plus the border
+    let curve_width = EQ_BAND_COUNT as u16 * 2 + 2;
+    let [settings_area, curve_area] = list_area.layout(&Layout::horizontal([
+      Constraint::Min(1),
+      Constraint::Length(curve_width),
+    ]));
+    draw_settings_list(f, app, settings_area);
+    draw_equalizer_curve(f, app, curve_area);
+  } else {
+    draw_settings_list(f, app, list_area);
+  }
   draw_settings_help(f, app, help_area);
 }
 
@@ -68,6 +82,17 @@ fn draw_settings_list(f: &mut Frame<'_>, app: &App, area: Rect) {
             }
             .to_string()
           }
+          SettingValue::Key(_) => {
+            // Show the keys recorded so far
+            let keys = app
+              .key_capture
+              .keys
+              .iter()
+              .map(|key| key.to_string())
+              .collect::<Vec<_>>()
+              .join(" ");
+            format!("{}▏", keys)
+          }
           _ => {
             // Show edit buffer with cursor
             format!("{}▏", app.settings_edit_buffer)
@@ -81,6 +106,7 @@ fn draw_settings_list(f: &mut Frame<'_>, app: &App, area: Rect) {
           }
           SettingValue::Number(v) => v.to_string(),
           SettingValue::String(v) => format!("\"{}\"", v),
+          SettingValue::Key(v) if v.is_empty() => "[unbound]".to_string(),
           SettingValue::Key(v) => format!("[{}]", v),
           SettingValue::Color(v) => format!("■ {}", v),
           SettingValue::Preset(v) => format!("◆ {} ◆", v), // Show preset name with arrows hint
@@ -106,10 +132,17 @@ fn draw_settings_list(f: &mut Frame<'_>, app: &App, area: Rect) {
         Style::default().fg(app.user_config.theme.inactive)
       };
 
-      let line = Line::from(vec![
+      let mut spans = vec![
         Span::styled(format!("{}: ", setting.name), name_style),
         Span::styled(value_str, value_style),
-      ]);
+      ];
+      if setting.requires_restart() {
+        spans.push(Span::styled(
+          " ⟳ restart",
+          Style::default().fg(app.user_config.theme.inactive),
+        ));
+      }
+      let line = Line::from(spans);
 
       ListItem::new(line)
     })
@@ -145,32 +178,100 @@ fn draw_settings_list(f: &mut Frame<'_>, app: &App, area: Rect) {
   f.render_widget(list, area);
 }
 
+/// Bar per band with 0 dB at half height. Dimmed while the equalizer is off.
+fn draw_equalizer_curve(f: &mut Frame<'_>, app: &App, area: Rect) {
+  let equalizer = &app.user_config.equalizer;
+  let bands = equalizer
+    .gains_db
+    .iter()
+    .map(|gain| {
+      let level = (gain + EQ_MAX_GAIN_DB) / (2.0 * EQ_MAX_GAIN_DB);
+      Band::from(f64::from(level.clamp(0.0, 1.0)))
+    })
+    .collect();
+
+  let block = Block::default()
+    .borders(Borders::ALL)
+    .title("Curve")
+    .style(app.user_config.theme.base_style())
+    .border_style(Style::default().fg(app.user_config.theme.inactive));
+  let inner = block.inner(area);
+  f.render_widget(block, area);
+
+  let curve = Equalizer {
+    bands,
+    brightness: if equalizer.enabled { 1.0 } else { 0.4 },
+  };
+  f.render_widget(curve, inner);
+}
+
 fn draw_settings_help(f: &mut Frame<'_>, app: &App, area: Rect) {
+  let mut help_style = Style::default().fg(app.user_config.theme.hint);
   let help_text = if app.settings_edit_mode {
     match app.settings_items.get(app.settings_selected_index) {
       Some(setting) => match &setting.value {
-        SettingValue::Bool(_) => "Space/Enter: Toggle | ←/→: Toggle | Esc: Cancel",
+        // A rejected number or text stays in edit mode with the reason
+        SettingValue::Number(_) | SettingValue::String(_) | SettingValue::Color(_)
+          if app.settings_edit_error.is_some() =>
+        {
+          help_style = Style::default().fg(app.user_config.theme.error_text);
+          format!(
+            "{} | Enter: Confirm | Esc: Cancel",
+            app.settings_edit_error.as_deref().unwrap_or_default()
+          )
+        }
+        SettingValue::Bool(_) => "Space/Enter: Toggle | ←/→: Toggle | Esc: Cancel".to_string(),
         SettingValue::Number(_) => {
-          "↑/↓: Increment/Decrement | Type numbers | Enter: Confirm | Esc: Cancel"
+          "↑/↓: Increment/Decrement | Type numbers | Enter: Confirm | Esc: Cancel".to_string()
         }
-        SettingValue::Key(_) => "Press any key to set binding | Esc: Cancel",
-        _ => "Type to edit | Enter: Confirm | Esc: Cancel",
+        SettingValue::Key(_) => {
+          let capture = &app.key_capture;
+          if capture.issue.is_some() {
+            help_style = Style::default().fg(app.user_config.theme.error_text);
+          }
+          match &capture.issue {
+            Some(KeyCaptureIssue::Reserved(message)) => {
+              format!("{} | Backspace: Delete key | Esc: Cancel", message)
+            }
+            Some(KeyCaptureIssue::Conflict { name, .. }) if capture.resolving => format!(
+              "Already used by {} | s: Swap keys | u: Unbind {} | Esc: Back",
+              name, name
+            ),
+            Some(KeyCaptureIssue::Conflict { name, .. }) => format!(
+              "Conflicts with {} | Enter: Swap or unbind | Backspace: Delete key | Esc: Cancel",
+              name
+            ),
+            None => "Press keys, several make a chord | Enter: Confirm | Backspace: Delete key | Esc: Cancel".to_string(),
+          }
+        }
+        _ => "Type to edit | Enter: Confirm | Esc: Cancel".to_string(),
       },
-      None => "",
+      None => String::new(),
     }
+  } else if app.settings_category == SettingsCategory::Keybindings {
+    format!(
+      "↑/↓: Select | ←/→: Switch Tab | Enter: Record keys | {}/{}: Reset/Reset all | {}: Save | Esc/{}: Exit",
+      app
+        .user_config
+        .keys
+        .display_in("settings", Action::ResetBinding),
+      app
+        .user_config
+        .keys
+        .display_in("settings", Action::ResetAllBindings),
+      app.user_config.keys.display(Action::SaveSettings),
+      app.user_config.keys.display(Action::Back)
+    )
   } else {
-    &format!(
-      "↑/↓: Select | ←/→: Switch Tab | Enter: Toggle/Edit | {}: Save | Esc/q: Exit",
-      app.user_config.keys.save_settings
+    format!(
+      "↑/↓: Select | ←/→: Switch Tab | Enter: Toggle/Edit | {}: Save | Esc/{}: Exit",
+      app.user_config.keys.display(Action::SaveSettings),
+      app.user_config.keys.display(Action::Back)
     )
   };
 
   let help = Paragraph::new(help_text)
-    .style(
-      Style::default()
-        .fg(app.user_config.theme.hint)
-        .bg(app.user_config.theme.background),
-    )
+    .style(help_style.bg(app.user_config.theme.background))
     .block(
       Block::default()
         .borders(Borders::ALL)
@@ -1,18 +1,23 @@
 use crate::core::app::{
   ActiveBlock, AlbumTableContext, App, EpisodeTableContext, RecommendationsContext,
 };
+use crate::core::columns::{resolve_widths, Column, ColumnField, ColumnTable};
 use ratatui::{
-  layout::{Constraint, Rect},
+  layout::{Alignment, Constraint, Rect},
   style::{Modifier, Style},
-  text::Span,
-  widgets::{Block, Borders, Row, Table},
+  text::{Line, Span},
+  widgets::{Block, Borders, Cell, Row, Table},
   Frame,
 };
-use rspotify::model::show::ResumePoint;
+use rspotify::model::show::{ResumePoint, SimplifiedEpisode};
+use rspotify::model::track::{FullTrack, SimplifiedTrack};
 use rspotify::model::PlayableItem;
 use rspotify::prelude::Id;
 
-use super::util::{create_artist_string, get_color, get_percentage_width, millis_to_minutes};
+use super::util::{
+  create_artist_string, draw_with_cover_thumbnail, get_color, get_percentage_width,
+  millis_to_minutes,
+};
 
 pub enum TableId {
   Album,
@@ -48,6 +53,7 @@ pub struct TableHeaderItem<'a> {
   pub id: ColumnId,
   pub text: &'a str,
   pub width: u16,
+  pub align: Alignment,
 }
 
 pub struct TableItem {
@@ -146,36 +152,13 @@ pub fn draw_podcast_table(f: &mut Frame<'_>, app: &App, layout_chunk: Rect) {
 }
 
 pub fn draw_album_table(f: &mut Frame<'_>, app: &App, layout_chunk: Rect) {
-  let header = TableHeader {
-    id: TableId::Album,
-    items: vec![
-      TableHeaderItem {
-        id: ColumnId::Liked,
-        text: "",
-        width: 2,
-      },
-      TableHeaderItem {
-        text: "#",
-        width: 3,
-        ..Default::default()
-      },
-      TableHeaderItem {
-        id: ColumnId::Title,
-        text: "Title",
-        width: get_percentage_width(layout_chunk.width, 2.0 / 5.0) - 5,
-      },
-      TableHeaderItem {
-        text: "Artist",
-        width: get_percentage_width(layout_chunk.width, 2.0 / 5.0),
-        ..Default::default()
-      },
-      TableHeaderItem {
-        text: "Length",
-        width: get_percentage_width(layout_chunk.width, 1.0 / 5.0),
-        ..Default::default()
-      },
-    ],
-  };
+  let columns = app.user_config.columns.get(ColumnTable::AlbumTracks);
+  let header = configured_header(
+    TableId::Album,
+    ColumnTable::AlbumTracks,
+    columns,
+    layout_chunk.width,
+  );
 
   let current_route = app.get_current_route();
   let highlight_state = (
@@ -183,31 +166,33 @@ pub fn draw_album_table(f: &mut Frame<'_>, app: &App, layout_chunk: Rect) {
     current_route.hovered_block == ActiveBlock::AlbumTracks,
   );
 
+  let album_items = |tracks: &[SimplifiedTrack], release_date: Option<&str>| {
+    tracks
+      .iter()
+      .map(|item| TableItem {
+        id: item
+          .id
+          .as_ref()
+          .map(|id| id.id().to_string())
+          .unwrap_or_else(|| "".to_string()),
+        format: columns
+          .iter()
+          .map(|column| album_track_cell(app, item, release_date, column.field))
+          .collect(),
+      })
+      .collect::<Vec<TableItem>>()
+  };
+
   let album_ui = match &app.album_table_context {
     AlbumTableContext::Simplified => {
       app
         .selected_album_simplified
         .as_ref()
         .map(|selected_album_simplified| AlbumUi {
-          items: selected_album_simplified
-            .tracks
-            .items
-            .iter()
-            .map(|item| TableItem {
-              id: item
-                .id
-                .as_ref()
-                .map(|id| id.id().to_string())
-                .unwrap_or_else(|| "".to_string()),
-              format: vec![
-                "".to_string(),
-                item.track_number.to_string(),
-                item.name.to_owned(),
-                create_artist_string(&item.artists),
-                millis_to_minutes(item.duration.num_milliseconds() as u128),
-              ],
-            })
-            .collect::<Vec<TableItem>>(),
+          items: album_items(
+            &selected_album_simplified.tracks.items,
+            selected_album_simplified.album.release_date.as_deref(),
+          ),
           title: format!(
             "{} by {}",
             selected_album_simplified.album.name,
@@ -216,28 +201,14 @@ pub fn draw_album_table(f: &mut Frame<'_>, app: &App, layout_chunk: Rect) {
           selected_index: selected_album_simplified.selected_index,
         })
     }
-    AlbumTableContext::Full => match app.selected_album_full.clone() {
-      Some(selected_album) => Some(AlbumUi {
-        items: selected_album
-          .album
-          .tracks
-          .items
-          .iter()
-          .map(|item| TableItem {
-            id: item
-              .id
-              .as_ref()
-              .map(|id| id.id().to_string())
-              .unwrap_or_else(|| "".to_string()),
-            format: vec![
-              "".to_string(),
-              item.track_number.to_string(),
-              item.name.to_owned(),
-              create_artist_string(&item.artists),
-              millis_to_minutes(item.duration.num_milliseconds() as u128),
-            ],
-          })
-          .collect::<Vec<TableItem>>(),
+    AlbumTableContext::Full => app
+      .selected_album_full
+      .as_ref()
+      .map(|selected_album| AlbumUi {
+        items: album_items(
+          &selected_album.album.tracks.items,
+          Some(selected_album.album.release_date.as_str()),
+        ),
         title: format!(
           "{} by {}",
           selected_album.album.name,
@@ -245,8 +216,6 @@ pub fn draw_album_table(f: &mut Frame<'_>, app: &App, layout_chunk: Rect) {
         ),
         selected_index: app.saved_album_tracks_index,
       }),
-      None => None,
-    },
   };
 
   if let Some(album_ui) = album_ui {
@@ -270,11 +239,13 @@ pub fn draw_recommendations_table(f: &mut Frame<'_>, app: &App, layout_chunk: Re
         id: ColumnId::Liked,
         text: "",
         width: 2,
+        ..Default::default()
       },
       TableHeaderItem {
         id: ColumnId::Title,
         text: "Title",
         width: get_percentage_width(layout_chunk.width, 0.3),
+        ..Default::default()
       },
       TableHeaderItem {
         text: "Artist",
@@ -343,36 +314,13 @@ pub fn draw_recommendations_table(f: &mut Frame<'_>, app: &App, layout_chunk: Re
 }
 
 pub fn draw_song_table(f: &mut Frame<'_>, app: &App, layout_chunk: Rect) {
-  let header = TableHeader {
-    id: TableId::Song,
-    items: vec![
-      TableHeaderItem {
-        id: ColumnId::Liked,
-        text: "",
-        width: 2,
-      },
-      TableHeaderItem {
-        id: ColumnId::Title,
-        text: "Title",
-        width: get_percentage_width(layout_chunk.width, 0.3),
-      },
-      TableHeaderItem {
-        text: "Artist",
-        width: get_percentage_width(layout_chunk.width, 0.3),
-        ..Default::default()
-      },
-      TableHeaderItem {
-        text: "Album",
-        width: get_percentage_width(layout_chunk.width, 0.3),
-        ..Default::default()
-      },
-      TableHeaderItem {
-        text: "Length",
-        width: get_percentage_width(layout_chunk.width, 0.1),
-        ..Default::default()
-      },
-    ],
-  };
+  let columns = app.user_config.columns.get(ColumnTable::Songs);
+  let header = configured_header(
+    TableId::Song,
+    ColumnTable::Songs,
+    columns,
+    layout_chunk.width,
+  );
 
   let current_route = app.get_current_route();
   let highlight_state = (
@@ -384,19 +332,17 @@ pub fn draw_song_table(f: &mut Frame<'_>, app: &App, layout_chunk: Rect) {
     .track_table
     .tracks
     .iter()
-    .map(|item| TableItem {
+    .enumerate()
+    .map(|(row, item)| TableItem {
       id: item
         .id
         .as_ref()
         .map(|id| id.id().to_string())
         .unwrap_or_else(|| "".to_string()),
-      format: vec![
-        "".to_string(),
-        item.name.to_owned(),
-        create_artist_string(&item.artists),
-        item.album.name.to_owned(),
-        millis_to_minutes(item.duration.num_milliseconds() as u128),
-      ],
+      format: columns
+        .iter()
+        .map(|column| song_cell(app, row, item, column.field))
+        .collect(),
     })
     .collect::<Vec<TableItem>>();
 
@@ -460,45 +406,32 @@ pub fn draw_album_list(f: &mut Frame<'_>, app: &App, layout_chunk: Rect) {
       })
       .collect::<Vec<TableItem>>();
 
-    draw_table(
-      f,
-      app,
-      layout_chunk,
-      ("Saved Albums", &header),
-      &items,
-      selected_song_index,
-      highlight_state,
-    )
+    let images = saved_albums
+      .items
+      .get(selected_song_index)
+      .map(|album_page| album_page.album.images.as_slice());
+    draw_with_cover_thumbnail(f, app, images, layout_chunk, |f, area| {
+      draw_table(
+        f,
+        app,
+        area,
+        ("Saved Albums", &header),
+        &items,
+        selected_song_index,
+        highlight_state,
+      )
+    });
   };
 }
 
 pub fn draw_show_episodes(f: &mut Frame<'_>, app: &App, layout_chunk: Rect) {
-  let header = TableHeader {
-    id: TableId::PodcastEpisodes,
-    items: vec![
-      TableHeaderItem {
-        // Column to mark an episode as fully played
-        text: "",
-        width: 2,
-        ..Default::default()
-      },
-      TableHeaderItem {
-        text: "Date",
-        width: get_percentage_width(layout_chunk.width, 0.5 / 5.0) - 2,
-        ..Default::default()
-      },
-      TableHeaderItem {
-        text: "Name",
-        width: get_percentage_width(layout_chunk.width, 3.5 / 5.0),
-        id: ColumnId::Title,
-      },
-      TableHeaderItem {
-        text: "Duration",
-        width: get_percentage_width(layout_chunk.width, 1.0 / 5.0),
-        ..Default::default()
-      },
-    ],
-  };
+  let columns = app.user_config.columns.get(ColumnTable::Episodes);
+  let header = configured_header(
+    TableId::PodcastEpisodes,
+    ColumnTable::Episodes,
+    columns,
+    layout_chunk.width,
+  );
 
   let current_route = app.get_current_route();
 
@@ -511,37 +444,12 @@ pub fn draw_show_episodes(f: &mut Frame<'_>, app: &App, layout_chunk: Rect) {
     let items = episodes
       .items
       .iter()
-      .map(|episode| {
-        let (played_str, time_str) = match episode.resume_point {
-          Some(ResumePoint {
-            fully_played,
-            resume_position,
-          }) => (
-            if fully_played {
-              " ✔".to_owned()
-            } else {
-              "".to_owned()
-            },
-            format!(
-              "{} / {}",
-              millis_to_minutes(resume_position.num_milliseconds() as u128),
-              millis_to_minutes(episode.duration.num_milliseconds() as u128)
-            ),
-          ),
-          None => (
-            "".to_owned(),
-            millis_to_minutes(episode.duration.num_milliseconds() as u128),
-          ),
-        };
-        TableItem {
-          id: episode.id.id().to_string(),
-          format: vec![
-            played_str,
-            episode.release_date.to_owned(),
-            episode.name.to_owned(),
-            time_str,
-          ],
-        }
+      .map(|episode| TableItem {
+        id: episode.id.id().to_string(),
+        format: columns
+          .iter()
+          .map(|column| episode_cell(episode, column.field))
+          .collect(),
       })
       .collect::<Vec<TableItem>>();
 
@@ -588,12 +496,14 @@ pub fn draw_recently_played_table(f: &mut Frame<'_>, app: &App, layout_chunk: Re
         id: ColumnId::Liked,
         text: "",
         width: 2,
+        ..Default::default()
       },
       TableHeaderItem {
         id: ColumnId::Title,
         text: "Title",
         // We need to subtract the fixed value of the previous column
         width: get_percentage_width(layout_chunk.width, 2.0 / 5.0) - 2,
+        ..Default::default()
       },
       TableHeaderItem {
         text: "Artist",
@@ -649,6 +559,127 @@ pub fn draw_recently_played_table(f: &mut Frame<'_>, app: &App, layout_chunk: Re
   };
 }
 
+/// Header of a table whose columns come from the `columns` config
+fn configured_header(
+  id: TableId,
+  table: ColumnTable,
+  columns: &[Column],
+  width: u16,
+) -> TableHeader<'static> {
+  let items = columns
+    .iter()
+    .zip(resolve_widths(columns, width))
+    .map(|(column, width)| TableHeaderItem {
+      id: match column.field {
+        ColumnField::Liked => ColumnId::Liked,
+        ColumnField::Title => ColumnId::Title,
+        _ => ColumnId::None,
+      },
+      text: column.field.header(table),
+      width,
+      align: column.align.into(),
+    })
+    .collect();
+  TableHeader { id, items }
+}
+
+fn song_cell(app: &App, row: usize, track: &FullTrack, field: ColumnField) -> String {
+  let id = track.id.as_ref().map(|id| id.id());
+  let added = app.track_table.added.get(row);
+  match field {
+    ColumnField::Title => track.name.to_owned(),
+    ColumnField::Artist => create_artist_string(&track.artists),
+    ColumnField::Album => track.album.name.to_owned(),
+    ColumnField::Length => millis_to_minutes(track.duration.num_milliseconds() as u128),
+    ColumnField::AddedAt => added
+      .and_then(|added| added.at)
+      .map(|at| at.format("%Y-%m-%d").to_string())
+      .unwrap_or_default(),
+    ColumnField::AddedBy => added.and_then(|added| added.by.clone()).unwrap_or_default(),
+    // Tracks the API leaves popularity out for come through as 0
+    ColumnField::Popularity => Some(track.popularity)
+      .filter(|popularity| *popularity > 0)
+      .map(|popularity| popularity.to_string())
+      .unwrap_or_default(),
+    ColumnField::ReleaseDate => track.album.release_date.clone().unwrap_or_default(),
+    ColumnField::ReleaseYear => release_year(track.album.release_date.as_deref()),
+    ColumnField::TrackNumber => track.track_number.to_string(),
+    ColumnField::Disc => track.disc_number.to_string(),
+    ColumnField::Explicit => explicit_mark(track.explicit),
+    ColumnField::Isrc => track.external_ids.get("isrc").cloned().unwrap_or_default(),
+    ColumnField::PlayCount => play_count(app, id),
+    // The liked icon is put in by `draw_table`
+    ColumnField::Liked | ColumnField::Played => "".to_string(),
+  }
+}
+
+fn album_track_cell(
+  app: &App,
+  track: &SimplifiedTrack,
+  release_date: Option<&str>,
+  field: ColumnField,
+) -> String {
+  match field {
+    ColumnField::Title => track.name.to_owned(),
+    ColumnField::Artist => create_artist_string(&track.artists),
+    ColumnField::Length => millis_to_minutes(track.duration.num_milliseconds() as u128),
+    ColumnField::ReleaseDate => release_date.unwrap_or_default().to_string(),
+    ColumnField::ReleaseYear => release_year(release_date),
+    ColumnField::TrackNumber => track.track_number.to_string(),
+    ColumnField::Disc => track.disc_number.to_string(),
+    ColumnField::Explicit => explicit_mark(track.explicit),
+    ColumnField::PlayCount => play_count(app, track.id.as_ref().map(|id| id.id())),
+    _ => "".to_string(),
+  }
+}
+
+fn episode_cell(episode: &SimplifiedEpisode, field: ColumnField) -> String {
+  let duration = millis_to_minutes(episode.duration.num_milliseconds() as u128);
+  match field {
+    ColumnField::Played => match episode.resume_point {
+      Some(ResumePoint {
+        fully_played: true, ..
+      }) => " ✔".to_owned(),
+      _ => "".to_owned(),
+    },
+    ColumnField::Title => episode.name.to_owned(),
+    ColumnField::Length => match &episode.resume_point {
+      Some(ResumePoint {
+        resume_position, ..
+      }) => format!(
+        "{} / {}",
+        millis_to_minutes(resume_position.num_milliseconds() as u128),
+        duration
+      ),
+      None => duration,
+    },
+    ColumnField::ReleaseDate => episode.release_date.to_owned(),
+    ColumnField::ReleaseYear => release_year(Some(&episode.release_date)),
+    ColumnField::Explicit => explicit_mark(episode.explicit),
+    _ => "".to_string(),
+  }
+}
+
+/// Release dates come as `1981`, `1981-12` or `1981-12-15` depending on how much is known
+fn release_year(release_date: Option<&str>) -> String {
+  release_date
+    .and_then(|date| date.get(..4))
+    .unwrap_or_default()
+    .to_string()
+}
+
+fn explicit_mark(explicit: bool) -> String {
+  let mark = if explicit { "E" } else { "" };
+  mark.to_string()
+}
+
+fn play_count(app: &App, track_id: Option<&str>) -> String {
+  track_id
+    .and_then(|id| app.play_counts.get(id))
+    .map(|count| count.to_string())
+    .unwrap_or_default()
+}
+
 fn draw_table(
   f: &mut Frame<'_>,
   app: &App,
@@ -680,6 +711,11 @@ fn draw_table(
   });
 
   let (title, header) = table_layout;
+  // Only the track table has a visual range
+  let visual_range = match header.id {
+    TableId::Song => app.visual_range(),
+    _ => None,
+  };
 
   // Make sure that the selected item is visible on the page. Need to add some rows of padding
   // to chunk height for header and header space to get a true table height
@@ -735,13 +771,24 @@ fn draw_table(
       _ => {}
     }
 
+    if visual_range
+      .as_ref()
+      .is_some_and(|range| range.contains(&(i + offset)))
+    {
+      style = style.add_modifier(Modifier::REVERSED);
+    }
+
     // Next check if the item is under selection.
     if Some(i) == selected_index.checked_sub(offset) {
       style = selected_style;
     }
 
     // Return row styled data
-    Row::new(formatted_row).style(style)
+    let cells = formatted_row
+      .into_iter()
+      .zip(&header.items)
+      .map(|(text, h)| Cell::from(Line::from(text).alignment(h.align)));
+    Row::new(cells).style(style)
   });
 
   let widths = header
@@ -752,8 +799,13 @@ fn draw_table(
 
   let table = Table::new(rows, &widths)
     .header(
-      Row::new(header.items.iter().map(|h| h.text))
-        .style(Style::default().fg(app.user_config.theme.header)),
+      Row::new(
+        header
+          .items
+          .iter()
+          .map(|h| Cell::from(Line::from(h.text).alignment(h.align))),
+      )
+      .style(Style::default().fg(app.user_config.theme.header)),
     )
     .block(
       Block::default()
@@ -2,12 +2,13 @@ use crate::core::app::{ActiveBlock, App, ArtistBlock, SearchResultBlock};
 use crate::core::user_config::Theme;
 use ratatui::{
   layout::Rect,
-  style::{Modifier, Style},
+  style::{Color, Modifier, Style},
   text::{Line, Span},
   widgets::{Block, BorderType, Borders, List, ListItem, ListState},
   Frame,
 };
 use rspotify::model::artist::SimplifiedArtist;
+use rspotify::model::Image;
 use std::time::Duration;
 
 pub const BASIC_VIEW_HEIGHT: u16 = 6;
@@ -38,6 +39,44 @@ pub fn get_artist_highlight_state(app: &App, block_to_match: ArtistBlock) -> (bo
   }
 }
 
+/// Linear colorgrad gradient through the given theme colors, evenly spaced
+pub fn build_gradient(colors: &[Color]) -> colorgrad::LinearGradient {
+  fn to_rgba(color: Color) -> (u8, u8, u8, u8) {
+    match color {
+      Color::Rgb(r, g, b) => (r, g, b, 255),
+      Color::Black => (0, 0, 0, 255),
+      Color::Red => (255, 0, 0, 255),
+      Color::Green => (0, 255, 0, 255),
+      Color::Yellow => (255, 255, 0, 255),
+      Color::Blue => (0, 0, 255, 255),
+      Color::Magenta => (255, 0, 255, 255),
+      Color::Cyan => (0, 255, 255, 255),
+      Color::Gray => (128, 128, 128, 255),
+      Color::DarkGray => (64, 64, 64, 255),
+      Color::LightRed => (255, 128, 128, 255),
+      Color::LightGreen => (128, 255, 128, 255),
+      Color::LightYellow => (255, 255, 128, 255),
+      Color::LightBlue => (128, 128, 255, 255),
+      Color::LightMagenta => (255, 128, 255, 255),
+      Color::LightCyan => (128, 255, 255, 255),
+      Color::White => (255, 255, 255, 255),
+      _ => (255, 255, 255, 255),
+    }
+  }
+
+  let colors: Vec<colorgrad::Color> = colors
+    .iter()
+    .map(|&color| {
+      let (r, g, b, a) = to_rgba(color);
+      colorgrad::Color::from_rgba8(r, g, b, a)
+    })
+    .collect();
+  colorgrad::GradientBuilder::new()
+    .colors(&colors)
+    .build::<colorgrad::LinearGradient>()
+    .unwrap()
+}
+
 pub fn get_color((is_active, is_hovered): (bool, bool), theme: Theme) -> Style {
   match (is_active, is_hovered) {
     (true, _) => Style::default().fg(theme.selected).bg(theme.background),
@@ -138,6 +177,50 @@ pub fn get_main_layout_margin(app: &App) -> u16 {
   }
 }
 
+/// Draw a list with the selected album's cover thumbnail on its right, once the thumbnail is
+/// loaded and thumbnails are turned on; otherwise the list takes the whole area
+pub fn draw_with_cover_thumbnail(
+  f: &mut Frame<'_>,
+  app: &App,
+  images: Option<&[Image]>,
+  area: Rect,
+  draw: impl FnOnce(&mut Frame<'_>, Rect),
+) {
+  #[cfg(feature = "cover-art")]
+  {
+    use crate::tui::cover_art::thumbnail_url;
+    use ratatui::layout::{Constraint, Layout};
+
+    let url = images.and_then(thumbnail_url).filter(|url| {
+      app.user_config.behavior.draw_cover_thumbnails && app.cover_art.has_thumbnail(url)
+    });
+    // Square, with cells about twice as tall as wide; skipped when the list would get cramped
+    let width = area.height.saturating_sub(2) * 2;
+    if let Some(url) = url.filter(|_| width > 0 && width * 3 <= area.width) {
+      let [list_area, _, thumbnail_area] = area.layout(&Layout::horizontal([
+        Constraint::Fill(1),
+        Constraint::Length(1),
+        Constraint::Length(width),
+      ]));
+      draw(f, list_area);
+      // Inset to line up with the list's content inside its borders
+      let [thumbnail_area] =
+        thumbnail_area.layout(&Layout::vertical([Constraint::Fill(1)]).vertical_margin(1));
+      app.cover_art.render_thumbnail(
+        f,
+        url,
+        thumbnail_area,
+        app.user_config.behavior.cover_art_fallback,
+      );
+      return;
+    }
+  }
+  #[cfg(not(feature = "cover-art"))]
+  let _ = (app, images);
+
+  draw(f, area);
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;